@@ -285,45 +285,45 @@ fn create_user() -> StatsigUser {
 }
 
 fn create_user_with_benchmark_payload() -> StatsigUser {
-    StatsigUser::new(StatsigUserData {
-        user_id: Some(dyn_value!("a_user_id")),
-        custom_ids: Some(StatsigUserDataMap::from([(
+    let mut user_data = StatsigUserData::default();
+    user_data.user_id = Some(dyn_value!("a_user_id"));
+    user_data.custom_ids = Some(StatsigUserDataMap::from([
+        (
             "custom_id".to_string(),
             dyn_value!("a_long_custom_id_value_goes_here"),
-        ), (
-            "employee_id".to_string(),
-            dyn_value!("456"),
-        )])),
-        email: Some(dyn_value!("test@test.com")),
-        ip: Some(dyn_value!("127.0.0.1")),
-        locale: Some(dyn_value!("en_US")),
-        app_version: Some(dyn_value!("1.0.0")),
-        country: Some(dyn_value!("US")),
-        user_agent: Some(dyn_value!("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36")),
-        custom: Some(StatsigUserDataMap::from([
-            ("custom_attr".to_string(), dyn_value!("custom_value")),
-            ("custom_array".to_string(), dyn_value!([1, 2, 3])),
-            (
-                "custom_object".to_string(),
-                dyn_value!(HashMap::from([("key".to_string(), "value".to_string())])),
-            ),
-            ("custom_number".to_string(), dyn_value!(123)),
-            ("custom_boolean".to_string(), dyn_value!(true)),
-            (
-                "large_custom_string".to_string(),
-                dyn_value!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
-            ),
-        ])),
-        statsig_environment: None,
-        private_attributes: Some(StatsigUserDataMap::from([
-            ("private_attr".to_string(), dyn_value!("secret")),
-            ("private_array".to_string(), dyn_value!([1, 2, 3])),
-            (
-                "private_object".to_string(),
-                dyn_value!(HashMap::from([("key".to_string(), "value".to_string())])),
-            ),
-        ])),
-    })
+        ),
+        ("employee_id".to_string(), dyn_value!("456")),
+    ]));
+    user_data.email = Some(dyn_value!("test@test.com"));
+    user_data.ip = Some(dyn_value!("127.0.0.1"));
+    user_data.locale = Some(dyn_value!("en_US"));
+    user_data.app_version = Some(dyn_value!("1.0.0"));
+    user_data.country = Some(dyn_value!("US"));
+    user_data.user_agent = Some(dyn_value!("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36"));
+    user_data.custom = Some(StatsigUserDataMap::from([
+        ("custom_attr".to_string(), dyn_value!("custom_value")),
+        ("custom_array".to_string(), dyn_value!([1, 2, 3])),
+        (
+            "custom_object".to_string(),
+            dyn_value!(HashMap::from([("key".to_string(), "value".to_string())])),
+        ),
+        ("custom_number".to_string(), dyn_value!(123)),
+        ("custom_boolean".to_string(), dyn_value!(true)),
+        (
+            "large_custom_string".to_string(),
+            dyn_value!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+        ),
+    ]));
+    user_data.private_attributes = Some(StatsigUserDataMap::from([
+        ("private_attr".to_string(), dyn_value!("secret")),
+        ("private_array".to_string(), dyn_value!([1, 2, 3])),
+        (
+            "private_object".to_string(),
+            dyn_value!(HashMap::from([("key".to_string(), "value".to_string())])),
+        ),
+    ]));
+
+    StatsigUser::new(user_data)
 }
 
 async fn benchmark<F>(
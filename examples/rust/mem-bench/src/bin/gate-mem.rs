@@ -93,31 +93,32 @@ async fn profile_gate_checks(gate_names: &[String]) {
 }
 
 fn create_user() -> StatsigUser {
+    let mut user_data = UserData::default();
+    user_data.user_id = Some(dyn_value!("a_user"));
+    user_data.email = Some(dyn_value!("daniel@statsig.com"));
+    user_data.ip = Some(dyn_value!("127.0.0.1"));
+    user_data.user_agent = Some("Mozilla/5.0 (iPhone; CPU iPhone OS 10_3_1 like Mac OS X) AppleWebKit/603.1.30 (KHTML, like Gecko) Version/10.0 Mobile/14E304 Safari/602.1".into());
+    user_data.country = Some(dyn_value!("US"));
+    user_data.locale = Some(dyn_value!("en-US"));
+    user_data.app_version = Some(dyn_value!("1.0.0"));
+    user_data.custom_ids = Some(HashMap::from([
+        ("companyID".into(), dyn_value!("statsig")),
+        ("groupID".to_string(), dyn_value!("sdk_team")),
+    ]));
+    user_data.custom = Some(HashMap::from([(
+        "test_custom_field".to_string(),
+        dyn_value!("test_custom_field_value"),
+    )]));
+    user_data.private_attributes = Some(HashMap::from([(
+        "test_private_attribute".to_string(),
+        dyn_value!("test_private_attribute_value"),
+    )]));
+    user_data.statsig_environment = Some(HashMap::from([(
+        "tier".to_string(),
+        dyn_value!("development"),
+    )]));
+
     StatsigUser {
-        data: Arc::new(UserData {
-            user_id: Some(dyn_value!("a_user")),
-            email: Some(dyn_value!("daniel@statsig.com")),
-            ip: Some(dyn_value!("127.0.0.1")),
-            user_agent: Some("Mozilla/5.0 (iPhone; CPU iPhone OS 10_3_1 like Mac OS X) AppleWebKit/603.1.30 (KHTML, like Gecko) Version/10.0 Mobile/14E304 Safari/602.1".into()),
-            country: Some(dyn_value!("US")),
-            locale: Some(dyn_value!("en-US")),
-            app_version: Some(dyn_value!("1.0.0")),
-            custom_ids: Some(HashMap::from([
-                ("companyID".into(), dyn_value!("statsig")),
-                ("groupID".to_string(), dyn_value!("sdk_team"),
-            )])),
-            custom: Some(HashMap::from([(
-                "test_custom_field".to_string(),
-                dyn_value!("test_custom_field_value"),
-            )])),
-            private_attributes: Some(HashMap::from([(
-                "test_private_attribute".to_string(),
-                dyn_value!("test_private_attribute_value"),
-            )])),
-            statsig_environment: Some(HashMap::from([(
-                "tier".to_string(),
-                dyn_value!("development"),
-            )])),
-        }),
+        data: Arc::new(user_data),
     }
 }
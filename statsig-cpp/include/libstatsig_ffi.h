@@ -424,6 +424,13 @@ uint64_t statsig_user_create(const char *user_id,
                              const char *custom_json,
                              const char *private_attributes_json);
 
+/**
+ * Returns a new user ref holding a copy of `user_ref` with the given `Sec-CH-UA*` values,
+ * e.g. `{"brands": "...", "platform": "\"Windows\"", "mobile": false}`. A null JSON clears them.
+ */
+uint64_t statsig_user_with_user_agent_client_hints(uint64_t user_ref,
+                                                   const char *user_agent_client_hints_json);
+
 void statsig_user_release(uint64_t user_ref);
 
 #ifdef __cplusplus
@@ -31,6 +31,13 @@ namespace Statsig
         [DllImport(__DllName, EntryPoint = "statsig_user_create", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ulong statsig_user_create(byte* user_id, byte* custom_ids_json, byte* email, byte* ip, byte* user_agent, byte* country, byte* locale, byte* app_version, byte* custom_json, byte* private_attributes_json);
 
+        /// <summary>
+        ///  Returns a new user ref holding a copy of `user_ref` with the given `Sec-CH-UA*` values,
+        ///  e.g. `{"brands": "...", "platform": "\"Windows\"", "mobile": false}`. A null JSON clears them.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "statsig_user_with_user_agent_client_hints", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ulong statsig_user_with_user_agent_client_hints(ulong user_ref, byte* user_agent_client_hints_json);
+
         [DllImport(__DllName, EntryPoint = "statsig_user_release", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern void statsig_user_release(ulong user_ref);
 
@@ -15,7 +15,7 @@ use crate::{
 use statsig_rust::{
     data_store_interface::DataStoreTrait, log_e, networking::proxy_config::ProxyConfig,
     output_logger::LogLevel, DynamicValue, EventLoggingAdapter, InstanceRegistry,
-    ObservabilityClient, PersistentStorage, SpecAdapterConfig, SpecsAdapter,
+    ObservabilityClient, PersistentStorage, RedactionPolicy, SpecAdapterConfig, SpecsAdapter,
     StatsigLocalFileEventLoggingAdapter, StatsigLocalFileSpecsAdapter, StatsigOptions,
 };
 use std::collections::HashMap;
@@ -56,6 +56,7 @@ pub struct StatsigOptionsData {
     proxy_auth: Option<String>,
     proxy_protocol: Option<String>,
     proxy_ca_cert_path: Option<String>,
+    redaction_policy: Option<RedactionPolicy>,
     service_name: Option<String>,
     specs_adapter_ref: Option<u64>,
     // -- START STATSIG FORWARD PROXY CONFIG --
@@ -174,6 +175,18 @@ impl From<StatsigOptionsData> for StatsigOptions {
             override_adapter: None,       // todo: add support for override adapter
             persistent_storage,
             proxy_config,
+            redaction_policy: data.redaction_policy,
+            cmab_learner: None,
+            runtime_options: None,
+            event_logging_max_flush_interval_ms: None,
+            clock: None,
+            id_list_storage: None,
+            regex_safety: None,
+            user_enricher: None,
+            disable_bot_exposure_logging: None,
+            exposure_dedupe_persistence: None,
+            exposure_sampling_policy: None,
+            share_http_connection_pool: None,
             service_name: data.service_name,
             spec_adapters_config,
             specs_adapter,
@@ -1,5 +1,5 @@
 use crate::ffi_utils::{c_char_to_string, parse_json_to_map, parse_json_to_str_map};
-use statsig_rust::{log_e, InstanceRegistry, StatsigUser};
+use statsig_rust::{log_e, InstanceRegistry, StatsigUser, UserAgentClientHints};
 use statsig_rust::{StatsigUserBuilder, StatsigUserData};
 use std::os::raw::c_char;
 
@@ -77,6 +77,38 @@ pub extern "C" fn statsig_user_create(
     })
 }
 
+/// Returns a new user ref holding a copy of `user_ref` with the given `Sec-CH-UA*` values,
+/// e.g. `{"brands": "...", "platform": "\"Windows\"", "mobile": false}`. A null JSON clears them.
+#[no_mangle]
+pub extern "C" fn statsig_user_with_user_agent_client_hints(
+    user_ref: u64,
+    user_agent_client_hints_json: *const c_char,
+) -> u64 {
+    let Some(user) = InstanceRegistry::get::<StatsigUser>(&user_ref) else {
+        log_e!(TAG, "Failed to get StatsigUser with ref {}", user_ref);
+        return 0;
+    };
+
+    let hints = match c_char_to_string(user_agent_client_hints_json) {
+        Some(json) => match serde_json::from_str::<UserAgentClientHints>(&json) {
+            Ok(hints) => Some(hints),
+            Err(_) => {
+                log_e!(TAG, "Failed to deserialize UserAgentClientHints");
+                return 0;
+            }
+        },
+        None => None,
+    };
+
+    let mut user = user.as_ref().clone();
+    user.set_user_agent_client_hints(hints);
+
+    InstanceRegistry::register(user).unwrap_or_else(|| {
+        log_e!(TAG, "Failed to create StatsigUser");
+        0
+    })
+}
+
 #[no_mangle]
 pub extern "C" fn statsig_user_release(user_ref: u64) {
     InstanceRegistry::remove(&user_ref);
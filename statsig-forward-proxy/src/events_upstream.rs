@@ -0,0 +1,60 @@
+use crate::forward_proxy_err::ForwardProxyErr;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use statsig_rust::log_event_payload::LogEventRequest;
+use statsig_rust::{StatsigHttpEventLoggingAdapter, StatsigOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Where the proxy forwards `LogEvents` batches to.
+#[async_trait]
+pub trait EventsUpstream: Send + Sync {
+    async fn forward_events(
+        &self,
+        sdk_key: &str,
+        request: LogEventRequest,
+    ) -> Result<(), ForwardProxyErr>;
+}
+
+/// Posts each batch to `log_event` using the SDK's own HTTP event logging adapter.
+pub struct HttpEventsUpstream {
+    options: StatsigOptions,
+    adapters: Mutex<HashMap<String, Arc<StatsigHttpEventLoggingAdapter>>>,
+}
+
+impl HttpEventsUpstream {
+    #[must_use]
+    pub fn new(options: Option<StatsigOptions>) -> Self {
+        Self {
+            options: options.unwrap_or_default(),
+            adapters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get_adapter(&self, sdk_key: &str) -> Arc<StatsigHttpEventLoggingAdapter> {
+        self.adapters
+            .lock()
+            .entry(sdk_key.to_string())
+            .or_insert_with(|| {
+                Arc::new(StatsigHttpEventLoggingAdapter::new(
+                    sdk_key,
+                    Some(&self.options),
+                ))
+            })
+            .clone()
+    }
+}
+
+#[async_trait]
+impl EventsUpstream for HttpEventsUpstream {
+    async fn forward_events(
+        &self,
+        sdk_key: &str,
+        request: LogEventRequest,
+    ) -> Result<(), ForwardProxyErr> {
+        self.get_adapter(sdk_key)
+            .send_events_over_http(&request)
+            .await
+            .map_err(ForwardProxyErr::Upstream)
+    }
+}
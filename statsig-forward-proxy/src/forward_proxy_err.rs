@@ -0,0 +1,48 @@
+use std::fmt::{Display, Formatter};
+
+use statsig_rust::StatsigErr;
+use tonic::Status;
+
+#[derive(Debug)]
+pub enum ForwardProxyErr {
+    Upstream(StatsigErr),
+    InvalidUpstreamResponse(String),
+    NoSpecsAvailable(String),
+    IdListNotFound(String),
+    InvalidConfig(String),
+    Unimplemented(String),
+    Transport(String),
+}
+
+impl Display for ForwardProxyErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ForwardProxyErr::Upstream(e) => write!(f, "Upstream request failed: {e}"),
+            ForwardProxyErr::InvalidUpstreamResponse(msg) => {
+                write!(f, "Invalid upstream response: {msg}")
+            }
+            ForwardProxyErr::NoSpecsAvailable(sdk_key) => {
+                write!(f, "No specs available for sdk key {sdk_key}")
+            }
+            ForwardProxyErr::IdListNotFound(msg) => write!(f, "ID list not found: {msg}"),
+            ForwardProxyErr::InvalidConfig(msg) => write!(f, "Invalid proxy config: {msg}"),
+            ForwardProxyErr::Unimplemented(msg) => write!(f, "{msg}"),
+            ForwardProxyErr::Transport(msg) => write!(f, "Transport error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ForwardProxyErr {}
+
+impl From<ForwardProxyErr> for Status {
+    fn from(err: ForwardProxyErr) -> Self {
+        match err {
+            ForwardProxyErr::NoSpecsAvailable(_) | ForwardProxyErr::IdListNotFound(_) => {
+                Status::not_found(err.to_string())
+            }
+            ForwardProxyErr::InvalidConfig(_) => Status::invalid_argument(err.to_string()),
+            ForwardProxyErr::Unimplemented(_) => Status::unimplemented(err.to_string()),
+            _ => Status::unavailable(err.to_string()),
+        }
+    }
+}
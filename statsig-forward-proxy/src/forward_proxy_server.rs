@@ -0,0 +1,133 @@
+use crate::events_upstream::EventsUpstream;
+use crate::forward_proxy_err::ForwardProxyErr;
+use crate::forward_proxy_service::ForwardProxyService;
+use crate::id_lists_upstream::IdListsUpstream;
+use crate::spec_cache::SpecCache;
+use crate::spec_stream_hub::SpecStreamHub;
+use crate::specs_upstream::SpecsUpstream;
+use sigstat_grpc::statsig_forward_proxy::statsig_forward_proxy_server::StatsigForwardProxyServer;
+use statsig_rust::log_i;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tonic::transport::Server;
+
+const TAG: &str = stringify!(ForwardProxyServer);
+
+pub const DEFAULT_LISTEN_ADDRESS: &str = "0.0.0.0:50051";
+
+#[derive(Clone, Debug, Default)]
+pub struct ForwardProxyServerConfig {
+    pub listen_address: Option<String>,
+    pub poll_interval_ms: Option<u64>,
+    pub cache_ttl_ms: Option<u64>,
+    pub cache_max_entries: Option<usize>,
+    pub stream_broadcast_capacity: Option<usize>,
+    pub stream_subscriber_buffer: Option<usize>,
+    pub log_events_max_batches: Option<usize>,
+    pub log_events_max_events: Option<usize>,
+}
+
+pub struct ForwardProxyServer {
+    address: SocketAddr,
+    cache_ttl_ms: Option<u64>,
+    log_events_max_batches: Option<usize>,
+    log_events_max_events: Option<usize>,
+    cache: Arc<SpecCache>,
+    hub: Arc<SpecStreamHub>,
+    id_lists_upstream: Option<Arc<dyn IdListsUpstream>>,
+    events_upstream: Option<Arc<dyn EventsUpstream>>,
+}
+
+impl ForwardProxyServer {
+    pub fn new(
+        config: ForwardProxyServerConfig,
+        upstream: Arc<dyn SpecsUpstream>,
+    ) -> Result<Self, ForwardProxyErr> {
+        let address = config
+            .listen_address
+            .as_deref()
+            .unwrap_or(DEFAULT_LISTEN_ADDRESS)
+            .parse::<SocketAddr>()
+            .map_err(|e| ForwardProxyErr::InvalidConfig(e.to_string()))?;
+
+        let cache = Arc::new(SpecCache::new(
+            upstream,
+            config.cache_ttl_ms,
+            config.cache_max_entries,
+        ));
+        let hub = Arc::new(SpecStreamHub::new(
+            cache.clone(),
+            config.poll_interval_ms,
+            config.stream_broadcast_capacity,
+            config.stream_subscriber_buffer,
+        ));
+
+        Ok(Self {
+            address,
+            cache_ttl_ms: config.cache_ttl_ms,
+            log_events_max_batches: config.log_events_max_batches,
+            log_events_max_events: config.log_events_max_events,
+            cache,
+            hub,
+            id_lists_upstream: None,
+            events_upstream: None,
+        })
+    }
+
+    /// Enables the `getIdListsManifest` and `getIdListFile` RPCs.
+    #[must_use]
+    pub fn with_id_lists_upstream(mut self, upstream: Arc<dyn IdListsUpstream>) -> Self {
+        self.id_lists_upstream = Some(upstream);
+        self
+    }
+
+    /// Enables the `LogEvents` RPC.
+    #[must_use]
+    pub fn with_events_upstream(mut self, upstream: Arc<dyn EventsUpstream>) -> Self {
+        self.events_upstream = Some(upstream);
+        self
+    }
+
+    #[must_use]
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    #[must_use]
+    pub fn stream_hub(&self) -> Arc<SpecStreamHub> {
+        self.hub.clone()
+    }
+
+    pub async fn serve(self) -> Result<(), ForwardProxyErr> {
+        self.serve_with_shutdown(std::future::pending()).await
+    }
+
+    /// Serves until `signal` resolves. Open spec streams are closed with `UNAVAILABLE` so
+    /// SDKs fall back to their backup sources instead of waiting on a dead connection.
+    pub async fn serve_with_shutdown<F>(self, signal: F) -> Result<(), ForwardProxyErr>
+    where
+        F: Future<Output = ()> + Send,
+    {
+        let service = ForwardProxyService::new(self.cache.clone(), self.hub.clone())
+            .with_id_lists_upstream(self.id_lists_upstream.clone())
+            .with_events_upstream(self.events_upstream.clone())
+            .with_manifest_ttl_ms(self.cache_ttl_ms)
+            .with_log_events_limits(self.log_events_max_batches, self.log_events_max_events);
+        let hub = self.hub.clone();
+
+        log_i!(TAG, "Forward proxy listening on {}", self.address);
+
+        let result = Server::builder()
+            .add_service(StatsigForwardProxyServer::new(service))
+            .serve_with_shutdown(self.address, async move {
+                signal.await;
+                hub.shutdown();
+            })
+            .await
+            .map_err(|e| ForwardProxyErr::Transport(e.to_string()));
+
+        self.hub.shutdown();
+        result
+    }
+}
@@ -0,0 +1,343 @@
+use crate::events_upstream::EventsUpstream;
+use crate::forward_proxy_err::ForwardProxyErr;
+use crate::id_lists_upstream::IdListsUpstream;
+use crate::spec_cache::{SpecCache, DEFAULT_CACHE_TTL_MS};
+use crate::spec_stream_hub::{SpecStream, SpecStreamHub};
+use parking_lot::Mutex;
+use serde_json::Value;
+use sigstat_grpc::statsig_forward_proxy::statsig_forward_proxy_server::StatsigForwardProxy;
+use sigstat_grpc::statsig_forward_proxy::{
+    ConfigSpecRequest, ConfigSpecResponse, IdListFileRequest, IdListFileResponse,
+    IdListManifestEntry, IdListsManifestRequest, IdListsManifestResponse, LogEventRequest,
+    LogEventsResponse,
+};
+use statsig_rust::log_event_payload::{LogEventPayload, LogEventRequest as StatsigLogEventRequest};
+use statsig_rust::{log_d, log_w};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tonic::{Request, Response, Status, Streaming};
+
+const TAG: &str = stringify!(ForwardProxyService);
+
+pub const DEFAULT_MAX_LOG_EVENT_BATCHES_PER_STREAM: usize = 100;
+pub const DEFAULT_MAX_LOG_EVENTS_PER_STREAM: usize = 100_000;
+
+/// Implements the `StatsigForwardProxy` gRPC service on top of a [`SpecCache`] and a
+/// [`SpecStreamHub`]. The ID list and event RPCs return `UNIMPLEMENTED` unless an upstream is
+/// configured for them.
+pub struct ForwardProxyService {
+    cache: Arc<SpecCache>,
+    hub: Arc<SpecStreamHub>,
+    id_lists_upstream: Option<Arc<dyn IdListsUpstream>>,
+    events_upstream: Option<Arc<dyn EventsUpstream>>,
+    manifest_ttl: Duration,
+    manifests: Mutex<HashMap<String, (Instant, Vec<IdListManifestEntry>)>>,
+    max_log_event_batches: usize,
+    max_log_events: usize,
+}
+
+impl ForwardProxyService {
+    #[must_use]
+    pub fn new(cache: Arc<SpecCache>, hub: Arc<SpecStreamHub>) -> Self {
+        Self {
+            cache,
+            hub,
+            id_lists_upstream: None,
+            events_upstream: None,
+            manifest_ttl: Duration::from_millis(DEFAULT_CACHE_TTL_MS),
+            manifests: Mutex::new(HashMap::new()),
+            max_log_event_batches: DEFAULT_MAX_LOG_EVENT_BATCHES_PER_STREAM,
+            max_log_events: DEFAULT_MAX_LOG_EVENTS_PER_STREAM,
+        }
+    }
+
+    #[must_use]
+    pub fn with_id_lists_upstream(mut self, upstream: Option<Arc<dyn IdListsUpstream>>) -> Self {
+        self.id_lists_upstream = upstream;
+        self
+    }
+
+    #[must_use]
+    pub fn with_events_upstream(mut self, upstream: Option<Arc<dyn EventsUpstream>>) -> Self {
+        self.events_upstream = upstream;
+        self
+    }
+
+    #[must_use]
+    pub fn with_manifest_ttl_ms(mut self, ttl_ms: Option<u64>) -> Self {
+        self.manifest_ttl = Duration::from_millis(ttl_ms.unwrap_or(DEFAULT_CACHE_TTL_MS));
+        self
+    }
+
+    /// Limits how much of a `LogEvents` stream is buffered before it is forwarded. A stream
+    /// going over either limit fails with `RESOURCE_EXHAUSTED` and nothing is forwarded.
+    #[must_use]
+    pub fn with_log_events_limits(
+        mut self,
+        max_batches: Option<usize>,
+        max_events: Option<usize>,
+    ) -> Self {
+        self.max_log_event_batches =
+            max_batches.unwrap_or(DEFAULT_MAX_LOG_EVENT_BATCHES_PER_STREAM);
+        self.max_log_events = max_events.unwrap_or(DEFAULT_MAX_LOG_EVENTS_PER_STREAM);
+        self
+    }
+
+    fn get_id_lists_upstream(&self) -> Result<&Arc<dyn IdListsUpstream>, ForwardProxyErr> {
+        self.id_lists_upstream.as_ref().ok_or_else(|| {
+            ForwardProxyErr::Unimplemented("ID lists are not enabled on this proxy".to_string())
+        })
+    }
+
+    fn get_cached_manifest(&self, sdk_key: &str) -> Option<Vec<IdListManifestEntry>> {
+        let manifests = self.manifests.lock();
+        let (fetched_at, entries) = manifests.get(sdk_key)?;
+        if fetched_at.elapsed() > self.manifest_ttl {
+            return None;
+        }
+
+        Some(entries.clone())
+    }
+
+    async fn get_manifest(
+        &self,
+        sdk_key: &str,
+        upstream: &Arc<dyn IdListsUpstream>,
+    ) -> Result<Vec<IdListManifestEntry>, ForwardProxyErr> {
+        if let Some(lists) = self.get_cached_manifest(sdk_key) {
+            return Ok(lists);
+        }
+
+        let lists = upstream
+            .fetch_manifest(sdk_key)
+            .await?
+            .into_iter()
+            .map(|metadata| IdListManifestEntry {
+                name: metadata.name,
+                url: metadata.url,
+                file_id: metadata.file_id,
+                size: metadata.size,
+                creation_time: metadata.creation_time,
+            })
+            .collect::<Vec<_>>();
+
+        self.manifests
+            .lock()
+            .insert(sdk_key.to_string(), (Instant::now(), lists.clone()));
+
+        Ok(lists)
+    }
+
+    /// Forwards the buffered batches, merged into one upstream request per SDK key. Returns the
+    /// indexes of the batches that were not forwarded.
+    async fn forward_batches(
+        &self,
+        upstream: &Arc<dyn EventsUpstream>,
+        batches: Vec<(String, LogEventPayload, u64, u32)>,
+    ) -> Vec<u32> {
+        let mut requests: Vec<(String, Vec<u32>, StatsigLogEventRequest)> = Vec::new();
+
+        for (index, (sdk_key, payload, event_count, retries)) in batches.into_iter().enumerate() {
+            let events = match payload.events {
+                Value::Array(events) => events,
+                other => vec![other],
+            };
+
+            match requests.iter_mut().find(|(key, _, _)| *key == sdk_key) {
+                Some((_, indexes, request)) => {
+                    if let Value::Array(merged) = &mut request.payload.events {
+                        merged.extend(events);
+                    }
+                    request.event_count += event_count;
+                    request.retries = request.retries.max(retries);
+                    indexes.push(index as u32);
+                }
+                None => requests.push((
+                    sdk_key,
+                    vec![index as u32],
+                    StatsigLogEventRequest {
+                        payload: LogEventPayload {
+                            events: Value::Array(events),
+                            statsig_metadata: payload.statsig_metadata,
+                        },
+                        event_count,
+                        retries,
+                    },
+                )),
+            }
+        }
+
+        let mut failed_batches = Vec::new();
+        for (sdk_key, indexes, request) in requests {
+            if let Err(e) = upstream.forward_events(&sdk_key, request).await {
+                log_w!(TAG, "Failed to forward {} batch(es): {}", indexes.len(), e);
+                failed_batches.extend(indexes);
+            }
+        }
+
+        failed_batches
+    }
+}
+
+fn validate_sdk_key(sdk_key: &str) -> Result<(), ForwardProxyErr> {
+    if sdk_key.is_empty() {
+        return Err(ForwardProxyErr::InvalidConfig(
+            "sdkKey is required".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[tonic::async_trait]
+impl StatsigForwardProxy for ForwardProxyService {
+    async fn get_config_spec(
+        &self,
+        request: Request<ConfigSpecRequest>,
+    ) -> Result<Response<ConfigSpecResponse>, Status> {
+        let request = request.into_inner();
+        validate_sdk_key(&request.sdk_key)?;
+
+        // specs are served uncompressed, so the caller's zstdDictId is not used
+        let since_time = request.since_time.filter(|t| *t > 0);
+
+        match self.cache.get(&request.sdk_key).await? {
+            Some(response) if since_time.is_some_and(|t| response.last_updated <= t) => {
+                Ok(Response::new(ConfigSpecResponse {
+                    spec: String::new(),
+                    last_updated: since_time.unwrap_or_default(),
+                    zstd_dict_id: None,
+                    has_updates: Some(false),
+                }))
+            }
+            Some(response) => Ok(Response::new((*response).clone())),
+            None => Err(ForwardProxyErr::NoSpecsAvailable(request.sdk_key).into()),
+        }
+    }
+
+    type StreamConfigSpecStream = SpecStream;
+
+    async fn stream_config_spec(
+        &self,
+        request: Request<ConfigSpecRequest>,
+    ) -> Result<Response<Self::StreamConfigSpecStream>, Status> {
+        let request = request.into_inner();
+        validate_sdk_key(&request.sdk_key)?;
+
+        log_d!(TAG, "New spec stream subscriber");
+        let stream = self
+            .hub
+            .subscribe(&request.sdk_key, request.since_time.filter(|t| *t > 0))
+            .await?;
+
+        Ok(Response::new(stream))
+    }
+
+    async fn get_id_lists_manifest(
+        &self,
+        request: Request<IdListsManifestRequest>,
+    ) -> Result<Response<IdListsManifestResponse>, Status> {
+        let request = request.into_inner();
+        validate_sdk_key(&request.sdk_key)?;
+        let upstream = self.get_id_lists_upstream()?;
+
+        let lists = self.get_manifest(&request.sdk_key, upstream).await?;
+        Ok(Response::new(IdListsManifestResponse { lists }))
+    }
+
+    async fn get_id_list_file(
+        &self,
+        request: Request<IdListFileRequest>,
+    ) -> Result<Response<IdListFileResponse>, Status> {
+        let request = request.into_inner();
+        validate_sdk_key(&request.sdk_key)?;
+        let upstream = self.get_id_lists_upstream()?;
+
+        // only files listed in the manifest of the caller's key are fetched, never a caller
+        // provided URL
+        let entry = self
+            .get_manifest(&request.sdk_key, upstream)
+            .await?
+            .into_iter()
+            .find(|entry| entry.name == request.name)
+            .ok_or_else(|| ForwardProxyErr::IdListNotFound(request.name.clone()))?;
+
+        if request.file_id.is_some() && request.file_id != entry.file_id {
+            return Err(ForwardProxyErr::IdListNotFound(format!(
+                "{} has no file {}",
+                request.name,
+                request.file_id.unwrap_or_default()
+            ))
+            .into());
+        }
+
+        let content = upstream
+            .fetch_file(&request.sdk_key, &entry.url, request.range_start)
+            .await?;
+
+        Ok(Response::new(IdListFileResponse {
+            file_size: request.range_start + content.len() as u64,
+            content,
+        }))
+    }
+
+    async fn log_events(
+        &self,
+        request: Request<Streaming<LogEventRequest>>,
+    ) -> Result<Response<LogEventsResponse>, Status> {
+        let upstream = self.events_upstream.as_ref().ok_or_else(|| {
+            ForwardProxyErr::Unimplemented("Event logging is not enabled on this proxy".to_string())
+        })?;
+
+        // nothing is forwarded until the stream ends, so a client retrying a failed stream
+        // never sends a batch twice
+        let mut stream = request.into_inner();
+        let mut batches = Vec::new();
+        let mut event_count = 0;
+        let mut buffered_events = 0;
+
+        while let Some(batch) = stream.message().await? {
+            validate_sdk_key(&batch.sdk_key)?;
+
+            if batches.len() >= self.max_log_event_batches {
+                return Err(Status::resource_exhausted(format!(
+                    "More than {} batches in one stream",
+                    self.max_log_event_batches
+                )));
+            }
+
+            let payload = serde_json::from_str::<LogEventPayload>(&batch.payload)
+                .map_err(|e| Status::invalid_argument(format!("Invalid payload: {e}")))?;
+
+            // counted from the payload, the reported event count is up to the client
+            buffered_events += payload.events.as_array().map_or(0, Vec::len);
+            if buffered_events > self.max_log_events {
+                return Err(Status::resource_exhausted(format!(
+                    "More than {} events in one stream",
+                    self.max_log_events
+                )));
+            }
+
+            event_count += batch.event_count;
+            batches.push((batch.sdk_key, payload, batch.event_count, batch.retries));
+        }
+
+        let batch_count = batches.len() as u64;
+        let failed_batches = self.forward_batches(upstream, batches).await;
+
+        log_d!(
+            TAG,
+            "Forwarded {} batch(es), {} failed",
+            batch_count,
+            failed_batches.len()
+        );
+
+        Ok(Response::new(LogEventsResponse {
+            success: failed_batches.is_empty(),
+            batch_count,
+            event_count,
+            failed_batches,
+        }))
+    }
+}
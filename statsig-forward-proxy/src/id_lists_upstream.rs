@@ -0,0 +1,134 @@
+use crate::forward_proxy_err::ForwardProxyErr;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use statsig_rust::networking::{NetworkClient, RequestArgs};
+use statsig_rust::statsig_metadata::StatsigMetadata;
+use statsig_rust::{IdListMetadata, StatsigErr, StatsigOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub const DEFAULT_UPSTREAM_ID_LISTS_URL: &str = "https://api.statsigcdn.com/v1/get_id_lists";
+const STATSIG_CDN_URL: &str = "https://api.statsigcdn.com";
+
+/// Where the proxy sources ID list manifests and files from.
+#[async_trait]
+pub trait IdListsUpstream: Send + Sync {
+    async fn fetch_manifest(&self, sdk_key: &str) -> Result<Vec<IdListMetadata>, ForwardProxyErr>;
+
+    /// Returns the list file from `range_start` (a byte offset) to its end.
+    async fn fetch_file(
+        &self,
+        sdk_key: &str,
+        url: &str,
+        range_start: u64,
+    ) -> Result<String, ForwardProxyErr>;
+}
+
+pub struct HttpIdListsUpstream {
+    id_lists_url: String,
+    options: StatsigOptions,
+    clients: Mutex<HashMap<String, Arc<NetworkClient>>>,
+}
+
+impl HttpIdListsUpstream {
+    #[must_use]
+    pub fn new(id_lists_url: Option<String>, options: Option<StatsigOptions>) -> Self {
+        Self {
+            id_lists_url: id_lists_url.unwrap_or_else(|| DEFAULT_UPSTREAM_ID_LISTS_URL.to_string()),
+            options: options.unwrap_or_default(),
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get_client(&self, sdk_key: &str) -> Arc<NetworkClient> {
+        let mut clients = self.clients.lock();
+        clients
+            .entry(sdk_key.to_string())
+            .or_insert_with(|| {
+                let headers = StatsigMetadata::get_constant_request_headers(
+                    sdk_key,
+                    self.options.service_name.as_deref(),
+                );
+                Arc::new(NetworkClient::new(
+                    sdk_key,
+                    Some(headers),
+                    Some(&self.options),
+                ))
+            })
+            .clone()
+    }
+
+    async fn get_string(
+        &self,
+        sdk_key: &str,
+        args: RequestArgs,
+    ) -> Result<String, ForwardProxyErr> {
+        let response = self
+            .get_client(sdk_key)
+            .get(args)
+            .await
+            .map_err(|e| ForwardProxyErr::Upstream(StatsigErr::NetworkError(e)))?;
+
+        match response.data {
+            Some(mut data) => data.read_to_string().map_err(ForwardProxyErr::Upstream),
+            None => Err(ForwardProxyErr::InvalidUpstreamResponse(
+                "Empty response from upstream".to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl IdListsUpstream for HttpIdListsUpstream {
+    async fn fetch_manifest(&self, sdk_key: &str) -> Result<Vec<IdListMetadata>, ForwardProxyErr> {
+        let body = self
+            .get_string(
+                sdk_key,
+                RequestArgs {
+                    url: format!("{}/{}.json", self.id_lists_url, sdk_key),
+                    accept_gzip_response: true,
+                    retries: 2,
+                    ..RequestArgs::new()
+                },
+            )
+            .await?;
+
+        let manifest = serde_json::from_str::<HashMap<String, IdListMetadata>>(&body)
+            .map_err(|e| ForwardProxyErr::InvalidUpstreamResponse(e.to_string()))?;
+
+        Ok(manifest.into_values().collect())
+    }
+
+    async fn fetch_file(
+        &self,
+        sdk_key: &str,
+        url: &str,
+        range_start: u64,
+    ) -> Result<String, ForwardProxyErr> {
+        let (headers, query_params) = if url.starts_with(STATSIG_CDN_URL) {
+            (
+                None,
+                Some(HashMap::from([("range".into(), format!("{range_start}-"))])),
+            )
+        } else {
+            (
+                Some(HashMap::from([(
+                    "Range".into(),
+                    format!("bytes={range_start}-"),
+                )])),
+                None,
+            )
+        };
+
+        self.get_string(
+            sdk_key,
+            RequestArgs {
+                url: url.to_string(),
+                headers,
+                query_params,
+                ..RequestArgs::new()
+            },
+        )
+        .await
+    }
+}
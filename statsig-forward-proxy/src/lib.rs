@@ -0,0 +1,23 @@
+//! A gRPC server for the `StatsigForwardProxy` service. It serves specs and ID lists from
+//! upstream and forwards logged events, so SDKs behind it do not each call Statsig.
+//!
+//! Specs are cached and polled per `sdkKey` only. A request's `zstdDictId` is ignored, since
+//! upstream is always asked for specs without a dictionary.
+
+pub mod events_upstream;
+pub mod forward_proxy_err;
+pub mod forward_proxy_server;
+pub mod forward_proxy_service;
+pub mod id_lists_upstream;
+pub mod spec_cache;
+pub mod spec_stream_hub;
+pub mod specs_upstream;
+
+pub use events_upstream::{EventsUpstream, HttpEventsUpstream};
+pub use forward_proxy_err::ForwardProxyErr;
+pub use forward_proxy_server::{ForwardProxyServer, ForwardProxyServerConfig};
+pub use forward_proxy_service::ForwardProxyService;
+pub use id_lists_upstream::{HttpIdListsUpstream, IdListsUpstream};
+pub use spec_cache::SpecCache;
+pub use spec_stream_hub::SpecStreamHub;
+pub use specs_upstream::{DataStoreSpecsUpstream, HttpSpecsUpstream, SpecsUpstream, UpstreamSpecs};
@@ -0,0 +1,51 @@
+use statsig_forward_proxy::{
+    ForwardProxyServer, ForwardProxyServerConfig, HttpEventsUpstream, HttpIdListsUpstream,
+    HttpSpecsUpstream,
+};
+use std::sync::Arc;
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+fn env_num<T: std::str::FromStr>(name: &str) -> Option<T> {
+    env_var(name).and_then(|v| v.parse().ok())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config = ForwardProxyServerConfig {
+        listen_address: env_var("STATSIG_PROXY_LISTEN_ADDRESS"),
+        poll_interval_ms: env_num("STATSIG_PROXY_POLL_INTERVAL_MS"),
+        cache_ttl_ms: env_num("STATSIG_PROXY_CACHE_TTL_MS"),
+        cache_max_entries: env_num("STATSIG_PROXY_CACHE_MAX_ENTRIES"),
+        stream_broadcast_capacity: env_num("STATSIG_PROXY_STREAM_BROADCAST_CAPACITY"),
+        stream_subscriber_buffer: env_num("STATSIG_PROXY_STREAM_SUBSCRIBER_BUFFER"),
+        log_events_max_batches: env_num("STATSIG_PROXY_LOG_EVENTS_MAX_BATCHES"),
+        log_events_max_events: env_num("STATSIG_PROXY_LOG_EVENTS_MAX_EVENTS"),
+    };
+
+    let upstream = Arc::new(HttpSpecsUpstream::new(
+        env_var("STATSIG_PROXY_UPSTREAM_SPECS_URL"),
+        None,
+    ));
+
+    let mut server = ForwardProxyServer::new(config, upstream)?;
+    if env_var("STATSIG_PROXY_DISABLE_ID_LISTS").is_none() {
+        server = server.with_id_lists_upstream(Arc::new(HttpIdListsUpstream::new(
+            env_var("STATSIG_PROXY_UPSTREAM_ID_LISTS_URL"),
+            None,
+        )));
+    }
+    if env_var("STATSIG_PROXY_DISABLE_LOG_EVENTS").is_none() {
+        server = server.with_events_upstream(Arc::new(HttpEventsUpstream::new(None)));
+    }
+
+    server
+        .serve_with_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::forward_proxy_err::ForwardProxyErr;
+use crate::specs_upstream::SpecsUpstream;
+use parking_lot::{Mutex, RwLock};
+use sigstat_grpc::statsig_forward_proxy::ConfigSpecResponse;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_CACHE_TTL_MS: u64 = 10_000;
+pub const DEFAULT_MAX_CACHE_ENTRIES: usize = 1_000;
+
+struct CacheEntry {
+    // `None` records that upstream had no specs for the key
+    response: Option<Arc<ConfigSpecResponse>>,
+    inserted_at: Instant,
+}
+
+/// Caches the latest upstream specs per `sdkKey`. Callers compare their own `sinceTime`
+/// against the cached `lastUpdated`, so every caller of a key shares one entry. Concurrent
+/// misses for the same key share a single upstream request. Entries are not keyed by
+/// `zstdDictId`, since upstream is always asked for specs without a dictionary.
+pub struct SpecCache {
+    upstream: Arc<dyn SpecsUpstream>,
+    ttl: Duration,
+    max_entries: usize,
+    entries: RwLock<HashMap<String, CacheEntry>>,
+    inflight: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl SpecCache {
+    #[must_use]
+    pub fn new(
+        upstream: Arc<dyn SpecsUpstream>,
+        ttl_ms: Option<u64>,
+        max_entries: Option<usize>,
+    ) -> Self {
+        Self {
+            upstream,
+            ttl: Duration::from_millis(ttl_ms.unwrap_or(DEFAULT_CACHE_TTL_MS)),
+            max_entries: max_entries.unwrap_or(DEFAULT_MAX_CACHE_ENTRIES),
+            entries: RwLock::new(HashMap::new()),
+            inflight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the latest specs for `sdk_key`, fetching them if the cached entry expired.
+    pub async fn get(
+        &self,
+        sdk_key: &str,
+    ) -> Result<Option<Arc<ConfigSpecResponse>>, ForwardProxyErr> {
+        if let Some(hit) = self.get_fresh(sdk_key) {
+            return Ok(hit);
+        }
+
+        self.fetch(sdk_key, false).await
+    }
+
+    /// Asks upstream for anything newer than the cached entry, even if it has not expired.
+    pub async fn refresh(
+        &self,
+        sdk_key: &str,
+    ) -> Result<Option<Arc<ConfigSpecResponse>>, ForwardProxyErr> {
+        self.fetch(sdk_key, true).await
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    async fn fetch(
+        &self,
+        sdk_key: &str,
+        force: bool,
+    ) -> Result<Option<Arc<ConfigSpecResponse>>, ForwardProxyErr> {
+        let key_lock = self
+            .inflight
+            .lock()
+            .entry(sdk_key.to_string())
+            .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+            .clone();
+
+        let _guard = key_lock.lock().await;
+
+        // another request may have filled the entry while we waited
+        if !force {
+            if let Some(hit) = self.get_fresh(sdk_key) {
+                return Ok(hit);
+            }
+        }
+
+        let cached = self
+            .entries
+            .read()
+            .get(sdk_key)
+            .and_then(|entry| entry.response.clone());
+
+        let result = self
+            .upstream
+            .fetch_specs(sdk_key, cached.as_ref().map(|r| r.last_updated), None)
+            .await;
+
+        let response = match result {
+            Ok(Some(specs)) => Some(Arc::new(ConfigSpecResponse {
+                spec: specs.spec,
+                last_updated: specs.last_updated,
+                zstd_dict_id: specs.zstd_dict_id,
+                has_updates: Some(true),
+            })),
+            // nothing newer than what is cached
+            Ok(None) => cached,
+            Err(e) => {
+                self.remove_inflight(sdk_key, &key_lock);
+                return Err(e);
+            }
+        };
+
+        self.insert(sdk_key, response.clone());
+        // only after the insert, so a request arriving now finds the entry instead of
+        // starting another fetch
+        self.remove_inflight(sdk_key, &key_lock);
+
+        Ok(response)
+    }
+
+    fn remove_inflight(&self, sdk_key: &str, key_lock: &Arc<tokio::sync::Mutex<()>>) {
+        let mut inflight = self.inflight.lock();
+        if inflight
+            .get(sdk_key)
+            .is_some_and(|lock| Arc::ptr_eq(lock, key_lock))
+        {
+            inflight.remove(sdk_key);
+        }
+    }
+
+    fn get_fresh(&self, sdk_key: &str) -> Option<Option<Arc<ConfigSpecResponse>>> {
+        let entries = self.entries.read();
+        let entry = entries.get(sdk_key)?;
+        if entry.inserted_at.elapsed() > self.ttl {
+            return None;
+        }
+
+        Some(entry.response.clone())
+    }
+
+    fn insert(&self, sdk_key: &str, response: Option<Arc<ConfigSpecResponse>>) {
+        let mut entries = self.entries.write();
+
+        if entries.len() >= self.max_entries && !entries.contains_key(sdk_key) {
+            let ttl = self.ttl;
+            entries.retain(|_, entry| entry.inserted_at.elapsed() <= ttl);
+        }
+
+        if entries.len() >= self.max_entries && !entries.contains_key(sdk_key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.inserted_at)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(
+            sdk_key.to_string(),
+            CacheEntry {
+                response,
+                inserted_at: Instant::now(),
+            },
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use crate::forward_proxy_err::ForwardProxyErr;
+use crate::spec_cache::SpecCache;
+use parking_lot::{Mutex, RwLock};
+use sigstat_grpc::statsig_forward_proxy::ConfigSpecResponse;
+use statsig_rust::{log_d, log_w};
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, watch};
+use tonic::codegen::tokio_stream::wrappers::ReceiverStream;
+use tonic::Status;
+
+const TAG: &str = stringify!(SpecStreamHub);
+
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 10_000;
+pub const DEFAULT_BROADCAST_CAPACITY: usize = 16;
+pub const DEFAULT_SUBSCRIBER_BUFFER: usize = 4;
+
+pub type SpecStream = ReceiverStream<Result<ConfigSpecResponse, Status>>;
+type SpecUpdates = broadcast::Receiver<Arc<ConfigSpecResponse>>;
+
+struct SdkKeyTopic {
+    sender: broadcast::Sender<Arc<ConfigSpecResponse>>,
+    latest: RwLock<Option<Arc<ConfigSpecResponse>>>,
+}
+
+/// Polls the upstream once per sdk key and fans updates out to every `StreamConfigSpec`
+/// subscriber of that key.
+///
+/// Each subscriber reads from a shared broadcast channel into its own small bounded
+/// buffer, so a slow client only blocks its own forwarding task. When a client falls so
+/// far behind that the broadcast channel overwrites updates it never read, it is resynced
+/// with the latest spec instead of replaying every intermediate update.
+pub struct SpecStreamHub {
+    cache: Arc<SpecCache>,
+    poll_interval: Duration,
+    broadcast_capacity: usize,
+    subscriber_buffer: usize,
+    topics: Mutex<HashMap<String, Arc<SdkKeyTopic>>>,
+    shutdown_tx: watch::Sender<bool>,
+}
+
+impl SpecStreamHub {
+    #[must_use]
+    pub fn new(
+        cache: Arc<SpecCache>,
+        poll_interval_ms: Option<u64>,
+        broadcast_capacity: Option<usize>,
+        subscriber_buffer: Option<usize>,
+    ) -> Self {
+        let (shutdown_tx, _) = watch::channel(false);
+
+        Self {
+            cache,
+            poll_interval: Duration::from_millis(
+                poll_interval_ms.unwrap_or(DEFAULT_POLL_INTERVAL_MS),
+            ),
+            broadcast_capacity: broadcast_capacity.unwrap_or(DEFAULT_BROADCAST_CAPACITY),
+            subscriber_buffer: subscriber_buffer.unwrap_or(DEFAULT_SUBSCRIBER_BUFFER),
+            topics: Mutex::new(HashMap::new()),
+            shutdown_tx,
+        }
+    }
+
+    pub async fn subscribe(
+        self: &Arc<Self>,
+        sdk_key: &str,
+        since_time: Option<u64>,
+    ) -> Result<SpecStream, ForwardProxyErr> {
+        let (topic, mut updates) = self.subscribe_to_topic(sdk_key).await?;
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        let (tx, rx) = mpsc::channel(self.subscriber_buffer);
+
+        let initial = topic.latest.read().clone();
+        let initial = initial.filter(|latest| since_time.is_none_or(|t| latest.last_updated > t));
+
+        tokio::spawn(async move {
+            if let Some(initial) = initial {
+                if tx.send(Ok((*initial).clone())).await.is_err() {
+                    return;
+                }
+            }
+
+            loop {
+                let update = tokio::select! {
+                    update = updates.recv() => update,
+                    _ = shutdown_rx.changed() => {
+                        let _ = tx.send(Err(Status::unavailable("Forward proxy shutting down"))).await;
+                        return;
+                    }
+                };
+
+                let response = match update {
+                    Ok(response) => response,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log_d!(TAG, "Subscriber lagged by {} update(s), resyncing", skipped);
+                        match topic.latest.read().clone() {
+                            Some(latest) => latest,
+                            None => continue,
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+
+                // awaiting here applies backpressure to this subscriber only
+                if tx.send(Ok((*response).clone())).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+
+    pub fn subscriber_count(&self, sdk_key: &str) -> usize {
+        self.topics
+            .lock()
+            .get(sdk_key)
+            .map_or(0, |topic| topic.sender.receiver_count())
+    }
+
+    pub fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(true);
+        self.topics.lock().clear();
+    }
+
+    /// Subscribes while holding the `topics` lock, so the poll task can never see the topic
+    /// as idle and remove it between lookup and subscription.
+    async fn subscribe_to_topic(
+        self: &Arc<Self>,
+        sdk_key: &str,
+    ) -> Result<(Arc<SdkKeyTopic>, SpecUpdates), ForwardProxyErr> {
+        if let Some(topic) = self.topics.lock().get(sdk_key) {
+            return Ok((topic.clone(), topic.sender.subscribe()));
+        }
+
+        let latest = self.cache.get(sdk_key).await?;
+
+        let (topic, updates, is_new) = {
+            let mut topics = self.topics.lock();
+            match topics.get(sdk_key) {
+                Some(existing) => (existing.clone(), existing.sender.subscribe(), false),
+                None => {
+                    let (sender, _) = broadcast::channel(self.broadcast_capacity);
+                    let topic = Arc::new(SdkKeyTopic {
+                        sender,
+                        latest: RwLock::new(latest),
+                    });
+                    let updates = topic.sender.subscribe();
+                    topics.insert(sdk_key.to_string(), topic.clone());
+                    (topic, updates, true)
+                }
+            }
+        };
+
+        if is_new {
+            self.spawn_poll_task(sdk_key.to_string(), Arc::downgrade(&topic));
+        }
+
+        Ok((topic, updates))
+    }
+
+    fn spawn_poll_task(self: &Arc<Self>, sdk_key: String, weak_topic: Weak<SdkKeyTopic>) {
+        let weak_hub = Arc::downgrade(self);
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let poll_interval = self.poll_interval;
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    () = tokio::time::sleep(poll_interval) => {}
+                    _ = shutdown_rx.changed() => return,
+                }
+
+                let (Some(hub), Some(topic)) = (weak_hub.upgrade(), weak_topic.upgrade()) else {
+                    return;
+                };
+
+                if hub.try_remove_idle_topic(&sdk_key, &topic) {
+                    log_d!(TAG, "No subscribers left, stopped polling");
+                    return;
+                }
+
+                hub.poll_once(&sdk_key, &topic).await;
+            }
+        });
+    }
+
+    async fn poll_once(&self, sdk_key: &str, topic: &SdkKeyTopic) {
+        let current = topic.latest.read().as_ref().map(|r| r.last_updated);
+
+        let response = match self.cache.refresh(sdk_key).await {
+            Ok(Some(response)) => response,
+            Ok(None) => return,
+            Err(e) => {
+                log_w!(TAG, "Failed to poll upstream: {}", e);
+                return;
+            }
+        };
+
+        if current.is_some_and(|c| response.last_updated <= c) {
+            return;
+        }
+
+        *topic.latest.write() = Some(response.clone());
+
+        // an error only means there are no subscribers right now
+        let _ = topic.sender.send(response);
+    }
+
+    fn try_remove_idle_topic(&self, sdk_key: &str, topic: &Arc<SdkKeyTopic>) -> bool {
+        let mut topics = self.topics.lock();
+        if topic.sender.receiver_count() > 0 {
+            return false;
+        }
+
+        if topics.get(sdk_key).is_some_and(|t| Arc::ptr_eq(t, topic)) {
+            topics.remove(sdk_key);
+        }
+        true
+    }
+}
@@ -0,0 +1,186 @@
+use crate::forward_proxy_err::ForwardProxyErr;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use statsig_rust::data_store_interface::{get_data_store_key, DataStoreTrait, RequestPath};
+use statsig_rust::hashing::HashUtil;
+use statsig_rust::networking::{NetworkClient, RequestArgs};
+use statsig_rust::statsig_metadata::StatsigMetadata;
+use statsig_rust::{StatsigErr, StatsigOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub const DEFAULT_UPSTREAM_SPECS_URL: &str = "https://api.statsigcdn.com/v2/download_config_specs";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpstreamSpecs {
+    pub spec: String,
+    pub last_updated: u64,
+    pub zstd_dict_id: Option<String>,
+}
+
+/// Where the proxy sources config specs from. Returning `Ok(None)` means the upstream has
+/// nothing newer than `since_time`.
+#[async_trait]
+pub trait SpecsUpstream: Send + Sync {
+    async fn fetch_specs(
+        &self,
+        sdk_key: &str,
+        since_time: Option<u64>,
+        zstd_dict_id: Option<&str>,
+    ) -> Result<Option<UpstreamSpecs>, ForwardProxyErr>;
+}
+
+#[derive(Deserialize)]
+struct SpecsEnvelope {
+    time: Option<u64>,
+    has_updates: Option<bool>,
+}
+
+fn parse_specs(
+    spec: String,
+    since_time: Option<u64>,
+    fallback_time: Option<u64>,
+) -> Result<Option<UpstreamSpecs>, ForwardProxyErr> {
+    let envelope = serde_json::from_str::<SpecsEnvelope>(&spec)
+        .map_err(|e| ForwardProxyErr::InvalidUpstreamResponse(e.to_string()))?;
+
+    if envelope.has_updates == Some(false) {
+        return Ok(None);
+    }
+
+    let last_updated = envelope.time.or(fallback_time).unwrap_or(0);
+    if matches!(since_time, Some(since) if since >= last_updated && last_updated != 0) {
+        return Ok(None);
+    }
+
+    Ok(Some(UpstreamSpecs {
+        spec,
+        last_updated,
+        zstd_dict_id: None,
+    }))
+}
+
+// ------------------------------------------------------------------------------- [ HTTP ]
+
+pub struct HttpSpecsUpstream {
+    specs_url: String,
+    options: StatsigOptions,
+    clients: Mutex<HashMap<String, Arc<NetworkClient>>>,
+}
+
+impl HttpSpecsUpstream {
+    #[must_use]
+    pub fn new(specs_url: Option<String>, options: Option<StatsigOptions>) -> Self {
+        Self {
+            specs_url: specs_url.unwrap_or_else(|| DEFAULT_UPSTREAM_SPECS_URL.to_string()),
+            options: options.unwrap_or_default(),
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get_client(&self, sdk_key: &str) -> Arc<NetworkClient> {
+        let mut clients = self.clients.lock();
+        clients
+            .entry(sdk_key.to_string())
+            .or_insert_with(|| {
+                let headers = StatsigMetadata::get_constant_request_headers(
+                    sdk_key,
+                    self.options.service_name.as_deref(),
+                );
+                Arc::new(NetworkClient::new(
+                    sdk_key,
+                    Some(headers),
+                    Some(&self.options),
+                ))
+            })
+            .clone()
+    }
+}
+
+#[async_trait]
+impl SpecsUpstream for HttpSpecsUpstream {
+    async fn fetch_specs(
+        &self,
+        sdk_key: &str,
+        since_time: Option<u64>,
+        _zstd_dict_id: Option<&str>,
+    ) -> Result<Option<UpstreamSpecs>, ForwardProxyErr> {
+        let mut params = HashMap::new();
+        if let Some(since_time) = since_time.filter(|t| *t > 0) {
+            params.insert("sinceTime".to_string(), since_time.to_string());
+        }
+
+        let response = self
+            .get_client(sdk_key)
+            .get(RequestArgs {
+                url: format!("{}/{}.json", self.specs_url, sdk_key),
+                query_params: Some(params),
+                accept_gzip_response: true,
+                retries: 2,
+                ..RequestArgs::new()
+            })
+            .await
+            .map_err(|e| ForwardProxyErr::Upstream(StatsigErr::NetworkError(e)))?;
+
+        let spec = match response.data {
+            Some(mut data) => data.read_to_string().map_err(ForwardProxyErr::Upstream)?,
+            None => {
+                return Err(ForwardProxyErr::InvalidUpstreamResponse(
+                    "Empty response from upstream".to_string(),
+                ))
+            }
+        };
+
+        parse_specs(spec, since_time, None)
+    }
+}
+
+// ------------------------------------------------------------------------------- [ Data Store ]
+
+/// Reads specs written by SDKs (or a separate writer) into a shared [`DataStoreTrait`],
+/// using the same key layout as the SDK's data store specs adapter.
+pub struct DataStoreSpecsUpstream {
+    data_store: Arc<dyn DataStoreTrait>,
+    options: StatsigOptions,
+    hashing: HashUtil,
+}
+
+impl DataStoreSpecsUpstream {
+    #[must_use]
+    pub fn new(data_store: Arc<dyn DataStoreTrait>, options: Option<StatsigOptions>) -> Self {
+        Self {
+            data_store,
+            options: options.unwrap_or_default(),
+            hashing: HashUtil::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl SpecsUpstream for DataStoreSpecsUpstream {
+    async fn fetch_specs(
+        &self,
+        sdk_key: &str,
+        since_time: Option<u64>,
+        _zstd_dict_id: Option<&str>,
+    ) -> Result<Option<UpstreamSpecs>, ForwardProxyErr> {
+        let key = get_data_store_key(
+            RequestPath::RulesetsV2,
+            sdk_key,
+            &self.hashing,
+            &self.options,
+        );
+
+        let response = self
+            .data_store
+            .get(&key)
+            .await
+            .map_err(ForwardProxyErr::Upstream)?;
+
+        match response.result {
+            Some(spec) => parse_specs(spec, since_time, response.time),
+            None => Ok(None),
+        }
+    }
+}
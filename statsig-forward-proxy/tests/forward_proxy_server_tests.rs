@@ -0,0 +1,438 @@
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use sigstat_grpc::statsig_forward_proxy::LogEventRequest;
+use sigstat_grpc::statsig_grpc_client::StatsigGrpcClient;
+use sigstat_grpc::statsig_grpc_err::StatsigGrpcErr;
+use statsig_forward_proxy::{
+    EventsUpstream, ForwardProxyErr, ForwardProxyServer, ForwardProxyServerConfig, IdListsUpstream,
+    SpecsUpstream, UpstreamSpecs,
+};
+use statsig_rust::log_event_payload::LogEventRequest as StatsigLogEventRequest;
+use statsig_rust::{IdListMetadata, StatsigErr};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tonic::Code;
+
+const SDK_KEY: &str = "server-proxy-test";
+
+struct FakeUpstream {
+    lcut: AtomicU64,
+    times_called: AtomicUsize,
+}
+
+impl FakeUpstream {
+    fn new(lcut: u64) -> Arc<Self> {
+        Arc::new(Self {
+            lcut: AtomicU64::new(lcut),
+            times_called: AtomicUsize::new(0),
+        })
+    }
+}
+
+#[async_trait]
+impl SpecsUpstream for FakeUpstream {
+    async fn fetch_specs(
+        &self,
+        _sdk_key: &str,
+        since_time: Option<u64>,
+        _zstd_dict_id: Option<&str>,
+    ) -> Result<Option<UpstreamSpecs>, ForwardProxyErr> {
+        self.times_called.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let lcut = self.lcut.load(Ordering::SeqCst);
+        if since_time.is_some_and(|t| t >= lcut) {
+            return Ok(None);
+        }
+
+        Ok(Some(UpstreamSpecs {
+            spec: format!("{{\"time\":{lcut}}}"),
+            last_updated: lcut,
+            zstd_dict_id: None,
+        }))
+    }
+}
+
+#[derive(Default)]
+struct FakeEventsUpstream {
+    received: Mutex<Vec<(String, u64)>>,
+    should_fail: AtomicBool,
+}
+
+#[async_trait]
+impl EventsUpstream for FakeEventsUpstream {
+    async fn forward_events(
+        &self,
+        sdk_key: &str,
+        request: StatsigLogEventRequest,
+    ) -> Result<(), ForwardProxyErr> {
+        if self.should_fail.load(Ordering::SeqCst) {
+            return Err(ForwardProxyErr::Upstream(StatsigErr::LogEventError(
+                "upstream down".to_string(),
+            )));
+        }
+
+        self.received
+            .lock()
+            .push((sdk_key.to_string(), request.event_count));
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct FakeIdListsUpstream {
+    fetched_urls: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl IdListsUpstream for FakeIdListsUpstream {
+    async fn fetch_manifest(&self, _sdk_key: &str) -> Result<Vec<IdListMetadata>, ForwardProxyErr> {
+        Ok(vec![IdListMetadata {
+            name: "list_a".to_string(),
+            url: "https://fake-id-list-cdn/list_a".to_string(),
+            file_id: Some("file_a".to_string()),
+            size: 6,
+            creation_time: 1,
+        }])
+    }
+
+    async fn fetch_file(
+        &self,
+        _sdk_key: &str,
+        url: &str,
+        _range_start: u64,
+    ) -> Result<String, ForwardProxyErr> {
+        self.fetched_urls.lock().push(url.to_string());
+        Ok("+a\n+b\n".to_string())
+    }
+}
+
+async fn start_proxy(port: u16, upstream: Arc<FakeUpstream>) -> Arc<Notify> {
+    start_proxy_with_events(port, upstream, None).await
+}
+
+async fn start_proxy_with_events(
+    port: u16,
+    upstream: Arc<FakeUpstream>,
+    events_upstream: Option<Arc<FakeEventsUpstream>>,
+) -> Arc<Notify> {
+    start_proxy_with_upstreams(port, upstream, events_upstream, None).await
+}
+
+async fn start_proxy_with_upstreams(
+    port: u16,
+    upstream: Arc<FakeUpstream>,
+    events_upstream: Option<Arc<FakeEventsUpstream>>,
+    id_lists_upstream: Option<Arc<FakeIdListsUpstream>>,
+) -> Arc<Notify> {
+    let mut server = ForwardProxyServer::new(create_config(port), upstream).unwrap();
+    if let Some(events_upstream) = events_upstream {
+        server = server.with_events_upstream(events_upstream);
+    }
+    if let Some(id_lists_upstream) = id_lists_upstream {
+        server = server.with_id_lists_upstream(id_lists_upstream);
+    }
+    serve_in_background(server).await
+}
+
+fn create_config(port: u16) -> ForwardProxyServerConfig {
+    ForwardProxyServerConfig {
+        listen_address: Some(format!("127.0.0.1:{port}")),
+        poll_interval_ms: Some(10),
+        cache_ttl_ms: Some(1_000),
+        ..ForwardProxyServerConfig::default()
+    }
+}
+
+async fn serve_in_background(server: ForwardProxyServer) -> Arc<Notify> {
+    let shutdown = Arc::new(Notify::new());
+    let signal = shutdown.clone();
+    tokio::spawn(async move {
+        let _ = server
+            .serve_with_shutdown(async move { signal.notified().await })
+            .await;
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    shutdown
+}
+
+fn create_client(port: u16) -> StatsigGrpcClient {
+    StatsigGrpcClient::new(
+        SDK_KEY,
+        &format!("http://127.0.0.1:{port}"),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+async fn test_get_config_spec_is_cached() {
+    let upstream = FakeUpstream::new(100);
+    let shutdown = start_proxy(50_351, upstream.clone()).await;
+    let client = create_client(50_351);
+
+    let (first, second) = tokio::join!(client.get_specs(None), client.get_specs(None));
+
+    assert_eq!(first.unwrap().last_updated, 100);
+    assert_eq!(second.unwrap().last_updated, 100);
+    assert_eq!(upstream.times_called.load(Ordering::SeqCst), 1);
+
+    shutdown.notify_one();
+}
+
+#[tokio::test]
+async fn test_get_config_spec_shares_one_entry_across_since_times() {
+    let upstream = FakeUpstream::new(100);
+    let shutdown = start_proxy(50_359, upstream.clone()).await;
+    let client = create_client(50_359);
+
+    let (no_update, older) = tokio::join!(client.get_specs(Some(100)), client.get_specs(Some(50)));
+
+    let no_update = no_update.unwrap();
+    assert_eq!(no_update.has_updates, Some(false));
+    assert_eq!(no_update.spec, "");
+    assert_eq!(no_update.zstd_dict_id, None);
+
+    let older = older.unwrap();
+    assert_eq!(older.has_updates, Some(true));
+    assert_eq!(older.last_updated, 100);
+    assert_eq!(older.zstd_dict_id, None);
+
+    assert_eq!(upstream.times_called.load(Ordering::SeqCst), 1);
+
+    shutdown.notify_one();
+}
+
+#[tokio::test]
+async fn test_stream_receives_updates() {
+    let upstream = FakeUpstream::new(100);
+    let shutdown = start_proxy(50_352, upstream.clone()).await;
+    let client = create_client(50_352);
+
+    let mut stream = client.get_specs_stream(None).await.unwrap();
+    let initial = stream.message().await.unwrap().unwrap();
+    assert_eq!(initial.last_updated, 100);
+
+    upstream.lcut.store(200, Ordering::SeqCst);
+    let update = tokio::time::timeout(Duration::from_secs(2), stream.message())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(update.last_updated, 200);
+
+    shutdown.notify_one();
+}
+
+#[tokio::test]
+async fn test_stream_closes_with_unavailable_on_shutdown() {
+    let upstream = FakeUpstream::new(100);
+    let shutdown = start_proxy(50_353, upstream).await;
+    let client = create_client(50_353);
+
+    let mut stream = client.get_specs_stream(Some(100)).await.unwrap();
+    shutdown.notify_one();
+
+    let result = tokio::time::timeout(Duration::from_secs(2), stream.message())
+        .await
+        .unwrap();
+    assert!(result.is_err());
+}
+
+fn create_log_event_batch(event_count: u64) -> LogEventRequest {
+    LogEventRequest {
+        sdk_key: String::new(),
+        payload: r#"{"events":[],"statsigMetadata":{}}"#.to_string(),
+        event_count,
+        retries: 0,
+    }
+}
+
+#[tokio::test]
+async fn test_log_events_are_forwarded_upstream() {
+    let events_upstream = Arc::new(FakeEventsUpstream::default());
+    let shutdown = start_proxy_with_events(
+        50_354,
+        FakeUpstream::new(100),
+        Some(events_upstream.clone()),
+    )
+    .await;
+    let client = create_client(50_354);
+
+    let response = client
+        .log_events(vec![create_log_event_batch(2), create_log_event_batch(3)])
+        .await
+        .unwrap();
+
+    assert!(response.success);
+    assert_eq!(response.batch_count, 2);
+    assert_eq!(response.event_count, 5);
+    // the batches of a stream are forwarded as a single upstream request
+    assert_eq!(
+        *events_upstream.received.lock(),
+        vec![(SDK_KEY.to_string(), 5)]
+    );
+
+    shutdown.notify_one();
+}
+
+#[tokio::test]
+async fn test_log_events_reports_failed_batches() {
+    let events_upstream = Arc::new(FakeEventsUpstream::default());
+    events_upstream.should_fail.store(true, Ordering::SeqCst);
+    let shutdown = start_proxy_with_events(
+        50_356,
+        FakeUpstream::new(100),
+        Some(events_upstream.clone()),
+    )
+    .await;
+    let client = create_client(50_356);
+
+    let response = client
+        .log_events(vec![create_log_event_batch(2), create_log_event_batch(3)])
+        .await
+        .unwrap();
+
+    assert!(!response.success);
+    assert_eq!(response.failed_batches, vec![0, 1]);
+
+    shutdown.notify_one();
+}
+
+#[tokio::test]
+async fn test_log_events_forwards_nothing_for_an_invalid_stream() {
+    let events_upstream = Arc::new(FakeEventsUpstream::default());
+    let shutdown = start_proxy_with_events(
+        50_357,
+        FakeUpstream::new(100),
+        Some(events_upstream.clone()),
+    )
+    .await;
+    let client = create_client(50_357);
+
+    let invalid_batch = LogEventRequest {
+        payload: "not json".to_string(),
+        ..create_log_event_batch(1)
+    };
+    let result = client
+        .log_events(vec![create_log_event_batch(2), invalid_batch])
+        .await;
+
+    assert!(result.is_err());
+    assert!(events_upstream.received.lock().is_empty());
+
+    shutdown.notify_one();
+}
+
+fn is_resource_exhausted<T>(result: &Result<T, StatsigGrpcErr>) -> bool {
+    matches!(
+        result,
+        Err(StatsigGrpcErr::ErrorGrpcStatus(status)) if status.code() == Code::ResourceExhausted
+    )
+}
+
+#[tokio::test]
+async fn test_log_events_rejects_streams_over_the_limits() {
+    let events_upstream = Arc::new(FakeEventsUpstream::default());
+    let config = ForwardProxyServerConfig {
+        log_events_max_batches: Some(2),
+        log_events_max_events: Some(3),
+        ..create_config(50_360)
+    };
+    let server = ForwardProxyServer::new(config, FakeUpstream::new(100))
+        .unwrap()
+        .with_events_upstream(events_upstream.clone());
+    let shutdown = serve_in_background(server).await;
+    let client = create_client(50_360);
+
+    let batch_with_events = |count: usize| LogEventRequest {
+        payload: format!(
+            r#"{{"events":[{}],"statsigMetadata":{{}}}}"#,
+            vec!["{}"; count].join(",")
+        ),
+        ..create_log_event_batch(count as u64)
+    };
+
+    let too_many_batches = client
+        .log_events(vec![
+            create_log_event_batch(0),
+            create_log_event_batch(0),
+            create_log_event_batch(0),
+        ])
+        .await;
+    assert!(is_resource_exhausted(&too_many_batches));
+
+    // the reported event count is not trusted, the payload is counted
+    let too_many_events = client
+        .log_events(vec![
+            batch_with_events(2),
+            LogEventRequest {
+                event_count: 0,
+                ..batch_with_events(2)
+            },
+        ])
+        .await;
+    assert!(is_resource_exhausted(&too_many_events));
+    assert!(events_upstream.received.lock().is_empty());
+
+    let response = client
+        .log_events(vec![batch_with_events(1), batch_with_events(2)])
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert_eq!(response.event_count, 3);
+
+    shutdown.notify_one();
+}
+
+#[tokio::test]
+async fn test_id_list_file_is_resolved_from_manifest() {
+    let id_lists_upstream = Arc::new(FakeIdListsUpstream::default());
+    let shutdown = start_proxy_with_upstreams(
+        50_358,
+        FakeUpstream::new(100),
+        None,
+        Some(id_lists_upstream.clone()),
+    )
+    .await;
+    let client = create_client(50_358);
+
+    let response = client
+        .get_id_list_file("list_a", Some("file_a".to_string()), 0)
+        .await
+        .unwrap();
+    assert_eq!(response.content, "+a\n+b\n");
+
+    assert!(client
+        .get_id_list_file("unknown_list", None, 0)
+        .await
+        .is_err());
+    assert!(client
+        .get_id_list_file("list_a", Some("stale_file".to_string()), 0)
+        .await
+        .is_err());
+
+    assert_eq!(
+        *id_lists_upstream.fetched_urls.lock(),
+        vec!["https://fake-id-list-cdn/list_a".to_string()]
+    );
+
+    shutdown.notify_one();
+}
+
+#[tokio::test]
+async fn test_id_lists_unimplemented_without_upstream() {
+    let shutdown = start_proxy(50_355, FakeUpstream::new(100)).await;
+    let client = create_client(50_355);
+
+    let result = client.get_id_lists_manifest().await;
+    assert!(result.is_err());
+
+    shutdown.notify_one();
+}
@@ -1,5 +1,6 @@
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::Arc;
@@ -9,14 +10,18 @@ use tokio::sync::Notify;
 use tokio::task::JoinHandle;
 use tonic::codegen::tokio_stream::wrappers::ReceiverStream;
 use tonic::transport::Server;
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 
 pub mod api {
     tonic::include_proto!("statsig_forward_proxy");
 }
 
 use api::statsig_forward_proxy_server::{StatsigForwardProxy, StatsigForwardProxyServer};
-use api::{ConfigSpecRequest, ConfigSpecResponse};
+use api::{
+    ConfigSpecRequest, ConfigSpecResponse, IdListFileRequest, IdListFileResponse,
+    IdListManifestEntry, IdListsManifestRequest, IdListsManifestResponse, LogEventRequest,
+    LogEventsResponse,
+};
 
 lazy_static! {
     static ref PORT_ID: AtomicI32 = AtomicI32::new(50051);
@@ -30,6 +35,7 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
             spec: "bg_sync".to_string(),
             last_updated: 123,
             zstd_dict_id: None,
+            has_updates: None,
         }))
         .await;
 
@@ -42,6 +48,9 @@ pub async fn wait_one_ms() {
 pub struct MockForwardProxy {
     pub proxy_address: SocketAddr,
     pub stubbed_get_config_spec_response: Mutex<ConfigSpecResponse>,
+    pub stubbed_id_lists: Mutex<HashMap<String, (IdListManifestEntry, String)>>,
+    pub received_id_list_file_requests: Mutex<Vec<IdListFileRequest>>,
+    pub received_log_event_requests: Mutex<Vec<LogEventRequest>>,
 
     shutdown_notifier: Arc<Notify>,
     server_handle: Mutex<Option<JoinHandle<()>>>,
@@ -61,7 +70,11 @@ impl MockForwardProxy {
                 spec: "NOT STUBBED".to_string(),
                 last_updated: 0,
                 zstd_dict_id: None,
+                has_updates: None,
             }),
+            stubbed_id_lists: Mutex::new(HashMap::new()),
+            received_id_list_file_requests: Mutex::new(Vec::new()),
+            received_log_event_requests: Mutex::new(Vec::new()),
 
             shutdown_notifier: Arc::new(Notify::new()),
             server_handle: Mutex::new(None),
@@ -85,6 +98,33 @@ impl MockForwardProxy {
         }
     }
 
+    /// Adds (or replaces) an ID list served by the manifest and file RPCs.
+    pub fn stub_id_list(&self, name: &str, file_id: &str, creation_time: i64, content: &str) {
+        let entry = IdListManifestEntry {
+            name: name.to_string(),
+            url: format!("https://fake-id-list-cdn/{file_id}"),
+            file_id: Some(file_id.to_string()),
+            size: content.len() as u64,
+            creation_time,
+        };
+
+        self.stubbed_id_lists
+            .lock()
+            .insert(name.to_string(), (entry, content.to_string()));
+    }
+
+    pub fn remove_stubbed_id_list(&self, name: &str) {
+        self.stubbed_id_lists.lock().remove(name);
+    }
+
+    pub fn get_received_log_event_requests(&self) -> Vec<LogEventRequest> {
+        self.received_log_event_requests.lock().clone()
+    }
+
+    pub fn get_received_id_list_file_requests(&self) -> Vec<IdListFileRequest> {
+        self.received_id_list_file_requests.lock().clone()
+    }
+
     pub async fn stop(&self) {
         let handle = self.server_handle.try_lock().unwrap().take();
         if let Some(handle) = handle {
@@ -156,4 +196,63 @@ impl StatsigForwardProxy for MockForwardProxyService {
         let stream = ReceiverStream::new(rx);
         Ok(Response::new(stream))
     }
+
+    async fn get_id_lists_manifest(
+        &self,
+        _request: Request<IdListsManifestRequest>,
+    ) -> Result<Response<IdListsManifestResponse>, Status> {
+        let lists = self
+            .proxy
+            .stubbed_id_lists
+            .lock()
+            .values()
+            .map(|(entry, _)| entry.clone())
+            .collect();
+
+        Ok(Response::new(IdListsManifestResponse { lists }))
+    }
+
+    async fn get_id_list_file(
+        &self,
+        request: Request<IdListFileRequest>,
+    ) -> Result<Response<IdListFileResponse>, Status> {
+        let request = request.into_inner();
+        self.proxy
+            .received_id_list_file_requests
+            .lock()
+            .push(request.clone());
+
+        let lists = self.proxy.stubbed_id_lists.lock();
+        let Some((_, content)) = lists.get(&request.name) else {
+            return Err(Status::not_found(format!("No ID list {}", request.name)));
+        };
+
+        let start = (request.range_start as usize).min(content.len());
+        Ok(Response::new(IdListFileResponse {
+            content: content[start..].to_string(),
+            file_size: content.len() as u64,
+        }))
+    }
+
+    async fn log_events(
+        &self,
+        request: Request<Streaming<LogEventRequest>>,
+    ) -> Result<Response<LogEventsResponse>, Status> {
+        let mut stream = request.into_inner();
+        let mut batch_count = 0;
+        let mut event_count = 0;
+
+        while let Some(batch) = stream.message().await? {
+            batch_count += 1;
+            event_count += batch.event_count;
+            self.proxy.received_log_event_requests.lock().push(batch);
+        }
+
+        Ok(Response::new(LogEventsResponse {
+            success: true,
+            batch_count,
+            event_count,
+            failed_batches: vec![],
+        }))
+    }
 }
@@ -1,9 +1,13 @@
 use crate::statsig_forward_proxy::config_spec_request::ApiVersion;
 use crate::statsig_forward_proxy::statsig_forward_proxy_client::StatsigForwardProxyClient;
-use crate::statsig_forward_proxy::{ConfigSpecRequest, ConfigSpecResponse};
+use crate::statsig_forward_proxy::{
+    ConfigSpecRequest, ConfigSpecResponse, IdListFileRequest, IdListFileResponse,
+    IdListsManifestRequest, IdListsManifestResponse, LogEventRequest, LogEventsResponse,
+};
 use crate::statsig_grpc_err::StatsigGrpcErr;
 use parking_lot::Mutex;
 use std::time::Duration;
+use tonic::codegen::tokio_stream;
 use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity};
 use tonic::Streaming;
 
@@ -79,6 +83,63 @@ impl StatsigGrpcClient {
             .map(|s| s.into_inner())
     }
 
+    pub async fn get_id_lists_manifest(&self) -> Result<IdListsManifestResponse, StatsigGrpcErr> {
+        let request = IdListsManifestRequest {
+            sdk_key: self.sdk_key.clone(),
+        };
+        let mut client = self.get_or_setup_grpc_client().await?;
+
+        client
+            .get_id_lists_manifest(request)
+            .await
+            .map_err(StatsigGrpcErr::ErrorGrpcStatus)
+            .map(|r| r.into_inner())
+    }
+
+    /// The proxy resolves the file from its own manifest for this client's SDK key.
+    pub async fn get_id_list_file(
+        &self,
+        name: &str,
+        file_id: Option<String>,
+        range_start: u64,
+    ) -> Result<IdListFileResponse, StatsigGrpcErr> {
+        let request = IdListFileRequest {
+            sdk_key: self.sdk_key.clone(),
+            name: name.to_string(),
+            file_id,
+            range_start,
+        };
+        let mut client = self.get_or_setup_grpc_client().await?;
+
+        client
+            .get_id_list_file(request)
+            .await
+            .map_err(StatsigGrpcErr::ErrorGrpcStatus)
+            .map(|r| r.into_inner())
+    }
+
+    /// Streams the given batches to the proxy over a single `LogEvents` call.
+    /// The `sdk_key` of each batch is filled in by the client.
+    pub async fn log_events(
+        &self,
+        batches: Vec<LogEventRequest>,
+    ) -> Result<LogEventsResponse, StatsigGrpcErr> {
+        let batches = batches
+            .into_iter()
+            .map(|batch| LogEventRequest {
+                sdk_key: self.sdk_key.clone(),
+                ..batch
+            })
+            .collect::<Vec<_>>();
+        let mut client = self.get_or_setup_grpc_client().await?;
+
+        client
+            .log_events(tokio_stream::iter(batches))
+            .await
+            .map_err(StatsigGrpcErr::ErrorGrpcStatus)
+            .map(|r| r.into_inner())
+    }
+
     fn setup_tls_client(
         authentication_mode: Option<String>,
         ca_cert_path: Option<String>,
@@ -21,4 +21,15 @@ export type SdkEvent =
   | 'dynamic_config_evaluated'
   | 'experiment_evaluated'
   | 'layer_evaluated'
-  | 'specs_updated';
+  | 'specs_updated'
+  | 'specs_sync_failed'
+  | 'id_list_added'
+  | 'id_list_updated'
+  | 'id_list_removed'
+  | 'events_dropped'
+  | 'event_flush_failed'
+  | 'override_set'
+  | 'override_removed'
+  | 'shutdown_started'
+  | 'shutdown_completed'
+  | 'overrides_reloaded';
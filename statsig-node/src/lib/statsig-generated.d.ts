@@ -88,6 +88,8 @@ export declare class StatsigUser {
   set locale(value: any)
   get appVersion(): string | null
   set appVersion(value: any)
+  get userAgentClientHints(): UserAgentClientHintsArgs | null
+  set userAgentClientHints(value?: UserAgentClientHintsArgs | undefined | null)
   toJSON(): any
 }
 
@@ -306,10 +308,19 @@ export interface StatsigUserArgs {
   country?: string
   locale?: string
   appVersion?: string
+  userAgentClientHints?: UserAgentClientHintsArgs
   statsigEnvironment?: { tier?: string, [key: string]: string | undefined } | undefined
   custom?: Record<string, string | number | boolean | Array<string | number | boolean> | null | undefined | Record<string, unknown>>
   privateAttributes?: Record<string, string | number | boolean | Array<string | number | boolean> | Record<string, unknown>>
 }
+/** The raw `Sec-CH-UA*` header values, e.g. `brands: '"Chromium";v="124"'`. */
+export interface UserAgentClientHintsArgs {
+  brands?: string
+  fullVersionList?: string
+  platform?: string
+  platformVersion?: string
+  mobile?: boolean
+}
 // ---- Manually defined typing section ----- 
 
 export type StickyValues = {
@@ -333,4 +344,15 @@ export type SdkEvent =
   | 'dynamic_config_evaluated'
   | 'experiment_evaluated'
   | 'layer_evaluated'
-  | 'specs_updated';
+  | 'specs_updated'
+  | 'specs_sync_failed'
+  | 'id_list_added'
+  | 'id_list_updated'
+  | 'id_list_removed'
+  | 'events_dropped'
+  | 'event_flush_failed'
+  | 'override_set'
+  | 'override_removed'
+  | 'shutdown_started'
+  | 'shutdown_completed'
+  | 'overrides_reloaded';
@@ -257,6 +257,18 @@ impl StatsigOptions<'_> {
             // Not exposed via the Node binding yet; defaults to the core value.
             dcs_delta_no_progress_threshold: None,
             id_lists_adapter: None,
+            redaction_policy: None,
+            cmab_learner: None,
+            runtime_options: None,
+            event_logging_max_flush_interval_ms: None,
+            clock: None,
+            id_list_storage: None,
+            regex_safety: None,
+            user_enricher: None,
+            disable_bot_exposure_logging: None,
+            exposure_dedupe_persistence: None,
+            exposure_sampling_policy: None,
+            share_http_connection_pool: None,
             specs_adapter: None,
             disable_disk_access: None,
         };
@@ -5,7 +5,7 @@ use napi_derive::napi;
 use serde_json::{json, Value};
 use statsig_rust::{
     dyn_value, log_w, user::user_data::UserData, DynamicValue, StatsigUser as StatsigUserActual,
-    StatsigUserDataMap,
+    StatsigUserDataMap, UserAgentClientHints,
 };
 
 const TAG: &str = "StatsigUserNapi";
@@ -24,6 +24,7 @@ pub struct StatsigUserArgs {
     pub country: Option<String>,
     pub locale: Option<String>,
     pub app_version: Option<String>,
+    pub user_agent_client_hints: Option<UserAgentClientHintsArgs>,
     #[napi(ts_type = "{ tier?: string, [key: string]: string | undefined } | undefined")]
     pub statsig_environment: Option<HashMap<String, String>>,
 
@@ -38,6 +39,40 @@ pub struct StatsigUserArgs {
     pub private_attributes: Option<HashMap<String, ValidPrimitives>>,
 }
 
+/// The raw `Sec-CH-UA*` header values, e.g. `brands: '"Chromium";v="124"'`.
+#[napi(object)]
+pub struct UserAgentClientHintsArgs {
+    pub brands: Option<String>,
+    pub full_version_list: Option<String>,
+    pub platform: Option<String>,
+    pub platform_version: Option<String>,
+    pub mobile: Option<bool>,
+}
+
+impl From<UserAgentClientHintsArgs> for UserAgentClientHints {
+    fn from(args: UserAgentClientHintsArgs) -> Self {
+        UserAgentClientHints {
+            brands: args.brands,
+            full_version_list: args.full_version_list,
+            platform: args.platform,
+            platform_version: args.platform_version,
+            mobile: args.mobile,
+        }
+    }
+}
+
+impl From<&UserAgentClientHints> for UserAgentClientHintsArgs {
+    fn from(hints: &UserAgentClientHints) -> Self {
+        UserAgentClientHintsArgs {
+            brands: hints.brands.clone(),
+            full_version_list: hints.full_version_list.clone(),
+            platform: hints.platform.clone(),
+            platform_version: hints.platform_version.clone(),
+            mobile: hints.mobile,
+        }
+    }
+}
+
 #[napi]
 pub struct StatsigUser {
     inner: StatsigUserActual,
@@ -55,10 +90,9 @@ macro_rules! set_dynamic_value_fields {
 
 fn unidentifiable_user() -> UserData {
     log_w!(TAG, "Must pass a valid user with a userID or customID for the server SDK to work. See https://docs.statsig.com/messages/serverRequiredUserID for more details.");
-    UserData {
-        user_id: Some(dyn_value!("")),
-        ..UserData::default()
-    }
+    let mut user_data = UserData::default();
+    user_data.user_id = Some(dyn_value!(""));
+    user_data
 }
 
 #[napi]
@@ -94,6 +128,7 @@ impl StatsigUser {
             app_version
         );
 
+        user_data.user_agent_client_hints = args.user_agent_client_hints.map(Into::into);
         user_data.custom = Self::convert_to_dynamic_value_map(args.custom);
         user_data.private_attributes = Self::convert_to_dynamic_value_map(args.private_attributes);
 
@@ -122,11 +157,11 @@ impl StatsigUser {
             Either3<String, f64, i64>,
         >,
     ) -> Self {
+        let mut user_data = UserData::default();
+        user_data.custom_ids = Some(Self::convert_custom_ids(custom_ids));
+
         Self {
-            inner: StatsigUserActual::new(UserData {
-                custom_ids: Some(Self::convert_custom_ids(custom_ids)),
-                ..UserData::default()
-            }),
+            inner: StatsigUserActual::new(user_data),
         }
     }
 
@@ -352,6 +387,17 @@ add_string_getter_setter!("appVersion", app_version, set_app_version);
 
 #[napi]
 impl StatsigUser {
+    #[napi(getter, js_name = "userAgentClientHints")]
+    pub fn user_agent_client_hints(&self) -> Option<UserAgentClientHintsArgs> {
+        self.inner.get_user_agent_client_hints().map(Into::into)
+    }
+
+    #[napi(setter, js_name = "userAgentClientHints")]
+    pub fn set_user_agent_client_hints(&mut self, value: Option<UserAgentClientHintsArgs>) {
+        self.inner
+            .set_user_agent_client_hints(value.map(UserAgentClientHints::from));
+    }
+
     #[napi(js_name = "toJSON")]
     pub fn to_json(&self) -> Value {
         serde_json::to_value(self.inner.data.as_ref()).unwrap_or_else(|_| json!({}))
@@ -27,6 +27,7 @@ __all__ = [
     "StatsigBasePy",
     "StatsigOptions",
     "StatsigUser",
+    "UserAgentClientHintsDict",
     "ValidNestedPrimitives",
     "ValidPrimitives",
     "notify_python_fork",
@@ -36,6 +37,7 @@ __all__ = [
 AttributesDict: TypeAlias = typing.Mapping[builtins.str, ValidNestedPrimitives]
 CustomIdsDict: TypeAlias = typing.Mapping[builtins.str, builtins.str | builtins.int | builtins.float]
 EnvironmentDict: TypeAlias = typing.Mapping[builtins.str, builtins.str]
+UserAgentClientHintsDict: TypeAlias = typing.Mapping[builtins.str, builtins.str | builtins.bool]
 ValidNestedPrimitives: TypeAlias = ValidPrimitives | typing.List[ValidPrimitives] | typing.Mapping[builtins.str, ValidPrimitives]
 ValidPrimitives: TypeAlias = builtins.str | builtins.int | builtins.float | builtins.bool
 class DataStoreBase:
@@ -426,6 +428,10 @@ class StatsigUser:
     @user_agent.setter
     def user_agent(self, value: typing.Optional[builtins.str]) -> None: ...
     @property
+    def user_agent_client_hints(self) -> typing.Optional[builtins.dict[builtins.str, builtins.str | builtins.bool]]: ...
+    @user_agent_client_hints.setter
+    def user_agent_client_hints(self, value: typing.Optional[UserAgentClientHintsDict]) -> None: ...
+    @property
     def custom_ids(self) -> typing.Optional[builtins.dict[builtins.str, builtins.str]]: ...
     @custom_ids.setter
     def custom_ids(self, value: typing.Optional[builtins.dict[builtins.str, typing.Union[builtins.str, builtins.int, builtins.float]]]) -> None: ...
@@ -441,7 +447,7 @@ class StatsigUser:
     def statsig_environment(self) -> typing.Optional[builtins.dict[builtins.str, builtins.str]]: ...
     @statsig_environment.setter
     def statsig_environment(self, value: typing.Optional[builtins.dict[builtins.str, builtins.str]]) -> None: ...
-    def __new__(cls, user_id: typing.Optional[builtins.str] = None, email: typing.Optional[builtins.str] = None, ip: typing.Optional[builtins.str] = None, country: typing.Optional[builtins.str] = None, locale: typing.Optional[builtins.str] = None, app_version: typing.Optional[builtins.str] = None, user_agent: typing.Optional[builtins.str] = None, custom: typing.Optional[AttributesDict] = None, custom_ids: typing.Optional[CustomIdsDict] = None, private_attributes: typing.Optional[AttributesDict] = None, statsig_environment: typing.Optional[EnvironmentDict] = None) -> StatsigUser: ...
+    def __new__(cls, user_id: typing.Optional[builtins.str] = None, email: typing.Optional[builtins.str] = None, ip: typing.Optional[builtins.str] = None, country: typing.Optional[builtins.str] = None, locale: typing.Optional[builtins.str] = None, app_version: typing.Optional[builtins.str] = None, user_agent: typing.Optional[builtins.str] = None, custom: typing.Optional[AttributesDict] = None, custom_ids: typing.Optional[CustomIdsDict] = None, private_attributes: typing.Optional[AttributesDict] = None, statsig_environment: typing.Optional[EnvironmentDict] = None, user_agent_client_hints: typing.Optional[UserAgentClientHintsDict] = None) -> StatsigUser: ...
     def __getstate__(self) -> bytes: ...
     def __setstate__(self, state: bytes) -> None: ...
 
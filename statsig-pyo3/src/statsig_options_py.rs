@@ -476,6 +476,18 @@ fn create_inner_statsig_options(
             ) as Arc<dyn OutputLogProvider>
         }),
         console_capture_options: None,
+        redaction_policy: None,
+        cmab_learner: None,
+        runtime_options: None,
+        event_logging_max_flush_interval_ms: None,
+        clock: None,
+        id_list_storage: None,
+        regex_safety: None,
+        user_enricher: None,
+        disable_bot_exposure_logging: None,
+        exposure_dedupe_persistence: None,
+        exposure_sampling_policy: None,
+        share_http_connection_pool: None,
         use_third_party_ua_parser: opts.use_third_party_ua_parser,
         disable_disk_access: opts.disable_disk_access,
         experimental_flags: opts.experimental_flags,
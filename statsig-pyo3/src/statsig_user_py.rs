@@ -1,12 +1,16 @@
 use crate::{
-    pyo_utils::opt_py_dict_ref_to_hashmap,
+    pyo_utils::{map_to_py_dict, opt_py_dict_ref_to_hashmap},
     unit_id_py::UnitIdPy,
     valid_primitives_py::{ValidPrimitivesPy, ValidPrimitivesPyRef},
 };
 use pyo3::types::PyBytes;
 use pyo3::{prelude::*, types::PyDict};
 use pyo3_stub_gen::derive::*;
-use statsig_rust::{log_e, log_w, DynamicValue, StatsigUser, StatsigUserData, StatsigUserDataMap};
+use serde_json::Value;
+use statsig_rust::{
+    log_e, log_w, DynamicValue, StatsigUser, StatsigUserData, StatsigUserDataMap,
+    UserAgentClientHints,
+};
 use std::{collections::HashMap, str};
 
 const TAG: &str = stringify!(StatsigUserPy);
@@ -29,6 +33,7 @@ gen_type_alias_from_python!(
     CustomIdsDict: TypeAlias = typing.Mapping[builtins.str, builtins.str | builtins.int | builtins.float]
     EnvironmentDict: TypeAlias = typing.Mapping[builtins.str, builtins.str]
     AttributesDict: TypeAlias = typing.Mapping[builtins.str, ValidNestedPrimitives]
+    UserAgentClientHintsDict: TypeAlias = typing.Mapping[builtins.str, builtins.str | builtins.bool]
     "#
 );
 
@@ -47,7 +52,8 @@ impl StatsigUserPy {
         custom=None,
         custom_ids=None,
         private_attributes=None,
-        statsig_environment=None
+        statsig_environment=None,
+        user_agent_client_hints=None
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -70,25 +76,31 @@ impl StatsigUserPy {
 
         #[gen_stub(override_type(type_repr = "typing.Optional[EnvironmentDict]"))] //
         statsig_environment: Option<&Bound<'_, PyDict>>,
+
+        #[gen_stub(override_type(type_repr = "typing.Optional[UserAgentClientHintsDict]"))] //
+        user_agent_client_hints: Option<&Bound<'_, PyDict>>,
     ) -> PyResult<Self> {
         if user_id.is_none() && custom_ids.is_none() {
             log_w!(TAG, "Either `user_id` or `custom_ids` must be provided.");
         }
 
         let internal_user_id = DynamicValue::from(user_id.unwrap_or_default());
-        let user_data = StatsigUserData {
-            user_id: Some(internal_user_id),
-            email: opt_str_to_dynamic_value(email),
-            ip: opt_str_to_dynamic_value(ip),
-            country: opt_str_to_dynamic_value(country),
-            locale: opt_str_to_dynamic_value(locale),
-            app_version: opt_str_to_dynamic_value(app_version),
-            user_agent: opt_str_to_dynamic_value(user_agent),
-            custom: opt_py_dict_ref_to_hashmap(custom),
-            custom_ids: opt_py_dict_ref_to_hashmap(custom_ids),
-            private_attributes: opt_py_dict_ref_to_hashmap(private_attributes),
-            statsig_environment: opt_py_dict_ref_to_hashmap(statsig_environment),
+        let mut user_data = StatsigUserData::default();
+        user_data.user_id = Some(internal_user_id);
+        user_data.email = opt_str_to_dynamic_value(email);
+        user_data.ip = opt_str_to_dynamic_value(ip);
+        user_data.country = opt_str_to_dynamic_value(country);
+        user_data.locale = opt_str_to_dynamic_value(locale);
+        user_data.app_version = opt_str_to_dynamic_value(app_version);
+        user_data.user_agent = opt_str_to_dynamic_value(user_agent);
+        user_data.user_agent_client_hints = match user_agent_client_hints {
+            Some(hints) => Some(py_dict_to_client_hints(hints)?),
+            None => None,
         };
+        user_data.custom = opt_py_dict_ref_to_hashmap(custom);
+        user_data.custom_ids = opt_py_dict_ref_to_hashmap(custom_ids);
+        user_data.private_attributes = opt_py_dict_ref_to_hashmap(private_attributes);
+        user_data.statsig_environment = opt_py_dict_ref_to_hashmap(statsig_environment);
 
         let user = StatsigUser::new(user_data);
         let instance = Self { inner: user };
@@ -179,6 +191,32 @@ impl StatsigUserPy {
         self.inner.set_user_agent(value);
     }
 
+    // ---------------------------------------- [User Agent Client Hints]
+
+    #[getter]
+    #[gen_stub(override_return_type(
+        type_repr = "typing.Optional[builtins.dict[builtins.str, builtins.str | builtins.bool]]"
+    ))]
+    fn get_user_agent_client_hints(&self, py: Python) -> Option<Py<PyAny>> {
+        let hints = self.inner.get_user_agent_client_hints()?;
+        let map = client_hints_to_map(hints);
+        Some(map_to_py_dict(py, &map))
+    }
+
+    #[setter]
+    fn set_user_agent_client_hints(
+        &mut self,
+        #[gen_stub(override_type(type_repr = "typing.Optional[UserAgentClientHintsDict]"))]
+        value: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let hints = match value {
+            Some(value) => Some(py_dict_to_client_hints(value)?),
+            None => None,
+        };
+        self.inner.set_user_agent_client_hints(hints);
+        Ok(())
+    }
+
     // ---------------------------------------- [Custom IDs]
 
     #[getter]
@@ -320,6 +358,49 @@ impl StatsigUserPy {
     }
 }
 
+/// Reads the raw header values from snake_case keys, e.g. `{"platform": '"Windows"', "mobile": False}`.
+fn py_dict_to_client_hints(dict: &Bound<'_, PyDict>) -> PyResult<UserAgentClientHints> {
+    let get_str = |key: &str| -> PyResult<Option<String>> {
+        match dict.get_item(key)? {
+            Some(value) => value.extract(),
+            None => Ok(None),
+        }
+    };
+
+    Ok(UserAgentClientHints {
+        brands: get_str("brands")?,
+        full_version_list: get_str("full_version_list")?,
+        platform: get_str("platform")?,
+        platform_version: get_str("platform_version")?,
+        mobile: match dict.get_item("mobile")? {
+            Some(value) => value.extract()?,
+            None => None,
+        },
+    })
+}
+
+fn client_hints_to_map(hints: &UserAgentClientHints) -> HashMap<String, Value> {
+    let mut map = HashMap::new();
+    let mut insert = |key: &str, value: Option<Value>| {
+        if let Some(value) = value {
+            map.insert(key.to_string(), value);
+        }
+    };
+
+    insert("brands", hints.brands.clone().map(Value::String));
+    insert(
+        "full_version_list",
+        hints.full_version_list.clone().map(Value::String),
+    );
+    insert("platform", hints.platform.clone().map(Value::String));
+    insert(
+        "platform_version",
+        hints.platform_version.clone().map(Value::String),
+    );
+    insert("mobile", hints.mobile.map(Value::Bool));
+    map
+}
+
 fn get_map_field_ref<'a>(
     field: &'a Option<StatsigUserDataMap>,
 ) -> Option<HashMap<&'a str, Option<ValidPrimitivesPyRef<'a>>>> {
@@ -0,0 +1,65 @@
+use crate::StatsigOptions;
+use chrono::Utc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Source of the current time, in milliseconds since the Unix epoch.
+///
+/// Used for `current_time` conditions, override start times, event and exposure timestamps,
+/// flush scheduling, exposure dedupe resets and spec staleness. Waiting between background
+/// syncs still uses tokio timers.
+pub trait Clock: Send + Sync {
+    fn now_ms(&self) -> u64;
+}
+
+/// Reads the system clock. Used when `StatsigOptions::clock` is not set.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        Utc::now().timestamp_millis().max(0) as u64
+    }
+}
+
+/// A clock that only moves when told to. Intended for tests.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now_ms: AtomicU64,
+}
+
+impl ManualClock {
+    pub fn new(start_ms: u64) -> Self {
+        Self {
+            now_ms: AtomicU64::new(start_ms),
+        }
+    }
+
+    /// Starts at the current system time.
+    pub fn from_system_time() -> Self {
+        Self::new(SystemClock.now_ms())
+    }
+
+    pub fn set_ms(&self, now_ms: u64) {
+        self.now_ms.store(now_ms, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now_ms
+            .fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+}
+
+pub(crate) fn get_clock(options: Option<&StatsigOptions>) -> Arc<dyn Clock> {
+    match options.and_then(|o| o.clock.clone()) {
+        Some(clock) => clock,
+        None => Arc::new(SystemClock),
+    }
+}
@@ -34,7 +34,7 @@ impl ConsoleCaptureHandler {
                 log_level,
                 Some(event.payload.join(" ")),
                 metadata,
-                Some(event.timestamp),
+                event.timestamp,
             ),
         });
     }
@@ -21,9 +21,13 @@ lazy_static! {
 }
 
 pub struct ConsoleCaptureInstance {
+    ops_stats_instance: Arc<OpsStatsForInstance>,
+    settings: RwLock<ConsoleCaptureSettings>,
+}
+
+struct ConsoleCaptureSettings {
     enabled: bool,
     allowed_log_levels: Vec<StatsigLogLineLevel>,
-    ops_stats_instance: Arc<OpsStatsForInstance>,
     console_capture_user: StatsigUserLoggable,
 }
 
@@ -36,13 +40,49 @@ impl ConsoleCaptureInstance {
         sdk_key: &str,
         statsig_options: &StatsigOptions,
         environment: &Option<HashMap<String, DynamicValue>>,
+    ) -> Self {
+        Self {
+            ops_stats_instance: OPS_STATS.get_for_instance(sdk_key),
+            settings: RwLock::new(ConsoleCaptureSettings::new(statsig_options, environment)),
+        }
+    }
+
+    /// Re-reads `console_capture_options` and `global_custom_fields` from `statsig_options`.
+    pub fn update(
+        &self,
+        statsig_options: &StatsigOptions,
+        environment: &Option<HashMap<String, DynamicValue>>,
+    ) {
+        let settings = ConsoleCaptureSettings::new(statsig_options, environment);
+        match self
+            .settings
+            .try_write_for(std::time::Duration::from_secs(5))
+        {
+            Some(mut guard) => *guard = settings,
+            None => log_e!(
+                TAG,
+                "Failed to acquire write lock for console capture settings"
+            ),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.settings
+            .try_read_for(std::time::Duration::from_secs(5))
+            .is_some_and(|settings| settings.enabled)
+    }
+}
+
+impl ConsoleCaptureSettings {
+    fn new(
+        statsig_options: &StatsigOptions,
+        environment: &Option<HashMap<String, DynamicValue>>,
     ) -> Self {
         let console_capture_options = statsig_options
             .console_capture_options
             .clone()
             .unwrap_or_default();
         let enabled = console_capture_options.enabled;
-        let ops_stats_instance = OPS_STATS.get_for_instance(sdk_key);
         let allowed_log_levels = console_capture_options
             .log_levels
             .filter(|levels| !levels.is_empty())
@@ -63,15 +103,10 @@ impl ConsoleCaptureInstance {
 
         Self {
             enabled,
-            ops_stats_instance,
             allowed_log_levels,
             console_capture_user: loggable_user,
         }
     }
-
-    pub fn is_enabled(&self) -> bool {
-        self.enabled
-    }
 }
 
 fn to_user_data_map(environment: &Option<HashMap<String, DynamicValue>>) -> Option<UserDataMap> {
@@ -180,12 +215,25 @@ impl ConsoleCaptureRegistry {
             return;
         };
 
-        if !instance.allowed_log_levels.contains(&log_level) {
-            log_e!(TAG, "Log level not allowed: {:?}", log_level);
-            return;
-        }
+        let user = {
+            let Some(settings) = instance
+                .settings
+                .try_read_for(std::time::Duration::from_secs(5))
+            else {
+                log_e!(
+                    TAG,
+                    "Failed to acquire read lock for console capture settings"
+                );
+                return;
+            };
+
+            if !settings.allowed_log_levels.contains(&log_level) {
+                log_e!(TAG, "Log level not allowed: {:?}", log_level);
+                return;
+            }
 
-        let user = instance.console_capture_user.clone();
+            settings.console_capture_user.clone()
+        };
 
         instance.ops_stats_instance.enqueue_console_capture_event(
             level,
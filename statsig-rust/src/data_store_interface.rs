@@ -10,6 +10,7 @@ pub enum RequestPath {
     RulesetsV1,
     IDListsV1,
     IDList,
+    Overrides,
 }
 
 impl Display for RequestPath {
@@ -17,6 +18,7 @@ impl Display for RequestPath {
         let value = match self {
             RequestPath::IDListsV1 => "/v1/get_id_lists",
             RequestPath::IDList => "id_list",
+            RequestPath::Overrides => "overrides",
             RequestPath::RulesetsV2 => "/v2/download_config_specs",
             RequestPath::RulesetsV1 => "/v1/download_config_specs",
         };
@@ -110,7 +112,7 @@ impl From<&str> for DataStoreKeyVersion {
 }
 
 #[must_use]
-pub(crate) fn get_data_store_key(
+pub fn get_data_store_key(
     path: RequestPath,
     sdk_key: &str,
     hashing: &HashUtil,
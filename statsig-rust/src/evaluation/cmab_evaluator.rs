@@ -1,3 +1,4 @@
+use super::cmab_learner::{CMABContext, CMABExplorationMode, TrainedCMABModel};
 use super::dynamic_string::DynamicString;
 use super::evaluator::Evaluator;
 use crate::evaluation::evaluation_types::SecondaryExposure;
@@ -14,9 +15,18 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 const EXPLORE_RULE_ID_SUFFIX: &str = "explore";
 
+/// The group ID of a CMAB rule ID, without the suffix added when the group was explored.
+pub(crate) fn strip_explore_suffix(rule_id: &str) -> &str {
+    rule_id
+        .strip_suffix(EXPLORE_RULE_ID_SUFFIX)
+        .and_then(|id| id.strip_suffix(':'))
+        .unwrap_or(rule_id)
+}
+
 lazy_static! {
     static ref NOT_STARTED_RULE: InternedString = InternedString::from_str_ref("prestart");
     static ref FAILS_TARGETING: InternedString =
@@ -46,48 +56,68 @@ pub fn get_cmab_ranked_list(ctx: &mut EvaluatorContext, name: &str) -> Vec<CMABR
         return vec![];
     }
 
-    let config = match &cmab.config {
-        Some(config) => config,
-        None => {
-            return get_shuffled_groups(cmab)
-                .iter()
-                .map(|group| CMABRankedGroup {
-                    score: 0.0001,
-                    variant_name: group.name.clone(),
-                    rule_id: group.id.clone(),
-                    value: group.parameter_values.get_json(),
-                    cmab_name: name.to_string(),
-                })
-                .collect();
+    if let Some((model, context)) = get_trained_model(ctx, name) {
+        let user_hash = get_user_hash(ctx, cmab);
+        if model.exploration == CMABExplorationMode::SampleRate
+            && is_sampled_for_explore(cmab, user_hash)
+        {
+            return get_explore_ranked_list(cmab, name, true);
         }
-    };
 
-    let hashing = ctx.hashing;
-    let unit_id = get_unit_id(ctx, &cmab.id_type);
-    let user_hash = hashing.evaluation_hash_dot2(cmab.salt.as_str(), unit_id);
+        let scores = model.score_groups(&cmab.groups, &context, cmab.higher_is_better, user_hash);
+        return rank_scored_groups(cmab, name, &scores);
+    }
 
-    let should_sample = match user_hash {
-        Some(hash) => ((hash % 10000) as f64) < (cmab.sample_rate * 10000.0),
-        None => false,
+    let config = match &cmab.config {
+        Some(config) => config,
+        None => return get_explore_ranked_list(cmab, name, false),
     };
 
-    if should_sample {
-        return get_shuffled_groups(cmab)
-            .iter()
-            .map(|group| CMABRankedGroup {
-                score: 0.0001,
-                variant_name: group.name.clone(),
-                rule_id: InternedString::from_str_parts(&[group.id.as_str(), ":explore"]),
-                value: group.parameter_values.get_json(),
-                cmab_name: name.to_string(),
-            })
-            .collect();
+    let user_hash = get_user_hash(ctx, cmab);
+    if is_sampled_for_explore(cmab, user_hash) {
+        return get_explore_ranked_list(cmab, name, true);
     }
-    let mut result = cmab
+
+    let scores = cmab
         .groups
+        .iter()
+        .map(|group| get_cmab_score_for_group(ctx, group, config))
+        .collect::<Vec<_>>();
+    rank_scored_groups(cmab, name, &scores)
+}
+
+fn get_explore_ranked_list(
+    cmab: &CMABConfig,
+    name: &str,
+    tag_explore: bool,
+) -> Vec<CMABRankedGroup> {
+    get_shuffled_groups(cmab)
         .iter()
         .map(|group| CMABRankedGroup {
-            score: get_cmab_score_for_group(ctx, group, config).unwrap_or(0.0),
+            score: 0.0001,
+            variant_name: group.name.clone(),
+            rule_id: if tag_explore {
+                InternedString::from_str_parts(&[group.id.as_str(), ":", EXPLORE_RULE_ID_SUFFIX])
+            } else {
+                group.id.clone()
+            },
+            value: group.parameter_values.get_json(),
+            cmab_name: name.to_string(),
+        })
+        .collect()
+}
+
+fn rank_scored_groups(
+    cmab: &CMABConfig,
+    name: &str,
+    scores: &[Option<f64>],
+) -> Vec<CMABRankedGroup> {
+    let mut result = cmab
+        .groups
+        .iter()
+        .zip(scores)
+        .map(|(group, score)| CMABRankedGroup {
+            score: score.unwrap_or(0.0),
             variant_name: group.name.clone(),
             rule_id: group.id.clone(),
             value: group.parameter_values.get_json(),
@@ -142,9 +172,21 @@ pub(crate) fn evaluate_cmab(
     ctx.result.version = Some(cmab.version);
     ctx.result.is_experiment_active = cmab.enabled;
 
-    let hashing = ctx.hashing;
-    let unit_id = get_unit_id(ctx, &cmab.id_type);
-    let user_hash = hashing.evaluation_hash_dot2(cmab.salt.as_str(), unit_id);
+    let user_hash = get_user_hash(ctx, cmab);
+
+    if let Some((model, context)) = get_trained_model(ctx, spec_name) {
+        if model.exploration == CMABExplorationMode::SampleRate
+            && is_sampled_for_explore(cmab, user_hash)
+            && apply_sampling_group(ctx, cmab, &model.group_configs)
+        {
+            return true;
+        }
+
+        let scores = model.score_groups(&cmab.groups, &context, cmab.higher_is_better, user_hash);
+        apply_best_scored_group(ctx, cmab, &scores);
+        return true;
+    }
+
     let config = match &cmab.config {
         Some(config) => config,
         None => {
@@ -153,18 +195,37 @@ pub(crate) fn evaluate_cmab(
         }
     };
 
-    let should_sample = match user_hash {
-        Some(hash) => ((hash % 10000) as f64) < (cmab.sample_rate * 10000.0),
-        None => false,
-    };
-
-    if should_sample && apply_sampling_group(ctx, cmab, config) {
+    if is_sampled_for_explore(cmab, user_hash) && apply_sampling_group(ctx, cmab, config) {
         return true;
     }
     apply_best_group(ctx, cmab, config);
     true
 }
 
+/// The locally trained model for the CMAB, if a `CMABLearner` is configured and has one,
+/// along with the user's features.
+fn get_trained_model(
+    ctx: &EvaluatorContext,
+    cmab_name: &str,
+) -> Option<(Arc<TrainedCMABModel>, CMABContext)> {
+    let learner = ctx.statsig?.options.cmab_learner.as_ref()?;
+    let model = learner.get_trained_model(cmab_name)?;
+    Some((model, learner.get_context_for_user(ctx.user)))
+}
+
+fn get_user_hash(ctx: &mut EvaluatorContext, cmab: &CMABConfig) -> Option<u64> {
+    let hashing = ctx.hashing;
+    let unit_id = get_unit_id(ctx, &cmab.id_type);
+    hashing.evaluation_hash_dot2(cmab.salt.as_str(), unit_id)
+}
+
+fn is_sampled_for_explore(cmab: &CMABConfig, user_hash: Option<u64>) -> bool {
+    match user_hash {
+        Some(hash) => ((hash % 10000) as f64) < (cmab.sample_rate * 10000.0),
+        None => false,
+    }
+}
+
 fn get_passes_targeting<'a>(ctx: &mut EvaluatorContext<'a>, cmab: &'a CMABConfig) -> bool {
     let targeting_gate_name = match &cmab.targeting_gate_name {
         Some(name) => name,
@@ -265,6 +326,19 @@ fn apply_best_group<'a>(
     ctx: &mut EvaluatorContext<'a>,
     cmab: &'a CMABConfig,
     config: &HashMap<String, CMABGroupConfig>,
+) {
+    let scores = cmab
+        .groups
+        .iter()
+        .map(|group| get_cmab_score_for_group(ctx, group, config))
+        .collect::<Vec<_>>();
+    apply_best_scored_group(ctx, cmab, &scores);
+}
+
+fn apply_best_scored_group<'a>(
+    ctx: &mut EvaluatorContext<'a>,
+    cmab: &'a CMABConfig,
+    scores: &[Option<f64>],
 ) {
     let higher_better = cmab.higher_is_better;
     let mut best_score = if higher_better {
@@ -274,9 +348,9 @@ fn apply_best_group<'a>(
     };
     let mut best_group = &cmab.groups[0];
     let mut has_score = false;
-    for group in &cmab.groups {
-        let score = match get_cmab_score_for_group(ctx, group, config) {
-            Some(s) => s,
+    for (group, score) in cmab.groups.iter().zip(scores) {
+        let score = match score {
+            Some(s) => *s,
             None => continue,
         };
         has_score = true;
@@ -0,0 +1,634 @@
+use crate::evaluation::cmab_evaluator::strip_explore_suffix;
+use crate::evaluation::dynamic_string::DynamicString;
+use crate::specs_response::cmab_types::{CMABGroup, CMABGroupConfig};
+use crate::user::StatsigUserInternal;
+use crate::{log_d, log_w};
+use parking_lot::{Mutex, RwLock};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const TAG: &str = stringify!(CMABLearner);
+
+pub const DEFAULT_CMAB_RIDGE_LAMBDA: f64 = 1.0;
+pub const DEFAULT_CMAB_MIN_RECORDS_PER_GROUP: u64 = 10;
+pub const DEFAULT_CMAB_RETRAIN_INTERVAL: u64 = 100;
+
+/// How a locally trained CMAB explores once a model is available.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CMABExplorationMode {
+    /// Same as spec-shipped weights: `sample_rate` of units get an explore group weighted
+    /// towards groups with few records, everyone else gets the best predicted group.
+    SampleRate,
+    /// Every unit is scored with weights drawn from each group's posterior.
+    ThompsonSampling { noise_variance: f64 },
+    /// Every unit is scored with the predicted reward plus `alpha` standard deviations
+    /// (minus, when lower is better).
+    Ucb { alpha: f64 },
+}
+
+#[derive(Clone, Debug)]
+pub struct CMABLearnerOptions {
+    /// User fields read as numbers, both when recording outcomes for a user and when ranking.
+    pub numerical_features: Vec<String>,
+    /// User fields one-hot encoded by their string value.
+    pub categorical_features: Vec<String>,
+    /// L2 penalty on every weight except the intercept.
+    pub ridge_lambda: f64,
+    pub exploration: CMABExplorationMode,
+    /// Groups with fewer records keep no model and are not scored.
+    pub min_records_per_group: u64,
+    /// Number of recorded outcomes for a CMAB after which its models are refit.
+    pub retrain_interval: u64,
+}
+
+impl Default for CMABLearnerOptions {
+    fn default() -> Self {
+        Self {
+            numerical_features: vec![],
+            categorical_features: vec![],
+            ridge_lambda: DEFAULT_CMAB_RIDGE_LAMBDA,
+            exploration: CMABExplorationMode::SampleRate,
+            min_records_per_group: DEFAULT_CMAB_MIN_RECORDS_PER_GROUP,
+            retrain_interval: DEFAULT_CMAB_RETRAIN_INTERVAL,
+        }
+    }
+}
+
+/// Context features of a single CMAB decision.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CMABContext {
+    pub numerical: HashMap<String, f64>,
+    pub categorical: HashMap<String, String>,
+}
+
+impl CMABContext {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_numerical(mut self, key: impl Into<String>, value: f64) -> Self {
+        self.numerical.insert(key.into(), value);
+        self
+    }
+
+    #[must_use]
+    pub fn with_categorical(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.categorical.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Fits per-group ridge regression models for CMABs from logged outcomes and serves them to
+/// the CMAB evaluator in place of the weights shipped in specs.
+///
+/// Only sufficient statistics are kept per group, so memory is bounded by the number of
+/// distinct features rather than the number of outcomes. Models are fit from a copy of those
+/// statistics, so recording outcomes never waits on a refit.
+pub struct CMABLearner {
+    options: CMABLearnerOptions,
+    training: Mutex<HashMap<String, CMABTrainingState>>,
+    trained: RwLock<HashMap<String, Arc<TrainedCMABModel>>>,
+}
+
+impl CMABLearner {
+    #[must_use]
+    pub fn new(options: CMABLearnerOptions) -> Self {
+        Self {
+            options,
+            training: Mutex::new(HashMap::new()),
+            trained: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Records the reward observed after showing `group_id` in the given context. An
+    /// `:explore` suffix on `group_id` is ignored, so explored groups train the same model. Models are
+    /// refit every `retrain_interval` outcomes, on the calling thread. `Statsig` refits in the
+    /// background instead, see [`Statsig::record_cmab_outcome`](crate::Statsig::record_cmab_outcome).
+    pub fn record(&self, cmab_name: &str, group_id: &str, context: &CMABContext, reward: f64) {
+        if self.record_outcome(cmab_name, group_id, context, reward) {
+            self.train(cmab_name);
+        }
+    }
+
+    /// Refits the models for `cmab_name` now. Returns false when no group has enough records.
+    pub fn train(&self, cmab_name: &str) -> bool {
+        let snapshot = {
+            let mut training = self.training.lock();
+            let Some(state) = training.get_mut(cmab_name) else {
+                return false;
+            };
+            state.pending = 0;
+            state.snapshot(&self.options)
+        };
+
+        self.store_model(cmab_name, snapshot.fit(&self.options))
+    }
+
+    pub fn train_all(&self) {
+        let names: Vec<String> = self.training.lock().keys().cloned().collect();
+        for name in names {
+            self.train(&name);
+        }
+    }
+
+    /// The trained weights for each group, in the same shape as spec-shipped CMAB configs.
+    pub fn get_trained_group_configs(
+        &self,
+        cmab_name: &str,
+    ) -> Option<HashMap<String, CMABGroupConfig>> {
+        self.get_trained_model(cmab_name)
+            .map(|model| model.group_configs.clone())
+    }
+
+    /// Drops all outcomes and models for `cmab_name`, returning it to spec-shipped weights.
+    pub fn reset(&self, cmab_name: &str) {
+        self.training.lock().remove(cmab_name);
+        self.trained.write().remove(cmab_name);
+    }
+
+    /// Adds the outcome to the training statistics. Returns true when a refit is due.
+    pub(crate) fn record_outcome(
+        &self,
+        cmab_name: &str,
+        group_id: &str,
+        context: &CMABContext,
+        reward: f64,
+    ) -> bool {
+        if !reward.is_finite() {
+            log_w!(TAG, "Ignoring non-finite reward for CMAB {}", cmab_name);
+            return false;
+        }
+
+        let group_id = strip_explore_suffix(group_id);
+
+        let mut training = self.training.lock();
+        let state = training.entry(cmab_name.to_string()).or_default();
+
+        let x = state.index.encode_and_extend(context);
+        let group = state.groups.entry(group_id.to_string()).or_default();
+        group.add(&x, reward);
+
+        state.generation += 1;
+        state.pending += 1;
+        if state.pending < self.options.retrain_interval.max(1) {
+            return false;
+        }
+
+        state.pending = 0;
+        true
+    }
+
+    pub(crate) fn get_trained_model(&self, cmab_name: &str) -> Option<Arc<TrainedCMABModel>> {
+        self.trained.read().get(cmab_name).cloned()
+    }
+
+    pub(crate) fn get_context_for_user(&self, user: &StatsigUserInternal) -> CMABContext {
+        let mut context = CMABContext::new();
+
+        for key in &self.options.numerical_features {
+            let value = user
+                .get_user_value(&Some(DynamicString::from(key.clone())))
+                .and_then(|v| v.float_value);
+            if let Some(value) = value {
+                context.numerical.insert(key.clone(), value);
+            }
+        }
+
+        for key in &self.options.categorical_features {
+            let value = user
+                .get_user_value(&Some(DynamicString::from(key.clone())))
+                .map(|v| match &v.string_value {
+                    Some(s) => s.value.unperformant_to_string(),
+                    None => v.json_value.to_string(),
+                });
+            if let Some(value) = value {
+                context.categorical.insert(key.clone(), value);
+            }
+        }
+
+        context
+    }
+
+    fn store_model(&self, cmab_name: &str, model: Option<TrainedCMABModel>) -> bool {
+        let Some(model) = model else {
+            return false;
+        };
+
+        let mut trained = self.trained.write();
+        // a slower refit of older statistics must not replace a newer model
+        if let Some(current) = trained.get(cmab_name) {
+            if current.generation > model.generation {
+                return true;
+            }
+        }
+
+        log_d!(
+            TAG,
+            "Trained {} group model(s) for CMAB {}",
+            model.groups.len(),
+            cmab_name
+        );
+        trained.insert(cmab_name.to_string(), Arc::new(model));
+        true
+    }
+}
+
+// -------------------------------------------------------------------------------------------- [ Training ]
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum CMABFeature {
+    Numerical(String),
+    Categorical(String, String),
+}
+
+/// Maps features to positions in the model vectors. Position 0 is the intercept.
+#[derive(Clone, Default)]
+struct FeatureIndex {
+    features: Vec<CMABFeature>,
+    positions: HashMap<CMABFeature, usize>,
+}
+
+impl FeatureIndex {
+    fn dim(&self) -> usize {
+        self.features.len() + 1
+    }
+
+    fn encode(&self, context: &CMABContext) -> Vec<f64> {
+        let mut x = vec![0.0; self.dim()];
+        x[0] = 1.0;
+
+        for (key, value) in &context.numerical {
+            if let Some(pos) = self.positions.get(&CMABFeature::Numerical(key.clone())) {
+                x[*pos] = *value;
+            }
+        }
+
+        for (key, value) in &context.categorical {
+            let feature = CMABFeature::Categorical(key.clone(), value.clone());
+            if let Some(pos) = self.positions.get(&feature) {
+                x[*pos] = 1.0;
+            }
+        }
+
+        x
+    }
+
+    fn encode_and_extend(&mut self, context: &CMABContext) -> Vec<f64> {
+        for (key, value) in &context.numerical {
+            if value.is_finite() {
+                self.insert(CMABFeature::Numerical(key.clone()));
+            }
+        }
+
+        for (key, value) in &context.categorical {
+            self.insert(CMABFeature::Categorical(key.clone(), value.clone()));
+        }
+
+        let mut x = self.encode(context);
+        for v in x.iter_mut().filter(|v| !v.is_finite()) {
+            *v = 0.0;
+        }
+        x
+    }
+
+    fn insert(&mut self, feature: CMABFeature) {
+        if self.positions.contains_key(&feature) {
+            return;
+        }
+
+        self.positions
+            .insert(feature.clone(), self.features.len() + 1);
+        self.features.push(feature);
+    }
+}
+
+/// Running XᵀX and Xᵀy for one group. Rows are added lazily as new features appear.
+#[derive(Clone, Default)]
+struct GroupStats {
+    dim: usize,
+    xtx: Vec<f64>,
+    xty: Vec<f64>,
+    records: u64,
+}
+
+impl GroupStats {
+    fn add(&mut self, x: &[f64], reward: f64) {
+        self.resize(x.len());
+
+        let n = self.dim;
+        for i in 0..n {
+            if x[i] == 0.0 {
+                continue;
+            }
+            self.xty[i] += x[i] * reward;
+            for j in 0..n {
+                self.xtx[i * n + j] += x[i] * x[j];
+            }
+        }
+        self.records += 1;
+    }
+
+    fn resize(&mut self, dim: usize) {
+        if dim <= self.dim {
+            return;
+        }
+
+        let mut xtx = vec![0.0; dim * dim];
+        for i in 0..self.dim {
+            for j in 0..self.dim {
+                xtx[i * dim + j] = self.xtx[i * self.dim + j];
+            }
+        }
+
+        self.xtx = xtx;
+        self.xty.resize(dim, 0.0);
+        self.dim = dim;
+    }
+}
+
+#[derive(Default)]
+struct CMABTrainingState {
+    index: FeatureIndex,
+    groups: HashMap<String, GroupStats>,
+    pending: u64,
+    /// Number of outcomes recorded, used to order models fit from different snapshots.
+    generation: u64,
+}
+
+impl CMABTrainingState {
+    /// Copies the statistics of the groups with enough records, so they can be fit without
+    /// holding the training lock.
+    fn snapshot(&self, options: &CMABLearnerOptions) -> CMABTrainingSnapshot {
+        let min_records = options.min_records_per_group.max(1);
+
+        CMABTrainingSnapshot {
+            index: self.index.clone(),
+            groups: self
+                .groups
+                .iter()
+                .filter(|(_, stats)| stats.records >= min_records)
+                .map(|(id, stats)| (id.clone(), stats.clone()))
+                .collect(),
+            generation: self.generation,
+        }
+    }
+}
+
+struct CMABTrainingSnapshot {
+    index: FeatureIndex,
+    groups: Vec<(String, GroupStats)>,
+    generation: u64,
+}
+
+impl CMABTrainingSnapshot {
+    fn fit(mut self, options: &CMABLearnerOptions) -> Option<TrainedCMABModel> {
+        let dim = self.index.dim();
+        let mut groups = HashMap::new();
+
+        for (group_id, stats) in &mut self.groups {
+            stats.resize(dim);
+            match fit_ridge(stats, options.ridge_lambda) {
+                Some(model) => {
+                    groups.insert(group_id.clone(), model);
+                }
+                None => log_w!(TAG, "Failed to fit CMAB model for group {}", group_id),
+            }
+        }
+
+        if groups.is_empty() {
+            return None;
+        }
+
+        let group_configs = groups
+            .iter()
+            .map(|(id, model)| (id.clone(), model.to_group_config(&self.index)))
+            .collect();
+
+        Some(TrainedCMABModel {
+            index: self.index,
+            groups,
+            group_configs,
+            exploration: options.exploration.clone(),
+            generation: self.generation,
+        })
+    }
+}
+
+fn fit_ridge(stats: &GroupStats, lambda: f64) -> Option<TrainedGroupModel> {
+    let n = stats.dim;
+    let mut a = stats.xtx.clone();
+    // the intercept is not penalized, but keeps a tiny ridge so a missing feature can't
+    // make the system singular
+    a[0] += 1e-9;
+    for i in 1..n {
+        a[i * n + i] += lambda.max(1e-9);
+    }
+
+    let chol = cholesky(&a, n)?;
+    let weights = cholesky_solve(&chol, n, &stats.xty);
+
+    let mut covariance = vec![0.0; n * n];
+    for j in 0..n {
+        let mut e = vec![0.0; n];
+        e[j] = 1.0;
+        let column = cholesky_solve(&chol, n, &e);
+        for i in 0..n {
+            covariance[i * n + j] = column[i];
+        }
+    }
+    let covariance_chol = cholesky(&covariance, n)?;
+
+    Some(TrainedGroupModel {
+        weights,
+        covariance,
+        covariance_chol,
+        records: stats.records,
+    })
+}
+
+// -------------------------------------------------------------------------------------------- [ Scoring ]
+
+pub(crate) struct TrainedGroupModel {
+    weights: Vec<f64>,
+    covariance: Vec<f64>,
+    covariance_chol: Vec<f64>,
+    records: u64,
+}
+
+impl TrainedGroupModel {
+    fn to_group_config(&self, index: &FeatureIndex) -> CMABGroupConfig {
+        let mut weights_numerical = HashMap::new();
+        let mut weights_categorical: HashMap<String, HashMap<String, f64>> = HashMap::new();
+
+        for (i, feature) in index.features.iter().enumerate() {
+            let Some(weight) = self.weights.get(i + 1) else {
+                continue;
+            };
+
+            match feature {
+                CMABFeature::Numerical(key) => {
+                    weights_numerical.insert(key.clone(), *weight);
+                }
+                CMABFeature::Categorical(key, value) => {
+                    weights_categorical
+                        .entry(key.clone())
+                        .or_default()
+                        .insert(value.clone(), *weight);
+                }
+            }
+        }
+
+        CMABGroupConfig {
+            alpha: 0.0,
+            intercept: self.weights[0],
+            records: self.records,
+            weights_numerical,
+            weights_categorical,
+        }
+    }
+
+    fn predict(&self, x: &[f64]) -> f64 {
+        dot(&self.weights, x)
+    }
+
+    fn variance(&self, x: &[f64]) -> f64 {
+        let n = self.weights.len();
+        let mut total = 0.0;
+        for i in 0..n {
+            if x[i] == 0.0 {
+                continue;
+            }
+            for j in 0..n {
+                total += x[i] * self.covariance[i * n + j] * x[j];
+            }
+        }
+        total.max(0.0)
+    }
+
+    fn sample<R: Rng>(&self, x: &[f64], noise_variance: f64, rng: &mut R) -> f64 {
+        let n = self.weights.len();
+        let z: Vec<f64> = (0..n).map(|_| standard_normal(rng)).collect();
+        let scale = noise_variance.max(0.0).sqrt();
+
+        let mut score = 0.0;
+        for (i, (weight, x_i)) in self.weights.iter().zip(x).enumerate() {
+            let mut theta = *weight;
+            for (j, z_j) in z.iter().enumerate().take(i + 1) {
+                theta += scale * self.covariance_chol[i * n + j] * z_j;
+            }
+            score += theta * x_i;
+        }
+        score
+    }
+}
+
+pub(crate) struct TrainedCMABModel {
+    index: FeatureIndex,
+    groups: HashMap<String, TrainedGroupModel>,
+    pub(crate) group_configs: HashMap<String, CMABGroupConfig>,
+    pub(crate) exploration: CMABExplorationMode,
+    generation: u64,
+}
+
+impl TrainedCMABModel {
+    /// The predicted reward of `group_id` for the context, without exploration.
+    pub(crate) fn predict(&self, group_id: &str, context: &CMABContext) -> Option<f64> {
+        let model = self.groups.get(group_id)?;
+        Some(model.predict(&self.index.encode(context)))
+    }
+
+    /// Scores each group for the context, applying the exploration mode. Groups without a
+    /// model get `None`. Thompson sampling draws from a generator seeded by `user_hash`, so a
+    /// unit keeps the same draw until the model is refit.
+    pub(crate) fn score_groups(
+        &self,
+        groups: &[CMABGroup],
+        context: &CMABContext,
+        higher_is_better: bool,
+        user_hash: Option<u64>,
+    ) -> Vec<Option<f64>> {
+        let x = self.index.encode(context);
+        let direction = if higher_is_better { 1.0 } else { -1.0 };
+        let mut rng = StdRng::seed_from_u64(user_hash.unwrap_or_default() ^ self.generation);
+
+        groups
+            .iter()
+            .map(|group| {
+                let model = self.groups.get(group.id.as_str())?;
+                let score = match &self.exploration {
+                    CMABExplorationMode::SampleRate => model.predict(&x),
+                    CMABExplorationMode::Ucb { alpha } => {
+                        model.predict(&x) + direction * alpha * model.variance(&x).sqrt()
+                    }
+                    CMABExplorationMode::ThompsonSampling { noise_variance } => {
+                        model.sample(&x, *noise_variance, &mut rng)
+                    }
+                };
+                Some(score)
+            })
+            .collect()
+    }
+}
+
+// -------------------------------------------------------------------------------------------- [ Linear Algebra ]
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Lower triangular L with A = LLᵀ, for a symmetric positive definite row-major A.
+fn cholesky(a: &[f64], n: usize) -> Option<Vec<f64>> {
+    let mut l = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..=i {
+            let mut sum = a[i * n + j];
+            for k in 0..j {
+                sum -= l[i * n + k] * l[j * n + k];
+            }
+
+            if i == j {
+                if sum <= 0.0 || !sum.is_finite() {
+                    return None;
+                }
+                l[i * n + i] = sum.sqrt();
+            } else {
+                l[i * n + j] = sum / l[j * n + j];
+            }
+        }
+    }
+    Some(l)
+}
+
+fn cholesky_solve(l: &[f64], n: usize, b: &[f64]) -> Vec<f64> {
+    let mut y = vec![0.0; n];
+    for i in 0..n {
+        let mut sum = b[i];
+        for k in 0..i {
+            sum -= l[i * n + k] * y[k];
+        }
+        y[i] = sum / l[i * n + i];
+    }
+
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let mut sum = y[i];
+        for k in (i + 1)..n {
+            sum -= l[k * n + i] * x[k];
+        }
+        x[i] = sum / l[i * n + i];
+    }
+    x
+}
+
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    // Box-Muller
+    let u1: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2: f64 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
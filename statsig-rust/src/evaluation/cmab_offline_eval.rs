@@ -0,0 +1,290 @@
+use super::cmab_learner::{CMABContext, CMABLearner, CMABLearnerOptions, TrainedCMABModel};
+use crate::specs_response::cmab_types::{CMABConfig, CMABGroupConfig};
+use crate::{log_d, StatsigErr};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::sync::Arc;
+
+const TAG: &str = stringify!(CMABOfflineEvaluator);
+
+const REWARD_MODEL_NAME: &str = "offline_eval";
+
+/// One logged CMAB decision: the context it was made in, the group that was shown, the
+/// probability the logging policy had of showing it, and the reward that followed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CMABLoggedRecord {
+    #[serde(default)]
+    pub context: CMABContext,
+    /// The group id that was shown.
+    pub action: String,
+    pub propensity: f64,
+    pub reward: f64,
+}
+
+/// Estimated value of a candidate CMAB config over a set of logged decisions.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CMABOfflineEvalReport {
+    /// Records used in the estimates.
+    pub records: u64,
+    /// Records ignored for an invalid propensity, a non-finite reward or an unknown group.
+    pub skipped_records: u64,
+    /// Mean reward observed under the logging policy.
+    pub logged_reward: f64,
+    /// Mean probability the candidate assigns to the logged group.
+    pub match_rate: f64,
+    /// Inverse propensity scoring estimate of the candidate's mean reward.
+    pub ips: f64,
+    /// Self-normalized IPS, which trades a little bias for less variance.
+    pub snips: f64,
+    /// Doubly robust estimate, using per-group ridge regressions fit on the logs as the
+    /// reward model.
+    pub doubly_robust: f64,
+}
+
+/// Estimates how a candidate `CMABConfig` would have performed on logged decisions, before
+/// it is shipped.
+///
+/// The candidate policy mirrors the evaluator: `sample_rate` of decisions explore, weighted
+/// towards groups with few records, and the rest go to the best scoring group. Targeting
+/// and the enabled flag are not considered.
+pub struct CMABOfflineEvaluator<'a> {
+    config: &'a CMABConfig,
+    reward_model_lambda: f64,
+}
+
+impl<'a> CMABOfflineEvaluator<'a> {
+    #[must_use]
+    pub fn new(config: &'a CMABConfig) -> Self {
+        Self {
+            config,
+            reward_model_lambda: CMABLearnerOptions::default().ridge_lambda,
+        }
+    }
+
+    /// L2 penalty of the reward model used by the doubly robust estimate.
+    #[must_use]
+    pub fn with_reward_model_lambda(mut self, lambda: f64) -> Self {
+        self.reward_model_lambda = lambda;
+        self
+    }
+
+    /// Reads one `CMABLoggedRecord` per line. Blank lines are ignored.
+    pub fn evaluate_jsonl<R: BufRead>(
+        &self,
+        reader: R,
+    ) -> Result<CMABOfflineEvalReport, StatsigErr> {
+        let mut records = Vec::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| StatsigErr::FileError(e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record = serde_json::from_str::<CMABLoggedRecord>(&line).map_err(|e| {
+                StatsigErr::JsonParseError(TAG.to_string(), format!("line {}: {e}", i + 1))
+            })?;
+            records.push(record);
+        }
+
+        self.evaluate(&records)
+    }
+
+    pub fn evaluate(
+        &self,
+        records: &[CMABLoggedRecord],
+    ) -> Result<CMABOfflineEvalReport, StatsigErr> {
+        let (valid, skipped): (Vec<_>, Vec<_>) =
+            records.iter().partition(|r| self.is_valid_record(r));
+
+        if valid.is_empty() {
+            return Err(StatsigErr::InvalidOperation(
+                "No usable records for CMAB offline evaluation".to_string(),
+            ));
+        }
+
+        let reward_model = self.fit_reward_model(&valid);
+        let predict = |group_id: &str, context: &CMABContext| {
+            reward_model
+                .as_ref()
+                .and_then(|model| model.predict(group_id, context))
+                .unwrap_or(0.0)
+        };
+
+        let mut logged_reward = 0.0;
+        let mut match_rate = 0.0;
+        let mut ips = 0.0;
+        let mut snips_weights = 0.0;
+        let mut doubly_robust = 0.0;
+
+        for record in &valid {
+            let probabilities = self.get_action_probabilities(&record.context);
+            let target = probabilities
+                .get(record.action.as_str())
+                .copied()
+                .unwrap_or(0.0);
+            let weight = target / record.propensity;
+
+            let direct = probabilities
+                .iter()
+                .map(|(group_id, p)| p * predict(group_id, &record.context))
+                .sum::<f64>();
+            let residual = record.reward - predict(&record.action, &record.context);
+
+            logged_reward += record.reward;
+            match_rate += target;
+            ips += weight * record.reward;
+            snips_weights += weight;
+            doubly_robust += direct + weight * residual;
+        }
+
+        let n = valid.len() as f64;
+        log_d!(
+            TAG,
+            "Evaluated CMAB config on {} record(s), skipped {}",
+            valid.len(),
+            skipped.len()
+        );
+
+        Ok(CMABOfflineEvalReport {
+            records: valid.len() as u64,
+            skipped_records: skipped.len() as u64,
+            logged_reward: logged_reward / n,
+            match_rate: match_rate / n,
+            ips: ips / n,
+            snips: if snips_weights > 0.0 {
+                ips / snips_weights
+            } else {
+                0.0
+            },
+            doubly_robust: doubly_robust / n,
+        })
+    }
+
+    fn is_valid_record(&self, record: &CMABLoggedRecord) -> bool {
+        record.propensity > 0.0
+            && record.propensity <= 1.0
+            && record.reward.is_finite()
+            && self
+                .config
+                .groups
+                .iter()
+                .any(|g| g.id.as_str() == record.action)
+    }
+
+    fn fit_reward_model(&self, records: &[&CMABLoggedRecord]) -> Option<Arc<TrainedCMABModel>> {
+        let learner = CMABLearner::new(CMABLearnerOptions {
+            ridge_lambda: self.reward_model_lambda,
+            min_records_per_group: 1,
+            retrain_interval: u64::MAX,
+            ..CMABLearnerOptions::default()
+        });
+
+        for record in records {
+            learner.record(
+                REWARD_MODEL_NAME,
+                &record.action,
+                &record.context,
+                record.reward,
+            );
+        }
+
+        learner.train(REWARD_MODEL_NAME);
+        learner.get_trained_model(REWARD_MODEL_NAME)
+    }
+
+    /// The probability of the candidate showing each group in the context.
+    fn get_action_probabilities(&self, context: &CMABContext) -> HashMap<&'a str, f64> {
+        let groups = &self.config.groups;
+        let uniform = 1.0 / groups.len() as f64;
+        let uniform_probabilities = || groups.iter().map(|g| (g.id.as_str(), uniform)).collect();
+
+        let Some(config) = &self.config.config else {
+            return uniform_probabilities();
+        };
+
+        let mut probabilities: HashMap<&str, f64> = match self.get_best_group(config, context) {
+            Some(best) => groups
+                .iter()
+                .map(|g| (g.id.as_str(), if g.id.as_str() == best { 1.0 } else { 0.0 }))
+                .collect(),
+            None => uniform_probabilities(),
+        };
+
+        let sample_rate = self.config.sample_rate.clamp(0.0, 1.0);
+        if sample_rate == 0.0 {
+            return probabilities;
+        }
+
+        let inverse_counts = groups
+            .iter()
+            .map(|g| {
+                let records = config.get(g.id.as_str()).map_or(0, |c| c.records);
+                (g.id.as_str(), 1.0 / (records + 1) as f64)
+            })
+            .collect::<Vec<_>>();
+        let total = inverse_counts.iter().map(|(_, w)| w).sum::<f64>();
+
+        for (group_id, inverse_count) in inverse_counts {
+            let exploit = probabilities.entry(group_id).or_default();
+            *exploit = sample_rate * inverse_count / total + (1.0 - sample_rate) * *exploit;
+        }
+        probabilities
+    }
+
+    fn get_best_group(
+        &self,
+        config: &HashMap<String, CMABGroupConfig>,
+        context: &CMABContext,
+    ) -> Option<&'a str> {
+        let higher_better = self.config.higher_is_better;
+        let mut best: Option<(&str, f64)> = None;
+
+        for group in &self.config.groups {
+            let Some(score) = config
+                .get(group.id.as_str())
+                .and_then(|c| get_score_for_context(c, context))
+            else {
+                continue;
+            };
+
+            let is_better = match best {
+                None => true,
+                Some((_, best_score)) if higher_better => score > best_score,
+                Some((_, best_score)) => score < best_score,
+            };
+            if is_better {
+                best = Some((group.id.as_str(), score));
+            }
+        }
+
+        best.map(|(id, _)| id)
+    }
+}
+
+/// Same as the evaluator's group score, with features read from the context instead of
+/// the user.
+fn get_score_for_context(config: &CMABGroupConfig, context: &CMABContext) -> Option<f64> {
+    if config.weights_numerical.is_empty() && config.weights_categorical.is_empty() {
+        return None;
+    }
+
+    let mut score = config.intercept + config.alpha;
+    for (key, weights) in &config.weights_categorical {
+        let weight = context
+            .categorical
+            .get(key)
+            .and_then(|value| weights.get(value));
+        if let Some(weight) = weight {
+            score += weight;
+        }
+    }
+
+    for (key, weight) in &config.weights_numerical {
+        if let Some(value) = context.numerical.get(key) {
+            score += value * weight;
+        }
+    }
+    Some(score)
+}
@@ -0,0 +1,269 @@
+use super::compare_versions::Version;
+use crate::{evaluation::evaluator_value::MemoizedEvaluatorValue, unwrap_or_return, DynamicValue};
+
+/// `version_in_range`: the target is a range expression such as `>=1.2, <2`, `^1.4`, `~2.1`,
+/// `1.x`, `1.2.0 - 1.4.0` or several of these joined with `||`. A bare version matches that
+/// version exactly, or every version it is a prefix of when partial (`1.2` is `>=1.2.0, <1.3.0`).
+pub(crate) fn compare_version_in_range(
+    left: &DynamicValue,
+    right: &MemoizedEvaluatorValue,
+) -> bool {
+    let left_dyn_str = unwrap_or_return!(&left.string_value, false);
+    let right_dyn_str = unwrap_or_return!(&right.string_value, false);
+
+    let version = unwrap_or_return!(Version::parse(&left_dyn_str.value), false);
+    let range = unwrap_or_return!(VersionRange::parse(&right_dyn_str.value), false);
+
+    range.matches(&version)
+}
+
+#[derive(Debug)]
+enum Comparator {
+    Gt(Version),
+    Gte(Version),
+    Lt(Version),
+    Lte(Version),
+    Eq(Version),
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        match self {
+            Comparator::Gt(bound) => version > bound,
+            Comparator::Gte(bound) => version >= bound,
+            Comparator::Lt(bound) => version < bound,
+            Comparator::Lte(bound) => version <= bound,
+            Comparator::Eq(bound) => version == bound,
+        }
+    }
+}
+
+/// Alternatives (`||`), each a set of comparators that must all pass.
+#[derive(Debug)]
+struct VersionRange {
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+impl VersionRange {
+    fn parse(input: &str) -> Option<Self> {
+        let alternatives = input
+            .split("||")
+            .map(parse_comparator_set)
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self { alternatives })
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        self.alternatives
+            .iter()
+            .any(|set| set.iter().all(|c| c.matches(version)))
+    }
+}
+
+fn parse_comparator_set(input: &str) -> Option<Vec<Comparator>> {
+    // hyphen range, "1.2 - 2.3"
+    if let Some((low, high)) = input.split_once(" - ") {
+        let mut comparators = Vec::new();
+        push_comparators(&mut comparators, ">=", PartialVersion::parse(low.trim())?);
+        push_comparators(&mut comparators, "<=", PartialVersion::parse(high.trim())?);
+        return Some(comparators);
+    }
+
+    let mut comparators = Vec::new();
+    let mut pending_op: Option<&str> = None;
+
+    for token in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+    {
+        let (op, version_str) = split_operator(token);
+
+        // the operator was written apart from its version, ">= 1.2"
+        let op = match (pending_op.take(), op) {
+            (Some(pending), "") => pending,
+            (Some(_), _) => return None,
+            (None, op) => op,
+        };
+
+        if version_str.is_empty() {
+            pending_op = Some(op);
+            continue;
+        }
+
+        push_comparators(&mut comparators, op, PartialVersion::parse(version_str)?);
+    }
+
+    if pending_op.is_some() {
+        return None;
+    }
+
+    Some(comparators)
+}
+
+fn split_operator(token: &str) -> (&str, &str) {
+    for op in [">=", "<=", "==", "~>", ">", "<", "=", "^", "~"] {
+        if let Some(rest) = token.strip_prefix(op) {
+            return (op, rest);
+        }
+    }
+    ("", token)
+}
+
+fn push_comparators(comparators: &mut Vec<Comparator>, op: &str, partial: PartialVersion) {
+    let PartialVersion { version, specified } = partial;
+
+    // "*", "x" and "" match everything
+    if specified == 0 {
+        if matches!(op, "<" | ">") {
+            // nothing is below or above "any version"
+            comparators.push(Comparator::Lt(Version::new(vec![0]).lowest_pre_release()));
+        }
+        return;
+    }
+
+    let is_partial = specified < 3 && version.pre_release.is_empty();
+
+    match op {
+        ">" if is_partial => comparators.push(Comparator::Gte(bump(&version, specified - 1))),
+        ">" => comparators.push(Comparator::Gt(version)),
+        ">=" => comparators.push(Comparator::Gte(version)),
+        "<" if is_partial => comparators.push(Comparator::Lt(version.lowest_pre_release())),
+        "<" => comparators.push(Comparator::Lt(version)),
+        "<=" if is_partial => comparators.push(Comparator::Lt(bump(&version, specified - 1))),
+        "<=" => comparators.push(Comparator::Lte(version)),
+        "^" => {
+            let significant = version.core[..specified]
+                .iter()
+                .position(|part| *part != 0)
+                .unwrap_or(specified - 1);
+            comparators.push(Comparator::Lt(bump(&version, significant)));
+            comparators.push(Comparator::Gte(version));
+        }
+        "~" | "~>" => {
+            let significant = if specified == 1 { 0 } else { 1 };
+            comparators.push(Comparator::Lt(bump(&version, significant)));
+            comparators.push(Comparator::Gte(version));
+        }
+        _ if is_partial => {
+            comparators.push(Comparator::Lt(bump(&version, specified - 1)));
+            comparators.push(Comparator::Gte(version));
+        }
+        _ => comparators.push(Comparator::Eq(version)),
+    }
+}
+
+/// The lowest pre-release of the version after `version` at `index` (`bump(1.2.3, 1)` is
+/// `1.3.0-0`).
+fn bump(version: &Version, index: usize) -> Version {
+    let mut core: Vec<i128> = version.core.iter().take(index + 1).copied().collect();
+    core.resize(index + 1, 0);
+    core[index] += 1;
+    Version::new(core).lowest_pre_release()
+}
+
+/// A version where trailing parts may be left out or given as `x`/`*`, e.g. `1.2` or `1.x`.
+struct PartialVersion {
+    version: Version,
+    specified: usize,
+}
+
+impl PartialVersion {
+    fn parse(input: &str) -> Option<Self> {
+        let input = input.strip_prefix(['v', 'V']).unwrap_or(input);
+        if input.is_empty() {
+            return Some(Self {
+                version: Version::new(vec![0]),
+                specified: 0,
+            });
+        }
+
+        let core_end = input.find(['-', '+']).unwrap_or(input.len());
+        let parts: Vec<&str> = input[..core_end].split('.').collect();
+        let specified = parts
+            .iter()
+            .position(|part| matches!(*part, "x" | "X" | "*"))
+            .unwrap_or(parts.len());
+
+        if specified < parts.len() {
+            let core = parts[..specified].join(".");
+            let version = if core.is_empty() {
+                Version::new(vec![0])
+            } else {
+                Version::parse(&core)?
+            };
+            return Some(Self { version, specified });
+        }
+
+        Some(Self {
+            version: Version::parse(input)?,
+            specified,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evaluation::comparisons::compare_version_in_range;
+    use crate::{dyn_value, test_only_make_eval_value};
+
+    fn in_range(version: &str, range: &str) -> bool {
+        compare_version_in_range(&dyn_value!(version), &test_only_make_eval_value!(range))
+    }
+
+    #[test]
+    fn test_comparator_sets() {
+        assert!(in_range("1.5.0", ">=1.2, <2"));
+        assert!(in_range("1.2.0", ">= 1.2 < 2"));
+        assert!(!in_range("2.0.0", ">=1.2, <2"));
+        assert!(!in_range("2.0.0-beta.1", ">=1.2, <2"));
+        assert!(!in_range("1.1.9", ">=1.2, <2"));
+    }
+
+    #[test]
+    fn test_caret_and_tilde() {
+        assert!(in_range("1.9.3", "^1.4"));
+        assert!(!in_range("1.3.9", "^1.4"));
+        assert!(!in_range("2.0.0", "^1.4"));
+        assert!(in_range("0.2.9", "^0.2.3"));
+        assert!(!in_range("0.3.0", "^0.2.3"));
+        assert!(in_range("1.2.9", "~1.2.3"));
+        assert!(!in_range("1.3.0", "~1.2.3"));
+        assert!(in_range("1.9.0", "~1"));
+    }
+
+    #[test]
+    fn test_partial_and_wildcard_versions() {
+        assert!(in_range("1.2.7", "1.2"));
+        assert!(in_range("1.2.7", "1.2.x"));
+        assert!(!in_range("1.3.0", "1.2.x"));
+        assert!(in_range("5.0.0", "*"));
+        assert!(in_range("1.2.3", "=1.2.3"));
+        assert!(!in_range("1.2.4", "1.2.3"));
+        assert!(in_range("1.3.0", ">1.2"));
+        assert!(!in_range("1.2.9", ">1.2"));
+        assert!(in_range("1.2.9", "<=1.2"));
+    }
+
+    #[test]
+    fn test_hyphen_ranges_and_alternatives() {
+        assert!(in_range("1.3.0", "1.2.0 - 1.4.0"));
+        assert!(!in_range("1.4.1", "1.2.0 - 1.4.0"));
+        assert!(in_range("3.1.0", "^1.2 || ^3.0"));
+        assert!(!in_range("2.1.0", "^1.2 || ^3.0"));
+    }
+
+    #[test]
+    fn test_app_versions() {
+        assert!(in_range("v2.4.1.1023", ">=2.4.1.1000, <2.5"));
+        assert!(!in_range("2.4.1.999", ">=2.4.1.1000, <2.5"));
+        assert!(in_range("2.0.0-rc.2", ">=2.0.0-rc.1, <2.0.0"));
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        assert!(!in_range("1.2.3", ">=abc"));
+        assert!(!in_range("1.2.3", ">="));
+        assert!(!in_range("not_a_version", "*"));
+    }
+}
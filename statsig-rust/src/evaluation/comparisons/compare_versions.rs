@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use crate::{evaluation::evaluator_value::MemoizedEvaluatorValue, unwrap_or_return, DynamicValue};
 
 pub(crate) fn compare_versions(
@@ -8,71 +10,142 @@ pub(crate) fn compare_versions(
     let left_dyn_str = unwrap_or_return!(&left.string_value, false);
     let right_dyn_str = unwrap_or_return!(&right.string_value, false);
 
-    let left_str = &left_dyn_str.value;
-    let right_str = &right_dyn_str.value;
+    let left_version = unwrap_or_return!(Version::parse(&left_dyn_str.value), false);
+    let right_version = unwrap_or_return!(Version::parse(&right_dyn_str.value), false);
 
-    let result = match compare_versions_impl(left_str, right_str) {
-        ComparisonResult::Ok(result) => result,
-        ComparisonResult::NumericParseFailure => return false,
-    };
+    let result = left_version.cmp(&right_version);
 
     match op {
-        "version_gt" => result > 0,
-        "version_gte" => result >= 0,
-        "version_lt" => result < 0,
-        "version_lte" => result <= 0,
-        "version_eq" => result == 0,
-        "version_neq" => result != 0,
+        "version_gt" => result.is_gt(),
+        "version_gte" => result.is_ge(),
+        "version_lt" => result.is_lt(),
+        "version_lte" => result.is_le(),
+        "version_eq" => result.is_eq(),
+        "version_neq" => result.is_ne(),
         _ => false,
     }
 }
 
-enum ComparisonResult {
-    NumericParseFailure,
-    Ok(i32),
+/// A version ordered by SemVer 2.0 precedence. Any number of dot separated parts is accepted,
+/// missing parts count as 0 (`1.2` == `1.2.0.0`), a leading `v` is ignored, and so is build
+/// metadata after `+`. A version with a pre-release (`-beta.2`) is lower than the same version
+/// without one.
+#[derive(Debug, Clone)]
+pub(crate) struct Version {
+    pub core: Vec<i128>,
+    pub pre_release: Vec<PreReleaseIdentifier>,
 }
 
-fn compare_versions_impl(left_str: &str, right_str: &str) -> ComparisonResult {
-    let left_version = left_str.split('-').next().unwrap_or("");
-    let right_version = right_str.split('-').next().unwrap_or("");
-
-    let mut left_parts = left_version.split('.');
-    let mut right_parts = right_version.split('.');
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PreReleaseIdentifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
 
-    loop {
-        let left_num = match next_num(left_parts.next()) {
-            Ok(v) => v,
-            Err(_) => return ComparisonResult::NumericParseFailure,
+impl Version {
+    pub fn parse(input: &str) -> Option<Self> {
+        let trimmed = input.trim();
+        let trimmed = trimmed
+            .strip_prefix(['v', 'V'])
+            .unwrap_or(trimmed)
+            .split('+')
+            .next()
+            .unwrap_or("");
+
+        let (core_str, pre_release_str) = match trimmed.split_once('-') {
+            Some((core, pre_release)) => (core, Some(pre_release)),
+            None => (trimmed, None),
         };
 
-        let right_num = match next_num(right_parts.next()) {
-            Ok(v) => v,
-            Err(_) => return ComparisonResult::NumericParseFailure,
-        };
+        let core = core_str
+            .split('.')
+            .map(|part| part.trim().parse::<i128>().ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        let pre_release = pre_release_str
+            .map(|pre| pre.split('.').map(PreReleaseIdentifier::parse).collect())
+            .unwrap_or_default();
 
-        if left_num.is_none() && right_num.is_none() {
-            break;
+        Some(Self { core, pre_release })
+    }
+
+    pub fn new(core: Vec<i128>) -> Self {
+        Self {
+            core,
+            pre_release: Vec::new(),
         }
+    }
+
+    /// The lowest possible pre-release of this version (`1.2.0-0`). Used as an exclusive upper
+    /// bound, so that pre-releases of the next version are not included.
+    pub fn lowest_pre_release(mut self) -> Self {
+        self.pre_release = vec![PreReleaseIdentifier::Numeric(0)];
+        self
+    }
+}
+
+impl PreReleaseIdentifier {
+    fn parse(identifier: &str) -> Self {
+        let is_numeric = !identifier.is_empty() && identifier.bytes().all(|b| b.is_ascii_digit());
+        match identifier.parse::<u64>() {
+            Ok(num) if is_numeric => PreReleaseIdentifier::Numeric(num),
+            _ => PreReleaseIdentifier::AlphaNumeric(identifier.to_string()),
+        }
+    }
+}
+
+impl Ord for PreReleaseIdentifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(left), Self::Numeric(right)) => left.cmp(right),
+            (Self::Numeric(_), Self::AlphaNumeric(_)) => Ordering::Less,
+            (Self::AlphaNumeric(_), Self::Numeric(_)) => Ordering::Greater,
+            (Self::AlphaNumeric(left), Self::AlphaNumeric(right)) => left.cmp(right),
+        }
+    }
+}
 
-        let left_num = left_num.unwrap_or(0);
-        let right_num = right_num.unwrap_or(0);
+impl PartialOrd for PreReleaseIdentifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        if left_num < right_num {
-            return ComparisonResult::Ok(-1);
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.core.len().max(other.core.len());
+        for i in 0..len {
+            let left = self.core.get(i).copied().unwrap_or(0);
+            let right = other.core.get(i).copied().unwrap_or(0);
+            match left.cmp(&right) {
+                Ordering::Equal => continue,
+                unequal => return unequal,
+            }
         }
 
-        if left_num > right_num {
-            return ComparisonResult::Ok(1);
+        match (self.pre_release.is_empty(), other.pre_release.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.pre_release.cmp(&other.pre_release),
         }
     }
+}
 
-    ComparisonResult::Ok(0)
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-fn next_num(part: Option<&str>) -> Result<Option<i128>, std::num::ParseIntError> {
-    part.map(|s| s.trim().parse::<i128>()).transpose()
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
 }
 
+impl Eq for Version {}
+
 #[cfg(test)]
 mod tests {
     use crate::evaluation::comparisons::compare_versions;
@@ -104,4 +177,58 @@ mod tests {
         let result = compare_versions(&left, &right, "version_lt");
         assert!(result);
     }
+
+    #[test]
+    fn test_version_comparison_pre_release_precedence() {
+        // from the SemVer 2.0 spec
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ];
+
+        for pair in ordered.windows(2) {
+            let left = dyn_value!(pair[0]);
+            let right = test_only_make_eval_value!(pair[1]);
+            assert!(
+                compare_versions(&left, &right, "version_lt"),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_version_comparison_tolerant_parsing() {
+        let cases = [
+            ("v1.2.3", "1.2.3", "version_eq"),
+            ("1.2.3+build.5", "1.2.3", "version_eq"),
+            ("1.2", "1.2.0.0", "version_eq"),
+            ("1.2.3.10", "1.2.3.9", "version_gt"),
+            ("2.0.0-beta.3", "2.0.0", "version_neq"),
+        ];
+
+        for (left, right, op) in cases {
+            let left_value = dyn_value!(left);
+            let right_value = test_only_make_eval_value!(right);
+            assert!(
+                compare_versions(&left_value, &right_value, op),
+                "{left} {op} {right}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_version_comparison_parse_failure() {
+        let left = dyn_value!("not.a.version");
+        let right = test_only_make_eval_value!("1.2.3");
+
+        assert!(!compare_versions(&left, &right, "version_neq"));
+    }
 }
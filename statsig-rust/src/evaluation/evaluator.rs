@@ -1,13 +1,12 @@
 use std::collections::HashMap;
 
-use chrono::Utc;
 use lazy_static::lazy_static;
 use serde_json::Value;
 
 use crate::evaluation::cmab_evaluator::evaluate_cmab;
 use crate::evaluation::comparisons::{
     compare_arrays, compare_numbers, compare_str_with_regex, compare_strings_in_array,
-    compare_time, compare_versions,
+    compare_time, compare_version_in_range, compare_versions,
 };
 use crate::evaluation::dynamic_returnable::DynamicReturnable;
 use crate::evaluation::dynamic_string::DynamicString;
@@ -352,7 +351,7 @@ fn try_apply_config_mapping(
         for override_rule in &mapping.rules {
             let start_time = override_rule.start_time.unwrap_or_default();
 
-            if start_time > Utc::now().timestamp_millis() {
+            if start_time > ctx.now_ms() {
                 continue;
             }
 
@@ -404,22 +403,25 @@ fn try_apply_override(
     };
 
     match spec_type {
-        SpecType::Gate => adapter.get_gate_override(ctx.user.user_ref, spec_name, &mut ctx.result),
+        SpecType::Gate => adapter.get_gate_override(&ctx.user.user_ref, spec_name, &mut ctx.result),
 
         SpecType::DynamicConfig => {
-            adapter.get_dynamic_config_override(ctx.user.user_ref, spec_name, &mut ctx.result)
+            adapter.get_dynamic_config_override(&ctx.user.user_ref, spec_name, &mut ctx.result)
         }
 
-        SpecType::Experiment => {
-            adapter.get_experiment_override(ctx.user.user_ref, spec_name, &mut ctx.result, opt_spec)
-        }
+        SpecType::Experiment => adapter.get_experiment_override(
+            &ctx.user.user_ref,
+            spec_name,
+            &mut ctx.result,
+            opt_spec,
+        ),
 
         SpecType::Layer => {
-            adapter.get_layer_override(ctx.user.user_ref, spec_name, &mut ctx.result)
+            adapter.get_layer_override(&ctx.user.user_ref, spec_name, &mut ctx.result)
         }
 
         SpecType::ParameterStore => {
-            adapter.get_parameter_store_override(ctx.user.user_ref, spec_name, &mut ctx.result)
+            adapter.get_parameter_store_override(&ctx.user.user_ref, spec_name, &mut ctx.result)
         }
     }
 }
@@ -506,9 +508,7 @@ fn evaluate_condition<'a>(
             temp_value.as_ref()
         }
         "current_time" => {
-            temp_value = Some(DynamicValue::for_timestamp_evaluation(
-                Utc::now().timestamp_millis(),
-            ));
+            temp_value = Some(DynamicValue::for_timestamp_evaluation(ctx.now_ms()));
             temp_value.as_ref()
         }
         "user_bucket" => {
@@ -547,6 +547,7 @@ fn evaluate_condition<'a>(
         // version comparisons
         "version_gt" | "version_gte" | "version_lt" | "version_lte" | "version_eq"
         | "version_neq" => compare_versions(value, target_value, operator),
+        "version_in_range" => compare_version_in_range(value, target_value),
 
         // string/array comparisons
         "any"
@@ -558,7 +559,10 @@ fn evaluate_condition<'a>(
         "any_case_sensitive" | "none_case_sensitive" => {
             compare_strings_in_array(value, target_value, operator, false)
         }
-        "str_matches" => compare_str_with_regex(value, target_value),
+        "str_matches" => match ctx.statsig.and_then(|s| s.regex_guard()) {
+            Some(guard) => guard.compare_str_with_regex(value, target_value, ctx.guarded_regexes),
+            None => compare_str_with_regex(value, target_value),
+        },
 
         // time comparisons
         "before" | "after" | "on" => compare_time(value, target_value, operator),
@@ -641,8 +645,10 @@ fn evaluate_experiment_group<'a>(
             return None;
         }
     };
-    let res = statsig.get_experiment_with_options(
-        ctx.user.user_ref,
+    // the enriched user is reused, so the enricher is not run again and the nested exposure
+    // is logged like the outer one
+    let res = statsig.get_experiment_for_user_internal(
+        ctx.user,
         exp_name.as_str(),
         ExperimentEvaluationOptions {
             disable_exposure_logging: ctx.disable_exposure_logging,
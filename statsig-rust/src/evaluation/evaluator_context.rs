@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::clock::{Clock, SystemClock};
 use crate::evaluation::dynamic_value::DynamicValue;
 use crate::evaluation::evaluator_result::EvaluatorResult;
+use crate::evaluation::guarded_regex::GuardedRegexes;
 use crate::hashing::HashUtil;
 use crate::id_lists_adapter::IdList;
 use crate::interned_string::InternedString;
@@ -25,6 +27,7 @@ pub enum IdListResolution<'a> {
 pub struct EvaluatorContext<'a> {
     pub user: &'a StatsigUserInternal<'a, 'a>,
     pub specs_data: &'a SpecsResponseFull,
+    pub guarded_regexes: &'a GuardedRegexes,
     pub id_list_resolver: IdListResolution<'a>,
     pub hashing: &'a HashUtil,
     pub result: EvaluatorResult,
@@ -43,6 +46,10 @@ pub struct EvaluatorContext<'a> {
     pub only_evaluate_overrides: bool,
 }
 
+lazy_static::lazy_static! {
+    static ref EMPTY_GUARDED_REGEXES: GuardedRegexes = GuardedRegexes::default();
+}
+
 impl<'a> EvaluatorContext<'a> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -61,6 +68,7 @@ impl<'a> EvaluatorContext<'a> {
         Self {
             user,
             specs_data,
+            guarded_regexes: &EMPTY_GUARDED_REGEXES,
             id_list_resolver,
             hashing,
             app_id,
@@ -77,6 +85,23 @@ impl<'a> EvaluatorContext<'a> {
         }
     }
 
+    /// The `str_matches` patterns compiled for the ruleset under `RegexSafetyOptions`.
+    #[must_use]
+    pub fn with_guarded_regexes(mut self, guarded_regexes: &'a GuardedRegexes) -> Self {
+        self.guarded_regexes = guarded_regexes;
+        self
+    }
+
+    /// The current time from the instance's clock, for `current_time` conditions and
+    /// override start times.
+    pub fn now_ms(&self) -> i64 {
+        let now = match self.statsig {
+            Some(statsig) => statsig.now_ms(),
+            None => SystemClock.now_ms(),
+        };
+        now as i64
+    }
+
     pub fn reset_result(&mut self) {
         self.nested_count = 0;
         self.result = EvaluatorResult::default();
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use fancy_regex::{Error as FancyError, Regex as FancyRegex, RegexBuilder, RuntimeError};
+use regex::Regex as LinearRegex;
+use serde::{Deserialize, Serialize};
+
+use crate::evaluation::comparisons::compare_str_with_regex;
+use crate::evaluation::evaluator_value::MemoizedEvaluatorValue;
+use crate::observability::observability_client_adapter::{MetricType, ObservabilityEvent};
+use crate::observability::ops_stats::{OpsStatsForInstance, OPS_STATS};
+use crate::specs_response::spec_types::SpecsResponseFull;
+use crate::{log_d, log_w, unwrap_or_return, DynamicValue};
+
+const TAG: &str = "GuardedRegex";
+
+const REGEX_LIMIT_EXCEEDED_METRIC: &str = "regex_limit_exceeded";
+
+/// Limits applied to `str_matches` conditions, which run patterns from specs against user
+/// supplied values. Any limit that trips makes the condition fail (as if it did not match)
+/// and increments the `regex_limit_exceeded` metric.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegexSafetyOptions {
+    /// Steps the backtracking engine may take on a single match. Only applies to patterns
+    /// that need backtracking (lookaround, backreferences). Defaults to fancy-regex's limit
+    /// of 1,000,000.
+    pub backtrack_limit: Option<usize>,
+
+    /// Values longer than this many bytes are not matched.
+    pub max_input_length: Option<usize>,
+
+    /// Compile patterns without lookaround or backreferences with the linear-time `regex`
+    /// engine, so they can never backtrack.
+    pub prefer_linear_engine: Option<bool>,
+}
+
+enum GuardedRegex {
+    Linear(LinearRegex),
+    Backtracking(FancyRegex),
+    Invalid,
+}
+
+/// The `str_matches` patterns of a ruleset, compiled under [`RegexSafetyOptions`] when the
+/// options call for a different engine or backtrack limit than spec parsing uses. Built once
+/// each time new specs are applied, and stored alongside them.
+#[derive(Default)]
+pub struct GuardedRegexes {
+    patterns: HashMap<String, GuardedRegex>,
+}
+
+impl GuardedRegexes {
+    pub fn compile(specs: &SpecsResponseFull, options: &RegexSafetyOptions) -> Self {
+        let mut patterns = HashMap::new();
+        if options.backtrack_limit.is_none() && options.prefer_linear_engine != Some(true) {
+            // the patterns compiled while parsing the specs already match these options
+            return Self { patterns };
+        }
+
+        for condition in specs.condition_map.values() {
+            if condition.operator.as_deref() != Some("str_matches") {
+                continue;
+            }
+
+            let pattern = condition
+                .target_value
+                .as_ref()
+                .and_then(|v| v.as_ref().string_value.as_ref());
+            if let Some(pattern) = pattern {
+                if !patterns.contains_key(pattern.value.as_str()) {
+                    let compiled = compile(&pattern.value, options);
+                    patterns.insert(pattern.value.to_string(), compiled);
+                }
+            }
+        }
+
+        Self { patterns }
+    }
+}
+
+/// Evaluates `str_matches` under [`RegexSafetyOptions`], using the patterns compiled for the
+/// current ruleset, or the one compiled while parsing the specs when no guarded one is needed.
+pub(crate) struct RegexGuard {
+    options: RegexSafetyOptions,
+    ops_stats: Arc<OpsStatsForInstance>,
+}
+
+impl RegexGuard {
+    pub fn new(sdk_key: &str, options: RegexSafetyOptions) -> Self {
+        Self {
+            options,
+            ops_stats: OPS_STATS.get_for_instance(sdk_key),
+        }
+    }
+
+    pub fn compare_str_with_regex(
+        &self,
+        value: &DynamicValue,
+        regex_value: &MemoizedEvaluatorValue,
+        guarded_regexes: &GuardedRegexes,
+    ) -> bool {
+        let value_str = unwrap_or_return!(&value.string_value, false);
+        let pattern = unwrap_or_return!(&regex_value.string_value, false);
+        let input = value_str.value.as_str();
+
+        if let Some(max_len) = self.options.max_input_length {
+            if input.len() > max_len {
+                self.log_limit_exceeded("max_input_length");
+                return false;
+            }
+        }
+
+        let regex = match guarded_regexes.patterns.get(pattern.value.as_str()) {
+            Some(regex) => regex,
+            None => return compare_str_with_regex(value, regex_value),
+        };
+
+        match regex {
+            GuardedRegex::Linear(regex) => regex.is_match(input),
+            GuardedRegex::Backtracking(regex) => match regex.is_match(input) {
+                Ok(matched) => matched,
+                Err(FancyError::RuntimeError(RuntimeError::BacktrackLimitExceeded)) => {
+                    self.log_limit_exceeded("backtrack_limit");
+                    false
+                }
+                Err(e) => {
+                    log_d!(TAG, "Regex match failed: {}", e);
+                    false
+                }
+            },
+            GuardedRegex::Invalid => false,
+        }
+    }
+
+    fn log_limit_exceeded(&self, limit: &str) {
+        log_d!(TAG, "Regex {} exceeded", limit);
+        self.ops_stats.log(ObservabilityEvent::new_event(
+            MetricType::Increment,
+            REGEX_LIMIT_EXCEEDED_METRIC.to_string(),
+            1.0,
+            Some(HashMap::from([("limit".to_string(), limit.to_string())])),
+        ));
+    }
+}
+
+fn compile(pattern: &str, options: &RegexSafetyOptions) -> GuardedRegex {
+    if options.prefer_linear_engine == Some(true) {
+        if let Ok(regex) = LinearRegex::new(pattern) {
+            return GuardedRegex::Linear(regex);
+        }
+    }
+
+    let mut builder = RegexBuilder::new(pattern);
+    if let Some(limit) = options.backtrack_limit {
+        builder.backtrack_limit(limit);
+    }
+
+    match builder.build() {
+        Ok(regex) => GuardedRegex::Backtracking(regex),
+        Err(e) => {
+            log_w!(TAG, "Failed to compile regex '{}': {}", pattern, e);
+            GuardedRegex::Invalid
+        }
+    }
+}
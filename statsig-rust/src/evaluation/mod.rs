@@ -14,9 +14,12 @@ pub mod secondary_exposure_key;
 pub mod user_agent_parsing;
 
 pub(crate) mod cmab_evaluator;
+pub mod cmab_learner;
+pub mod cmab_offline_eval;
 pub(crate) mod comparisons;
 pub(crate) mod country_lookup;
 pub(crate) mod get_unit_id;
+pub mod guarded_regex;
 
 #[cfg(test)]
 mod __tests__;
@@ -0,0 +1,216 @@
+use crate::user::UserAgentClientHints;
+use crate::{dyn_value, DynamicValue};
+
+pub struct ClientHintsParser;
+
+impl ClientHintsParser {
+    /// Reads `field` from the client hints, using the names and version format of the UA
+    /// string parsers. `None` when the hints do not carry the field.
+    pub fn get_value_from_client_hints(
+        field: &str,
+        hints: &UserAgentClientHints,
+    ) -> Option<DynamicValue> {
+        let value = match field {
+            "os_name" | "osname" => get_os_name(hints)?,
+            "os_version" | "osversion" => get_os_version(hints)?,
+            "browser_name" | "browsername" => get_browser(hints)?.0,
+            "browser_version" | "browserversion" => get_browser(hints)?.1,
+            // tablets also send `?0`, so only a mobile device is known from the hints
+            "device_type" | "devicetype" if hints.mobile == Some(true) => "mobile".to_string(),
+            _ => return None,
+        };
+
+        Some(dyn_value!(value))
+    }
+}
+
+fn get_os_name(hints: &UserAgentClientHints) -> Option<String> {
+    let platform = unquote(hints.platform.as_deref()?);
+
+    let name = match platform {
+        "" | "Unknown" => return None,
+        "macOS" => "Mac OS X",
+        "Chrome OS" | "Chromium OS" => "Chrome OS",
+        other => other,
+    };
+
+    Some(name.to_string())
+}
+
+fn get_os_version(hints: &UserAgentClientHints) -> Option<String> {
+    let os_name = get_os_name(hints)?;
+    let version = unquote(hints.platform_version.as_deref()?);
+    if version.is_empty() {
+        return None;
+    }
+
+    if os_name != "Windows" {
+        return Some(normalize_version(version));
+    }
+
+    // Windows reports the UAP version, 13 and up is Windows 11, 1 to 12 is Windows 10
+    let major = version.split('.').next()?.parse::<u32>().ok()?;
+    let windows_version = match major {
+        13.. => "11",
+        1.. => "10",
+        _ => "8.1",
+    };
+    Some(normalize_version(windows_version))
+}
+
+/// The most specific brand, and its full version when known.
+fn get_browser(hints: &UserAgentClientHints) -> Option<(String, String)> {
+    let full_versions = hints
+        .full_version_list
+        .as_deref()
+        .map(parse_brand_list)
+        .unwrap_or_default();
+    let major_versions = hints
+        .brands
+        .as_deref()
+        .map(parse_brand_list)
+        .unwrap_or_default();
+
+    let brands = if full_versions.is_empty() {
+        &major_versions
+    } else {
+        &full_versions
+    };
+
+    let (brand, version) = brands
+        .iter()
+        .filter(|(brand, _)| !is_grease_brand(brand))
+        .min_by_key(|(brand, _)| *brand == "Chromium")?;
+
+    let is_mobile = hints.mobile == Some(true);
+    let name = match (*brand, is_mobile) {
+        ("Google Chrome", true) => "Chrome Mobile",
+        ("Google Chrome", false) => "Chrome",
+        ("Microsoft Edge", true) => "Edge Mobile",
+        ("Microsoft Edge", false) => "Edge",
+        ("Opera", true) => "Opera Mobile",
+        ("Yandex", _) => "Yandex Browser",
+        (other, _) => other,
+    };
+
+    Some((name.to_string(), normalize_version(version)))
+}
+
+/// `"Chromium";v="124", "Not-A.Brand";v="99"` to `[("Chromium", "124"), ("Not-A.Brand", "99")]`
+fn parse_brand_list(value: &str) -> Vec<(&str, &str)> {
+    split_unquoted(value, ',')
+        .into_iter()
+        .filter_map(|entry| {
+            let mut parts = split_unquoted(entry, ';').into_iter().map(str::trim);
+            let brand = unquote(parts.next()?);
+            let version = parts.find_map(|p| p.strip_prefix("v=")).map(unquote)?;
+            (!brand.is_empty()).then_some((brand, version))
+        })
+        .collect()
+}
+
+/// Splits on `separator`, except inside quoted strings (brands may contain `;` or `,`).
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+
+    parts
+}
+
+// browsers add a made up brand, e.g. "Not-A.Brand" or "Not)A;Brand", so servers do not
+// depend on the order or number of brands
+fn is_grease_brand(brand: &str) -> bool {
+    brand.starts_with("Not") && brand.contains("Brand")
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim().trim_matches('"')
+}
+
+/// Keeps up to `major.minor.patch`, adding missing parts as 0 like the UA string parsers.
+fn normalize_version(version: &str) -> String {
+    let mut parts: Vec<&str> = version.split('.').take(3).collect();
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    parts.join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClientHintsParser;
+    use crate::user::UserAgentClientHints;
+
+    fn get(field: &str, hints: &UserAgentClientHints) -> Option<String> {
+        ClientHintsParser::get_value_from_client_hints(field, hints)
+            .and_then(|v| v.string_value)
+            .map(|s| s.value.to_string())
+    }
+
+    #[test]
+    fn test_desktop_chrome() {
+        let hints = UserAgentClientHints {
+            brands: Some(
+                r#""Chromium";v="124", "Google Chrome";v="124", "Not-A.Brand";v="99""#.into(),
+            ),
+            full_version_list: Some(
+                r#""Chromium";v="124.0.6367.118", "Google Chrome";v="124.0.6367.118", "Not-A.Brand";v="99.0.0.0""#.into(),
+            ),
+            platform: Some(r#""Windows""#.into()),
+            platform_version: Some(r#""15.0.0""#.into()),
+            mobile: Some(false),
+        };
+
+        assert_eq!(get("browser_name", &hints).as_deref(), Some("Chrome"));
+        assert_eq!(
+            get("browser_version", &hints).as_deref(),
+            Some("124.0.6367")
+        );
+        assert_eq!(get("os_name", &hints).as_deref(), Some("Windows"));
+        assert_eq!(get("os_version", &hints).as_deref(), Some("11.0.0"));
+        assert_eq!(get("device_type", &hints), None);
+    }
+
+    #[test]
+    fn test_mobile_and_major_versions_only() {
+        let hints = UserAgentClientHints {
+            brands: Some(r#""Not)A;Brand";v="8", "Chromium";v="138""#.into()),
+            platform: Some(r#""Android""#.into()),
+            platform_version: Some(r#""14.0.0""#.into()),
+            mobile: Some(true),
+            ..UserAgentClientHints::default()
+        };
+
+        assert_eq!(get("browser_name", &hints).as_deref(), Some("Chromium"));
+        assert_eq!(get("browser_version", &hints).as_deref(), Some("138.0.0"));
+        assert_eq!(get("os_name", &hints).as_deref(), Some("Android"));
+        assert_eq!(get("os_version", &hints).as_deref(), Some("14.0.0"));
+        assert_eq!(get("device_type", &hints).as_deref(), Some("mobile"));
+    }
+
+    #[test]
+    fn test_missing_hints() {
+        let hints = UserAgentClientHints {
+            platform: Some(r#""macOS""#.into()),
+            ..UserAgentClientHints::default()
+        };
+
+        assert_eq!(get("os_name", &hints).as_deref(), Some("Mac OS X"));
+        assert_eq!(get("os_version", &hints), None);
+        assert_eq!(get("browser_name", &hints), None);
+        assert_eq!(get("browser_version", &hints), None);
+    }
+}
@@ -31,6 +31,22 @@ impl FirstPartyUserAgentParser {
                     .get_version_string()
                     .unwrap_or("0.0.0".to_string())))
             }
+            "device_type" | "devicetype" => {
+                let device = UaParser::parse_device(user_agent);
+                Some(dyn_value!(device.device_type))
+            }
+            "device_brand" | "devicebrand" => {
+                let device = UaParser::parse_device(user_agent);
+                device.brand.map(|brand| dyn_value!(brand))
+            }
+            "device_model" | "devicemodel" => {
+                let device = UaParser::parse_device(user_agent);
+                device.model.map(|model| dyn_value!(model))
+            }
+            "is_bot" | "isbot" => {
+                let device = UaParser::parse_device(user_agent);
+                Some(dyn_value!(device.is_bot))
+            }
             _ => {
                 log_w!(TAG, "Unsupported field: {}", field);
                 None
@@ -1,3 +1,4 @@
+pub mod client_hints_parser;
 pub mod first_party_ua_parser;
 pub mod statsig_uaparser;
 pub mod third_party_ua_parser;
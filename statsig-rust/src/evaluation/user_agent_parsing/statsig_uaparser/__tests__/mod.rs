@@ -1,4 +1,5 @@
 pub mod test_helpers;
 pub mod test_tokenizer;
 pub mod test_ua_parser_browser;
+pub mod test_ua_parser_device;
 pub mod test_ua_parser_os;
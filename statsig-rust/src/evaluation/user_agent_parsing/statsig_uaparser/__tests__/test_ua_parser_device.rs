@@ -0,0 +1,164 @@
+use super::super::ua_parser::UaParser;
+
+fn assert_device(
+    user_agent: &str,
+    device_type: &str,
+    brand: Option<&str>,
+    model: Option<&str>,
+    is_bot: bool,
+) {
+    let device = UaParser::parse_device(user_agent);
+
+    assert_eq!(device.device_type, device_type, "User Agent: {user_agent}");
+    assert_eq!(device.brand, brand, "User Agent: {user_agent}");
+    assert_eq!(device.model, model, "User Agent: {user_agent}");
+    assert_eq!(device.is_bot, is_bot, "User Agent: {user_agent}");
+}
+
+#[test]
+fn parsing_apple_devices() {
+    assert_device(
+        "Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Mobile/15E148 Safari/604.1",
+        "mobile",
+        Some("Apple"),
+        Some("iPhone"),
+        false,
+    );
+    assert_device(
+        "Mozilla/5.0 (iPad; CPU OS 16_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.6 Mobile/15E148 Safari/604.1",
+        "tablet",
+        Some("Apple"),
+        Some("iPad"),
+        false,
+    );
+    assert_device(
+        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Safari/605.1.15",
+        "desktop",
+        Some("Apple"),
+        Some("Mac"),
+        false,
+    );
+    assert_device(
+        "AppleTV11,1/11.1",
+        "tv",
+        Some("Apple"),
+        Some("Apple TV"),
+        false,
+    );
+}
+
+#[test]
+fn parsing_android_devices() {
+    assert_device(
+        "Mozilla/5.0 (Linux; Android 14; SM-S918B Build/UP1A.231005.007; wv) AppleWebKit/537.36 (KHTML, like Gecko) Version/4.0 Chrome/124.0.6367.82 Mobile Safari/537.36",
+        "mobile",
+        Some("Samsung"),
+        Some("SM-S918B"),
+        false,
+    );
+    assert_device(
+        "Mozilla/5.0 (Linux; Android 14; Pixel 8 Pro) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Mobile Safari/537.36",
+        "mobile",
+        Some("Google"),
+        Some("Pixel 8 Pro"),
+        false,
+    );
+    assert_device(
+        "Mozilla/5.0 (Linux; U; Android 4.4.2; en-us; SAMSUNG SM-T530NU Build/KOT49H) AppleWebKit/537.36 (KHTML, like Gecko) Version/1.5 Chrome/28.0.1500.94 Safari/537.36",
+        "tablet",
+        Some("Samsung"),
+        Some("SM-T530NU"),
+        false,
+    );
+    assert_device(
+        "Mozilla/5.0 (Linux; Android 9; AFTMM Build/PS7633) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/118.0.5993.144 Mobile Safari/537.36",
+        "tv",
+        Some("Amazon"),
+        Some("AFTMM"),
+        false,
+    );
+}
+
+#[test]
+fn parsing_reduced_android_user_agent() {
+    // Chrome reports every Android device as model "K"
+    assert_device(
+        "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Mobile Safari/537.36",
+        "mobile",
+        None,
+        None,
+        false,
+    );
+}
+
+#[test]
+fn parsing_desktop_and_tv() {
+    assert_device(
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36 Edg/124.0.0.0",
+        "desktop",
+        None,
+        None,
+        false,
+    );
+    assert_device(
+        "Mozilla/5.0 (X11; CrOS x86_64 14541.0.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
+        "desktop",
+        None,
+        None,
+        false,
+    );
+    assert_device(
+        "Mozilla/5.0 (SMART-TV; LINUX; Tizen 6.0) AppleWebKit/537.36 (KHTML, like Gecko) 76.0.3809.146/6.0 TV Safari/537.36",
+        "tv",
+        None,
+        None,
+        false,
+    );
+    assert_device(
+        "Mozilla/5.0 (X11; Linux armv7l) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/108.0.0.0 Safari/537.36 CrKey/1.56.500000 DeviceType/Chromecast",
+        "tv",
+        Some("Google"),
+        Some("Chromecast"),
+        false,
+    );
+}
+
+#[test]
+fn parsing_bots() {
+    assert_device(
+        "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        "other",
+        None,
+        None,
+        true,
+    );
+    assert_device(
+        "Mozilla/5.0 (Linux; Android 6.0.1; Nexus 5X Build/MMB29P) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.6367.201 Mobile Safari/537.36 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        "mobile",
+        Some("Google"),
+        Some("Nexus 5X"),
+        true,
+    );
+    assert_device(
+        "Mozilla/5.0 (compatible; YandexSpider/3.0)",
+        "other",
+        None,
+        None,
+        true,
+    );
+    assert_device(
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/124.0.0.0 Safari/537.36",
+        "desktop",
+        None,
+        None,
+        true,
+    );
+    assert_device("curl/8.4.0", "other", None, None, true);
+    assert_device("python-requests/2.31.0", "other", None, None, true);
+}
+
+#[test]
+fn parsing_native_app_http_clients() {
+    assert_device("okhttp/4.12.0", "other", None, None, false);
+    assert_device("Java/17.0.2", "other", None, None, false);
+}
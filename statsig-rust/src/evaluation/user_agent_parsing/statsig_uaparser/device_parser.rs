@@ -0,0 +1,252 @@
+use super::tokenizer::{Tokenizer, TokenizerResult};
+use super::UaParser;
+
+pub const DEVICE_TYPE_MOBILE: &str = "mobile";
+pub const DEVICE_TYPE_TABLET: &str = "tablet";
+pub const DEVICE_TYPE_DESKTOP: &str = "desktop";
+pub const DEVICE_TYPE_TV: &str = "tv";
+pub const DEVICE_TYPE_OTHER: &str = "other";
+
+// ------------------------------------------------------------------------------------------------
+// Rules. Patterns are matched case-insensitively against the whole user agent, in order.
+// ------------------------------------------------------------------------------------------------
+
+/// Crawlers, monitors and HTTP libraries that do not include "bot" or "crawler" in a product
+/// token (those are already found by the tokenizer). HTTP clients that native apps send
+/// requests with, such as OkHttp and Java, are not listed, since those users are real.
+const BOT_PATTERNS: &[&str] = &[
+    "spider",
+    "slurp",
+    "crawl",
+    "headlesschrome",
+    "phantomjs",
+    "lighthouse",
+    "pingdom",
+    "facebookexternalhit",
+    "facebookcatalog",
+    "embedly",
+    "whatsapp/",
+    "python-requests",
+    "python-urllib",
+    "go-http-client",
+    "curl/",
+    "wget/",
+    "apache-httpclient",
+    "libwww-perl",
+    "scrapy",
+];
+
+const TV_PATTERNS: &[&str] = &[
+    "smart-tv",
+    "smarttv",
+    "smart tv",
+    "googletv",
+    "crkey",
+    "android tv",
+    "hbbtv",
+    "netcast",
+    "web0s",
+    "webos.tv",
+    "bravia",
+    "roku",
+    "tizen tv",
+];
+
+const TABLET_PATTERNS: &[&str] = &["tablet", "kindle", "silk/", "playbook"];
+
+/// Known model prefixes, for Android devices that only report a model in the user agent.
+const MODEL_BRANDS: &[(&str, &str)] = &[
+    ("SM-", "Samsung"),
+    ("GT-", "Samsung"),
+    ("SAMSUNG", "Samsung"),
+    ("Galaxy", "Samsung"),
+    ("Pixel", "Google"),
+    ("Nexus", "Google"),
+    ("Redmi", "Xiaomi"),
+    ("POCO", "Xiaomi"),
+    ("Mi ", "Xiaomi"),
+    ("MI ", "Xiaomi"),
+    ("moto", "Motorola"),
+    ("Moto", "Motorola"),
+    ("XT", "Motorola"),
+    ("ONEPLUS", "OnePlus"),
+    ("OnePlus", "OnePlus"),
+    ("CPH", "OPPO"),
+    ("OPPO", "OPPO"),
+    ("RMX", "realme"),
+    ("vivo", "vivo"),
+    ("HUAWEI", "Huawei"),
+    ("HONOR", "Honor"),
+    ("LM-", "LG"),
+    ("LG-", "LG"),
+    ("Nokia", "Nokia"),
+    ("XQ-", "Sony"),
+    ("KF", "Amazon"),
+    ("AFT", "Amazon"),
+    ("Lenovo", "Lenovo"),
+    ("Infinix", "Infinix"),
+    ("TECNO", "Tecno"),
+];
+
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct DeviceResult<'a> {
+    pub device_type: &'static str,
+    pub brand: Option<&'a str>,
+    pub model: Option<&'a str>,
+    pub is_bot: bool,
+}
+
+impl UaParser {
+    pub fn parse_device(agent: &str) -> DeviceResult<'_> {
+        let result = Tokenizer::run(agent);
+        let lowered = agent.to_ascii_lowercase();
+
+        let is_bot = result.bot_detected
+            || result.crawler_hint
+            || BOT_PATTERNS.iter().any(|p| lowered.contains(p));
+
+        let (brand, model) = get_brand_and_model(agent, &result);
+
+        DeviceResult {
+            device_type: get_device_type(agent, &lowered, &result, model),
+            brand,
+            model,
+            is_bot,
+        }
+    }
+}
+
+fn get_device_type(
+    agent: &str,
+    lowered: &str,
+    result: &TokenizerResult,
+    model: Option<&str>,
+) -> &'static str {
+    if has_tag(result, "ATV OS X") || TV_PATTERNS.iter().any(|p| lowered.contains(p)) {
+        return DEVICE_TYPE_TV;
+    }
+
+    if has_tag(result, "iPad") || TABLET_PATTERNS.iter().any(|p| lowered.contains(p)) {
+        return DEVICE_TYPE_TABLET;
+    }
+
+    // Amazon Fire TV and Fire tablet models
+    match model {
+        Some(m) if m.starts_with("AFT") => return DEVICE_TYPE_TV,
+        Some(m) if m.starts_with("KF") => return DEVICE_TYPE_TABLET,
+        _ => {}
+    }
+
+    let is_android = lowered.contains("android");
+    if has_tag(result, "iPhone")
+        || lowered.contains("ipod")
+        || lowered.contains("windows phone")
+        || lowered.contains("mobi")
+        || result.mobile_hint
+        || result.cfnetwork_hint
+    {
+        return DEVICE_TYPE_MOBILE;
+    }
+
+    // Android tablets leave "Mobile" out of the user agent
+    if is_android {
+        return DEVICE_TYPE_TABLET;
+    }
+
+    if result.windows_hint
+        || result.macos_hint
+        || result.linux_hint
+        || agent.contains("CrOS")
+        || lowered.contains("x11")
+    {
+        return DEVICE_TYPE_DESKTOP;
+    }
+
+    DEVICE_TYPE_OTHER
+}
+
+fn get_brand_and_model<'a>(
+    agent: &'a str,
+    result: &TokenizerResult,
+) -> (Option<&'a str>, Option<&'a str>) {
+    let apple_model = if has_tag(result, "iPhone") {
+        Some("iPhone")
+    } else if has_tag(result, "iPad") {
+        Some("iPad")
+    } else if agent.contains("iPod") {
+        Some("iPod")
+    } else if has_tag(result, "ATV OS X") {
+        Some("Apple TV")
+    } else if result.macos_hint && !result.ios_hint {
+        Some("Mac")
+    } else {
+        None
+    };
+    if apple_model.is_some() {
+        return (Some("Apple"), apple_model);
+    }
+
+    if agent.contains("CrKey") {
+        return (Some("Google"), Some("Chromecast"));
+    }
+
+    if let Some(model) = get_android_model(agent) {
+        let brand = MODEL_BRANDS
+            .iter()
+            .find(|(prefix, _)| model.starts_with(prefix))
+            .map(|(_, brand)| *brand);
+
+        // "SAMSUNG SM-S918B" is reported by Samsung Internet
+        let model = model.strip_prefix("SAMSUNG ").unwrap_or(model);
+        return (brand, Some(model));
+    }
+
+    if result.playstation_hint {
+        return (Some("Sony"), Some("PlayStation"));
+    }
+
+    if agent.contains("Xbox") {
+        return (Some("Microsoft"), Some("Xbox"));
+    }
+
+    if agent.contains("Nintendo") {
+        return (Some("Nintendo"), None);
+    }
+
+    (None, None)
+}
+
+/// The model in `(Linux; Android 14; SM-S918B Build/UP1A.231005.007; wv)`. `None` for the
+/// reduced user agent, which always reports the model as `K`.
+fn get_android_model(agent: &str) -> Option<&str> {
+    let start = agent.find('(')?;
+    let end = agent[start..].find(')')? + start;
+    let mut parts = agent[start + 1..end].split(';').map(str::trim);
+
+    parts.find(|p| p.starts_with("Android"))?;
+
+    let model = parts.find(|p| !is_android_filler(p))?;
+    let model = match model.find("Build/") {
+        Some(i) => model[..i].trim(),
+        None => model,
+    };
+
+    (!model.is_empty() && model != "K").then_some(model)
+}
+
+// "U", "wv" and locales like "en-us" can appear between the Android version and the model
+fn is_android_filler(part: &str) -> bool {
+    let is_lang = |s: &str| s.len() == 2 && s.chars().all(|c| c.is_ascii_alphabetic());
+    let is_locale = match part.split_once(['-', '_']) {
+        Some((lang, region)) => is_lang(lang) && is_lang(region),
+        None => is_lang(part) && part.chars().all(|c| c.is_ascii_lowercase()),
+    };
+
+    part.is_empty() || part == "U" || part == "wv" || part.starts_with("Build/") || is_locale
+}
+
+fn has_tag(result: &TokenizerResult, tag: &str) -> bool {
+    result.tokens.iter().any(|t| t.tag == tag)
+}
@@ -1,7 +1,9 @@
+pub mod device_parser;
 pub mod tokenizer;
 pub mod ua_parser;
 pub mod window_iter;
 
+pub use device_parser::*;
 pub use ua_parser::*;
 
 #[cfg(test)]
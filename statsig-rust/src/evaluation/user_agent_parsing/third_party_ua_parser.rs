@@ -1,3 +1,4 @@
+use super::first_party_ua_parser::FirstPartyUserAgentParser;
 use crate::{dyn_value, log_d, log_e, DynamicValue};
 use parking_lot::RwLock;
 use std::borrow::Cow;
@@ -17,6 +18,13 @@ impl ThirdPartyUserAgentParser {
         field: &str,
         user_agent: &str,
     ) -> Result<Option<DynamicValue>, &'static str> {
+        // the bundled regexes have no device rules, these come from the first party rules
+        if is_device_field(field) {
+            return Ok(FirstPartyUserAgentParser::get_value_from_user_agent(
+                field, user_agent,
+            ));
+        }
+
         let lock = PARSER
             .try_read_for(std::time::Duration::from_secs(5))
             .ok_or("lock_failure")?;
@@ -101,3 +109,17 @@ impl ThirdPartyUserAgentParser {
         }
     }
 }
+
+fn is_device_field(field: &str) -> bool {
+    matches!(
+        field,
+        "device_type"
+            | "devicetype"
+            | "device_brand"
+            | "devicebrand"
+            | "device_model"
+            | "devicemodel"
+            | "is_bot"
+            | "isbot"
+    )
+}
@@ -3,10 +3,13 @@ use std::sync::Arc;
 use crate::evaluation::dynamic_string::DynamicString;
 use crate::evaluation::user_agent_parsing::ParsedUserAgentValue;
 use crate::interned_string::InternedString;
+use crate::user::user_data::UserData;
 use crate::user::StatsigUserInternal;
-use crate::{log_w, unwrap_or_return, DynamicValue, StatsigOptions, StatsigUser};
+use crate::{log_w, DynamicValue, StatsigOptions, StatsigUser};
 
+use super::client_hints_parser::ClientHintsParser;
 use super::first_party_ua_parser::FirstPartyUserAgentParser;
+use super::statsig_uaparser::UaParser;
 use super::third_party_ua_parser::ThirdPartyUserAgentParser;
 
 lazy_static::lazy_static! {
@@ -30,6 +33,12 @@ fn get_third_party_ua_value(key: &str, ua: &str) -> Option<InternedString> {
         .and_then(|dv| dv.string_value.map(|s| s.value))
 }
 
+fn get_client_hints_value(key: &str, user: &StatsigUser) -> Option<InternedString> {
+    let hints = user.get_user_agent_client_hints()?;
+    ClientHintsParser::get_value_from_client_hints(key, hints)
+        .and_then(|v| v.string_value.map(|s| s.value))
+}
+
 impl UserAgentParser {
     pub fn get_value_from_user_agent(
         user: &StatsigUserInternal,
@@ -42,6 +51,15 @@ impl UserAgentParser {
             _ => return None,
         };
 
+        // Chromium freezes parts of the UA string, so client hints are preferred when present
+        if let Some(hints) = user.user_ref.get_user_agent_client_hints() {
+            if let Some(value) =
+                ClientHintsParser::get_value_from_client_hints(field_lowered, hints)
+            {
+                return Some(value);
+            }
+        }
+
         let user_agent = {
             let v = user.get_user_value(&USER_AGENT_STRING)?;
             match &v.string_value {
@@ -71,6 +89,25 @@ impl UserAgentParser {
         }
     }
 
+    /// Whether the user agent string is from a bot, crawler or HTTP library. Client hints are
+    /// only sent by browsers, so they are not checked.
+    pub fn is_bot(user_data: &UserData) -> bool {
+        let user_agent = match user_data
+            .user_agent
+            .as_ref()
+            .and_then(|v| v.string_value.as_ref())
+        {
+            Some(s) => s.value.as_str(),
+            None => return false,
+        };
+
+        if user_agent.len() > 1000 {
+            return false;
+        }
+
+        UaParser::parse_device(user_agent).is_bot
+    }
+
     pub fn load_parser() {
         ThirdPartyUserAgentParser::load_parser();
     }
@@ -79,20 +116,29 @@ impl UserAgentParser {
         user: &StatsigUser,
         options: &Arc<StatsigOptions>,
     ) -> Option<ParsedUserAgentValue> {
-        let user_agent_str = unwrap_or_return!(user.get_user_agent(), None);
-        match options.use_third_party_ua_parser {
-            Some(true) => Some(ParsedUserAgentValue {
-                os_name: get_third_party_ua_value("os_name", user_agent_str),
-                os_version: get_third_party_ua_value("os_version", user_agent_str),
-                browser_name: get_third_party_ua_value("browser_name", user_agent_str),
-                browser_version: get_third_party_ua_value("browser_version", user_agent_str),
-            }),
-            _ => Some(ParsedUserAgentValue {
-                os_name: get_first_party_ua_value("os_name", user_agent_str),
-                os_version: get_first_party_ua_value("os_version", user_agent_str),
-                browser_name: get_first_party_ua_value("browser_name", user_agent_str),
-                browser_version: get_first_party_ua_value("browser_version", user_agent_str),
-            }),
+        if user.get_user_agent().is_none() && user.get_user_agent_client_hints().is_none() {
+            return None;
         }
+
+        let user_agent_str = user.get_user_agent().unwrap_or_default();
+        let use_third_party = options.use_third_party_ua_parser == Some(true);
+        let get_value = |key: &str| {
+            get_client_hints_value(key, user).or_else(|| {
+                if user_agent_str.is_empty() {
+                    None
+                } else if use_third_party {
+                    get_third_party_ua_value(key, user_agent_str)
+                } else {
+                    get_first_party_ua_value(key, user_agent_str)
+                }
+            })
+        };
+
+        Some(ParsedUserAgentValue {
+            os_name: get_value("os_name"),
+            os_version: get_value("os_version"),
+            browser_name: get_value("browser_name"),
+            browser_version: get_value("browser_version"),
+        })
     }
 }
@@ -2,9 +2,10 @@ use super::{
     event_queue::{
         batch::EventBatch,
         queue::{EventQueue, QueueReconcileResult},
-        queued_event::{EnqueueOperation, QueuedEvent},
+        queued_event::{EnqueueOperation, QueuedEvent, QueuedExposure},
         queued_secondary_expo::EnqueueSecondaryExposureAsPrimaryOp,
     },
+    exposure_dedupe_persistence::ExposureDedupePersister,
     exposure_sampling::ExposureSampling,
     flush_interval::FlushInterval,
     flush_type::FlushType,
@@ -12,19 +13,23 @@ use super::{
     statsig_event_internal::StatsigEventInternal,
 };
 use crate::{
+    clock::{get_clock, Clock},
+    evaluation::user_agent_parsing::UserAgentParser,
     event_logging::{
         event_logger_constants::EventLoggerConstants, event_queue::queue::QueueAddResult,
     },
+    hashing::HashUtil,
     log_d, log_e, log_w,
     networking::NetworkError,
     observability::ops_stats::{OpsStatsForInstance, OPS_STATS},
+    sdk_event_emitter::{SdkEvent, SdkEventEmitter},
     statsig_metadata::StatsigMetadata,
     EventLoggingAdapter, StatsigErr, StatsigOptions, StatsigRuntime,
 };
 use dashmap::DashMap;
 use std::{collections::HashMap, sync::Arc};
 use std::{
-    sync::atomic::{AtomicU64, Ordering},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
     time::Duration,
 };
 use tokio::sync::{Notify, Semaphore};
@@ -37,9 +42,23 @@ pub const DEFAULT_PENDING_BATCH_COUNT_MAX: u32 = 100;
 
 const BG_LOOP_TAG: &str = "EVT_LOG_BG_LOOP";
 const LIMIT_FLUSH_TAG: &str = "EVT_LOG_LIMIT_FLUSH";
+const LIMIT_FLUSH_LISTENER_TAG: &str = "EVT_LOG_LIMIT_FLUSH_LISTENER";
 const DEFAULT_BATCH_SIZE: u32 = MAX_BATCH_SIZE;
 const MAX_LIMIT_FLUSH_TASKS: usize = 5;
 
+const DROP_REASON_QUEUE_LIMIT: &str = "event_queue_limit";
+const DROP_REASON_PENDING_BATCH_LIMIT: &str = "max_pending_batches_limit";
+const DROP_REASON_FLUSH_FAILURE: &str = "flush_failure";
+
+/// Who drives scheduled flushes. With `External`, no background loop is spawned and the
+/// owner must call `run_scheduled_flush_tick` every tick interval. Full batches are flushed
+/// as soon as they fill up either way.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FlushScheduling {
+    Internal,
+    External,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ExposureTrigger {
     Auto,
@@ -51,8 +70,11 @@ const TAG: &str = stringify!(EvtLogger);
 pub struct EventLogger {
     queue: EventQueue,
     options: Arc<StatsigOptions>,
+    disable_all_logging: AtomicBool,
+    disable_bot_exposure_logging: AtomicBool,
     logging_adapter: Arc<dyn EventLoggingAdapter>,
     event_sampler: ExposureSampling,
+    dedupe_persister: Option<ExposureDedupePersister>,
     // S2SDK-16 fix: incremented on (nearly) every evaluation that has exposure
     // logging disabled - 99.9% of calls for high-QPS customers. A single
     // global RwLock<HashMap> here serialized the whole multi-core eval
@@ -67,6 +89,9 @@ pub struct EventLogger {
     ops_stats: Arc<OpsStatsForInstance>,
     sec_expo_experiment: SecExpoAsPrimaryExperiment,
     enqueue_dropped_events_count: AtomicU64,
+    hashing: HashUtil,
+    event_emitter: Arc<SdkEventEmitter>,
+    clock: Arc<dyn Clock>,
 }
 
 impl EventLogger {
@@ -75,7 +100,12 @@ impl EventLogger {
         options: &Arc<StatsigOptions>,
         event_logging_adapter: &Arc<dyn EventLoggingAdapter>,
         statsig_rt: &Arc<StatsigRuntime>,
+        event_emitter: Arc<SdkEventEmitter>,
+        flush_scheduling: FlushScheduling,
     ) -> Arc<Self> {
+        let clock = get_clock(Some(options));
+        let dedupe_persister =
+            ExposureDedupePersister::from_options(sdk_key, options, clock.now_ms());
         let me = Arc::new(Self {
             queue: EventQueue::new(
                 options
@@ -85,12 +115,20 @@ impl EventLogger {
                     .event_logging_max_pending_batch_queue_size
                     .unwrap_or(DEFAULT_PENDING_BATCH_COUNT_MAX),
             ),
-            event_sampler: ExposureSampling::with_max_keys(
+            event_sampler: ExposureSampling::with_clock(
                 sdk_key,
                 options.exposure_dedupe_max_keys,
-            ),
-            flush_interval: FlushInterval::new(),
+                clock.clone(),
+            )
+            .with_policy(options.exposure_sampling_policy.clone())
+            .with_dedupe_journal(dedupe_persister.is_some()),
+            dedupe_persister,
+            flush_interval: FlushInterval::new(clock.clone()),
             options: options.clone(),
+            disable_all_logging: AtomicBool::new(options.disable_all_logging == Some(true)),
+            disable_bot_exposure_logging: AtomicBool::new(
+                options.disable_bot_exposure_logging == Some(true),
+            ),
             logging_adapter: event_logging_adapter.clone(),
             non_exposed_checks: DashMap::new(),
             shutdown_notify: Notify::new(),
@@ -99,14 +137,55 @@ impl EventLogger {
             ops_stats: OPS_STATS.get_for_instance(sdk_key),
             sec_expo_experiment: SecExpoAsPrimaryExperiment::new(sdk_key, options),
             enqueue_dropped_events_count: AtomicU64::new(0),
+            hashing: HashUtil::new(),
+            event_emitter,
+            clock,
         });
 
-        me.spawn_background_task(statsig_rt);
+        me.flush_interval
+            .set_max_flush_interval_ms(options.event_logging_max_flush_interval_ms);
+
+        match flush_scheduling {
+            FlushScheduling::Internal => me.spawn_background_task(statsig_rt),
+            FlushScheduling::External => me.spawn_limit_flush_listener(statsig_rt),
+        }
         me
     }
 
+    /// Applies the settings that can change at runtime. Events that are already queued
+    /// stay queued, even if logging is now disabled.
+    pub fn update_options(&self, options: &StatsigOptions) {
+        self.disable_all_logging
+            .store(options.disable_all_logging == Some(true), Ordering::Relaxed);
+        self.disable_bot_exposure_logging.store(
+            options.disable_bot_exposure_logging == Some(true),
+            Ordering::Relaxed,
+        );
+        self.queue.update_limits(
+            options
+                .event_logging_max_queue_size
+                .unwrap_or(DEFAULT_BATCH_SIZE),
+            options
+                .event_logging_max_pending_batch_queue_size
+                .unwrap_or(DEFAULT_PENDING_BATCH_COUNT_MAX),
+        );
+        self.flush_interval
+            .set_max_flush_interval_ms(options.event_logging_max_flush_interval_ms);
+        self.event_sampler
+            .update_max_keys(options.exposure_dedupe_max_keys);
+
+        if self.queue.contains_at_least_one_full_batch() {
+            self.limit_flush_notify.notify_one();
+        }
+    }
+
+    /// The current time according to `StatsigOptions::clock`, used to timestamp events.
+    pub fn now_ms(&self) -> u64 {
+        self.clock.now_ms()
+    }
+
     pub fn enqueue(self: &Arc<Self>, operation: impl EnqueueOperation) {
-        if self.options.disable_all_logging == Some(true) {
+        if self.disable_all_logging.load(Ordering::Relaxed) {
             return;
         }
 
@@ -115,6 +194,13 @@ impl EventLogger {
             .event_sampler
             .get_sampling_decision(&operation, sec_expo_as_primary_active);
 
+        // checked after dedupe, so the user agent is only parsed for exposures that would
+        // otherwise be logged
+        if (sec_expo_as_primary_active || decision.should_log()) && self.is_bot_exposure(&operation)
+        {
+            return;
+        }
+
         if sec_expo_as_primary_active {
             let should_log_parent = decision.should_log();
             let pending_event = operation.into_queued_event(decision);
@@ -133,6 +219,13 @@ impl EventLogger {
         self.add_pending_event(pending_event);
     }
 
+    fn is_bot_exposure(&self, operation: &impl EnqueueOperation) -> bool {
+        self.disable_bot_exposure_logging.load(Ordering::Relaxed)
+            && operation
+                .as_exposure()
+                .is_some_and(|expo| UserAgentParser::is_bot(expo.get_user_data_ref()))
+    }
+
     fn enqueue_event_with_secondary_exposures_as_primary(
         &self,
         mut pending_event: QueuedEvent,
@@ -177,7 +270,9 @@ impl EventLogger {
         }
     }
 
-    fn add_pending_event(&self, pending_event: QueuedEvent) {
+    fn add_pending_event(&self, mut pending_event: QueuedEvent) {
+        self.redact_pending_event(&mut pending_event);
+
         match self.queue.add(pending_event) {
             QueueAddResult::Noop => (),
             QueueAddResult::NeedsFlush => self.limit_flush_notify.notify_one(),
@@ -189,6 +284,13 @@ impl EventLogger {
         }
     }
 
+    // Every event must pass through here before it is queued.
+    fn redact_pending_event(&self, pending_event: &mut QueuedEvent) {
+        if let Some(policy) = &self.options.redaction_policy {
+            policy.redact_queued_event(pending_event, &self.hashing);
+        }
+    }
+
     pub fn increment_non_exposure_checks(&self, name: &str) {
         // Fast path: the counter for this gate already exists. Concurrent
         // callers share a shard read-lock and only do a relaxed atomic add,
@@ -236,10 +338,61 @@ impl EventLogger {
         // tasks. Each of these can hold a batch it already dequeued; aborting
         // them mid network-request silently drops those exposures.
         statsig_rt.await_tasks_with_tag(BG_LOOP_TAG).await;
+        statsig_rt
+            .await_tasks_with_tag(LIMIT_FLUSH_LISTENER_TAG)
+            .await;
         statsig_rt.await_tasks_with_tag(LIMIT_FLUSH_TAG).await;
 
-        // Finally flush whatever remains in the queue.
-        self.try_flush_all_pending_events(FlushType::Shutdown).await
+        // Finally flush whatever remains in the queue, then let the adapter finish any
+        // deliveries it still has in flight.
+        let flush_result = self.try_flush_all_pending_events(FlushType::Shutdown).await;
+        let adapter_result = self.logging_adapter.shutdown().await;
+        flush_result.and(adapter_result)
+    }
+
+    /// One iteration of the background loop, for loggers with `FlushScheduling::External`.
+    /// Full batches are still flushed as soon as they fill up, by limit flush tasks.
+    pub async fn run_scheduled_flush_tick(&self) {
+        self.try_scheduled_flush().await;
+        self.event_sampler.try_reset_all_sampling();
+        self.try_snapshot_exposure_dedupe().await;
+    }
+
+    /// Loads the persisted exposure dedupe keys, when `exposure_dedupe_persistence` is set.
+    pub async fn warm_exposure_dedupe(&self) {
+        let Some(persister) = &self.dedupe_persister else {
+            return;
+        };
+
+        if let Some(snapshot) = persister.load().await {
+            self.event_sampler.warm_dedupe_set(snapshot);
+        }
+    }
+
+    /// Persists the exposure dedupe keys, when `exposure_dedupe_persistence` is set.
+    pub async fn save_exposure_dedupe_snapshot(&self) {
+        if let Some(persister) = &self.dedupe_persister {
+            let snapshot = self.event_sampler.create_dedupe_snapshot();
+            persister
+                .save(
+                    snapshot,
+                    self.clock.now_ms(),
+                    self.event_sampler.get_dedupe_ttl_ms(),
+                    self.event_sampler.get_dedupe_max_keys().get(),
+                )
+                .await;
+        }
+    }
+
+    async fn try_snapshot_exposure_dedupe(&self) {
+        let is_due = self
+            .dedupe_persister
+            .as_ref()
+            .is_some_and(|p| p.is_snapshot_due(self.clock.now_ms()));
+
+        if is_due {
+            self.save_exposure_dedupe_snapshot().await;
+        }
     }
 
     pub fn force_shutdown(&self) {
@@ -273,6 +426,7 @@ impl EventLogger {
                 }
 
                 me.event_sampler.try_reset_all_sampling();
+                me.try_snapshot_exposure_dedupe().await;
             }
         });
 
@@ -281,6 +435,32 @@ impl EventLogger {
         }
     }
 
+    /// Stands in for the background loop's limit flushes when scheduled flushes are external.
+    fn spawn_limit_flush_listener(self: &Arc<Self>, rt: &Arc<StatsigRuntime>) {
+        let me = self.clone();
+        let rt_clone = rt.clone();
+
+        let spawn_result = rt.spawn(LIMIT_FLUSH_LISTENER_TAG, |rt_shutdown_notify| async move {
+            loop {
+                let can_limit_flush = me.flush_interval.has_completely_recovered_from_backoff();
+                let retry_backoff = Duration::from_millis(EventLoggerConstants::tick_interval_ms());
+
+                tokio::select! {
+                    () = rt_shutdown_notify.notified() => return,
+                    _ = me.shutdown_notify.notified() => return,
+                    _ = me.limit_flush_notify.notified(), if can_limit_flush => {
+                        Self::spawn_new_limit_flush_task(&me, &rt_clone);
+                    }
+                    () = tokio::time::sleep(retry_backoff), if !can_limit_flush => {}
+                }
+            }
+        });
+
+        if let Err(e) = spawn_result {
+            log_e!(TAG, "Failed to spawn limit flush listener: {e}");
+        }
+    }
+
     fn spawn_new_limit_flush_task(inst: &Arc<Self>, rt: &Arc<StatsigRuntime>) {
         let permit = match inst.limit_flush_semaphore.clone().try_acquire_owned() {
             Ok(permit) => permit,
@@ -392,8 +572,8 @@ impl EventLogger {
     ) -> Result<(), StatsigErr> {
         let statsig_metadata = StatsigMetadata::get_with_log_event_extras(
             self.flush_interval.get_current_flush_interval_ms(),
-            self.queue.batch_size,
-            self.queue.max_pending_batches,
+            self.queue.batch_size(),
+            self.queue.max_pending_batches(),
             flush_type.to_string(),
         );
 
@@ -429,7 +609,7 @@ impl EventLogger {
         };
 
         if dropped_events_count > 0 {
-            self.log_dropped_event_warning(dropped_events_count);
+            self.report_dropped_events(dropped_events_count, DROP_REASON_QUEUE_LIMIT);
 
             self.ops_stats.log_batching_dropped_events(
                 StatsigErr::LogEventError("Dropped events due to event queue limit".to_string()),
@@ -453,8 +633,15 @@ impl EventLogger {
         );
 
         let is_max_retries = batch.attempts > EventLoggerConstants::max_log_event_retries();
+        let will_retry = !is_non_retryable && !is_max_retries;
 
-        if is_non_retryable || is_max_retries {
+        self.event_emitter.emit(SdkEvent::EventFlushFailed {
+            event_count: batch.events.len() as u64,
+            error: &error.to_string(),
+            will_retry,
+        });
+
+        if !will_retry {
             self.drop_events_for_failure(error, batch, flush_type);
             return;
         }
@@ -472,7 +659,7 @@ impl EventLogger {
             return;
         }
 
-        self.log_dropped_event_warning(dropped_events_count);
+        self.report_dropped_events(dropped_events_count, DROP_REASON_PENDING_BATCH_LIMIT);
 
         self.ops_stats.log_batching_dropped_events(
             StatsigErr::LogEventError(
@@ -512,6 +699,11 @@ impl EventLogger {
 
         self.ops_stats
             .log_event_request_failure(dropped_events_count, flush_type);
+
+        self.event_emitter.emit(SdkEvent::EventsDropped {
+            count: dropped_events_count,
+            reason: DROP_REASON_FLUSH_FAILURE,
+        });
     }
 
     fn try_add_non_exposed_checks_event(&self) {
@@ -537,9 +729,12 @@ impl EventLogger {
             return;
         }
 
-        let result = self.queue.add(QueuedEvent::Passthrough(
-            StatsigEventInternal::new_non_exposed_checks_event(checks),
-        ));
+        let mut pending_event = QueuedEvent::Passthrough(
+            StatsigEventInternal::new_non_exposed_checks_event(self.clock.now_ms(), checks),
+        );
+        self.redact_pending_event(&mut pending_event);
+
+        let result = self.queue.add(pending_event);
 
         if let QueueAddResult::NeedsFlushAndDropped(dropped_events_count) = result {
             self.enqueue_dropped_events_count
@@ -553,7 +748,7 @@ impl EventLogger {
             return;
         }
 
-        self.log_dropped_event_warning(dropped_events_count);
+        self.report_dropped_events(dropped_events_count, DROP_REASON_PENDING_BATCH_LIMIT);
 
         self.ops_stats.log_batching_dropped_events(
             StatsigErr::LogEventError(
@@ -566,15 +761,20 @@ impl EventLogger {
         );
     }
 
-    fn log_dropped_event_warning(&self, dropped_events_count: u64) {
+    fn report_dropped_events(&self, dropped_events_count: u64, reason: &str) {
+        self.event_emitter.emit(SdkEvent::EventsDropped {
+            count: dropped_events_count,
+            reason,
+        });
+
         let approximate_pending_events_count = self.queue.approximate_pending_events_count();
         log_w!(
             TAG,
             "Too many events. Dropped {}. Approx pending events {}. Max pending batches {}. Max queue size {}",
             dropped_events_count,
             approximate_pending_events_count,
-            self.queue.max_pending_batches,
-            self.queue.batch_size
+            self.queue.max_pending_batches(),
+            self.queue.batch_size()
         );
     }
 }
@@ -45,8 +45,8 @@ impl OpsStatsForInstance {
         flush_type: FlushType,
     ) {
         let curr_flush_interval = flush_interval.get_current_flush_interval_ms();
-        let batch_size = queue.batch_size;
-        let max_pending_batches_count = queue.max_pending_batches;
+        let batch_size = queue.batch_size();
+        let max_pending_batches_count = queue.max_pending_batches();
 
         self.log_error(ErrorBoundaryEvent {
             tag: "statsig::log_event_dropped_event_count".to_string(),
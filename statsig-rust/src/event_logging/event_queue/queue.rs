@@ -42,8 +42,9 @@ pub enum QueueReconcileResult {
 }
 
 pub struct EventQueue {
-    pub batch_size: usize,
-    pub max_pending_batches: usize,
+    // Limits can be changed at runtime through `update_limits`.
+    batch_size: AtomicUsize,
+    max_pending_batches: AtomicUsize,
 
     // Sharded pending-event buffers (see PENDING_SHARD_COUNT). Each add only
     // briefly locks one stripe instead of contending on a single global lock.
@@ -60,7 +61,7 @@ pub struct EventQueue {
     // FIFO order across shards when draining.
     next_seq: AtomicU64,
     batches: RwLock<VecDeque<EventBatch>>,
-    max_pending_events: usize,
+    max_pending_events: AtomicUsize,
 }
 
 impl EventQueue {
@@ -87,17 +88,39 @@ impl EventQueue {
             next_shard: AtomicUsize::new(0),
             next_seq: AtomicU64::new(0),
             batches: RwLock::new(VecDeque::new()),
-            batch_size,
-            max_pending_batches: max_queue_size,
-            max_pending_events,
+            batch_size: AtomicUsize::new(batch_size),
+            max_pending_batches: AtomicUsize::new(max_queue_size),
+            max_pending_events: AtomicUsize::new(max_pending_events),
         }
     }
 
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.load(Ordering::Relaxed)
+    }
+
+    pub fn max_pending_batches(&self) -> usize {
+        self.max_pending_batches.load(Ordering::Relaxed)
+    }
+
+    /// Applies new limits to events added from now on. The shard count chosen in `new` is
+    /// kept, so shrinking below it makes the drop-oldest cap approximate (see `add`).
+    /// Batches that are already formed keep their size until they are flushed.
+    pub fn update_limits(&self, batch_size: u32, max_queue_size: u32) {
+        let batch_size = batch_size as usize;
+        let max_queue_size = max_queue_size as usize;
+
+        self.batch_size.store(batch_size, Ordering::Relaxed);
+        self.max_pending_batches
+            .store(max_queue_size, Ordering::Relaxed);
+        self.max_pending_events
+            .store(batch_size * max_queue_size, Ordering::Relaxed);
+    }
+
     pub fn approximate_pending_events_count(&self) -> usize {
         // Lock-free read of the sharded pending total plus the batched events.
         let pending_len = self.pending_count.load(Ordering::Relaxed);
         let batches_len = read_lock_or_return!(TAG, self.batches, 0).len();
-        pending_len + (batches_len * self.batch_size)
+        pending_len + (batches_len * self.batch_size())
     }
 
     pub fn add(&self, pending_event: QueuedEvent) -> QueueAddResult {
@@ -142,14 +165,17 @@ impl EventQueue {
         // only the new event, we skip eviction this round and let a fuller shard
         // absorb it on a later add; the queue may then briefly exceed capacity
         // by at most one event per near-empty shard, which is bounded and fine.
-        if total > self.max_pending_events && shard.len() > 1 && shard.pop_front().is_some() {
+        if total > self.max_pending_events.load(Ordering::Relaxed)
+            && shard.len() > 1
+            && shard.pop_front().is_some()
+        {
             self.pending_count.fetch_sub(1, Ordering::Relaxed);
             drop(shard);
             return QueueAddResult::NeedsFlushAndDropped(1);
         }
         drop(shard);
 
-        if total.is_multiple_of(self.batch_size) {
+        if total.is_multiple_of(self.batch_size()) {
             return QueueAddResult::NeedsFlush;
         }
 
@@ -161,7 +187,7 @@ impl EventQueue {
         let mut batches =
             write_lock_or_return!(TAG, self.batches, QueueReconcileResult::DroppedEvents(len));
 
-        if batches.len() > self.max_pending_batches {
+        if batches.len() > self.max_pending_batches() {
             return QueueReconcileResult::DroppedEvents(len);
         }
 
@@ -177,13 +203,14 @@ impl EventQueue {
     }
 
     pub fn contains_at_least_one_full_batch(&self) -> bool {
-        if self.pending_count.load(Ordering::Relaxed) >= self.batch_size {
+        let batch_size = self.batch_size();
+        if self.pending_count.load(Ordering::Relaxed) >= batch_size {
             return true;
         }
 
         let batches = read_lock_or_return!(TAG, self.batches, false);
         for batch in batches.iter() {
-            if batch.events.len() >= self.batch_size {
+            if batch.events.len() >= batch_size {
                 return true;
             }
         }
@@ -216,9 +243,10 @@ impl EventQueue {
             write_lock_or_return!(TAG, self.batches, QueueReconcileResult::LockFailure);
         let old_batches = std::mem::take(&mut *batches);
 
+        let batch_size = self.batch_size();
         let (full_batches, partial_batches): (VecDeque<_>, VecDeque<_>) = old_batches
             .into_iter()
-            .partition(|batch| batch.events.len() >= self.batch_size);
+            .partition(|batch| batch.events.len() >= batch_size);
 
         for batch in partial_batches {
             pending_events.extend(batch.events);
@@ -306,9 +334,10 @@ impl EventQueue {
         &self,
         mut pending_events: VecDeque<StatsigEventInternal>,
     ) -> Vec<EventBatch> {
+        let batch_size = self.batch_size();
         let mut batches = Vec::new();
         while !pending_events.is_empty() {
-            let drain_count = self.batch_size.min(pending_events.len());
+            let drain_count = batch_size.min(pending_events.len());
             let chunk = pending_events.drain(..drain_count).collect::<Vec<_>>();
             batches.push(EventBatch::new(chunk));
         }
@@ -317,12 +346,13 @@ impl EventQueue {
     }
 
     fn clamp_batches(&self, batches: &mut VecDeque<EventBatch>) -> u64 {
-        if batches.len() <= self.max_pending_batches {
+        let max_pending_batches = self.max_pending_batches();
+        if batches.len() <= max_pending_batches {
             return 0;
         }
 
         let mut dropped_events_count = 0;
-        while batches.len() > self.max_pending_batches {
+        while batches.len() > max_pending_batches {
             if let Some(batch) = batches.pop_front() {
                 dropped_events_count += batch.events.len() as u64;
             }
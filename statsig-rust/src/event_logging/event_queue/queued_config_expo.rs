@@ -9,7 +9,7 @@ use crate::{
     },
     interned_string::InternedString,
     statsig_types::DynamicConfig,
-    user::{StatsigUserInternal, StatsigUserLoggable},
+    user::{user_data::UserData, StatsigUserInternal, StatsigUserLoggable},
     EvaluationDetails, SecondaryExposure,
 };
 
@@ -68,6 +68,10 @@ impl<'a> QueuedExposure<'a> for EnqueueConfigExpoOp<'a> {
         ExposureSamplingKey::new(evaluation, user_data, additional_hash, unit_id_type)
     }
 
+    fn get_spec_name_ref(&self) -> &'a str {
+        &self.config.name
+    }
+
     fn get_rule_id_ref(&self) -> &'a str {
         &self.config.rule_id
     }
@@ -80,6 +84,10 @@ impl<'a> QueuedExposure<'a> for EnqueueConfigExpoOp<'a> {
             .exposure_info
             .as_ref()
     }
+
+    fn get_user_data_ref(&self) -> &'a UserData {
+        &self.user.user_ref.data
+    }
 }
 
 pub struct QueuedConfigExposureEvent {
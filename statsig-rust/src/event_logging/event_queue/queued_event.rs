@@ -11,7 +11,7 @@ use crate::{
         exposure_sampling::{EvtSamplingDecision, ExposureSamplingKey},
         statsig_event_internal::StatsigEventInternal,
     },
-    user::StatsigUserLoggable,
+    user::{user_data::UserData, StatsigUserLoggable},
     SecondaryExposure,
 };
 
@@ -22,8 +22,10 @@ pub trait EnqueueOperation {
 
 pub trait QueuedExposure<'a> {
     fn create_exposure_sampling_key(&self) -> ExposureSamplingKey;
+    fn get_spec_name_ref(&'a self) -> &'a str;
     fn get_rule_id_ref(&'a self) -> &'a str;
     fn get_extra_exposure_info_ref(&'a self) -> Option<&'a ExtraExposureInfo>;
+    fn get_user_data_ref(&'a self) -> &'a UserData;
 }
 
 pub enum QueuedEvent {
@@ -76,6 +78,18 @@ impl QueuedEvent {
         }
     }
 
+    pub fn user_mut(&mut self) -> &mut StatsigUserLoggable {
+        match self {
+            QueuedEvent::Exposure(event) => &mut event.user,
+            QueuedEvent::Passthrough(event) => &mut event.user,
+            QueuedEvent::GateExposure(event) => &mut event.user,
+            QueuedEvent::ConfigExposure(event) => &mut event.user,
+            QueuedEvent::ExperimentExposure(event) => &mut event.user,
+            QueuedEvent::LayerParamExposure(event) => &mut event.user,
+            QueuedEvent::SecondaryExposureAsPrimary(event) => &mut event.user,
+        }
+    }
+
     pub fn take_secondary_exposures_for_primary_logging(&mut self) -> Vec<SecondaryExposure> {
         match self {
             QueuedEvent::Exposure(event) => event.take_secondary_exposures_for_primary_logging(),
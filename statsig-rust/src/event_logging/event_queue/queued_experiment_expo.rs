@@ -9,7 +9,7 @@ use crate::{
     },
     interned_string::InternedString,
     statsig_types::Experiment,
-    user::{StatsigUserInternal, StatsigUserLoggable},
+    user::{user_data::UserData, StatsigUserInternal, StatsigUserLoggable},
     EvaluationDetails, SecondaryExposure,
 };
 
@@ -71,6 +71,10 @@ impl<'a> QueuedExposure<'a> for EnqueueExperimentExpoOp<'a> {
         )
     }
 
+    fn get_spec_name_ref(&self) -> &'a str {
+        &self.experiment.name
+    }
+
     fn get_rule_id_ref(&self) -> &'a str {
         &self.experiment.rule_id
     }
@@ -83,6 +87,10 @@ impl<'a> QueuedExposure<'a> for EnqueueExperimentExpoOp<'a> {
             .exposure_info
             .as_ref()
     }
+
+    fn get_user_data_ref(&self) -> &'a UserData {
+        &self.user.user_ref.data
+    }
 }
 
 pub struct QueuedExperimentExposureEvent {
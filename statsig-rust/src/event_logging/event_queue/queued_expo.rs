@@ -1,7 +1,5 @@
 use std::{collections::HashMap, fmt::Display};
 
-use chrono::Utc;
-
 use crate::{
     evaluation::{
         evaluation_types::ExtraExposureInfo,
@@ -19,7 +17,7 @@ use crate::{
     },
     interned_string::InternedString,
     specs_response::explicit_params::ExplicitParameters,
-    user::{StatsigUserInternal, StatsigUserLoggable},
+    user::{user_data::UserData, StatsigUserInternal, StatsigUserLoggable},
     EvaluationDetails, SecondaryExposure,
 };
 
@@ -71,6 +69,7 @@ pub struct ExposureData {
 
 impl<'a> EnqueueExposureOp<'a> {
     pub fn gate_exposure(
+        exposure_time: u64,
         user: &'a StatsigUserInternal<'a, 'a>,
         spec_name: &InternedString,
         trigger: ExposureTrigger,
@@ -79,6 +78,7 @@ impl<'a> EnqueueExposureOp<'a> {
     ) -> Self {
         let gate_value = result.as_ref().is_some_and(|r| r.bool_value);
         let mut op = Self::new(
+            exposure_time,
             GATE_EXPOSURE_EVENT_NAME,
             UserLoggableOrInternal::Internal(user),
             spec_name,
@@ -92,6 +92,7 @@ impl<'a> EnqueueExposureOp<'a> {
     }
 
     pub fn dynamic_config_exposure(
+        exposure_time: u64,
         user: &'a StatsigUserInternal<'a, 'a>,
         spec_name: &InternedString,
         trigger: ExposureTrigger,
@@ -101,6 +102,7 @@ impl<'a> EnqueueExposureOp<'a> {
         let rule_passed = result.as_ref().is_some_and(|r| r.bool_value);
 
         let mut op = Self::new(
+            exposure_time,
             CONFIG_EXPOSURE_EVENT_NAME,
             UserLoggableOrInternal::Internal(user),
             spec_name,
@@ -114,6 +116,7 @@ impl<'a> EnqueueExposureOp<'a> {
     }
 
     pub fn experiment_exposure(
+        exposure_time: u64,
         user: &'a StatsigUserInternal<'a, 'a>,
         spec_name: &InternedString,
         trigger: ExposureTrigger,
@@ -122,6 +125,7 @@ impl<'a> EnqueueExposureOp<'a> {
     ) -> Self {
         let is_user_in_experiment = result.as_ref().is_some_and(|r| r.is_experiment_group);
         let mut op = Self::new(
+            exposure_time,
             CONFIG_EXPOSURE_EVENT_NAME,
             UserLoggableOrInternal::Internal(user),
             spec_name,
@@ -135,6 +139,7 @@ impl<'a> EnqueueExposureOp<'a> {
     }
 
     pub fn layer_param_exposure(
+        exposure_time: u64,
         user: &'a StatsigUserInternal<'a, 'a>,
         spec_name: &InternedString,
         parameter_name: InternedString,
@@ -154,6 +159,7 @@ impl<'a> EnqueueExposureOp<'a> {
         }
 
         let mut op = Self::new(
+            exposure_time,
             LAYER_EXPOSURE_EVENT_NAME,
             UserLoggableOrInternal::Internal(user),
             spec_name,
@@ -171,6 +177,7 @@ impl<'a> EnqueueExposureOp<'a> {
     }
 
     fn new(
+        exposure_time: u64,
         event_name: &'static str,
         user: UserLoggableOrInternal<'a>,
         spec_name: &InternedString,
@@ -182,7 +189,7 @@ impl<'a> EnqueueExposureOp<'a> {
             event_name,
             spec_name: spec_name.clone(),
             rule_id: None,
-            exposure_time: Utc::now().timestamp_millis() as u64,
+            exposure_time,
             trigger,
             evaluation_details: details,
             secondary_exposures: None,
@@ -214,6 +221,7 @@ impl<'a> EnqueueExposureOp<'a> {
 #[cfg(feature = "ffi-support")]
 impl<'a> EnqueueExposureOp<'a> {
     pub(crate) fn layer_param_exposure_from_partial_raw(
+        exposure_time: u64,
         parameter_name: InternedString,
         trigger: ExposureTrigger,
         partial_raw: crate::statsig_types_raw::PartialLayerRaw,
@@ -234,7 +242,7 @@ impl<'a> EnqueueExposureOp<'a> {
             event_name: LAYER_EXPOSURE_EVENT_NAME,
             spec_name: partial_raw.name,
             rule_id: rule_id.cloned(),
-            exposure_time: Utc::now().timestamp_millis() as u64,
+            exposure_time,
             trigger,
             evaluation_details: partial_raw.details,
             secondary_exposures: partial_raw.secondary_exposures,
@@ -301,6 +309,10 @@ impl<'a> QueuedExposure<'a> for EnqueueExposureOp<'a> {
         }
     }
 
+    fn get_spec_name_ref(&'a self) -> &'a str {
+        self.data.spec_name.as_str()
+    }
+
     fn get_rule_id_ref(&'a self) -> &'a str {
         self.data.rule_id.as_ref().map_or("", |id| id.as_str())
     }
@@ -308,6 +320,13 @@ impl<'a> QueuedExposure<'a> for EnqueueExposureOp<'a> {
     fn get_extra_exposure_info_ref(&'a self) -> Option<&'a ExtraExposureInfo> {
         self.data.exposure_info.as_ref()
     }
+
+    fn get_user_data_ref(&'a self) -> &'a UserData {
+        match &self.user {
+            UserLoggableOrInternal::Loggable(loggable) => &loggable.data,
+            UserLoggableOrInternal::Internal(internal) => &internal.user_ref.data,
+        }
+    }
 }
 
 pub struct QueuedExposureEvent {
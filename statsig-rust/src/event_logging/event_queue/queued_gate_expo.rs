@@ -10,7 +10,7 @@ use crate::{
         statsig_event_internal::{StatsigEventInternal, GATE_EXPOSURE_EVENT_NAME},
     },
     interned_string::InternedString,
-    user::{StatsigUserInternal, StatsigUserLoggable},
+    user::{user_data::UserData, StatsigUserInternal, StatsigUserLoggable},
     EvaluationDetails, SecondaryExposure,
 };
 
@@ -72,6 +72,10 @@ impl<'a> QueuedExposure<'a> for EnqueueGateExpoOp<'a> {
         ExposureSamplingKey::new(evaluation, user_data, additional_hash, unit_id_type)
     }
 
+    fn get_spec_name_ref(&'a self) -> &'a str {
+        self.queried_gate_name
+    }
+
     fn get_rule_id_ref(&'a self) -> &'a str {
         rule_id_ref(&self.evaluation)
     }
@@ -79,6 +83,10 @@ impl<'a> QueuedExposure<'a> for EnqueueGateExpoOp<'a> {
     fn get_extra_exposure_info_ref(&'a self) -> Option<&'a ExtraExposureInfo> {
         self.evaluation.as_ref()?.base.exposure_info.as_ref()
     }
+
+    fn get_user_data_ref(&'a self) -> &'a UserData {
+        &self.user.user_ref.data
+    }
 }
 
 pub struct QueuedGateExposureEvent {
@@ -10,7 +10,7 @@ use crate::{
     hashing::ahash_str,
     interned_string::InternedString,
     statsig_types::Layer,
-    user::StatsigUserLoggable,
+    user::{user_data::UserData, StatsigUserLoggable},
     EvaluationDetails, SecondaryExposure,
 };
 
@@ -86,6 +86,10 @@ impl<'a> QueuedExposure<'a> for EnqueueLayerParamExpoOp<'a> {
         ExposureSamplingKey::new(evaluation, user_data, pname_hash, unit_id_type)
     }
 
+    fn get_spec_name_ref(&'a self) -> &'a str {
+        &self.get_layer_ref().name
+    }
+
     fn get_rule_id_ref(&'a self) -> &'a str {
         &self.get_layer_ref().rule_id
     }
@@ -93,6 +97,10 @@ impl<'a> QueuedExposure<'a> for EnqueueLayerParamExpoOp<'a> {
     fn get_extra_exposure_info_ref(&'a self) -> Option<&'a ExtraExposureInfo> {
         get_layer_exposure_info(self.get_layer_ref())
     }
+
+    fn get_user_data_ref(&'a self) -> &'a UserData {
+        &self.get_layer_ref().__user.data
+    }
 }
 
 pub struct QueuedLayerParamExposureEvent {
@@ -5,7 +5,7 @@ use crate::{
         statsig_event::StatsigEvent,
         statsig_event_internal::{StatsigEventInternal, GATE_EXPOSURE_EVENT_NAME},
     },
-    user::StatsigUserLoggable,
+    user::{user_data::UserData, StatsigUserLoggable},
     SecondaryExposure,
 };
 
@@ -81,6 +81,10 @@ impl<'a> QueuedExposure<'a> for EnqueueSecondaryExposureAsPrimaryOp {
         }
     }
 
+    fn get_spec_name_ref(&'a self) -> &'a str {
+        self.secondary_exposure.gate.as_str()
+    }
+
     fn get_rule_id_ref(&'a self) -> &'a str {
         self.secondary_exposure.rule_id.as_str()
     }
@@ -90,4 +94,8 @@ impl<'a> QueuedExposure<'a> for EnqueueSecondaryExposureAsPrimaryOp {
     ) -> Option<&'a crate::evaluation::evaluation_types::ExtraExposureInfo> {
         None
     }
+
+    fn get_user_data_ref(&'a self) -> &'a UserData {
+        &self.user.data
+    }
 }
@@ -0,0 +1,314 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use ahash::AHashSet;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+
+use super::exposure_sampling::{ExposureDedupeSnapshot, ExposureDedupeSnapshotEntry};
+use crate::data_store_interface::DataStoreTrait;
+use crate::hashing::HashUtil;
+use crate::{log_d, log_w, HashAlgorithm, StatsigErr, StatsigOptions};
+
+const TAG: &str = stringify!(ExposureDedupePersistence);
+const DEFAULT_SNAPSHOT_INTERVAL_MS: u64 = 60_000;
+
+// followed by little endian u64s: the window start, the key count, then 5 words per key
+const SNAPSHOT_MAGIC: &[u8; 4] = b"SXD1";
+const SNAPSHOT_HEADER_LEN: usize = 16;
+const SNAPSHOT_ENTRY_LEN: usize = 40;
+
+/// Where the exposure dedupe snapshot is kept.
+#[derive(Clone, Debug)]
+pub enum ExposureDedupeStore {
+    /// A binary file. It is replaced on every snapshot.
+    File(PathBuf),
+    /// `StatsigOptions::data_store`, under a key derived from the SDK key. Stores without
+    /// `set_bytes` support get the snapshot as a base64 string.
+    DataStore,
+}
+
+/// Keeps exposure dedupe keys across restarts, so a deploy does not re-log an exposure for
+/// every active user. Keys are snapshotted periodically and on shutdown, and loaded during
+/// initialize. A snapshot whose dedupe window has expired is ignored.
+///
+/// Instances sharing a store merge their keys into one snapshot, up to
+/// `StatsigOptions::exposure_dedupe_max_keys`, so an instance that restarts on a new host
+/// still finds the keys of its previous run.
+#[derive(Clone, Debug)]
+pub struct ExposureDedupePersistence {
+    store: ExposureDedupeStore,
+    snapshot_interval_ms: u64,
+}
+
+impl ExposureDedupePersistence {
+    #[must_use]
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::new(ExposureDedupeStore::File(path.into()))
+    }
+
+    /// Uses `StatsigOptions::data_store`. Nothing is persisted when it is not set.
+    #[must_use]
+    pub fn data_store() -> Self {
+        Self::new(ExposureDedupeStore::DataStore)
+    }
+
+    #[must_use]
+    pub fn new(store: ExposureDedupeStore) -> Self {
+        Self {
+            store,
+            snapshot_interval_ms: DEFAULT_SNAPSHOT_INTERVAL_MS,
+        }
+    }
+
+    /// How often the dedupe keys are snapshotted while running. Defaults to 60 seconds.
+    #[must_use]
+    pub fn with_snapshot_interval_ms(mut self, snapshot_interval_ms: u64) -> Self {
+        self.snapshot_interval_ms = snapshot_interval_ms;
+        self
+    }
+}
+
+pub(crate) struct ExposureDedupePersister {
+    store: ResolvedStore,
+    snapshot_interval_ms: u64,
+    last_snapshot_ms: AtomicU64,
+}
+
+enum ResolvedStore {
+    File(PathBuf),
+    DataStore(Arc<dyn DataStoreTrait>, String),
+}
+
+impl ExposureDedupePersister {
+    pub(crate) fn from_options(
+        sdk_key: &str,
+        options: &StatsigOptions,
+        now_ms: u64,
+    ) -> Option<Self> {
+        let persistence = options.exposure_dedupe_persistence.as_ref()?;
+
+        let store = match &persistence.store {
+            ExposureDedupeStore::File(_) if options.disable_disk_access == Some(true) => {
+                log_w!(
+                    TAG,
+                    "exposure_dedupe_persistence uses a file, but disk access is disabled"
+                );
+                return None;
+            }
+            ExposureDedupeStore::File(path) => ResolvedStore::File(path.clone()),
+            ExposureDedupeStore::DataStore => {
+                let Some(data_store) = options.data_store.clone() else {
+                    log_w!(
+                        TAG,
+                        "exposure_dedupe_persistence uses the data store, but no data_store is set"
+                    );
+                    return None;
+                };
+                ResolvedStore::DataStore(data_store, get_data_store_key(sdk_key))
+            }
+        };
+
+        Some(Self {
+            store,
+            snapshot_interval_ms: persistence.snapshot_interval_ms,
+            last_snapshot_ms: AtomicU64::new(now_ms),
+        })
+    }
+
+    pub(crate) async fn load(&self) -> Option<ExposureDedupeSnapshot> {
+        let data = match &self.store {
+            ResolvedStore::File(path) => read_file(path).await,
+            ResolvedStore::DataStore(data_store, key) => read_data_store(data_store, key).await,
+        };
+
+        let data = match data {
+            Ok(Some(data)) => data,
+            Ok(None) => return None,
+            Err(e) => {
+                log_w!(TAG, "Failed to load exposure dedupe snapshot: {}", e);
+                return None;
+            }
+        };
+
+        match decode_snapshot(&data) {
+            Ok(snapshot) => Some(snapshot),
+            Err(e) => {
+                log_w!(TAG, "Failed to parse exposure dedupe snapshot: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Whether the snapshot interval has passed since the last save.
+    pub(crate) fn is_snapshot_due(&self, now_ms: u64) -> bool {
+        let last_snapshot_ms = self.last_snapshot_ms.load(Ordering::Relaxed);
+        now_ms.saturating_sub(last_snapshot_ms) >= self.snapshot_interval_ms
+    }
+
+    /// Merges `snapshot` with the stored one, keeping the stored keys that are still in their
+    /// dedupe window (`ttl_ms`), and writes the result back.
+    pub(crate) async fn save(
+        &self,
+        snapshot: ExposureDedupeSnapshot,
+        now_ms: u64,
+        ttl_ms: u64,
+        max_keys: usize,
+    ) {
+        self.last_snapshot_ms.store(now_ms, Ordering::Relaxed);
+
+        let snapshot = match self.load().await {
+            Some(stored) => merge_snapshots(snapshot, stored, now_ms, ttl_ms, max_keys),
+            None => snapshot,
+        };
+        let data = encode_snapshot(&snapshot);
+
+        let result = match &self.store {
+            ResolvedStore::File(path) => write_file(path, data).await,
+            ResolvedStore::DataStore(data_store, key) => {
+                write_data_store(data_store, key, &data, now_ms).await
+            }
+        };
+
+        match result {
+            Ok(()) => log_d!(
+                TAG,
+                "Saved exposure dedupe snapshot with {} keys",
+                snapshot.keys.len()
+            ),
+            Err(e) => log_w!(TAG, "Failed to save exposure dedupe snapshot: {}", e),
+        }
+    }
+}
+
+/// Adds the stored keys missing from `ours`, up to `max_keys`, unless the stored window has
+/// expired. The merged window starts with the older of the two, so no key outlives its own
+/// window.
+fn merge_snapshots(
+    mut ours: ExposureDedupeSnapshot,
+    stored: ExposureDedupeSnapshot,
+    now_ms: u64,
+    ttl_ms: u64,
+    max_keys: usize,
+) -> ExposureDedupeSnapshot {
+    let is_expired = stored.window_start_ms > now_ms || now_ms - stored.window_start_ms > ttl_ms;
+    if is_expired || stored.keys.is_empty() {
+        return ours;
+    }
+
+    let key_of = |entry: &ExposureDedupeSnapshotEntry| [entry[0], entry[1], entry[2], entry[3]];
+    let mut seen: AHashSet<[u64; 4]> = ours.keys.iter().map(key_of).collect();
+    for entry in stored.keys {
+        if ours.keys.len() >= max_keys {
+            break;
+        }
+        if seen.insert(key_of(&entry)) {
+            ours.keys.push(entry);
+        }
+    }
+
+    ours.window_start_ms = ours.window_start_ms.min(stored.window_start_ms);
+    ours
+}
+
+fn encode_snapshot(snapshot: &ExposureDedupeSnapshot) -> Vec<u8> {
+    let mut data = Vec::with_capacity(
+        SNAPSHOT_MAGIC.len() + SNAPSHOT_HEADER_LEN + snapshot.keys.len() * SNAPSHOT_ENTRY_LEN,
+    );
+    data.extend_from_slice(SNAPSHOT_MAGIC);
+    data.extend_from_slice(&snapshot.window_start_ms.to_le_bytes());
+    data.extend_from_slice(&(snapshot.keys.len() as u64).to_le_bytes());
+    for word in snapshot.keys.iter().flatten() {
+        data.extend_from_slice(&word.to_le_bytes());
+    }
+    data
+}
+
+fn decode_snapshot(data: &[u8]) -> Result<ExposureDedupeSnapshot, StatsigErr> {
+    let invalid = || StatsigErr::SerializationError("Invalid exposure dedupe snapshot".into());
+
+    let body = data.strip_prefix(SNAPSHOT_MAGIC).ok_or_else(invalid)?;
+    if body.len() < SNAPSHOT_HEADER_LEN {
+        return Err(invalid());
+    }
+
+    let mut words = body.chunks_exact(8).map(|chunk| {
+        let mut word = [0; 8];
+        word.copy_from_slice(chunk);
+        u64::from_le_bytes(word)
+    });
+    let window_start_ms = words.next().ok_or_else(invalid)?;
+    let count = words.next().ok_or_else(invalid)? as usize;
+    if count.checked_mul(SNAPSHOT_ENTRY_LEN) != Some(body.len() - SNAPSHOT_HEADER_LEN) {
+        return Err(invalid());
+    }
+
+    let keys = (0..count)
+        .map(|_| std::array::from_fn(|_| words.next().unwrap_or_default()))
+        .collect();
+
+    Ok(ExposureDedupeSnapshot {
+        window_start_ms,
+        keys,
+    })
+}
+
+fn get_data_store_key(sdk_key: &str) -> String {
+    let hashed_key = HashUtil::new().hash(sdk_key, &HashAlgorithm::Sha256);
+    format!("statsig|exposure_dedupe|{hashed_key}")
+}
+
+async fn read_file(path: &Path) -> Result<Option<Vec<u8>>, StatsigErr> {
+    match tokio::fs::read(path).await {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(StatsigErr::FileError(e.to_string())),
+    }
+}
+
+// written to a temporary file first, so a crash mid-write leaves the previous snapshot intact
+async fn write_file(path: &Path, data: Vec<u8>) -> Result<(), StatsigErr> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+
+    tokio::fs::write(&tmp_path, data)
+        .await
+        .map_err(|e| StatsigErr::FileError(e.to_string()))?;
+    tokio::fs::rename(&tmp_path, path)
+        .await
+        .map_err(|e| StatsigErr::FileError(e.to_string()))
+}
+
+async fn read_data_store(
+    data_store: &Arc<dyn DataStoreTrait>,
+    key: &str,
+) -> Result<Option<Vec<u8>>, StatsigErr> {
+    match data_store.get_bytes(key).await {
+        Err(StatsigErr::BytesNotImplemented) => {}
+        result => return result.map(|response| response.result),
+    }
+
+    let Some(encoded) = data_store.get(key).await?.result else {
+        return Ok(None);
+    };
+    BASE64_STANDARD
+        .decode(encoded)
+        .map(Some)
+        .map_err(|e| StatsigErr::SerializationError(e.to_string()))
+}
+
+async fn write_data_store(
+    data_store: &Arc<dyn DataStoreTrait>,
+    key: &str,
+    data: &[u8],
+    now_ms: u64,
+) -> Result<(), StatsigErr> {
+    match data_store.set_bytes(key, data, Some(now_ms)).await {
+        Err(StatsigErr::BytesNotImplemented) => {
+            let encoded = BASE64_STANDARD.encode(data);
+            data_store.set(key, &encoded, Some(now_ms)).await
+        }
+        result => result,
+    }
+}
@@ -1,5 +1,9 @@
 use super::event_queue::queued_event::{EnqueueOperation, QueuedExposure};
+use super::exposure_sampling_policy::{
+    ExposureSamplingDecision, ExposureSamplingInput, ExposureSamplingPolicy,
+};
 use crate::{
+    clock::{Clock, SystemClock},
     evaluation::evaluation_types::{BaseEvaluation, ExtraExposureInfo},
     global_configs::GlobalConfigs,
     log_d, log_e,
@@ -7,18 +11,19 @@ use crate::{
     write_lock_or_noop, write_lock_or_return, DynamicValue,
 };
 use ahash::AHashSet;
-use chrono::Utc;
 use lru::LruCache;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use std::collections::VecDeque;
 use std::num::NonZeroUsize;
 use std::sync::{
-    atomic::{AtomicU64, Ordering},
+    atomic::{AtomicU64, AtomicUsize, Ordering},
     Arc,
 };
 
 const TAG: &str = "ExposureSampling";
 const DEFAULT_EXPOSURE_SAMPLING_TTL_MS: u64 = 60_000;
 pub const SAMPLING_MAX_KEYS: usize = 100_000;
+const DEDUPE_JOURNAL_CHUNK_SIZE: usize = 1024;
 
 #[derive(Debug)]
 pub enum EvtSamplingMode {
@@ -32,13 +37,17 @@ pub enum EvtSamplingDecision {
     NotSampled,
     ForceSampled,
     Sampled(Option<u64>, EvtSamplingMode, bool),
+    /// Logged by an [`ExposureSamplingPolicy`], with the decision and sampling rate.
+    PolicySampled(ExposureSamplingDecision, u64),
 }
 
 impl EvtSamplingDecision {
     pub fn should_log(&self) -> bool {
         match self {
             EvtSamplingDecision::Deduped | EvtSamplingDecision::NotSampled => false,
-            EvtSamplingDecision::ForceSampled | EvtSamplingDecision::Sampled(_, _, _) => true,
+            EvtSamplingDecision::ForceSampled
+            | EvtSamplingDecision::Sampled(_, _, _)
+            | EvtSamplingDecision::PolicySampled(_, _) => true,
         }
     }
 }
@@ -48,11 +57,17 @@ pub struct ExposureSampling {
     spec_sampling_set: RwLock<AHashSet<SpecAndRuleHashTuple>>,
     last_spec_sampling_reset: AtomicU64,
 
-    exposure_dedupe_set: RwLock<LruCache<ExposureSamplingKey, ()>>,
-    exposure_dedupe_max_keys: NonZeroUsize,
+    /// Values are the time the key was first seen.
+    exposure_dedupe_set: RwLock<LruCache<ExposureSamplingKey, u64>>,
+    exposure_dedupe_max_keys: AtomicUsize,
     last_exposure_dedupe_reset: AtomicU64,
+    /// Keys added in the current dedupe window, kept for snapshots when the dedupe set is
+    /// persisted.
+    dedupe_journal: Option<Mutex<DedupeJournal>>,
 
+    policy: Option<Arc<dyn ExposureSamplingPolicy>>,
     global_configs: Arc<GlobalConfigs>,
+    clock: Arc<dyn Clock>,
 }
 
 impl ExposureSampling {
@@ -61,27 +76,60 @@ impl ExposureSampling {
     }
 
     pub fn with_max_keys(sdk_key: &str, max_keys: Option<u32>) -> Self {
-        let now = Utc::now().timestamp_millis() as u64;
+        Self::with_clock(sdk_key, max_keys, Arc::new(SystemClock))
+    }
 
-        let cap = max_keys
-            .map(|v| v as usize)
-            .and_then(NonZeroUsize::new)
-            .unwrap_or_else(|| {
-                NonZeroUsize::new(SAMPLING_MAX_KEYS).expect("SAMPLING_MAX_KEYS must be non-zero")
-            });
+    /// The TTL resets of the sampling and dedupe sets are measured with `clock`.
+    pub fn with_clock(sdk_key: &str, max_keys: Option<u32>, clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now_ms();
+
+        let cap = get_dedupe_capacity(max_keys);
 
         Self {
             spec_sampling_set: RwLock::from(AHashSet::default()),
             last_spec_sampling_reset: AtomicU64::from(now),
 
             exposure_dedupe_set: RwLock::from(LruCache::new(cap)),
-            exposure_dedupe_max_keys: cap,
+            exposure_dedupe_max_keys: AtomicUsize::new(cap.get()),
             last_exposure_dedupe_reset: AtomicU64::from(now),
+            dedupe_journal: None,
 
+            policy: None,
             global_configs: GlobalConfigs::get_instance(sdk_key),
+            clock,
         }
     }
 
+    /// Asks `policy` how to sample each exposure before falling back to the built-in sampling.
+    #[must_use]
+    pub fn with_policy(mut self, policy: Option<Arc<dyn ExposureSamplingPolicy>>) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Journals new dedupe keys, so [`Self::create_dedupe_snapshot`] can be called.
+    #[must_use]
+    pub fn with_dedupe_journal(mut self, enabled: bool) -> Self {
+        self.dedupe_journal = enabled.then(|| Mutex::new(DedupeJournal::default()));
+        self
+    }
+
+    /// Resizes the dedupe cache, evicting the least recently used keys if it shrinks.
+    pub fn update_max_keys(&self, max_keys: Option<u32>) {
+        let cap = get_dedupe_capacity(max_keys);
+        let Some(mut dedupe_map) = self
+            .exposure_dedupe_set
+            .try_write_for(std::time::Duration::from_secs(5))
+        else {
+            log_e!(TAG, "Failed to acquire write lock for exposure dedupe set");
+            return;
+        };
+
+        dedupe_map.resize(cap);
+        self.exposure_dedupe_max_keys
+            .store(cap.get(), Ordering::Relaxed);
+    }
+
     pub fn get_sampling_decision(
         &self,
         payload: &impl EnqueueOperation,
@@ -97,6 +145,10 @@ impl ExposureSampling {
             return EvtSamplingDecision::Deduped;
         }
 
+        if let Some(decision) = self.get_policy_decision(exposure, &expo_sampling_key) {
+            return decision;
+        }
+
         let sampling_mode = match self.global_configs.get_sampling_mode() {
             Some(sampling_mode) => sampling_mode,
             None => return EvtSamplingDecision::ForceSampled,
@@ -140,10 +192,124 @@ impl ExposureSampling {
             return true;
         }
 
-        dedupe_set.put(sampling_key.clone(), ());
+        let now = self.clock.now_ms();
+        dedupe_set.put(sampling_key.clone(), now);
+        self.journal_dedupe_key(sampling_key.to_snapshot_entry(now), dedupe_set.cap());
         false
     }
 
+    fn journal_dedupe_key(&self, entry: ExposureDedupeSnapshotEntry, max_keys: NonZeroUsize) {
+        if let Some(journal) = &self.dedupe_journal {
+            journal.lock().push(entry, max_keys.get());
+        }
+    }
+
+    /// The dedupe keys of the current dedupe window, most recently added first. Taken from the
+    /// dedupe journal, which is only locked while its sealed chunks are cloned, so exposure
+    /// logging does not wait on the copy. Empty unless the journal is enabled.
+    pub fn create_dedupe_snapshot(&self) -> ExposureDedupeSnapshot {
+        let window_start_ms = self.last_exposure_dedupe_reset.load(Ordering::Relaxed);
+        let Some(journal) = &self.dedupe_journal else {
+            return ExposureDedupeSnapshot {
+                window_start_ms,
+                keys: Vec::new(),
+            };
+        };
+
+        let (sealed, current) = {
+            let journal = journal.lock();
+            (journal.sealed.clone(), journal.current.clone())
+        };
+
+        let len = current.len() + sealed.iter().map(|chunk| chunk.len()).sum::<usize>();
+        let mut keys = Vec::with_capacity(len);
+        keys.extend(current.iter().rev());
+        for chunk in sealed.iter().rev() {
+            keys.extend(chunk.iter().rev());
+        }
+
+        ExposureDedupeSnapshot {
+            window_start_ms,
+            keys,
+        }
+    }
+
+    /// Adds the keys of a snapshot to the dedupe set. The snapshot's dedupe window is kept, so
+    /// the keys are reset when they would have been had the process not restarted. A snapshot
+    /// whose window has already expired is ignored.
+    pub fn warm_dedupe_set(&self, snapshot: ExposureDedupeSnapshot) {
+        let ttl_ms = self.global_configs.get_exposure_dedupe_ttl_ms();
+        let now = self.clock.now_ms();
+
+        let is_future = snapshot.window_start_ms > now;
+        if is_future || now - snapshot.window_start_ms > ttl_ms {
+            log_d!(TAG, "Ignoring expired exposure dedupe snapshot");
+            return;
+        }
+
+        let mut dedupe_set = write_lock_or_noop!(TAG, self.exposure_dedupe_set);
+        let mut warmed = 0;
+        for entry in snapshot.keys {
+            if dedupe_set.len() >= dedupe_set.cap().get() {
+                break;
+            }
+
+            let (key, seen_at_ms) = ExposureSamplingKey::from_snapshot_entry(entry);
+            if dedupe_set.contains(&key) {
+                continue;
+            }
+
+            // keys seen since startup stay the most recently used
+            dedupe_set.push(key.clone(), seen_at_ms);
+            dedupe_set.demote(&key);
+            self.journal_dedupe_key(entry, dedupe_set.cap());
+            warmed += 1;
+        }
+
+        let window_start = self.last_exposure_dedupe_reset.load(Ordering::Relaxed);
+        self.last_exposure_dedupe_reset.store(
+            window_start.min(snapshot.window_start_ms),
+            Ordering::Relaxed,
+        );
+
+        log_d!(TAG, "Warmed exposure dedupe set with {} keys", warmed);
+    }
+
+    fn get_policy_decision<'a>(
+        &self,
+        exposure: &'a impl QueuedExposure<'a>,
+        sampling_key: &ExposureSamplingKey,
+    ) -> Option<EvtSamplingDecision> {
+        let policy = self.policy.as_ref()?;
+
+        let input = ExposureSamplingInput {
+            spec_name: exposure.get_spec_name_ref(),
+            rule_id: exposure.get_rule_id_ref(),
+            user: exposure.get_user_data_ref(),
+            spec_sampling_rate: exposure
+                .get_extra_exposure_info_ref()
+                .and_then(|info| info.sampling_rate),
+            exposure_hash: sampling_key.get_hash(),
+        };
+
+        let decision = policy.sample(&input);
+        let sampled = |rate: u64| EvtSamplingDecision::PolicySampled(decision, rate.max(1));
+
+        match decision {
+            ExposureSamplingDecision::Default => None,
+            ExposureSamplingDecision::Log => Some(sampled(1)),
+            ExposureSamplingDecision::LogWithRate(rate) => Some(sampled(rate)),
+            ExposureSamplingDecision::SampleByHash(rate)
+                if sampling_key.is_sampled(Some(rate.max(1))) =>
+            {
+                Some(sampled(rate))
+            }
+            ExposureSamplingDecision::SampleByHash(_) | ExposureSamplingDecision::Drop => {
+                Some(EvtSamplingDecision::NotSampled)
+            }
+        }
+    }
+
     fn should_sample_based_on_evaluation(
         &self,
         extra_info: Option<&ExtraExposureInfo>,
@@ -190,7 +356,7 @@ impl ExposureSampling {
 
     fn try_reset_spec_sampling_set(&self) {
         let ttl_ms = self.global_configs.get_exposure_spec_sampling_ttl_ms();
-        let now = Utc::now().timestamp_millis() as u64;
+        let now = self.clock.now_ms();
         let last_sampling_reset = self.last_spec_sampling_reset.load(Ordering::Relaxed);
         let mut sampling_map = write_lock_or_noop!(TAG, self.spec_sampling_set);
 
@@ -211,7 +377,7 @@ impl ExposureSampling {
 
     fn try_reset_exposure_dedupe_set(&self) {
         let ttl_ms = self.global_configs.get_exposure_dedupe_ttl_ms();
-        let now = Utc::now().timestamp_millis() as u64;
+        let now = self.clock.now_ms();
         let last_dedupe_reset = self.last_exposure_dedupe_reset.load(Ordering::Relaxed);
         let mut dedupe_map = match self
             .exposure_dedupe_set
@@ -228,12 +394,24 @@ impl ExposureSampling {
 
         if has_expired {
             log_d!(TAG, "Resetting exposure dedupe set (ttl expired)");
-            *dedupe_map = LruCache::new(self.exposure_dedupe_max_keys);
+            *dedupe_map = LruCache::new(self.get_dedupe_max_keys());
+            if let Some(journal) = &self.dedupe_journal {
+                *journal.lock() = DedupeJournal::default();
+            }
             self.last_exposure_dedupe_reset
                 .store(now, Ordering::Relaxed);
         }
     }
 
+    pub fn get_dedupe_ttl_ms(&self) -> u64 {
+        self.global_configs.get_exposure_dedupe_ttl_ms()
+    }
+
+    pub fn get_dedupe_max_keys(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.exposure_dedupe_max_keys.load(Ordering::Relaxed))
+            .unwrap_or_else(|| get_dedupe_capacity(None))
+    }
+
     fn sample_key_exists(&self, key: &SpecAndRuleHashTuple) -> bool {
         match self
             .spec_sampling_set
@@ -325,7 +503,71 @@ pub struct ExposureSamplingKey {
     pub additional_hash: u64,
 }
 
+/// `[spec_name_hash, rule_id_hash, user_values_hash, additional_hash, seen_at_ms]`
+pub type ExposureDedupeSnapshotEntry = [u64; 5];
+
+#[derive(Debug, Default)]
+pub struct ExposureDedupeSnapshot {
+    /// When the dedupe window the keys belong to started.
+    pub window_start_ms: u64,
+    pub keys: Vec<ExposureDedupeSnapshotEntry>,
+}
+
+/// Append-only log of the keys added to the dedupe set. Full chunks are sealed behind an
+/// `Arc`, so snapshots clone pointers rather than keys. The oldest chunks are dropped once the
+/// rest hold `max_keys` keys.
+#[derive(Default)]
+struct DedupeJournal {
+    sealed: VecDeque<Arc<[ExposureDedupeSnapshotEntry]>>,
+    sealed_len: usize,
+    current: Vec<ExposureDedupeSnapshotEntry>,
+}
+
+impl DedupeJournal {
+    fn push(&mut self, entry: ExposureDedupeSnapshotEntry, max_keys: usize) {
+        self.current.push(entry);
+        if self.current.len() < DEDUPE_JOURNAL_CHUNK_SIZE {
+            return;
+        }
+
+        let chunk: Arc<[ExposureDedupeSnapshotEntry]> = std::mem::take(&mut self.current).into();
+        self.sealed_len += chunk.len();
+        self.sealed.push_back(chunk);
+
+        while self
+            .sealed
+            .front()
+            .is_some_and(|oldest| self.sealed_len - oldest.len() >= max_keys)
+        {
+            if let Some(oldest) = self.sealed.pop_front() {
+                self.sealed_len -= oldest.len();
+            }
+        }
+    }
+}
+
 impl ExposureSamplingKey {
+    fn to_snapshot_entry(&self, seen_at_ms: u64) -> ExposureDedupeSnapshotEntry {
+        [
+            self.spec_name_hash,
+            self.rule_id_hash,
+            self.user_values_hash,
+            self.additional_hash,
+            seen_at_ms,
+        ]
+    }
+
+    fn from_snapshot_entry(entry: ExposureDedupeSnapshotEntry) -> (Self, u64) {
+        let [spec_name_hash, rule_id_hash, user_values_hash, additional_hash, seen_at_ms] = entry;
+        let key = Self {
+            spec_name_hash,
+            rule_id_hash,
+            user_values_hash,
+            additional_hash,
+        };
+        (key, seen_at_ms)
+    }
+
     pub fn new(
         evaluation: Option<&BaseEvaluation>,
         user: &UserData,
@@ -350,16 +592,27 @@ impl ExposureSamplingKey {
             None => return true, // without a sampling rate, we should sample
         };
 
-        let final_hash =
-            self.spec_name_hash ^ self.rule_id_hash ^ self.user_values_hash ^ self.additional_hash;
+        self.get_hash().is_multiple_of(sampling_rate)
+    }
 
-        final_hash.is_multiple_of(sampling_rate)
+    pub fn get_hash(&self) -> u64 {
+        self.spec_name_hash ^ self.rule_id_hash ^ self.user_values_hash ^ self.additional_hash
     }
 }
 
+fn get_dedupe_capacity(max_keys: Option<u32>) -> NonZeroUsize {
+    max_keys
+        .map(|v| v as usize)
+        .and_then(NonZeroUsize::new)
+        .unwrap_or_else(|| {
+            NonZeroUsize::new(SAMPLING_MAX_KEYS).expect("SAMPLING_MAX_KEYS must be non-zero")
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::ManualClock;
 
     fn make_key(unique: u64) -> ExposureSamplingKey {
         ExposureSamplingKey {
@@ -435,4 +688,81 @@ mod tests {
             "evicted key should be treated as new again"
         );
     }
+
+    #[test]
+    fn test_dedupe_snapshot_round_trip() {
+        let clock = Arc::new(ManualClock::new(1_000_000));
+        let sampling =
+            ExposureSampling::with_clock("secret-test-snapshot", Some(10), clock.clone())
+                .with_dedupe_journal(true);
+        for i in 0..3u64 {
+            sampling.should_dedupe_exposure(&make_key(i));
+        }
+
+        let snapshot = sampling.create_dedupe_snapshot();
+        assert_eq!(snapshot.window_start_ms, 1_000_000);
+        assert_eq!(snapshot.keys.len(), 3);
+
+        clock.advance(std::time::Duration::from_secs(30));
+        let restarted =
+            ExposureSampling::with_clock("secret-test-snapshot", Some(10), clock.clone());
+        assert!(!restarted.should_dedupe_exposure(&make_key(7)));
+        restarted.warm_dedupe_set(snapshot);
+
+        for i in 0..3u64 {
+            assert!(restarted.should_dedupe_exposure(&make_key(i)));
+        }
+
+        // the warmed keys expire with the window they were seen in
+        assert_eq!(
+            restarted.last_exposure_dedupe_reset.load(Ordering::Relaxed),
+            1_000_000
+        );
+    }
+
+    #[test]
+    fn test_dedupe_snapshot_spans_journal_chunks() {
+        let clock = Arc::new(ManualClock::new(1_000_000));
+        let max_keys = DEDUPE_JOURNAL_CHUNK_SIZE * 2;
+        let sampling = ExposureSampling::with_clock(
+            "secret-test-snapshot-chunks",
+            Some(max_keys as u32),
+            clock.clone(),
+        )
+        .with_dedupe_journal(true);
+
+        let total = DEDUPE_JOURNAL_CHUNK_SIZE * 4 + 10;
+        for i in 0..total as u64 {
+            sampling.should_dedupe_exposure(&make_key(i));
+        }
+
+        // the oldest chunks are dropped once the newer ones hold max_keys keys
+        let snapshot = sampling.create_dedupe_snapshot();
+        assert_eq!(snapshot.keys.len(), max_keys + 10);
+        assert_eq!(snapshot.keys[0][2], total as u64 - 1);
+
+        // a new dedupe window starts an empty journal
+        clock.advance(std::time::Duration::from_millis(
+            DEFAULT_EXPOSURE_SAMPLING_TTL_MS + 1,
+        ));
+        sampling.try_reset_all_sampling();
+        assert!(sampling.create_dedupe_snapshot().keys.is_empty());
+    }
+
+    #[test]
+    fn test_expired_dedupe_snapshot_is_ignored() {
+        let clock = Arc::new(ManualClock::new(1_000_000));
+        let snapshot = ExposureDedupeSnapshot {
+            window_start_ms: 1_000_000,
+            keys: vec![make_key(1).to_snapshot_entry(1_000_000)],
+        };
+
+        clock.advance(std::time::Duration::from_millis(
+            DEFAULT_EXPOSURE_SAMPLING_TTL_MS + 1,
+        ));
+        let sampling = ExposureSampling::with_clock("secret-test-expired", Some(10), clock);
+        sampling.warm_dedupe_set(snapshot);
+
+        assert!(!sampling.should_dedupe_exposure(&make_key(1)));
+    }
 }
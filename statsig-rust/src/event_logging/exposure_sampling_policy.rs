@@ -0,0 +1,51 @@
+use crate::user::user_data::UserData;
+
+/// Decides how exposures are sampled, in place of the sampling configured for the project.
+/// Registered with `StatsigOptions::exposure_sampling_policy`.
+///
+/// `sample` is called for every exposure that was not deduped, on the thread that queues it,
+/// so it should be cheap. Returning [`ExposureSamplingDecision::Default`] keeps the built-in
+/// sampling for that exposure. The decision and rate are added to the exposure's
+/// `statsigMetadata` (`samplingMode: "policy"`, `samplingDecision`, `samplingRate`), so
+/// sampled exposures can be re-weighted downstream.
+pub trait ExposureSamplingPolicy: Send + Sync {
+    fn sample(&self, exposure: &ExposureSamplingInput) -> ExposureSamplingDecision;
+}
+
+pub struct ExposureSamplingInput<'a> {
+    pub spec_name: &'a str,
+    /// Empty when the exposure has no rule.
+    pub rule_id: &'a str,
+    pub user: &'a UserData,
+    /// The sampling rate the server set for the spec, if any.
+    pub spec_sampling_rate: Option<u64>,
+    /// Stable hash of the spec, rule and user, the same one the built-in sampling uses.
+    pub exposure_hash: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExposureSamplingDecision {
+    /// Uses the built-in sampling.
+    Default,
+    /// Logs the exposure with a sampling rate of 1.
+    Log,
+    /// Logs the exposure, which the policy kept as 1 of every `rate` (budget or reservoir
+    /// sampling, for example).
+    LogWithRate(u64),
+    /// Logs 1 of every `rate` exposures, chosen deterministically by `exposure_hash`.
+    SampleByHash(u64),
+    /// Does not log the exposure.
+    Drop,
+}
+
+impl ExposureSamplingDecision {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ExposureSamplingDecision::Default => "default",
+            ExposureSamplingDecision::Log => "log",
+            ExposureSamplingDecision::LogWithRate(_) => "log_with_rate",
+            ExposureSamplingDecision::SampleByHash(_) => "sample_by_hash",
+            ExposureSamplingDecision::Drop => "drop",
+        }
+    }
+}
@@ -10,6 +10,13 @@ pub(crate) fn get_statsig_metadata_with_sampling_decision(
         EvtSamplingDecision::Sampled(sampling_rate, mode, was_sampled) => {
             (sampling_rate, mode, was_sampled)
         }
+        EvtSamplingDecision::PolicySampled(decision, sampling_rate) => {
+            return HashMap::from([
+                ("samplingRate".into(), sampling_rate.into()),
+                ("samplingMode".into(), "policy".into()),
+                ("samplingDecision".into(), decision.as_str().into()),
+            ]);
+        }
         _ => return HashMap::new(),
     };
 
@@ -1,22 +1,37 @@
 use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
 
-use crate::{event_logging::event_logger_constants::EventLoggerConstants, log_d};
-use chrono::Utc;
+use crate::{clock::Clock, event_logging::event_logger_constants::EventLoggerConstants, log_d};
 use std::sync::atomic::Ordering::Relaxed;
 
 const TAG: &str = stringify!(FlushInterval);
 
-#[derive(Default)]
 pub struct FlushInterval {
     current_flush_interval_ms: AtomicU64,
     last_scheduled_flush_attempt_time: AtomicU64,
+    // 0 means EventLoggerConstants::max_flush_interval_ms
+    max_flush_interval_ms: AtomicU64,
+    clock: Arc<dyn Clock>,
 }
 
 impl FlushInterval {
-    pub fn new() -> Self {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
         Self {
             current_flush_interval_ms: AtomicU64::new(EventLoggerConstants::min_flush_interval_ms()),
-            last_scheduled_flush_attempt_time: AtomicU64::new(get_now_timestamp()),
+            last_scheduled_flush_attempt_time: AtomicU64::new(clock.now_ms()),
+            max_flush_interval_ms: AtomicU64::new(0),
+            clock,
+        }
+    }
+
+    pub fn set_max_flush_interval_ms(&self, max_flush_interval_ms: Option<u32>) {
+        let max_flush_interval_ms = max_flush_interval_ms.map(u64::from).unwrap_or(0);
+        self.max_flush_interval_ms
+            .store(max_flush_interval_ms, Relaxed);
+
+        let max = self.load_max_interval();
+        if self.load_current_interval() > max {
+            self.current_flush_interval_ms.store(max, Relaxed);
         }
     }
 
@@ -25,7 +40,7 @@ impl FlushInterval {
     }
 
     pub fn mark_scheduled_flush_attempt(&self) {
-        let now = get_now_timestamp();
+        let now = self.clock.now_ms();
         self.last_scheduled_flush_attempt_time.store(now, Relaxed);
     }
 
@@ -49,7 +64,7 @@ impl FlushInterval {
 
     pub fn adjust_for_failure(&self) {
         let current = self.load_current_interval();
-        let adjusted = (current * 2).min(EventLoggerConstants::max_flush_interval_ms());
+        let adjusted = (current * 2).min(self.load_max_interval());
         self.current_flush_interval_ms.store(adjusted, Relaxed);
 
         log_d!(
@@ -64,14 +79,13 @@ impl FlushInterval {
         let last_flush_attempt_time = self.load_last_scheduled_flush_attempt_time();
         let flush_interval_ms = self.load_current_interval();
         let next_flush_time = last_flush_attempt_time + flush_interval_ms;
-        next_flush_time < get_now_timestamp()
+        next_flush_time < self.clock.now_ms()
     }
 
     pub fn has_waited_max_allowed_interval(&self) -> bool {
         let last_flush_attempt_time = self.load_last_scheduled_flush_attempt_time();
-        let next_flush_time =
-            last_flush_attempt_time + EventLoggerConstants::max_flush_interval_ms();
-        next_flush_time < get_now_timestamp()
+        let next_flush_time = last_flush_attempt_time + self.load_max_interval();
+        next_flush_time < self.clock.now_ms()
     }
 
     pub fn has_completely_recovered_from_backoff(&self) -> bool {
@@ -83,22 +97,27 @@ impl FlushInterval {
         self.current_flush_interval_ms.load(Relaxed)
     }
 
+    fn load_max_interval(&self) -> u64 {
+        match self.max_flush_interval_ms.load(Relaxed) {
+            0 => EventLoggerConstants::max_flush_interval_ms(),
+            max => max,
+        }
+    }
+
     fn load_last_scheduled_flush_attempt_time(&self) -> u64 {
         self.last_scheduled_flush_attempt_time.load(Relaxed)
     }
 }
 
-fn get_now_timestamp() -> u64 {
-    Utc::now().timestamp_millis() as u64
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::{ManualClock, SystemClock};
+    use std::time::Duration;
 
     #[test]
     fn test_failure_doubles_backoff() {
-        let flush_interval = FlushInterval::new();
+        let flush_interval = FlushInterval::new(Arc::new(SystemClock));
 
         flush_interval.adjust_for_failure();
         assert_eq!(flush_interval.get_current_flush_interval_ms(), 2000);
@@ -109,7 +128,7 @@ mod tests {
 
     #[test]
     fn test_failure_backoff_max() {
-        let flush_interval = FlushInterval::new();
+        let flush_interval = FlushInterval::new(Arc::new(SystemClock));
 
         for _ in 0..1000 {
             flush_interval.adjust_for_failure();
@@ -117,9 +136,23 @@ mod tests {
         assert_eq!(flush_interval.get_current_flush_interval_ms(), 60000);
     }
 
+    #[test]
+    fn test_failure_backoff_custom_max() {
+        let flush_interval = FlushInterval::new(Arc::new(SystemClock));
+        flush_interval.set_max_flush_interval_ms(Some(5000));
+
+        for _ in 0..1000 {
+            flush_interval.adjust_for_failure();
+        }
+        assert_eq!(flush_interval.get_current_flush_interval_ms(), 5000);
+
+        flush_interval.set_max_flush_interval_ms(Some(3000));
+        assert_eq!(flush_interval.get_current_flush_interval_ms(), 3000);
+    }
+
     #[test]
     fn test_success_halves_backoff() {
-        let flush_interval = FlushInterval::new();
+        let flush_interval = FlushInterval::new(Arc::new(SystemClock));
 
         for _ in 0..1000 {
             flush_interval.adjust_for_failure();
@@ -134,7 +167,7 @@ mod tests {
 
     #[test]
     fn test_success_backoff_min() {
-        let flush_interval = FlushInterval::new();
+        let flush_interval = FlushInterval::new(Arc::new(SystemClock));
 
         for _ in 0..1000 {
             flush_interval.adjust_for_success();
@@ -142,4 +175,20 @@ mod tests {
 
         assert_eq!(flush_interval.get_current_flush_interval_ms(), 1000);
     }
+
+    #[test]
+    fn test_max_interval_follows_clock() {
+        let clock = Arc::new(ManualClock::new(1_000_000));
+        let flush_interval = FlushInterval::new(clock.clone());
+        flush_interval.set_max_flush_interval_ms(Some(5000));
+
+        clock.advance(Duration::from_millis(5000));
+        assert!(!flush_interval.has_waited_max_allowed_interval());
+
+        clock.advance(Duration::from_millis(1));
+        assert!(flush_interval.has_waited_max_allowed_interval());
+
+        flush_interval.mark_scheduled_flush_attempt();
+        assert!(!flush_interval.has_waited_max_allowed_interval());
+    }
 }
@@ -2,10 +2,13 @@ pub mod event_logger;
 pub mod event_logger_constants;
 pub mod event_logger_ops_stats;
 pub mod event_queue;
+pub mod exposure_dedupe_persistence;
 pub mod exposure_sampling;
+pub mod exposure_sampling_policy;
 mod exposure_utils;
 pub mod flush_interval;
 pub mod flush_type;
+pub mod redaction_policy;
 mod sec_expo_as_primary_experiment;
 pub mod statsig_event;
 pub mod statsig_event_internal;
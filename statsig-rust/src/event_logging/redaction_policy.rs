@@ -0,0 +1,314 @@
+use crate::event_logging::event_queue::queued_event::QueuedEvent;
+use crate::event_logging::statsig_event_internal::STATSIG_LOG_LINE_EVENT_NAME;
+use crate::hashing::HashUtil;
+use crate::user::StatsigUserLoggable;
+use crate::DynamicValue;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Matches every key of a map based rule set (custom fields, custom IDs, metadata).
+pub const REDACT_ALL_KEYS: &str = "*";
+
+// Added by the SDK to every log line. These are never redacted by a wildcard rule.
+const RESERVED_LOG_LINE_METADATA_KEYS: [&str; 3] = ["status", "source", "log_level"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RedactionAction {
+    /// Removes the field entirely.
+    Drop,
+    /// Replaces the value with its sha256 hash.
+    Hash,
+    /// Keeps at most `max_length` characters of the value.
+    Truncate { max_length: usize },
+}
+
+/// Declarative rules applied to users and event payloads before they enter the event queue.
+///
+/// `user_fields` accepts the top level user fields (`userID`, `email`, `ip`, `userAgent`,
+/// `country`, `locale`, `appVersion`). All other maps are keyed by the field, custom ID or
+/// metadata key name, and accept [`REDACT_ALL_KEYS`] as a fallback. Exposure metadata is
+/// never modified, only the user attached to the exposure.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionPolicy {
+    pub user_fields: HashMap<String, RedactionAction>,
+    pub custom_fields: HashMap<String, RedactionAction>,
+    pub custom_ids: HashMap<String, RedactionAction>,
+    pub event_metadata: HashMap<String, RedactionAction>,
+    pub event_value: Option<RedactionAction>,
+    pub log_line_metadata: HashMap<String, RedactionAction>,
+    pub log_line_value: Option<RedactionAction>,
+}
+
+impl RedactionPolicy {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn user_field(mut self, field: &str, action: RedactionAction) -> Self {
+        self.user_fields.insert(field.to_string(), action);
+        self
+    }
+
+    #[must_use]
+    pub fn custom_field(mut self, key: &str, action: RedactionAction) -> Self {
+        self.custom_fields.insert(key.to_string(), action);
+        self
+    }
+
+    #[must_use]
+    pub fn custom_id(mut self, key: &str, action: RedactionAction) -> Self {
+        self.custom_ids.insert(key.to_string(), action);
+        self
+    }
+
+    #[must_use]
+    pub fn event_metadata_key(mut self, key: &str, action: RedactionAction) -> Self {
+        self.event_metadata.insert(key.to_string(), action);
+        self
+    }
+
+    #[must_use]
+    pub fn event_value(mut self, action: RedactionAction) -> Self {
+        self.event_value = Some(action);
+        self
+    }
+
+    #[must_use]
+    pub fn log_line_metadata_key(mut self, key: &str, action: RedactionAction) -> Self {
+        self.log_line_metadata.insert(key.to_string(), action);
+        self
+    }
+
+    #[must_use]
+    pub fn log_line_value(mut self, action: RedactionAction) -> Self {
+        self.log_line_value = Some(action);
+        self
+    }
+
+    /// Walks a logged payload (e.g. `LogEventPayload::events`) and returns the JSON path of
+    /// every string that still contains one of `sensitive_values`. Intended for tests:
+    /// `assert!(RedactionPolicy::find_leaks(&payload.events, &["a@b.com"]).is_empty())`.
+    pub fn find_leaks(events: &Value, sensitive_values: &[&str]) -> Vec<String> {
+        let mut leaks = Vec::new();
+        collect_leaks(events, "$", sensitive_values, &mut leaks);
+        leaks
+    }
+
+    pub(crate) fn redact_queued_event(&self, event: &mut QueuedEvent, hashing: &HashUtil) {
+        self.redact_user(event.user_mut(), hashing);
+
+        let QueuedEvent::Passthrough(event) = event else {
+            return;
+        };
+
+        let data = &mut event.event_data;
+        if data.event_name == STATSIG_LOG_LINE_EVENT_NAME {
+            redact_optional_value(&mut data.value, self.log_line_value.as_ref(), hashing);
+            if let Some(metadata) = data.metadata.as_mut() {
+                metadata.retain(|key, value| {
+                    if RESERVED_LOG_LINE_METADATA_KEYS.contains(&key.as_str()) {
+                        return true;
+                    }
+                    apply_to_json(lookup(&self.log_line_metadata, key), value, hashing)
+                });
+            }
+            return;
+        }
+
+        if data.event_name.starts_with("statsig::") {
+            return;
+        }
+
+        redact_optional_value(&mut data.value, self.event_value.as_ref(), hashing);
+        if let Some(metadata) = data.metadata.as_mut() {
+            metadata.retain(|key, value| {
+                apply_to_json(lookup(&self.event_metadata, key), value, hashing)
+            });
+        }
+    }
+
+    pub(crate) fn redact_user(&self, user: &mut StatsigUserLoggable, hashing: &HashUtil) {
+        if let Some(global_custom) = user.global_custom.as_mut() {
+            if !self.custom_fields.is_empty() {
+                global_custom.retain(|key, value| {
+                    apply_to_dynamic(lookup(&self.custom_fields, key), value, hashing)
+                });
+            }
+        }
+
+        if !self.user_needs_redaction(user) {
+            return;
+        }
+
+        // copy-on-write, the user data is shared with the caller's StatsigUser
+        let data = Arc::make_mut(&mut user.data);
+
+        for (field, action) in &self.user_fields {
+            let slot = match normalize_user_field(field).as_str() {
+                "userid" => &mut data.user_id,
+                "email" => &mut data.email,
+                "ip" => &mut data.ip,
+                "useragent" => &mut data.user_agent,
+                "country" => &mut data.country,
+                "locale" => &mut data.locale,
+                "appversion" => &mut data.app_version,
+                _ => continue,
+            };
+
+            if let Some(value) = slot.as_mut() {
+                if !apply_to_dynamic(Some(action), value, hashing) {
+                    *slot = None;
+                }
+            }
+        }
+
+        if let Some(custom) = data.custom.as_mut() {
+            custom.retain(|key, value| {
+                apply_to_dynamic(lookup(&self.custom_fields, key), value, hashing)
+            });
+        }
+
+        if let Some(custom_ids) = data.custom_ids.as_mut() {
+            custom_ids.retain(|key, value| {
+                apply_to_dynamic(lookup(&self.custom_ids, key), value, hashing)
+            });
+        }
+    }
+
+    fn user_needs_redaction(&self, user: &StatsigUserLoggable) -> bool {
+        let data = user.data.as_ref();
+
+        let has_top_level =
+            self.user_fields
+                .keys()
+                .any(|field| match normalize_user_field(field).as_str() {
+                    "userid" => data.user_id.is_some(),
+                    "email" => data.email.is_some(),
+                    "ip" => data.ip.is_some(),
+                    "useragent" => data.user_agent.is_some(),
+                    "country" => data.country.is_some(),
+                    "locale" => data.locale.is_some(),
+                    "appversion" => data.app_version.is_some(),
+                    _ => false,
+                });
+
+        let has_custom = data.custom.as_ref().is_some_and(|custom| {
+            custom
+                .keys()
+                .any(|k| lookup(&self.custom_fields, k).is_some())
+        });
+
+        let has_custom_ids = data
+            .custom_ids
+            .as_ref()
+            .is_some_and(|ids| ids.keys().any(|k| lookup(&self.custom_ids, k).is_some()));
+
+        has_top_level || has_custom || has_custom_ids
+    }
+}
+
+fn normalize_user_field(field: &str) -> String {
+    field.to_lowercase().replace('_', "")
+}
+
+fn lookup<'a>(
+    rules: &'a HashMap<String, RedactionAction>,
+    key: &str,
+) -> Option<&'a RedactionAction> {
+    rules.get(key).or_else(|| rules.get(REDACT_ALL_KEYS))
+}
+
+fn redact_string(action: &RedactionAction, input: &str, hashing: &HashUtil) -> Option<String> {
+    match action {
+        RedactionAction::Drop => None,
+        RedactionAction::Hash => Some(hashing.sha256(input)),
+        RedactionAction::Truncate { max_length } => Some(input.chars().take(*max_length).collect()),
+    }
+}
+
+/// Returns false when the value should be dropped.
+fn apply_to_dynamic(
+    action: Option<&RedactionAction>,
+    value: &mut DynamicValue,
+    hashing: &HashUtil,
+) -> bool {
+    let Some(action) = action else {
+        return true;
+    };
+
+    let input = match (&value.string_value, &value.json_value) {
+        (Some(s), _) => s.value.to_string(),
+        (None, Value::Null) => return true,
+        (None, other) => other.to_string(),
+    };
+
+    match redact_string(action, &input, hashing) {
+        Some(redacted) => {
+            *value = DynamicValue::from(redacted);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns false when the value should be dropped.
+fn apply_to_json(action: Option<&RedactionAction>, value: &mut Value, hashing: &HashUtil) -> bool {
+    let Some(action) = action else {
+        return true;
+    };
+
+    let input = match &*value {
+        Value::String(s) => s.clone(),
+        Value::Null => return true,
+        other => other.to_string(),
+    };
+
+    match redact_string(action, &input, hashing) {
+        Some(redacted) => {
+            *value = Value::String(redacted);
+            true
+        }
+        None => false,
+    }
+}
+
+fn redact_optional_value(
+    value: &mut Option<Value>,
+    action: Option<&RedactionAction>,
+    hashing: &HashUtil,
+) {
+    if let Some(inner) = value.as_mut() {
+        if !apply_to_json(action, inner, hashing) {
+            *value = None;
+        }
+    }
+}
+
+fn collect_leaks(value: &Value, path: &str, sensitive: &[&str], leaks: &mut Vec<String>) {
+    match value {
+        Value::String(s) if sensitive.iter().any(|needle| s.contains(needle)) => {
+            leaks.push(path.to_string());
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect_leaks(item, &format!("{path}[{i}]"), sensitive, leaks);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let child_path = format!("{path}.{key}");
+                if sensitive.iter().any(|needle| key.contains(needle)) {
+                    leaks.push(child_path.clone());
+                }
+                collect_leaks(item, &child_path, sensitive, leaks);
+            }
+        }
+        _ => {}
+    }
+}
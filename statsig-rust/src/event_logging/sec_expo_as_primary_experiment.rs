@@ -1,5 +1,9 @@
-use crate::{global_configs::GlobalConfigs, hashing::HashUtil, DynamicValue, StatsigOptions};
-use chrono::Utc;
+use crate::{
+    clock::{get_clock, Clock},
+    global_configs::GlobalConfigs,
+    hashing::HashUtil,
+    DynamicValue, StatsigOptions,
+};
 use std::sync::{
     atomic::{AtomicU64, Ordering},
     Arc,
@@ -17,6 +21,7 @@ pub(super) struct SecExpoAsPrimaryExperiment {
     global_configs: Arc<GlobalConfigs>,
     cached_sec_expo_number: AtomicU64,
     last_sec_expo_number_refresh_time: AtomicU64,
+    clock: Arc<dyn Clock>,
 }
 
 impl SecExpoAsPrimaryExperiment {
@@ -38,6 +43,7 @@ impl SecExpoAsPrimaryExperiment {
             global_configs: GlobalConfigs::get_instance(sdk_key),
             cached_sec_expo_number: AtomicU64::new(NO_SEC_EXPO_NUMBER),
             last_sec_expo_number_refresh_time: AtomicU64::new(0),
+            clock: get_clock(Some(options)),
         }
     }
 
@@ -51,7 +57,7 @@ impl SecExpoAsPrimaryExperiment {
     }
 
     fn get_cached_sec_expo_number(&self) -> Option<u64> {
-        let now = Utc::now().timestamp_millis() as u64;
+        let now = self.clock.now_ms();
         let last_refresh = self
             .last_sec_expo_number_refresh_time
             .load(Ordering::Relaxed);
@@ -5,7 +5,6 @@ use crate::sdk_diagnostics::diagnostics::DIAGNOSTICS_EVENT;
 use crate::user::StatsigUserLoggable;
 use crate::{evaluation::evaluation_types::SecondaryExposure, statsig_metadata::StatsigMetadata};
 
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
@@ -14,6 +13,7 @@ pub const GATE_EXPOSURE_EVENT_NAME: &str = "statsig::gate_exposure";
 pub const CONFIG_EXPOSURE_EVENT_NAME: &str = "statsig::config_exposure";
 pub const LAYER_EXPOSURE_EVENT_NAME: &str = "statsig::layer_exposure";
 pub const STATSIG_LOG_LINE_EVENT_NAME: &str = "statsig::log_line";
+pub const CMAB_REWARD_EVENT_NAME: &str = "statsig::cmab_reward";
 
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -42,6 +42,7 @@ impl StatsigEventInternal {
     }
 
     pub fn new_custom_event(
+        time: u64,
         user: StatsigUserLoggable,
         event_name: String,
         value: Option<Value>,
@@ -49,7 +50,7 @@ impl StatsigEventInternal {
     ) -> Self {
         let metadata = metadata.map(string_metadata_to_value_metadata);
         StatsigEventInternal::new(
-            Utc::now().timestamp_millis() as u64,
+            time,
             user,
             StatsigEvent {
                 event_name,
@@ -62,13 +63,14 @@ impl StatsigEventInternal {
     }
 
     pub fn new_custom_event_with_typed_metadata(
+        time: u64,
         user: StatsigUserLoggable,
         event_name: String,
         value: Option<Value>,
         metadata: Option<HashMap<String, Value>>,
     ) -> Self {
         StatsigEventInternal::new(
-            Utc::now().timestamp_millis() as u64,
+            time,
             user,
             StatsigEvent {
                 event_name,
@@ -80,7 +82,7 @@ impl StatsigEventInternal {
         )
     }
 
-    pub fn new_diagnostic_event(metadata: HashMap<String, String>) -> Self {
+    pub fn new_diagnostic_event(time: u64, metadata: HashMap<String, String>) -> Self {
         StatsigEventInternal {
             event_data: StatsigEvent {
                 event_name: DIAGNOSTICS_EVENT.to_string(),
@@ -89,12 +91,12 @@ impl StatsigEventInternal {
                 statsig_metadata: None,
             },
             user: StatsigUserLoggable::null(),
-            time: Utc::now().timestamp_millis() as u64,
+            time,
             secondary_exposures: None,
         }
     }
 
-    pub fn new_non_exposed_checks_event(checks: HashMap<String, u64>) -> Self {
+    pub fn new_non_exposed_checks_event(time: u64, checks: HashMap<String, u64>) -> Self {
         let checks_json = match serde_json::to_string(&checks) {
             Ok(json) => json,
             Err(_) => "STATSIG_ERROR_SERIALIZING_NON_EXPOSED_CHECKS".into(),
@@ -113,7 +115,7 @@ impl StatsigEventInternal {
         StatsigEventInternal {
             event_data: event,
             user: StatsigUserLoggable::null(),
-            time: Utc::now().timestamp_millis() as u64,
+            time,
             secondary_exposures: None,
         }
     }
@@ -123,7 +125,7 @@ impl StatsigEventInternal {
         log_level: StatsigLogLineLevel,
         value: Option<String>,
         metadata: Option<HashMap<String, String>>,
-        timestamp_override: Option<u64>,
+        time: u64,
     ) -> Self {
         let mut populated_metadata = metadata.unwrap_or_default();
         populated_metadata.insert("status".to_string(), log_level.to_status_string());
@@ -141,7 +143,7 @@ impl StatsigEventInternal {
                 statsig_metadata: None,
             },
             user,
-            time: timestamp_override.unwrap_or(Utc::now().timestamp_millis() as u64),
+            time,
             secondary_exposures: None,
         }
     }
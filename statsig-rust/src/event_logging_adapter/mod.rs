@@ -1,7 +1,16 @@
 pub use event_logging_adapter_trait::*;
+pub use statsig_composite_event_logging_adapter::{
+    EventDestination, EventDestinationFilter, EventDestinationStats, LoggedEventKind,
+    StatsigCompositeEventLoggingAdapter,
+};
+#[cfg(feature = "with_grpc")]
+pub use statsig_grpc_event_logging_adapter::StatsigGrpcEventLoggingAdapter;
 pub use statsig_http_event_logging_adapter::StatsigHttpEventLoggingAdapter;
 pub mod event_logging_adapter_trait;
 pub mod log_event_payload;
+pub mod statsig_composite_event_logging_adapter;
+#[cfg(feature = "with_grpc")]
+pub mod statsig_grpc_event_logging_adapter;
 pub mod statsig_http_event_logging_adapter;
 
 // depends on file locking, which is unavailable on any wasm target:
@@ -0,0 +1,534 @@
+use crate::event_logging::statsig_event_internal::{
+    CONFIG_EXPOSURE_EVENT_NAME, GATE_EXPOSURE_EVENT_NAME, LAYER_EXPOSURE_EVENT_NAME,
+};
+use crate::event_logging_adapter::EventLoggingAdapter;
+use crate::log_event_payload::{LogEventPayload, LogEventRequest};
+use crate::networking::NetworkError;
+use crate::observability::observability_client_adapter::{MetricType, ObservabilityEvent};
+use crate::observability::ops_stats::{OpsStatsForInstance, OPS_STATS};
+use crate::sdk_diagnostics::diagnostics::DIAGNOSTICS_EVENT;
+use crate::{log_d, log_w, StatsigErr, StatsigRuntime};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+const TAG: &str = stringify!(StatsigCompositeEventLoggingAdapter);
+const RETRY_TAG: &str = "COMPOSITE_EVT_LOG_RETRY";
+
+const DEFAULT_MAX_RETRIES: u32 = 2;
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LoggedEventKind {
+    Custom,
+    GateExposure,
+    ConfigExposure,
+    LayerExposure,
+    Diagnostics,
+    /// Any other `statsig::` prefixed event, e.g. log lines or non-exposed checks.
+    Internal,
+}
+
+impl LoggedEventKind {
+    pub const EXPOSURES: [LoggedEventKind; 3] = [
+        LoggedEventKind::GateExposure,
+        LoggedEventKind::ConfigExposure,
+        LoggedEventKind::LayerExposure,
+    ];
+
+    #[must_use]
+    pub fn from_event_name(event_name: &str) -> Self {
+        match event_name {
+            GATE_EXPOSURE_EVENT_NAME => LoggedEventKind::GateExposure,
+            CONFIG_EXPOSURE_EVENT_NAME => LoggedEventKind::ConfigExposure,
+            LAYER_EXPOSURE_EVENT_NAME => LoggedEventKind::LayerExposure,
+            DIAGNOSTICS_EVENT => LoggedEventKind::Diagnostics,
+            name if name.starts_with("statsig::") => LoggedEventKind::Internal,
+            _ => LoggedEventKind::Custom,
+        }
+    }
+}
+
+/// Decides which events are forwarded to a destination. An event is accepted when its kind
+/// is allowed (all kinds by default), its name is in `event_names` (when set), and its name
+/// is not in `excluded_event_names`.
+#[derive(Clone, Debug, Default)]
+pub struct EventDestinationFilter {
+    pub kinds: Option<HashSet<LoggedEventKind>>,
+    pub event_names: Option<HashSet<String>>,
+    pub excluded_event_names: HashSet<String>,
+}
+
+impl EventDestinationFilter {
+    #[must_use]
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn only_kinds(kinds: &[LoggedEventKind]) -> Self {
+        Self {
+            kinds: Some(kinds.iter().copied().collect()),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn exposures_only() -> Self {
+        Self::only_kinds(&LoggedEventKind::EXPOSURES)
+    }
+
+    #[must_use]
+    pub fn with_event_names(mut self, names: &[&str]) -> Self {
+        self.event_names = Some(names.iter().map(|n| n.to_string()).collect());
+        self
+    }
+
+    #[must_use]
+    pub fn excluding_event_names(mut self, names: &[&str]) -> Self {
+        self.excluded_event_names
+            .extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    pub fn accepts_all(&self) -> bool {
+        self.kinds.is_none() && self.event_names.is_none() && self.excluded_event_names.is_empty()
+    }
+
+    pub fn accepts(&self, event_name: &str) -> bool {
+        if self.excluded_event_names.contains(event_name) {
+            return false;
+        }
+
+        if let Some(names) = &self.event_names {
+            if !names.contains(event_name) {
+                return false;
+            }
+        }
+
+        match &self.kinds {
+            Some(kinds) => kinds.contains(&LoggedEventKind::from_event_name(event_name)),
+            None => true,
+        }
+    }
+}
+
+/// A single destination of a [`StatsigCompositeEventLoggingAdapter`]. Each destination
+/// retries on its own and off the flush path, so a slow or failing destination neither delays
+/// flushes nor causes events to be resent to the others.
+pub struct EventDestination {
+    pub name: String,
+    pub adapter: Arc<dyn EventLoggingAdapter>,
+    pub filter: EventDestinationFilter,
+    pub max_retries: u32,
+    pub retry_backoff_ms: u64,
+}
+
+impl EventDestination {
+    #[must_use]
+    pub fn new(name: &str, adapter: Arc<dyn EventLoggingAdapter>) -> Self {
+        Self {
+            name: name.to_string(),
+            adapter,
+            filter: EventDestinationFilter::all(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_backoff_ms: DEFAULT_RETRY_BACKOFF_MS,
+        }
+    }
+
+    #[must_use]
+    pub fn with_filter(mut self, filter: EventDestinationFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    #[must_use]
+    pub fn with_retry_backoff_ms(mut self, retry_backoff_ms: u64) -> Self {
+        self.retry_backoff_ms = retry_backoff_ms;
+        self
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventDestinationStats {
+    pub name: String,
+    pub requests_succeeded: u64,
+    pub requests_failed: u64,
+    pub retries: u64,
+    pub events_sent: u64,
+    pub events_dropped: u64,
+    pub events_filtered: u64,
+    pub last_error: Option<String>,
+}
+
+enum DeliveryOutcome {
+    Skipped,
+    Delivered,
+    RetryScheduled,
+    Failed(StatsigErr),
+}
+
+enum AttemptResult {
+    Skipped,
+    Delivered,
+    Retryable(StatsigErr),
+    Failed(StatsigErr),
+}
+
+/// The events of a shared request that a destination accepts. The request is only copied
+/// into the destination's own request when it is sent.
+struct DestinationRequest {
+    request: Arc<LogEventRequest>,
+    /// Indexes of the accepted events, or `None` when every event is accepted.
+    event_indexes: Option<Vec<usize>>,
+    event_count: u64,
+}
+
+impl DestinationRequest {
+    fn new(filter: &EventDestinationFilter, request: &Arc<LogEventRequest>) -> Option<Self> {
+        if filter.accepts_all() {
+            return Some(Self {
+                request: request.clone(),
+                event_indexes: None,
+                event_count: request.event_count,
+            });
+        }
+
+        let events = request.payload.events.as_array()?;
+        let event_indexes: Vec<usize> = events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| {
+                let name = event
+                    .get("eventName")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                filter.accepts(name)
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        if event_indexes.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            request: request.clone(),
+            event_count: event_indexes.len() as u64,
+            event_indexes: Some(event_indexes),
+        })
+    }
+
+    fn build(&self) -> LogEventRequest {
+        let indexes = match &self.event_indexes {
+            Some(indexes) => indexes,
+            None => return self.request.as_ref().clone(),
+        };
+
+        let events = self
+            .request
+            .payload
+            .events
+            .as_array()
+            .map(|events| {
+                indexes
+                    .iter()
+                    .filter_map(|i| events.get(*i).cloned())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        LogEventRequest {
+            event_count: self.event_count,
+            payload: LogEventPayload {
+                events: Value::Array(events),
+                statsig_metadata: self.request.payload.statsig_metadata.clone(),
+            },
+            retries: self.request.retries,
+        }
+    }
+}
+
+struct DestinationState {
+    destination: EventDestination,
+    stats: Mutex<EventDestinationStats>,
+    ops_stats: Option<Arc<OpsStatsForInstance>>,
+}
+
+impl DestinationState {
+    async fn attempt(&self, request: &DestinationRequest, attempt: u32) -> AttemptResult {
+        let result = self.destination.adapter.log_events(request.build()).await;
+
+        let error = match result {
+            Ok(true) => {
+                self.record_success(request.event_count);
+                return AttemptResult::Delivered;
+            }
+            Err(StatsigErr::NetworkError(NetworkError::DisableNetworkOn(_))) => {
+                return AttemptResult::Skipped;
+            }
+            Ok(false) => StatsigErr::LogEventError("Unknown Failure".into()),
+            Err(e) => e,
+        };
+
+        let is_non_retryable = matches!(
+            error,
+            StatsigErr::NetworkError(NetworkError::RequestNotRetryable(_, _, _))
+        );
+
+        if is_non_retryable || attempt >= self.destination.max_retries {
+            self.record_failure(request.event_count, &error);
+            return AttemptResult::Failed(error);
+        }
+
+        AttemptResult::Retryable(error)
+    }
+
+    async fn retry(&self, request: DestinationRequest) {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            self.stats.lock().retries += 1;
+
+            let backoff = self.destination.retry_backoff_ms * 2u64.pow(attempt - 1);
+            tokio::time::sleep(Duration::from_millis(backoff)).await;
+
+            match self.attempt(&request, attempt).await {
+                AttemptResult::Retryable(error) => {
+                    log_d!(
+                        TAG,
+                        "Retrying destination '{}' (attempt {}): {}",
+                        self.destination.name,
+                        attempt + 1,
+                        error
+                    );
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn record_success(&self, event_count: u64) {
+        {
+            let mut stats = self.stats.lock();
+            stats.requests_succeeded += 1;
+            stats.events_sent += event_count;
+        }
+
+        self.log_metric("event_destination_events_sent_count", event_count);
+    }
+
+    fn record_failure(&self, event_count: u64, error: &StatsigErr) {
+        {
+            let mut stats = self.stats.lock();
+            stats.requests_failed += 1;
+            stats.events_dropped += event_count;
+            stats.last_error = Some(error.to_string());
+        }
+
+        log_w!(
+            TAG,
+            "Destination '{}' failed. {} Event(s) will be dropped. {}",
+            self.destination.name,
+            event_count,
+            error
+        );
+
+        self.log_metric("event_destination_events_dropped_count", event_count);
+    }
+
+    fn log_metric(&self, metric_name: &str, value: u64) {
+        let ops_stats = match &self.ops_stats {
+            Some(ops_stats) => ops_stats,
+            None => return,
+        };
+
+        ops_stats.log(ObservabilityEvent::new_event(
+            MetricType::Increment,
+            metric_name.to_string(),
+            value as f64,
+            Some(HashMap::from([(
+                "destination".to_string(),
+                self.destination.name.clone(),
+            )])),
+        ));
+    }
+}
+
+/// Fans a single [`LogEventRequest`] out to several [`EventLoggingAdapter`]s.
+///
+/// Events are filtered per destination by inspecting the already built payload, which is
+/// shared between destinations and never re-serialized. Each destination gets one attempt on
+/// the flush path; its retries run in the background, and `shutdown` waits for them. The
+/// composite only reports a failure back to the event logger when no destination delivered
+/// or is still retrying, which keeps the logger's own retries from duplicating events on
+/// destinations that already succeeded. Events a destination drops are counted in
+/// [`EventDestinationStats::events_dropped`] and logged as a warning.
+pub struct StatsigCompositeEventLoggingAdapter {
+    destinations: Vec<Arc<DestinationState>>,
+    statsig_runtime: Mutex<Option<Arc<StatsigRuntime>>>,
+}
+
+impl StatsigCompositeEventLoggingAdapter {
+    /// When `sdk_key` is provided, per-destination metrics are also forwarded to the
+    /// `ObservabilityClient` of the Statsig instance using that key.
+    #[must_use]
+    pub fn new(sdk_key: Option<&str>, destinations: Vec<EventDestination>) -> Self {
+        let ops_stats = sdk_key.map(|key| OPS_STATS.get_for_instance(key));
+
+        Self {
+            destinations: destinations
+                .into_iter()
+                .map(|destination| {
+                    Arc::new(DestinationState {
+                        stats: Mutex::new(EventDestinationStats {
+                            name: destination.name.clone(),
+                            ..EventDestinationStats::default()
+                        }),
+                        destination,
+                        ops_stats: ops_stats.clone(),
+                    })
+                })
+                .collect(),
+            statsig_runtime: Mutex::new(None),
+        }
+    }
+
+    pub fn destination_stats(&self) -> Vec<EventDestinationStats> {
+        self.destinations
+            .iter()
+            .map(|d| d.stats.lock().clone())
+            .collect()
+    }
+
+    async fn deliver(
+        &self,
+        state: &Arc<DestinationState>,
+        request: &Arc<LogEventRequest>,
+    ) -> DeliveryOutcome {
+        let total_count = request.event_count;
+
+        let request = match DestinationRequest::new(&state.destination.filter, request) {
+            Some(request) => request,
+            None => {
+                state.stats.lock().events_filtered += total_count;
+                return DeliveryOutcome::Skipped;
+            }
+        };
+
+        state.stats.lock().events_filtered += total_count.saturating_sub(request.event_count);
+
+        match state.attempt(&request, 0).await {
+            AttemptResult::Delivered => DeliveryOutcome::Delivered,
+            AttemptResult::Skipped => DeliveryOutcome::Skipped,
+            AttemptResult::Failed(error) => DeliveryOutcome::Failed(error),
+            AttemptResult::Retryable(error) => self.schedule_retry(state, request, error),
+        }
+    }
+
+    fn schedule_retry(
+        &self,
+        state: &Arc<DestinationState>,
+        request: DestinationRequest,
+        error: StatsigErr,
+    ) -> DeliveryOutcome {
+        log_d!(
+            TAG,
+            "Retrying destination '{}' in the background: {}",
+            state.destination.name,
+            error
+        );
+
+        let statsig_runtime = match self.statsig_runtime.lock().clone() {
+            Some(statsig_runtime) => statsig_runtime,
+            None => {
+                state.record_failure(request.event_count, &error);
+                return DeliveryOutcome::Failed(error);
+            }
+        };
+
+        let event_count = request.event_count;
+        let retrying_state = state.clone();
+        let spawn_result = statsig_runtime.spawn(RETRY_TAG, move |_| async move {
+            retrying_state.retry(request).await;
+        });
+
+        match spawn_result {
+            Ok(_) => DeliveryOutcome::RetryScheduled,
+            Err(e) => {
+                state.record_failure(event_count, &e);
+                DeliveryOutcome::Failed(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventLoggingAdapter for StatsigCompositeEventLoggingAdapter {
+    async fn start(&self, statsig_runtime: &Arc<StatsigRuntime>) -> Result<(), StatsigErr> {
+        *self.statsig_runtime.lock() = Some(statsig_runtime.clone());
+
+        let results = futures::future::join_all(
+            self.destinations
+                .iter()
+                .map(|d| d.destination.adapter.start(statsig_runtime)),
+        )
+        .await;
+
+        results.into_iter().collect::<Result<Vec<_>, _>>()?;
+        Ok(())
+    }
+
+    async fn log_events(&self, request: LogEventRequest) -> Result<bool, StatsigErr> {
+        let request = Arc::new(request);
+        let outcomes =
+            futures::future::join_all(self.destinations.iter().map(|d| self.deliver(d, &request)))
+                .await;
+
+        let mut last_error = None;
+        for outcome in outcomes {
+            match outcome {
+                DeliveryOutcome::Delivered | DeliveryOutcome::RetryScheduled => return Ok(true),
+                DeliveryOutcome::Failed(e) => last_error = Some(e),
+                DeliveryOutcome::Skipped => {}
+            }
+        }
+
+        match last_error {
+            Some(e) => Err(e),
+            None => Ok(true),
+        }
+    }
+
+    async fn shutdown(&self) -> Result<(), StatsigErr> {
+        let statsig_runtime = self.statsig_runtime.lock().clone();
+        if let Some(statsig_runtime) = statsig_runtime {
+            statsig_runtime.await_tasks_with_tag(RETRY_TAG).await;
+        }
+
+        let results = futures::future::join_all(
+            self.destinations
+                .iter()
+                .map(|d| d.destination.adapter.shutdown()),
+        )
+        .await;
+
+        results.into_iter().collect::<Result<Vec<_>, _>>()?;
+        Ok(())
+    }
+
+    fn should_schedule_background_flush(&self) -> bool {
+        self.destinations
+            .iter()
+            .any(|d| d.destination.adapter.should_schedule_background_flush())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::event_logging_adapter::EventLoggingAdapter;
+use crate::log_event_payload::{LogEventPayload, LogEventRequest};
+use crate::{log_d, SpecAdapterConfig, StatsigErr, StatsigRuntime};
+use async_trait::async_trait;
+use serde_json::Value;
+use sigstat_grpc::statsig_forward_proxy::LogEventRequest as GrpcLogEventRequest;
+use sigstat_grpc::statsig_grpc_client::StatsigGrpcClient;
+use std::sync::Arc;
+
+pub const DEFAULT_GRPC_LOG_EVENT_CHUNK_SIZE: usize = 500;
+
+const TAG: &str = stringify!(StatsigGrpcEventLoggingAdapter);
+
+/// Sends log_event payloads through a forward proxy over the client-streaming `LogEvents` RPC.
+///
+/// Each flush is split into chunks of at most `chunk_size` events, which are streamed to the
+/// proxy as separate `LogEventRequest` batches on a single call.
+pub struct StatsigGrpcEventLoggingAdapter {
+    grpc_client: StatsigGrpcClient,
+    chunk_size: usize,
+}
+
+impl StatsigGrpcEventLoggingAdapter {
+    /// `config.specs_url` is the address of the forward proxy, the same one used by
+    /// `StatsigGrpcSpecsAdapter`.
+    #[must_use]
+    pub fn new(sdk_key: &str, config: &SpecAdapterConfig) -> Self {
+        Self {
+            grpc_client: StatsigGrpcClient::new(
+                sdk_key,
+                &config.specs_url.clone().unwrap_or("INVALID".to_owned()),
+                config.authentication_mode.clone(),
+                config.ca_cert_path.clone(),
+                config.client_cert_path.clone(),
+                config.client_key_path.clone(),
+                config.domain_name.clone(),
+            ),
+            chunk_size: DEFAULT_GRPC_LOG_EVENT_CHUNK_SIZE,
+        }
+    }
+
+    #[must_use]
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    fn to_grpc_batches(
+        &self,
+        request: &LogEventRequest,
+    ) -> Result<Vec<GrpcLogEventRequest>, StatsigErr> {
+        let events = match &request.payload.events {
+            Value::Array(events) => events.as_slice(),
+            other => std::slice::from_ref(other),
+        };
+
+        events
+            .chunks(self.chunk_size)
+            .map(|chunk| {
+                let payload = LogEventPayload {
+                    events: Value::Array(chunk.to_vec()),
+                    statsig_metadata: request.payload.statsig_metadata.clone(),
+                };
+
+                let payload = serde_json::to_string(&payload)
+                    .map_err(|e| StatsigErr::SerializationError(e.to_string()))?;
+
+                Ok(GrpcLogEventRequest {
+                    // filled in by the client
+                    sdk_key: String::new(),
+                    payload,
+                    event_count: chunk.len() as u64,
+                    retries: request.retries,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventLoggingAdapter for StatsigGrpcEventLoggingAdapter {
+    async fn start(&self, _statsig_runtime: &Arc<StatsigRuntime>) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
+    async fn log_events(&self, request: LogEventRequest) -> Result<bool, StatsigErr> {
+        let batches = self.to_grpc_batches(&request)?;
+        if batches.is_empty() {
+            return Ok(true);
+        }
+
+        log_d!(
+            TAG,
+            "Logging Events ({}) in {} batch(es)",
+            request.event_count,
+            batches.len()
+        );
+
+        let response = self
+            .grpc_client
+            .log_events(batches)
+            .await
+            .map_err(|e| StatsigErr::GrpcError(e.to_string()))?;
+
+        if response.success {
+            Ok(true)
+        } else {
+            Err(StatsigErr::LogEventError(
+                "Unsuccessful response from forward proxy".into(),
+            ))
+        }
+    }
+
+    async fn shutdown(&self) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
+    fn should_schedule_background_flush(&self) -> bool {
+        true
+    }
+}
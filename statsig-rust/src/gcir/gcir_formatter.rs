@@ -66,7 +66,7 @@ impl GCIRFormatter {
         let layers = get_layer_evaluations(context, options, &mut sec_expo_hash_memo)?;
 
         let param_stores = get_serializeable_param_stores(context, options);
-        let evaluated_keys = EvaluatedKeys::from_user(context.user.user_ref);
+        let evaluated_keys = EvaluatedKeys::from_user(&context.user.user_ref);
         let session_replay_info = get_session_replay_info(context, options);
 
         let mut full_response_hash: Option<String> = None;
@@ -113,7 +113,7 @@ impl GCIRFormatter {
         let mut exposures = HashMap::new();
 
         let param_stores = get_serializeable_param_stores(context, options);
-        let evaluated_keys = EvaluatedKeys::from_user(context.user.user_ref);
+        let evaluated_keys = EvaluatedKeys::from_user(&context.user.user_ref);
         let session_replay_info = get_session_replay_info(context, options);
 
         Ok(InitializeEvaluationsResponse {
@@ -167,7 +167,7 @@ impl GCIRFormatter {
         let mut exposure_map = AHashMap::new();
         let mut exposures = HashMap::new();
         let param_stores = get_serializeable_param_stores(context, options);
-        let evaluated_keys = EvaluatedKeys::from_user(context.user.user_ref);
+        let evaluated_keys = EvaluatedKeys::from_user(&context.user.user_ref);
         let session_replay_info = get_session_replay_info(context, options);
 
         Ok(InitializeV2Response {
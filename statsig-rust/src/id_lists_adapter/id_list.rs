@@ -1,27 +1,30 @@
 use serde::Serialize;
 
 use crate::{
-    id_lists_adapter::{IdListMetadata, IdListUpdate},
+    id_lists_adapter::{IdListMetadata, IdListStorage, IdListStorageMode, IdListUpdate},
     unwrap_or_noop,
 };
-use std::collections::HashSet;
 
 #[derive(Clone, Serialize)]
 pub struct IdList {
     pub metadata: IdListMetadata,
 
     #[serde(skip_serializing)]
-    pub ids: HashSet<String>,
+    pub ids: IdListStorage,
 }
 
 impl IdList {
     pub fn new(metadata: IdListMetadata) -> Self {
+        Self::with_storage(metadata, &IdListStorageMode::HashSet)
+    }
+
+    pub fn with_storage(metadata: IdListMetadata, mode: &IdListStorageMode) -> Self {
         let mut local_metadata = metadata;
         local_metadata.size = 0;
 
         Self {
             metadata: local_metadata,
-            ids: HashSet::new(),
+            ids: IdListStorage::new(mode),
         }
     }
 
@@ -37,25 +40,7 @@ impl IdList {
 
         let changeset_data = unwrap_or_noop!(&update.raw_changeset);
 
-        for change in changeset_data.lines() {
-            let trimmed = change.trim();
-            if trimmed.len() <= 1 {
-                continue;
-            }
-
-            let op = change.chars().next();
-            let id = &change[1..];
-
-            match op {
-                Some('+') => {
-                    self.ids.insert(id.to_string());
-                }
-                Some('-') => {
-                    self.ids.remove(id);
-                }
-                _ => continue,
-            }
-        }
+        self.ids.apply_changeset(changeset_data);
 
         self.metadata.size += changeset_data.len() as u64;
     }
@@ -0,0 +1,750 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufWriter, Write},
+    iter::Peekable,
+    path::PathBuf,
+    sync::Arc,
+};
+
+use ahash::RandomState;
+use memmap2::Mmap;
+use ouroboros::self_referencing;
+use rkyv::{
+    munge::munge,
+    rancor::{Fallible, Source},
+    ser::{writer::IoWriter, Allocator, Writer},
+    string::{ArchivedString, StringResolver},
+    vec::ArchivedVec,
+    Archive, Archived, Place, Serialize as RkyvSerialize, SerializeUnsized,
+};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::{log_d, log_e, StatsigErr};
+
+const TAG: &str = "IdListStorage";
+
+const MIN_BLOOM_FALSE_POSITIVE_RATE: f64 = 1e-12;
+
+/// How the IDs of an ID list are held. Lookup IDs are 8 character hashes, which the
+/// `Compact`, `Mmap` and `Bloom` modes store without a per-ID allocation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum IdListStorageMode {
+    /// Each ID is a `String` in a `HashSet`. Largest in memory, cheapest to update.
+    #[default]
+    HashSet,
+
+    /// IDs packed into a sorted array of integers (8 bytes per ID) and looked up with a
+    /// binary search. Each update rebuilds the array.
+    Compact,
+
+    /// The `Compact` layout written to a temporary file in `directory` and memory mapped,
+    /// so the OS can page it out. The file is removed once the list is replaced or dropped.
+    Mmap { directory: String },
+
+    /// Approximate membership. IDs in the list always match, while IDs outside of it match
+    /// at up to `false_positive_rate` (e.g. `0.001`). Removed IDs are tracked exactly.
+    Bloom { false_positive_rate: f64 },
+}
+
+/// Selects an [`IdListStorageMode`] for each ID list, by list name.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdListStorageConfig {
+    pub default_mode: IdListStorageMode,
+    #[serde(default)]
+    pub per_list: HashMap<String, IdListStorageMode>,
+}
+
+impl IdListStorageConfig {
+    #[must_use]
+    pub fn new(default_mode: IdListStorageMode) -> Self {
+        Self {
+            default_mode,
+            per_list: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_list(mut self, list_name: impl Into<String>, mode: IdListStorageMode) -> Self {
+        self.per_list.insert(list_name.into(), mode);
+        self
+    }
+
+    pub fn mode_for(&self, list_name: &str) -> &IdListStorageMode {
+        self.per_list.get(list_name).unwrap_or(&self.default_mode)
+    }
+}
+
+/// The IDs of a single ID list, held according to its [`IdListStorageMode`].
+#[derive(Clone, Default)]
+pub struct IdListStorage {
+    inner: StorageInner,
+}
+
+#[derive(Clone)]
+enum StorageInner {
+    HashSet(HashSet<String>),
+    Compact(CompactIdSet),
+    Mmap(MmapIdSet),
+    Bloom(BloomIdSet),
+}
+
+impl Default for StorageInner {
+    fn default() -> Self {
+        StorageInner::HashSet(HashSet::new())
+    }
+}
+
+impl IdListStorage {
+    pub fn new(mode: &IdListStorageMode) -> Self {
+        let inner = match mode {
+            IdListStorageMode::HashSet => StorageInner::HashSet(HashSet::new()),
+            IdListStorageMode::Compact => StorageInner::Compact(CompactIdSet::default()),
+            IdListStorageMode::Mmap { directory } => {
+                StorageInner::Mmap(MmapIdSet::new(PathBuf::from(directory)))
+            }
+            IdListStorageMode::Bloom {
+                false_positive_rate,
+            } => StorageInner::Bloom(BloomIdSet::new(*false_positive_rate)),
+        };
+
+        Self { inner }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        match &self.inner {
+            StorageInner::HashSet(ids) => ids.contains(id),
+            StorageInner::Compact(ids) => ids.contains(id),
+            StorageInner::Mmap(ids) => ids.contains(id),
+            StorageInner::Bloom(ids) => ids.contains(id),
+        }
+    }
+
+    /// Number of IDs held. Approximate for `Bloom` storage.
+    pub fn len(&self) -> usize {
+        match &self.inner {
+            StorageInner::HashSet(ids) => ids.len(),
+            StorageInner::Compact(ids) => ids.len(),
+            StorageInner::Mmap(ids) => ids.len(),
+            StorageInner::Bloom(ids) => ids.len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn clear(&mut self) {
+        match &mut self.inner {
+            StorageInner::HashSet(ids) => ids.clear(),
+            StorageInner::Compact(ids) => *ids = CompactIdSet::default(),
+            StorageInner::Mmap(ids) => ids.loaded = None,
+            StorageInner::Bloom(ids) => *ids = BloomIdSet::new(ids.false_positive_rate),
+        }
+    }
+
+    pub(crate) fn apply_changeset(&mut self, raw_changeset: &str) {
+        let changeset = Changeset::parse(raw_changeset);
+
+        match &mut self.inner {
+            StorageInner::HashSet(ids) => {
+                for id in changeset.removes() {
+                    ids.remove(id);
+                }
+                ids.extend(changeset.adds().map(str::to_string));
+            }
+            StorageInner::Compact(ids) => ids.apply(&changeset),
+            StorageInner::Mmap(ids) => {
+                if let Err(e) = ids.apply(&changeset) {
+                    log_e!(
+                        TAG,
+                        "Failed to write mmap ID list, keeping it in memory: {}",
+                        e
+                    );
+                    let mut updated = ids.to_compact();
+                    updated.apply(&changeset);
+                    self.inner = StorageInner::Compact(updated);
+                }
+            }
+            StorageInner::Bloom(ids) => ids.apply(&changeset),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------- [ Changeset ]
+
+/// A raw `+id`/`-id` changeset, resolved so that the last operation on an ID wins. Only
+/// removed IDs are indexed, since changesets are mostly additions.
+struct Changeset<'a> {
+    raw: &'a str,
+    // id -> (line of the last removal, added again after that line)
+    removed: HashMap<&'a str, (usize, bool)>,
+}
+
+impl<'a> Changeset<'a> {
+    fn parse(raw: &'a str) -> Self {
+        let mut removed = HashMap::new();
+        for (line, is_add, id) in parse_lines(raw) {
+            if !is_add {
+                removed.insert(id, (line, false));
+            }
+        }
+
+        if !removed.is_empty() {
+            for (line, is_add, id) in parse_lines(raw) {
+                match removed.get_mut(id) {
+                    Some((removed_at, readded)) if is_add && line > *removed_at => *readded = true,
+                    _ => {}
+                }
+            }
+        }
+
+        Self { raw, removed }
+    }
+
+    fn adds(&self) -> impl Iterator<Item = &'a str> + '_ {
+        parse_lines(self.raw).filter_map(|(line, is_add, id)| {
+            if !is_add {
+                return None;
+            }
+
+            match self.removed.get(id) {
+                Some((removed_at, _)) if line < *removed_at => None,
+                _ => Some(id),
+            }
+        })
+    }
+
+    fn removes(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.removed
+            .iter()
+            .filter(|(_, (_, readded))| !*readded)
+            .map(|(id, _)| *id)
+    }
+}
+
+fn parse_lines(raw: &str) -> impl Iterator<Item = (usize, bool, &str)> {
+    raw.lines().enumerate().filter_map(|(line, change)| {
+        if change.trim().len() <= 1 {
+            return None;
+        }
+
+        let id = &change[1..];
+        match change.chars().next() {
+            Some('+') => Some((line, true, id)),
+            Some('-') => Some((line, false, id)),
+            _ => None,
+        }
+    })
+}
+
+// ------------------------------------------------------------------------------- [ Compact ]
+
+#[derive(Clone, Default, Archive, RkyvSerialize)]
+struct CompactIdSet {
+    // 8 byte IDs as big endian integers, sorted
+    packed: Vec<u64>,
+    // any ID of a different length, sorted
+    other: Vec<String>,
+}
+
+fn pack_id(id: &str) -> Option<u64> {
+    let bytes: [u8; 8] = id.as_bytes().try_into().ok()?;
+    Some(u64::from_be_bytes(bytes))
+}
+
+impl CompactIdSet {
+    fn contains(&self, id: &str) -> bool {
+        match pack_id(id) {
+            Some(packed) => self.packed.binary_search(&packed).is_ok(),
+            None => self
+                .other
+                .binary_search_by(|other| other.as_str().cmp(id))
+                .is_ok(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.packed.len() + self.other.len()
+    }
+
+    fn apply(&mut self, changeset: &Changeset) {
+        let changes = SplitChanges::new(changeset);
+        merge_sorted(
+            &mut self.packed,
+            changes.packed_adds,
+            changes.packed_removes,
+        );
+        merge_sorted(
+            &mut self.other,
+            changes.other_adds.into_iter().map(str::to_string).collect(),
+            changes
+                .other_removes
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        );
+    }
+}
+
+/// The adds and removes of a changeset, split by whether the ID packs into an integer.
+#[derive(Default)]
+struct SplitChanges<'a> {
+    packed_adds: Vec<u64>,
+    packed_removes: Vec<u64>,
+    other_adds: Vec<&'a str>,
+    other_removes: Vec<&'a str>,
+}
+
+impl<'a> SplitChanges<'a> {
+    fn new(changeset: &Changeset<'a>) -> Self {
+        let mut changes = Self::default();
+        for id in changeset.adds() {
+            match pack_id(id) {
+                Some(packed) => changes.packed_adds.push(packed),
+                None => changes.other_adds.push(id),
+            }
+        }
+        for id in changeset.removes() {
+            match pack_id(id) {
+                Some(packed) => changes.packed_removes.push(packed),
+                None => changes.other_removes.push(id),
+            }
+        }
+        changes
+    }
+
+    fn sort(&mut self) {
+        self.packed_adds.sort_unstable();
+        self.packed_adds.dedup();
+        self.packed_removes.sort_unstable();
+        self.other_adds.sort_unstable();
+        self.other_adds.dedup();
+        self.other_removes.sort_unstable();
+    }
+}
+
+impl ArchivedCompactIdSet {
+    fn contains(&self, id: &str) -> bool {
+        match pack_id(id) {
+            Some(packed) => self
+                .packed
+                .binary_search_by(|v| v.to_native().cmp(&packed))
+                .is_ok(),
+            None => self
+                .other
+                .binary_search_by(|other| other.as_str().cmp(id))
+                .is_ok(),
+        }
+    }
+
+    fn to_compact(&self) -> CompactIdSet {
+        CompactIdSet {
+            packed: self.packed.iter().map(|v| v.to_native()).collect(),
+            other: self.other.iter().map(|v| v.as_str().to_string()).collect(),
+        }
+    }
+}
+
+/// Merges `adds` into the sorted `current` in a single pass, then drops `removes`.
+fn merge_sorted<T: Ord>(current: &mut Vec<T>, mut adds: Vec<T>, mut removes: Vec<T>) {
+    if adds.is_empty() && removes.is_empty() {
+        return;
+    }
+
+    adds.sort_unstable();
+    adds.dedup();
+    removes.sort_unstable();
+
+    let mut merged = Vec::with_capacity(current.len() + adds.len());
+    let mut adds = adds.into_iter().peekable();
+    for id in current.drain(..) {
+        while let Some(add) = adds.next_if(|add| *add < id) {
+            merged.push(add);
+        }
+        if adds.peek() == Some(&id) {
+            adds.next();
+        }
+        merged.push(id);
+    }
+    merged.extend(adds);
+
+    if !removes.is_empty() {
+        merged.retain(|id| removes.binary_search(id).is_err());
+    }
+
+    merged.shrink_to_fit();
+    *current = merged;
+}
+
+// ------------------------------------------------------------------------------- [ Mmap ]
+
+#[self_referencing]
+struct LoadedIdListMmap {
+    // deletes the file once the mapping is dropped
+    file: NamedTempFile,
+    mmap: Mmap,
+
+    #[borrows(mmap)]
+    archived: &'this ArchivedCompactIdSet,
+}
+
+#[derive(Clone)]
+struct MmapIdSet {
+    directory: PathBuf,
+    loaded: Option<Arc<LoadedIdListMmap>>,
+}
+
+impl MmapIdSet {
+    fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            loaded: None,
+        }
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.loaded
+            .as_ref()
+            .is_some_and(|loaded| loaded.borrow_archived().contains(id))
+    }
+
+    fn len(&self) -> usize {
+        self.loaded.as_ref().map_or(0, |loaded| {
+            let archived = loaded.borrow_archived();
+            archived.packed.len() + archived.other.len()
+        })
+    }
+
+    fn to_compact(&self) -> CompactIdSet {
+        self.loaded
+            .as_ref()
+            .map(|loaded| loaded.borrow_archived().to_compact())
+            .unwrap_or_default()
+    }
+
+    /// Writes the current IDs with `changeset` applied to a new file and maps it. The IDs
+    /// are merged straight from the mapped file, so the list is never copied to the heap.
+    fn apply(&mut self, changeset: &Changeset) -> Result<(), StatsigErr> {
+        let mut changes = SplitChanges::new(changeset);
+        changes.sort();
+
+        let current = self.loaded.clone();
+        let (packed, other): (&[Archived<u64>], &[ArchivedString]) = match &current {
+            Some(loaded) => {
+                let archived = loaded.borrow_archived();
+                (&archived.packed, &archived.other)
+            }
+            None => (&[], &[]),
+        };
+
+        let merged = MergedIdSet::new(
+            MergeSorted::new(
+                packed.iter().map(|v| v.to_native()),
+                &changes.packed_adds,
+                &changes.packed_removes,
+            ),
+            MergeSorted::new(
+                other.iter().map(ArchivedString::as_str),
+                &changes.other_adds,
+                &changes.other_removes,
+            ),
+        );
+
+        let mut file = NamedTempFile::with_prefix_in("statsig_id_list_", &self.directory)
+            .map_err(|e| StatsigErr::FileError(e.to_string()))?;
+        let mut writer = rkyv::api::high::to_bytes_in::<_, rkyv::rancor::Error>(
+            &merged,
+            IoWriter::new(BufWriter::new(file.as_file_mut())),
+        )
+        .map_err(|e| StatsigErr::SerializationError(e.to_string()))?
+        .into_inner();
+        writer
+            .flush()
+            .map_err(|e| StatsigErr::FileError(e.to_string()))?;
+        drop(writer);
+        drop(current);
+
+        let mmap = map_file(file.as_file())?;
+        let mapped_len = mmap.len();
+        let loaded = LoadedIdListMmapTryBuilder {
+            file,
+            mmap,
+            archived_builder: |mmap| {
+                rkyv::access::<ArchivedCompactIdSet, rkyv::rancor::Error>(mmap)
+            },
+        }
+        .try_build()
+        .map_err(|e| StatsigErr::SerializationError(e.to_string()))?;
+
+        log_d!(TAG, "Mapped {} bytes of ID list data", mapped_len);
+
+        self.loaded = Some(Arc::new(loaded));
+        Ok(())
+    }
+}
+
+/// Yields the sorted union of `current` and the sorted, deduplicated `adds`, without
+/// `removes`.
+#[derive(Clone)]
+struct MergeSorted<'a, T: Copy, I: Iterator<Item = T>> {
+    current: Peekable<I>,
+    adds: Peekable<std::iter::Copied<std::slice::Iter<'a, T>>>,
+    removes: &'a [T],
+}
+
+impl<'a, T: Ord + Copy, I: Iterator<Item = T> + Clone> MergeSorted<'a, T, I> {
+    fn new(current: I, adds: &'a [T], removes: &'a [T]) -> Self {
+        Self {
+            current: current.peekable(),
+            adds: adds.iter().copied().peekable(),
+            removes,
+        }
+    }
+}
+
+impl<T: Ord + Copy, I: Iterator<Item = T>> Iterator for MergeSorted<'_, T, I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let next = match (self.current.peek(), self.adds.peek()) {
+                (Some(current), Some(add)) if add < current => self.adds.next(),
+                (Some(current), Some(add)) if add == current => {
+                    self.adds.next();
+                    self.current.next()
+                }
+                (Some(_), _) => self.current.next(),
+                (None, _) => self.adds.next(),
+            }?;
+
+            if self.removes.binary_search(&next).is_err() {
+                return Some(next);
+            }
+        }
+    }
+}
+
+/// Serializes as an [`ArchivedCompactIdSet`], reading the IDs from merge iterators.
+struct MergedIdSet<P, O> {
+    packed: P,
+    packed_len: usize,
+    other: O,
+    other_len: usize,
+}
+
+impl<P: Iterator<Item = u64> + Clone, O: Iterator + Clone> MergedIdSet<P, O> {
+    fn new(packed: P, other: O) -> Self {
+        Self {
+            packed_len: packed.clone().count(),
+            packed,
+            other_len: other.clone().count(),
+            other,
+        }
+    }
+}
+
+impl<P, O> Archive for MergedIdSet<P, O> {
+    type Archived = ArchivedCompactIdSet;
+    type Resolver = CompactIdSetResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedCompactIdSet { packed, other } = out);
+        ArchivedVec::resolve_from_len(self.packed_len, resolver.packed, packed);
+        ArchivedVec::resolve_from_len(self.other_len, resolver.other, other);
+    }
+}
+
+impl<'a, P, O, S> RkyvSerialize<S> for MergedIdSet<P, O>
+where
+    P: Iterator<Item = u64> + Clone,
+    O: Iterator<Item = &'a str> + Clone,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+    str: SerializeUnsized<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let other = ExactLen {
+            iter: self.other.clone().map(StrRef),
+            remaining: self.other_len,
+        };
+
+        Ok(CompactIdSetResolver {
+            packed: ArchivedVec::serialize_from_unknown_length_iter(
+                &mut self.packed.clone(),
+                serializer,
+            )?,
+            other: ArchivedVec::serialize_from_iter::<StrRef, _, _>(other, serializer)?,
+        })
+    }
+}
+
+/// An iterator of a length counted upfront.
+#[derive(Clone)]
+struct ExactLen<I> {
+    iter: I,
+    remaining: usize,
+}
+
+impl<I: Iterator> Iterator for ExactLen<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let next = self.iter.next()?;
+        self.remaining -= 1;
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for ExactLen<I> {}
+
+/// Serializes a borrowed `str` as an [`ArchivedString`].
+struct StrRef<'a>(&'a str);
+
+impl Archive for StrRef<'_> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(self.0, resolver, out);
+    }
+}
+
+impl<S> RkyvSerialize<S> for StrRef<'_>
+where
+    S: Fallible + ?Sized,
+    S::Error: Source,
+    str: SerializeUnsized<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.0, serializer)
+    }
+}
+
+fn map_file(file: &File) -> Result<Mmap, StatsigErr> {
+    // Safety: the file is a private temp file that is never written to after this point
+    unsafe { Mmap::map(file) }.map_err(|e| StatsigErr::FileError(e.to_string()))
+}
+
+// ------------------------------------------------------------------------------- [ Bloom ]
+
+/// A scalable Bloom filter. Each update that adds new IDs appends a filter sized for those
+/// IDs, with a false positive rate half that of the previous filter, so the combined rate
+/// stays below the configured one.
+#[derive(Clone)]
+struct BloomIdSet {
+    false_positive_rate: f64,
+    filters: Vec<BloomFilter>,
+    removed: HashSet<String>,
+    len: usize,
+}
+
+impl BloomIdSet {
+    fn new(false_positive_rate: f64) -> Self {
+        Self {
+            false_positive_rate,
+            filters: Vec::new(),
+            removed: HashSet::new(),
+            len: 0,
+        }
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.maybe_in_filters(id) && !self.removed.contains(id)
+    }
+
+    fn maybe_in_filters(&self, id: &str) -> bool {
+        let hashes = BloomHashes::new(id);
+        self.filters.iter().any(|f| f.contains(&hashes))
+    }
+
+    fn apply(&mut self, changeset: &Changeset) {
+        for id in changeset.removes() {
+            if self.contains(id) {
+                self.removed.insert(id.to_string());
+                self.len = self.len.saturating_sub(1);
+            }
+        }
+
+        let mut new_ids = Vec::new();
+        for id in changeset.adds() {
+            if self.removed.remove(id) {
+                self.len += 1;
+            } else if !self.maybe_in_filters(id) {
+                new_ids.push(id);
+            }
+        }
+
+        if new_ids.is_empty() {
+            return;
+        }
+
+        let rate = self.false_positive_rate / 2f64.powi(self.filters.len() as i32 + 1);
+        let mut filter = BloomFilter::new(new_ids.len(), rate.max(MIN_BLOOM_FALSE_POSITIVE_RATE));
+        for id in &new_ids {
+            filter.insert(&BloomHashes::new(id));
+        }
+
+        self.len += new_ids.len();
+        self.filters.push(filter);
+    }
+}
+
+#[derive(Clone)]
+struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let ln2 = std::f64::consts::LN_2;
+        let rate = false_positive_rate.clamp(MIN_BLOOM_FALSE_POSITIVE_RATE, 0.5);
+        let items = expected_items.max(1) as f64;
+
+        let num_bits = (-items * rate.ln() / (ln2 * ln2)).ceil().max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / items) * ln2).round().max(1.0) as u32;
+
+        Self {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    fn insert(&mut self, hashes: &BloomHashes) {
+        for i in 0..self.num_hashes {
+            let bit = hashes.nth(i) % self.num_bits;
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    fn contains(&self, hashes: &BloomHashes) -> bool {
+        (0..self.num_hashes).all(|i| {
+            let bit = hashes.nth(i) % self.num_bits;
+            self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0
+        })
+    }
+}
+
+/// Double hashing, deriving every probe from two base hashes.
+struct BloomHashes(u64, u64);
+
+impl BloomHashes {
+    fn new(id: &str) -> Self {
+        let first = RandomState::with_seeds(1, 2, 3, 4).hash_one(id);
+        let second = RandomState::with_seeds(5, 6, 7, 8).hash_one(id);
+        Self(first, second | 1)
+    }
+
+    fn nth(&self, i: u32) -> u64 {
+        self.0.wrapping_add((i as u64).wrapping_mul(self.1))
+    }
+}
@@ -20,6 +20,24 @@ pub struct IdListMetadata {
     pub creation_time: i64,
 }
 
+impl IdListMetadata {
+    /// Returns the byte offset this list should be downloaded from, given the metadata that is
+    /// currently held. `None` means the current copy is already up to date.
+    pub(crate) fn get_download_range_start(&self, current: Option<&IdListMetadata>) -> Option<u64> {
+        let Some(current) = current else {
+            return Some(0);
+        };
+
+        if self.creation_time > current.creation_time || self.file_id != current.file_id {
+            Some(0)
+        } else if self.size > current.size {
+            Some(current.size)
+        } else {
+            None
+        }
+    }
+}
+
 pub struct IdListUpdate {
     pub raw_changeset: Option<String>,
     pub new_metadata: IdListMetadata,
@@ -59,6 +77,14 @@ pub trait IdListsAdapter: Send + Sync {
         statsig_runtime: &Arc<StatsigRuntime>,
     ) -> Result<(), StatsigErr>;
 
+    /// Called during Statsig::update_options when `id_lists_sync_interval_ms` changes.
+    /// Adapters that poll on an interval should apply it to their running background sync.
+    ///
+    /// # Arguments
+    ///
+    /// * `sync_interval_ms` - The new interval between background syncs.
+    fn update_sync_interval(&self, _sync_interval_ms: u32) {}
+
     /// Returns the type name of the adapter. Used for logging and error messages.
     fn get_type_name(&self) -> String;
 }
@@ -1,7 +1,15 @@
 pub use id_list::*;
+pub use id_list_storage::{IdListStorage, IdListStorageConfig, IdListStorageMode};
 pub use id_lists_adapter_trait::*;
+#[cfg(feature = "with_grpc")]
+pub use statsig_grpc_id_lists_adapter::StatsigGrpcIdListsAdapter;
 pub use statsig_http_id_lists_adapter::*;
+pub use statsig_local_file_id_lists_adapter::StatsigLocalFileIdListsAdapter;
 
 mod id_list;
+mod id_list_storage;
 mod id_lists_adapter_trait;
+#[cfg(feature = "with_grpc")]
+mod statsig_grpc_id_lists_adapter;
 mod statsig_http_id_lists_adapter;
+mod statsig_local_file_id_lists_adapter;
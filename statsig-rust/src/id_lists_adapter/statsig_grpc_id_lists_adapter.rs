@@ -0,0 +1,232 @@
+use super::IdListMetadata;
+use crate::id_lists_adapter::{IdListUpdate, IdListsAdapter, IdListsUpdateListener};
+use crate::observability::observability_client_adapter::{MetricType, ObservabilityEvent};
+use crate::observability::ops_stats::{OpsStatsForInstance, OPS_STATS};
+use crate::observability::sdk_errors_observer::ErrorBoundaryEvent;
+use crate::{
+    log_d, log_e, log_error_to_statsig_and_console, SpecAdapterConfig, StatsigErr, StatsigOptions,
+    StatsigRuntime,
+};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use sigstat_grpc::statsig_grpc_client::StatsigGrpcClient;
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+
+const DEFAULT_ID_LIST_SYNC_INTERVAL_MS: u32 = 60_000;
+
+const TAG: &str = stringify!(StatsigGrpcIdListsAdapter);
+
+/// Syncs ID lists through a forward proxy, using the `getIdListsManifest` and `getIdListFile`
+/// RPCs in place of the CDN manifest and file downloads.
+pub struct StatsigGrpcIdListsAdapter {
+    grpc_client: StatsigGrpcClient,
+    listener: RwLock<Option<Arc<dyn IdListsUpdateListener>>>,
+    sync_interval_duration: Duration,
+    ops_stats: Arc<OpsStatsForInstance>,
+    shutdown_notify: Arc<Notify>,
+}
+
+impl StatsigGrpcIdListsAdapter {
+    /// `config.specs_url` is the address of the forward proxy, the same one used by
+    /// `StatsigGrpcSpecsAdapter`.
+    #[must_use]
+    pub fn new(sdk_key: &str, config: &SpecAdapterConfig, options: &StatsigOptions) -> Self {
+        let sync_interval_duration = Duration::from_millis(u64::from(
+            options
+                .id_lists_sync_interval_ms
+                .unwrap_or(DEFAULT_ID_LIST_SYNC_INTERVAL_MS),
+        ));
+
+        Self {
+            grpc_client: StatsigGrpcClient::new(
+                sdk_key,
+                &config.specs_url.clone().unwrap_or("INVALID".to_owned()),
+                config.authentication_mode.clone(),
+                config.ca_cert_path.clone(),
+                config.client_cert_path.clone(),
+                config.client_key_path.clone(),
+                config.domain_name.clone(),
+            ),
+            listener: RwLock::new(None),
+            sync_interval_duration,
+            ops_stats: OPS_STATS.get_for_instance(sdk_key),
+            shutdown_notify: Arc::new(Notify::new()),
+        }
+    }
+
+    async fn fetch_manifest(&self) -> Result<HashMap<String, IdListMetadata>, StatsigErr> {
+        let result = self
+            .grpc_client
+            .get_id_lists_manifest()
+            .await
+            .map_err(|e| StatsigErr::GrpcError(e.to_string()));
+
+        let metric_name = if result.is_ok() {
+            "id_list_manifest_download_success"
+        } else {
+            "id_list_manifest_download_failure"
+        };
+        self.ops_stats.log(ObservabilityEvent::new_event(
+            MetricType::Increment,
+            metric_name.to_string(),
+            1.0,
+            None,
+        ));
+
+        let manifest = result?
+            .lists
+            .into_iter()
+            .map(|entry| {
+                (
+                    entry.name.clone(),
+                    IdListMetadata {
+                        name: entry.name,
+                        url: entry.url,
+                        file_id: entry.file_id,
+                        size: entry.size,
+                        creation_time: entry.creation_time,
+                    },
+                )
+            })
+            .collect();
+
+        Ok(manifest)
+    }
+
+    async fn fetch_id_list_changes(
+        &self,
+        metadata: &IdListMetadata,
+        range_start: u64,
+    ) -> Result<String, StatsigErr> {
+        self.grpc_client
+            .get_id_list_file(&metadata.name, metadata.file_id.clone(), range_start)
+            .await
+            .map(|response| response.content)
+            .map_err(|e| StatsigErr::GrpcError(e.to_string()))
+    }
+
+    async fn sync_id_lists(&self) -> Result<(), StatsigErr> {
+        let new_manifest = self.fetch_manifest().await?;
+        let listener = self.get_listener()?;
+        let curr_manifest = listener.get_current_id_list_metadata();
+
+        let mut updates = HashMap::new();
+        for (list_name, new_metadata) in new_manifest {
+            let range_start = new_metadata.get_download_range_start(curr_manifest.get(&list_name));
+
+            let raw_changeset = match range_start {
+                Some(range_start) => Some(
+                    self.fetch_id_list_changes(&new_metadata, range_start)
+                        .await?,
+                ),
+                None => None,
+            };
+
+            updates.insert(
+                list_name,
+                IdListUpdate {
+                    raw_changeset,
+                    new_metadata,
+                },
+            );
+        }
+
+        listener.did_receive_id_list_updates(updates);
+        Ok(())
+    }
+
+    async fn run_background_sync(weak_self: &Weak<Self>) {
+        let Some(strong_self) = weak_self.upgrade() else {
+            return;
+        };
+
+        if let Err(e) = strong_self.sync_id_lists().await {
+            log_e!(TAG, "IDList background sync failed {}", e);
+        }
+    }
+
+    fn set_listener(&self, listener: Arc<dyn IdListsUpdateListener>) {
+        match self
+            .listener
+            .try_write_for(std::time::Duration::from_secs(5))
+        {
+            Some(mut lock) => *lock = Some(listener),
+            None => {
+                log_error_to_statsig_and_console!(
+                    self.ops_stats.clone(),
+                    TAG,
+                    StatsigErr::LockFailure("Failed to acquire write lock on listener".to_string())
+                );
+            }
+        }
+    }
+
+    fn get_listener(&self) -> Result<Arc<dyn IdListsUpdateListener>, StatsigErr> {
+        let lock = self
+            .listener
+            .try_read_for(std::time::Duration::from_secs(5))
+            .ok_or_else(|| {
+                StatsigErr::LockFailure("Failed to acquire read lock on listener".to_string())
+            })?;
+
+        lock.clone()
+            .ok_or_else(|| StatsigErr::UnstartedAdapter("Listener not set".to_string()))
+    }
+}
+
+#[async_trait]
+impl IdListsAdapter for StatsigGrpcIdListsAdapter {
+    async fn start(
+        self: Arc<Self>,
+        _statsig_runtime: &Arc<StatsigRuntime>,
+        listener: Arc<dyn IdListsUpdateListener + Send + Sync>,
+    ) -> Result<(), StatsigErr> {
+        self.set_listener(listener);
+        self.sync_id_lists().await
+    }
+
+    async fn shutdown(&self, _timeout: Duration) -> Result<(), StatsigErr> {
+        self.shutdown_notify.notify_one();
+        Ok(())
+    }
+
+    async fn schedule_background_sync(
+        self: Arc<Self>,
+        statsig_runtime: &Arc<StatsigRuntime>,
+    ) -> Result<(), StatsigErr> {
+        let weak_self = Arc::downgrade(&self);
+        let interval_duration = self.sync_interval_duration;
+        let shutdown_notify = self.shutdown_notify.clone();
+
+        statsig_runtime.spawn(
+            "grpc_id_list_bg_sync",
+            move |rt_shutdown_notify| async move {
+                loop {
+                    tokio::select! {
+                        () = sleep(interval_duration) => {
+                            Self::run_background_sync(&weak_self).await;
+                        }
+                        () = rt_shutdown_notify.notified() => {
+                            log_d!(TAG, "Runtime shutdown. Shutting down id list background sync");
+                            break;
+                        },
+                        () = shutdown_notify.notified() => {
+                            log_d!(TAG, "Shutting down id list background sync");
+                            break;
+                        }
+                    }
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn get_type_name(&self) -> String {
+        TAG.to_string()
+    }
+}
@@ -8,6 +8,7 @@ use crate::sdk_diagnostics::diagnostics::ContextType;
 use crate::sdk_diagnostics::marker::{ActionType, KeyType, Marker, StepType};
 use crate::statsig_metadata::StatsigMetadata;
 use crate::utils::split_host_and_path;
+use crate::utils::sync_interval::SyncInterval;
 use crate::{
     log_d, log_e, log_error_to_statsig_and_console, StatsigErr, StatsigOptions, StatsigRuntime,
 };
@@ -18,7 +19,6 @@ use std::collections::HashMap;
 use std::sync::{Arc, Weak};
 use std::time::Duration;
 use tokio::sync::Notify;
-use tokio::time::sleep;
 
 const STATSIG_CDN_URL: &str = "https://api.statsigcdn.com";
 const DEFAULT_CDN_ID_LISTS_MANIFEST_URL: &str = "https://api.statsigcdn.com/v1/get_id_lists";
@@ -38,7 +38,7 @@ pub struct StatsigHttpIdListsAdapter {
     fallback_url: Option<String>,
     listener: RwLock<Option<Arc<dyn IdListsUpdateListener>>>,
     network: NetworkClient,
-    sync_interval_duration: Duration,
+    sync_interval: Arc<SyncInterval>,
     request_timeout_ms: u64,
     ops_stats: Arc<OpsStatsForInstance>,
     shutdown_notify: Arc<Notify>,
@@ -59,11 +59,11 @@ impl StatsigHttpIdListsAdapter {
             fallback_url = Some(make_default_cdn_url(sdk_key));
         }
 
-        let sync_interval_duration = Duration::from_millis(u64::from(
+        let sync_interval = Arc::new(SyncInterval::new(u64::from(
             options
                 .id_lists_sync_interval_ms
                 .unwrap_or(DEFAULT_ID_LIST_SYNC_INTERVAL_MS),
-        ));
+        )));
 
         let network = NetworkClient::new(
             sdk_key,
@@ -80,7 +80,7 @@ impl StatsigHttpIdListsAdapter {
             fallback_url,
             listener: RwLock::new(None),
             network,
-            sync_interval_duration,
+            sync_interval,
             // 0 means "use the network provider's default timeout".
             request_timeout_ms: options.id_lists_request_timeout_ms.unwrap_or(0),
             ops_stats: OPS_STATS.get_for_instance(sdk_key),
@@ -408,26 +408,12 @@ impl StatsigHttpIdListsAdapter {
         );
 
         for (list_name, entry) in new_manifest {
-            let (requires_download, range_start) = match curr_manifest.get(&list_name) {
-                Some(current) => {
-                    if entry.creation_time > current.creation_time
-                        || entry.file_id != current.file_id
-                    {
-                        (true, 0u64)
-                    } else if entry.size > current.size {
-                        (true, current.size)
-                    } else {
-                        (false, 0u64)
-                    }
-                }
-                None => (true, 0),
-            };
+            let range_start = entry.get_download_range_start(curr_manifest.get(&list_name));
 
             changes.insert(
                 list_name.clone(),
                 IdListChangeSet {
                     new_metadata: entry,
-                    requires_download,
                     range_start,
                 },
             );
@@ -438,7 +424,7 @@ impl StatsigHttpIdListsAdapter {
         for (list_name, changeset) in changes {
             let new_metadata = changeset.new_metadata;
 
-            if !changeset.requires_download {
+            let Some(range_start) = changeset.range_start else {
                 updates.insert(
                     list_name,
                     IdListUpdate {
@@ -447,12 +433,12 @@ impl StatsigHttpIdListsAdapter {
                     },
                 );
                 continue;
-            }
+            };
 
             let single_id_list_download_result = self
                 .fetch_individual_id_list_changes_from_network(
                     &new_metadata.url,
-                    changeset.range_start,
+                    range_start,
                     new_metadata.size,
                     new_metadata.file_id.clone(),
                 )
@@ -583,8 +569,8 @@ impl StatsigHttpIdListsAdapter {
 
 struct IdListChangeSet {
     new_metadata: IdListMetadata,
-    requires_download: bool,
-    range_start: u64,
+    // `None` when the current copy is already up to date
+    range_start: Option<u64>,
 }
 
 #[async_trait]
@@ -612,15 +598,17 @@ impl IdListsAdapter for StatsigHttpIdListsAdapter {
         statsig_runtime: &Arc<StatsigRuntime>,
     ) -> Result<(), StatsigErr> {
         let weak_self = Arc::downgrade(&self);
-        let interval_duration = self.sync_interval_duration;
+        let sync_interval = self.sync_interval.clone();
 
         statsig_runtime.spawn(
             "http_id_list_bg_sync",
             move |rt_shutdown_notify| async move {
                 loop {
                     tokio::select! {
-                        () = sleep(interval_duration) => {
-                            Self::run_background_sync(&weak_self).await;
+                        elapsed = sync_interval.wait() => {
+                            if elapsed {
+                                Self::run_background_sync(&weak_self).await;
+                            }
                         }
                         () = rt_shutdown_notify.notified() => {
                             log_d!(TAG, "Runtime shutdown. Shutting down id list background sync");
@@ -638,6 +626,10 @@ impl IdListsAdapter for StatsigHttpIdListsAdapter {
         Ok(())
     }
 
+    fn update_sync_interval(&self, sync_interval_ms: u32) {
+        self.sync_interval.update(u64::from(sync_interval_ms));
+    }
+
     fn get_type_name(&self) -> String {
         TAG.to_string()
     }
@@ -691,7 +683,7 @@ mod tests {
                     return false;
                 }
 
-                sleep(Duration::from_millis(10)).await;
+                tokio::time::sleep(Duration::from_millis(10)).await;
             }
         }
     }
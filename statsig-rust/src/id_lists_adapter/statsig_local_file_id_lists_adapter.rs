@@ -0,0 +1,387 @@
+use super::IdListMetadata;
+use crate::hashing::djb2;
+use crate::id_lists_adapter::{IdListUpdate, IdListsAdapter, IdListsUpdateListener};
+use crate::utils::sync_interval::SyncInterval;
+use crate::{log_d, log_e, StatsigErr, StatsigRuntime};
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Notify;
+
+const TAG: &str = stringify!(StatsigLocalFileIdListsAdapter);
+
+const DEFAULT_WATCH_INTERVAL_MS: u32 = 1_000;
+const LIST_FILE_EXTENSION: &str = ".txt";
+const CHANGESET_FILE_EXTENSION: &str = ".changeset";
+
+/// Loads ID lists from a directory instead of the network.
+///
+/// Each list is a file named after the list, optionally ending in `.txt`, holding one ID per
+/// line. Lines may also use the `+id`/`-id` format of downloaded ID lists. A file named
+/// `<list_name>.changeset` holds further `+id`/`-id` lines applied on top of the list. It is
+/// expected to be append-only, only the newly appended lines are sent on each sync, and
+/// truncating it reloads the list.
+///
+/// IDs are matched the same way as downloaded lists, so they must be lookup IDs (the first 8
+/// characters of the base64 encoded SHA-256 of the unit ID).
+///
+/// The directory is checked for changes every `watch_interval_ms`. Lists whose files are
+/// removed are removed from the SDK.
+pub struct StatsigLocalFileIdListsAdapter {
+    directory: PathBuf,
+    listener: RwLock<Option<Arc<dyn IdListsUpdateListener>>>,
+    loaded: Mutex<HashMap<String, LoadedListFile>>,
+    sync_interval: Arc<SyncInterval>,
+    shutdown_notify: Arc<Notify>,
+}
+
+#[derive(Clone, PartialEq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+struct LoadedListFile {
+    stamp: FileStamp,
+    content_hash: String,
+    creation_time: i64,
+    list_len: u64,
+    changeset_len: u64,
+    // bumped whenever the changeset file shrinks, which forces a full reload
+    generation: u32,
+}
+
+impl LoadedListFile {
+    fn file_id(&self) -> String {
+        format!("{}.{}", self.content_hash, self.generation)
+    }
+}
+
+impl StatsigLocalFileIdListsAdapter {
+    #[must_use]
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: PathBuf::from(directory),
+            listener: RwLock::new(None),
+            loaded: Mutex::new(HashMap::new()),
+            sync_interval: Arc::new(SyncInterval::new(u64::from(DEFAULT_WATCH_INTERVAL_MS))),
+            shutdown_notify: Arc::new(Notify::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn with_watch_interval_ms(self, watch_interval_ms: u32) -> Self {
+        self.sync_interval.update(u64::from(watch_interval_ms));
+        self
+    }
+
+    /// Reads the directory and sends any changes to the listener.
+    pub fn resync_from_directory(&self) -> Result<(), StatsigErr> {
+        let listener = match self
+            .listener
+            .try_read_for(std::time::Duration::from_secs(5))
+        {
+            Some(lock) => match lock.as_ref() {
+                Some(listener) => listener.clone(),
+                None => return Err(StatsigErr::UnstartedAdapter("Listener not set".to_string())),
+            },
+            None => {
+                return Err(StatsigErr::LockFailure(
+                    "Failed to acquire read lock on listener".to_string(),
+                ))
+            }
+        };
+
+        let current = listener.get_current_id_list_metadata();
+        let updates = self.read_updates(&current)?;
+        listener.did_receive_id_list_updates(updates);
+
+        Ok(())
+    }
+
+    fn read_updates(
+        &self,
+        current: &HashMap<String, IdListMetadata>,
+    ) -> Result<HashMap<String, IdListUpdate>, StatsigErr> {
+        let list_names = self.find_list_files()?;
+
+        let mut loaded = self.loaded.lock();
+        loaded.retain(|name, _| list_names.contains_key(name));
+
+        let mut updates = HashMap::new();
+        for (list_name, list_path) in list_names {
+            let changeset_path = self
+                .directory
+                .join(format!("{list_name}{CHANGESET_FILE_EXTENSION}"));
+
+            match self.read_update(
+                &list_name,
+                &list_path,
+                &changeset_path,
+                &mut loaded,
+                current,
+            ) {
+                Ok(update) => {
+                    updates.insert(list_name, update);
+                }
+                Err(e) => {
+                    log_e!(TAG, "Failed to read ID list '{}': {}", list_name, e);
+                    if let Some(metadata) = current.get(&list_name) {
+                        // keep what was already loaded
+                        updates.insert(
+                            list_name,
+                            IdListUpdate {
+                                raw_changeset: None,
+                                new_metadata: metadata.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(updates)
+    }
+
+    fn read_update(
+        &self,
+        list_name: &str,
+        list_path: &Path,
+        changeset_path: &Path,
+        loaded: &mut HashMap<String, LoadedListFile>,
+        current: &HashMap<String, IdListMetadata>,
+    ) -> Result<IdListUpdate, StatsigErr> {
+        let stamp = get_file_stamp(list_path)?;
+        let mut list_content = None;
+
+        let needs_reload = loaded.get(list_name).is_none_or(|l| l.stamp != stamp);
+        if needs_reload {
+            let content = read_list_file(list_path)?;
+            let content_hash = djb2(&content);
+            let previous = loaded.remove(list_name);
+
+            let (creation_time, changeset_len, generation) = match previous {
+                Some(prev) if prev.content_hash == content_hash => {
+                    (prev.creation_time, prev.changeset_len, prev.generation)
+                }
+                _ => (get_modified_ms(&stamp), 0, 0),
+            };
+
+            loaded.insert(
+                list_name.to_string(),
+                LoadedListFile {
+                    stamp,
+                    content_hash,
+                    creation_time,
+                    list_len: content.len() as u64,
+                    changeset_len,
+                    generation,
+                },
+            );
+            list_content = Some(content);
+        }
+
+        let Some(state) = loaded.get_mut(list_name) else {
+            return Err(StatsigErr::FileError(format!("Failed to load {list_name}")));
+        };
+
+        let changeset = read_changeset_file(changeset_path)?;
+        let changeset_len = changeset.len() as u64;
+        if changeset_len < state.changeset_len {
+            log_d!(TAG, "Changeset for '{}' shrank, reloading", list_name);
+            state.generation += 1;
+        }
+        state.changeset_len = changeset_len;
+
+        let new_metadata = IdListMetadata {
+            name: list_name.to_string(),
+            url: format!("file://{}", list_path.display()),
+            file_id: Some(state.file_id()),
+            size: state.list_len + changeset_len,
+            creation_time: state.creation_time,
+        };
+
+        let raw_changeset = match new_metadata.get_download_range_start(current.get(list_name)) {
+            None => None,
+            Some(start) if start >= state.list_len => {
+                let offset = (start - state.list_len) as usize;
+                Some(changeset[offset..].to_string())
+            }
+            Some(_) => {
+                let content = match list_content {
+                    Some(content) => content,
+                    None => read_list_file(list_path)?,
+                };
+                Some(content + &changeset)
+            }
+        };
+
+        Ok(IdListUpdate {
+            raw_changeset,
+            new_metadata,
+        })
+    }
+
+    fn find_list_files(&self) -> Result<HashMap<String, PathBuf>, StatsigErr> {
+        let entries =
+            fs::read_dir(&self.directory).map_err(|e| StatsigErr::FileError(e.to_string()))?;
+
+        let mut lists = HashMap::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+
+            if file_name.starts_with('.') || file_name.ends_with(CHANGESET_FILE_EXTENSION) {
+                continue;
+            }
+
+            let list_name = file_name
+                .strip_suffix(LIST_FILE_EXTENSION)
+                .unwrap_or(file_name);
+            lists.insert(list_name.to_string(), path.clone());
+        }
+
+        Ok(lists)
+    }
+
+    fn set_listener(&self, listener: Arc<dyn IdListsUpdateListener>) {
+        match self
+            .listener
+            .try_write_for(std::time::Duration::from_secs(5))
+        {
+            Some(mut lock) => *lock = Some(listener),
+            None => {
+                log_e!(TAG, "Failed to acquire write lock on listener");
+            }
+        }
+    }
+
+    fn run_background_sync(weak_self: &Weak<Self>) {
+        let Some(strong_self) = weak_self.upgrade() else {
+            return;
+        };
+
+        if let Err(e) = strong_self.resync_from_directory() {
+            log_e!(TAG, "Local ID list sync failed {}", e);
+        }
+    }
+}
+
+fn get_file_stamp(path: &Path) -> Result<FileStamp, StatsigErr> {
+    let metadata = fs::metadata(path).map_err(|e| StatsigErr::FileError(e.to_string()))?;
+    Ok(FileStamp {
+        len: metadata.len(),
+        modified: metadata.modified().ok(),
+    })
+}
+
+fn get_modified_ms(stamp: &FileStamp) -> i64 {
+    stamp
+        .modified
+        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as i64)
+}
+
+/// Reads a list file, prefixing bare IDs with `+`.
+fn read_list_file(path: &Path) -> Result<String, StatsigErr> {
+    let raw = fs::read_to_string(path).map_err(|e| StatsigErr::FileError(e.to_string()))?;
+
+    let mut content = String::with_capacity(raw.len() + raw.len() / 8);
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if !trimmed.starts_with('+') && !trimmed.starts_with('-') {
+            content.push('+');
+        }
+        content.push_str(trimmed);
+        content.push('\n');
+    }
+
+    Ok(content)
+}
+
+/// Reads the complete lines of a changeset file. A trailing partial line is left for the next
+/// sync, in case it is still being written.
+fn read_changeset_file(path: &Path) -> Result<String, StatsigErr> {
+    let mut raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(String::new()),
+        Err(e) => return Err(StatsigErr::FileError(e.to_string())),
+    };
+
+    let complete_len = raw.rfind('\n').map_or(0, |i| i + 1);
+    raw.truncate(complete_len);
+    Ok(raw)
+}
+
+#[async_trait]
+impl IdListsAdapter for StatsigLocalFileIdListsAdapter {
+    async fn start(
+        self: Arc<Self>,
+        _statsig_runtime: &Arc<StatsigRuntime>,
+        listener: Arc<dyn IdListsUpdateListener + Send + Sync>,
+    ) -> Result<(), StatsigErr> {
+        self.set_listener(listener);
+        self.resync_from_directory()
+    }
+
+    async fn shutdown(&self, _timeout: Duration) -> Result<(), StatsigErr> {
+        self.shutdown_notify.notify_one();
+        Ok(())
+    }
+
+    async fn schedule_background_sync(
+        self: Arc<Self>,
+        statsig_runtime: &Arc<StatsigRuntime>,
+    ) -> Result<(), StatsigErr> {
+        let weak_self = Arc::downgrade(&self);
+        let sync_interval = self.sync_interval.clone();
+        let shutdown_notify = self.shutdown_notify.clone();
+
+        statsig_runtime.spawn(
+            "local_file_id_list_bg_sync",
+            move |rt_shutdown_notify| async move {
+                loop {
+                    tokio::select! {
+                        elapsed = sync_interval.wait() => {
+                            if elapsed {
+                                Self::run_background_sync(&weak_self);
+                            }
+                        }
+                        () = rt_shutdown_notify.notified() => {
+                            log_d!(TAG, "Runtime shutdown. Shutting down local id list sync");
+                            break;
+                        },
+                        () = shutdown_notify.notified() => {
+                            log_d!(TAG, "Shutting down local id list sync");
+                            break;
+                        }
+                    }
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn update_sync_interval(&self, sync_interval_ms: u32) {
+        self.sync_interval.update(u64::from(sync_interval_ms));
+    }
+
+    fn get_type_name(&self) -> String {
+        TAG.to_string()
+    }
+}
@@ -1,12 +1,32 @@
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use evaluation::cmab_learner::{
+    CMABContext, CMABExplorationMode, CMABLearner, CMABLearnerOptions,
+};
+pub use evaluation::cmab_offline_eval::{
+    CMABLoggedRecord, CMABOfflineEvalReport, CMABOfflineEvaluator,
+};
 pub use evaluation::dynamic_returnable::DynamicReturnable;
 pub use evaluation::dynamic_value::DynamicValue;
 pub use evaluation::evaluation_details::EvaluationDetails;
 pub use evaluation::evaluation_types::SecondaryExposure;
+pub use evaluation::guarded_regex::RegexSafetyOptions;
+pub use event_logging::exposure_dedupe_persistence::{
+    ExposureDedupePersistence, ExposureDedupeStore,
+};
+pub use event_logging::exposure_sampling_policy::{
+    ExposureSamplingDecision, ExposureSamplingInput, ExposureSamplingPolicy,
+};
+pub use event_logging::redaction_policy::{RedactionAction, RedactionPolicy};
 pub use event_logging_adapter::*;
 pub use gcir::gcir_formatter::GCIRResponseFormat;
 pub use gcir::gcir_options::ClientInitResponseOptions;
 pub use hashing::HashAlgorithm;
-pub use id_lists_adapter::{IdListsAdapter, StatsigHttpIdListsAdapter};
+#[cfg(feature = "with_grpc")]
+pub use id_lists_adapter::StatsigGrpcIdListsAdapter;
+pub use id_lists_adapter::{
+    IdListMetadata, IdListStorageConfig, IdListStorageMode, IdListUpdate, IdListsAdapter,
+    IdListsUpdateListener, StatsigHttpIdListsAdapter, StatsigLocalFileIdListsAdapter,
+};
 pub use init_details::{FailureDetails, InitializeDetails};
 pub use initialize_response::InitializeResponse;
 pub use instance_registry::InstanceRegistry;
@@ -15,7 +35,13 @@ pub use observability::{
     ops_stats::OPS_STATS,
 };
 pub use override_adapter::{
-    override_adapter_trait::OverrideAdapter,
+    override_adapter_trait::{OverrideAdapter, OverridesUpdateListener},
+    override_matcher::{
+        ActiveOverride, OverrideCondition, OverrideConditionOperator, OverrideMatcher,
+        OverrideSpecType,
+    },
+    statsig_data_store_override_adapter::{SharedOverrideRecord, StatsigDataStoreOverrideAdapter},
+    statsig_file_override_adapter::StatsigFileOverrideAdapter,
     statsig_local_override_adapter::StatsigLocalOverrideAdapter,
 };
 pub use persistent_storage::persistent_storage_trait::*;
@@ -27,13 +53,17 @@ pub use statsig_core_api_options::{
     LayerEvaluationOptions, ParameterStoreEvaluationOptions,
 };
 pub use statsig_err::StatsigErr;
+pub use statsig_hub::{StatsigHub, StatsigHubDiagnostics, StatsigHubProjectDiagnostics};
 pub use statsig_options::StatsigOptions;
 pub use statsig_runtime::StatsigRuntime;
 pub use user::user_data::{
     UserData as StatsigUserData, UserDataMap as StatsigUserDataMap,
     UserDataStringMap as StatsigUserDataStringMap,
 };
-pub use user::{StatsigUser, StatsigUserBuilder};
+pub use user::{
+    HeaderPolicy, IpPlacement, StatsigUser, StatsigUserBuilder, UserAgentClientHints, UserEnricher,
+    UserEnrichment,
+};
 
 pub mod compression;
 pub mod console_capture;
@@ -64,6 +94,7 @@ pub mod statsig_types;
 pub mod statsig_types_raw;
 pub mod user;
 
+mod clock;
 mod dcs_str;
 mod event_logging_adapter;
 mod id_lists_adapter;
@@ -76,6 +107,7 @@ mod spec_store;
 mod specs_adapter;
 mod statsig;
 mod statsig_err;
+mod statsig_hub;
 mod statsig_type_factories;
 mod utils;
 
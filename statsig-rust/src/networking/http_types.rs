@@ -2,7 +2,6 @@ use crate::networking::proxy_config::ProxyConfig;
 use crate::sdk_diagnostics::marker::KeyType;
 use crate::StatsigErr;
 use async_trait::async_trait;
-use chrono::Utc;
 use serde::de::DeserializeOwned;
 use std::io::Cursor;
 use std::{
@@ -28,6 +27,9 @@ pub struct RequestArgs {
     pub disable_file_streaming: Option<bool>,
     /// Opt-in flag for reusing request-scoped reqwest client for log_event traffic.
     pub log_event_connection_reuse: bool,
+    /// Sends the request through the provider's pooled client, which is shared by every
+    /// request that sets this and has the same proxy and CA configuration.
+    pub share_connection_pool: bool,
 }
 
 impl Default for RequestArgs {
@@ -55,6 +57,7 @@ impl RequestArgs {
             ca_cert_pem: None,
             disable_file_streaming: None,
             log_event_connection_reuse: false,
+            share_connection_pool: false,
         }
     }
 
@@ -82,11 +85,6 @@ impl RequestArgs {
         let mut headers = HashMap::new();
         headers.extend(extra_headers);
 
-        headers.insert(
-            "STATSIG-CLIENT-TIME".into(),
-            Utc::now().timestamp_millis().to_string(),
-        );
-
         if let Some(my_headers) = &mut self.headers {
             my_headers.extend(headers);
         } else {
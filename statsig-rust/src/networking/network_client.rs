@@ -1,8 +1,7 @@
-use chrono::Utc;
-
 use super::network_error::NetworkError;
 use super::providers::get_network_provider;
 use super::{HttpMethod, NetworkProvider, RequestArgs, Response};
+use crate::clock::{get_clock, Clock};
 use crate::networking::proxy_config::ProxyConfig;
 use crate::observability::observability_client_adapter::{MetricType, ObservabilityEvent};
 use crate::observability::ops_stats::{OpsStatsForInstance, OPS_STATS};
@@ -53,7 +52,9 @@ pub struct NetworkClient {
     silent_on_network_failure: bool,
     disable_file_streaming: bool,
     log_event_connection_reuse: bool,
+    share_connection_pool: bool,
     loggable_sdk_key: String,
+    clock: Arc<dyn Clock>,
 }
 
 impl NetworkClient {
@@ -109,7 +110,10 @@ impl NetworkClient {
                 .map(|opts| opts.disable_disk_access.unwrap_or(false))
                 .unwrap_or(false),
             log_event_connection_reuse,
+            share_connection_pool: options
+                .is_some_and(|opts| opts.share_http_connection_pool == Some(true)),
             loggable_sdk_key: get_loggable_sdk_key(sdk_key),
+            clock: get_clock(options),
         }
     }
 
@@ -160,13 +164,17 @@ impl NetworkClient {
             request_args.log_event_connection_reuse = true;
         }
 
+        if self.share_connection_pool {
+            request_args.share_connection_pool = true;
+        }
+
         let mut merged_headers = request_args.headers.unwrap_or_default();
         if !self.headers.is_empty() {
             merged_headers.extend(self.headers.clone());
         }
         merged_headers.insert(
             "STATSIG-CLIENT-TIME".into(),
-            Utc::now().timestamp_millis().to_string(),
+            self.clock.now_ms().to_string(),
         );
         request_args.headers = Some(merged_headers);
 
@@ -16,6 +16,7 @@ use crate::{
 };
 
 use crate::networking::proxy_config::ProxyConfig;
+use parking_lot::Mutex;
 use reqwest::Method;
 
 const TAG: &str = "NetworkProviderReqwest";
@@ -25,6 +26,8 @@ const SDK_EXCEPTION_REUSE_PATH: &[&str] = &["v1", "sdk_exception"];
 pub struct NetworkProviderReqwest {
     has_file_write_access: bool,
     shared_client: reqwest::Client,
+    /// Clients for requests with `share_connection_pool`, keyed by their proxy and CA config.
+    pooled_clients: Mutex<HashMap<String, reqwest::Client>>,
 }
 
 impl NetworkProviderReqwest {
@@ -32,6 +35,7 @@ impl NetworkProviderReqwest {
         Self {
             has_file_write_access: tempfile::tempfile().is_ok(),
             shared_client: reqwest::Client::new(),
+            pooled_clients: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -140,6 +144,10 @@ impl NetworkProviderReqwest {
     }
 
     fn get_client(&self, request_args: &RequestArgs) -> reqwest::Client {
+        if request_args.share_connection_pool {
+            return self.get_pooled_client(request_args);
+        }
+
         if !self.should_use_shared_client(request_args) {
             return Self::build_client(request_args);
         }
@@ -147,6 +155,19 @@ impl NetworkProviderReqwest {
         self.shared_client.clone()
     }
 
+    fn get_pooled_client(&self, request_args: &RequestArgs) -> reqwest::Client {
+        let key = format!(
+            "{:?}|{:?}",
+            request_args.proxy_config, request_args.ca_cert_pem
+        );
+
+        self.pooled_clients
+            .lock()
+            .entry(key)
+            .or_insert_with(|| Self::build_client(request_args))
+            .clone()
+    }
+
     fn should_use_shared_client(&self, request_args: &RequestArgs) -> bool {
         (request_args.log_event_connection_reuse && is_log_event_endpoint(&request_args.url))
             || is_sdk_exception_endpoint(&request_args.url)
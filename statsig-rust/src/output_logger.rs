@@ -134,6 +134,28 @@ pub fn initialize_output_logger(
     }
 }
 
+/// Changes the level of an initialized logger. When no provider is set, the `log` crate's
+/// max level is updated as well.
+pub fn set_output_log_level(level: &LogLevel) {
+    let mut state = match LOGGER_STATE.try_write_for(Duration::from_secs(5)) {
+        Some(state) => state,
+        None => {
+            eprintln!(
+                "[Statsig] Failed to acquire write lock for set_output_log_level: Failed to lock LOGGER_STATE"
+            );
+            return;
+        }
+    };
+    state.level = level.clone();
+
+    if state.provider.is_none() {
+        match level.to_third_party_level() {
+            Some(level) => log::set_max_level(level.to_level_filter()),
+            None => log::set_max_level(log::LevelFilter::Off),
+        }
+    }
+}
+
 pub fn shutdown_output_logger() {
     let mut state = match LOGGER_STATE.try_write_for(Duration::from_secs(5)) {
         Some(state) => state,
@@ -1,2 +1,5 @@
 pub mod override_adapter_trait;
+pub mod override_matcher;
+pub mod statsig_data_store_override_adapter;
+pub mod statsig_file_override_adapter;
 pub mod statsig_local_override_adapter;
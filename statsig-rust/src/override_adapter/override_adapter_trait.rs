@@ -1,10 +1,26 @@
+use super::override_matcher::{ActiveOverride, OverrideMatcher};
 use crate::evaluation::evaluator_result::EvaluatorResult;
 use crate::specs_response::spec_types::Spec;
-use crate::StatsigUser;
+use crate::{log_w, StatsigErr, StatsigRuntime, StatsigUser};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Notified when an adapter swaps in overrides loaded from an external source.
+pub trait OverridesUpdateListener: Send + Sync {
+    fn did_reload_overrides(&self, source: &str, override_count: usize);
+}
 
 pub trait OverrideAdapter: Send + Sync {
+    /// Called before [`OverrideAdapter::start`]. Adapters that reload overrides from a file or
+    /// data store report each reload to `listener`.
+    fn initialize(&self, _listener: Arc<dyn OverridesUpdateListener>) {}
+
+    /// Called during [`crate::Statsig::initialize`] so adapters can schedule background work.
+    fn start(self: Arc<Self>, _statsig_runtime: &Arc<StatsigRuntime>) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
     fn get_gate_override(
         &self,
         user: &StatsigUser,
@@ -54,4 +70,121 @@ pub trait OverrideAdapter: Send + Sync {
     fn remove_layer_override(&self, key: &str, id: Option<&str>);
     fn remove_parameter_store_override(&self, key: &str, id: Option<&str>);
     fn remove_all_overrides(&self);
+
+    // ---------------------------------------- [Matcher based overrides]
+    // Adapters that only support id based overrides can rely on these defaults, which
+    // forward plain id matchers to the methods above and ignore everything else.
+
+    fn override_gate_with_matcher(&self, key: &str, value: bool, matcher: OverrideMatcher) {
+        if let Some(id) = id_only_or_warn(&matcher) {
+            self.override_gate(key, value, id);
+        }
+    }
+
+    fn override_dynamic_config_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(id) = id_only_or_warn(&matcher) {
+            self.override_dynamic_config(key, value, id);
+        }
+    }
+
+    fn override_experiment_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(id) = id_only_or_warn(&matcher) {
+            self.override_experiment(key, value, id);
+        }
+    }
+
+    fn override_experiment_by_group_name_with_matcher(
+        &self,
+        key: &str,
+        group_name: &str,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(id) = id_only_or_warn(&matcher) {
+            self.override_experiment_by_group_name(key, group_name, id);
+        }
+    }
+
+    fn override_layer_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(id) = id_only_or_warn(&matcher) {
+            self.override_layer(key, value, id);
+        }
+    }
+
+    fn override_parameter_store_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(id) = id_only_or_warn(&matcher) {
+            self.override_parameter_store(key, value, id);
+        }
+    }
+
+    fn remove_gate_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        if let Some(id) = id_only_or_warn(matcher) {
+            self.remove_gate_override(key, id);
+        }
+    }
+
+    fn remove_dynamic_config_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        if let Some(id) = id_only_or_warn(matcher) {
+            self.remove_dynamic_config_override(key, id);
+        }
+    }
+
+    fn remove_experiment_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        if let Some(id) = id_only_or_warn(matcher) {
+            self.remove_experiment_override(key, id);
+        }
+    }
+
+    fn remove_layer_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        if let Some(id) = id_only_or_warn(matcher) {
+            self.remove_layer_override(key, id);
+        }
+    }
+
+    fn remove_parameter_store_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        if let Some(id) = id_only_or_warn(matcher) {
+            self.remove_parameter_store_override(key, id);
+        }
+    }
+
+    /// Returns every override that has not yet expired. Adapters that cannot enumerate
+    /// their overrides return an empty list.
+    fn list_overrides(&self) -> Vec<ActiveOverride> {
+        vec![]
+    }
+}
+
+const TAG: &str = stringify!(OverrideAdapter);
+
+// Outer None means the matcher cannot be applied, inner None means "no id".
+#[allow(clippy::option_option)]
+fn id_only_or_warn(matcher: &OverrideMatcher) -> Option<Option<&str>> {
+    if matcher.is_id_only() {
+        return Some(matcher.id.as_deref());
+    }
+
+    log_w!(
+        TAG,
+        "This OverrideAdapter does not support attribute or expiring overrides. Ignoring."
+    );
+    None
 }
@@ -0,0 +1,262 @@
+use crate::evaluation::dynamic_value::DynamicValue;
+use crate::StatsigUser;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideConditionOperator {
+    Equals,
+    NotEquals,
+    StartsWith,
+    EndsWith,
+    Contains,
+    AnyOf,
+    NoneOf,
+}
+
+/// A single check against a user attribute. `field` accepts the top level user fields
+/// (`userID`, `email`, `ip`, `country`, `locale`, `appVersion`, `userAgent`), the
+/// environment `tier`, or any key found in `custom`, `privateAttributes` or `customIDs`.
+/// String comparisons are case-insensitive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverrideCondition {
+    pub field: String,
+    pub operator: OverrideConditionOperator,
+    pub values: Vec<String>,
+}
+
+impl OverrideCondition {
+    pub fn new(
+        field: impl Into<String>,
+        operator: OverrideConditionOperator,
+        values: Vec<String>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            operator,
+            values,
+        }
+    }
+
+    pub fn equals(field: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::new(field, OverrideConditionOperator::Equals, vec![value.into()])
+    }
+
+    pub fn ends_with(field: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::new(
+            field,
+            OverrideConditionOperator::EndsWith,
+            vec![value.into()],
+        )
+    }
+
+    pub fn any_of(field: impl Into<String>, values: Vec<String>) -> Self {
+        Self::new(field, OverrideConditionOperator::AnyOf, values)
+    }
+
+    pub fn environment_tier(tier: impl Into<String>) -> Self {
+        Self::equals(ENVIRONMENT_TIER_FIELD, tier)
+    }
+
+    fn matches(&self, user: &StatsigUser, environment_tier: Option<&str>) -> bool {
+        let actual = match get_field_as_string(user, &self.field, environment_tier) {
+            Some(actual) => actual.to_lowercase(),
+            None => return self.operator == OverrideConditionOperator::NoneOf,
+        };
+
+        let mut targets = self.values.iter().map(|v| v.to_lowercase());
+
+        match self.operator {
+            OverrideConditionOperator::Equals | OverrideConditionOperator::AnyOf => {
+                targets.any(|t| actual == t)
+            }
+            OverrideConditionOperator::NotEquals | OverrideConditionOperator::NoneOf => {
+                !targets.any(|t| actual == t)
+            }
+            OverrideConditionOperator::StartsWith => targets.any(|t| actual.starts_with(&t)),
+            OverrideConditionOperator::EndsWith => targets.any(|t| actual.ends_with(&t)),
+            OverrideConditionOperator::Contains => targets.any(|t| actual.contains(&t)),
+        }
+    }
+}
+
+/// Describes which users an override applies to. An empty matcher applies to everyone.
+/// When `id` is set, the user must have a matching user ID or custom ID. All `conditions`
+/// must pass. Once `expires_at_ms` (unix millis) has passed, the override is ignored and
+/// pruned from the adapter.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverrideMatcher {
+    pub id: Option<String>,
+    #[serde(default)]
+    pub conditions: Vec<OverrideCondition>,
+    pub expires_at_ms: Option<u64>,
+    // Set by `expires_in`. The adapter turns it into `expires_at_ms` with its clock.
+    #[serde(skip)]
+    expires_after_ms: Option<u64>,
+}
+
+impl OverrideMatcher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn for_id(id: impl Into<String>) -> Self {
+        Self {
+            id: Some(id.into()),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn from_id(id: Option<&str>) -> Self {
+        Self {
+            id: id.map(str::to_string),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_condition(mut self, condition: OverrideCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    #[must_use]
+    pub fn expires_at(mut self, expires_at_ms: u64) -> Self {
+        self.expires_at_ms = Some(expires_at_ms);
+        self.expires_after_ms = None;
+        self
+    }
+
+    /// Measured from when the override is set, using the adapter's clock
+    /// (`StatsigOptions::clock` when set).
+    #[must_use]
+    pub fn expires_in(mut self, duration: Duration) -> Self {
+        self.expires_at_ms = None;
+        self.expires_after_ms = Some(duration.as_millis() as u64);
+        self
+    }
+
+    /// Turns an `expires_in` duration into `expires_at_ms`, counting from `now_ms`.
+    #[must_use]
+    pub(crate) fn with_expiry_from(mut self, now_ms: u64) -> Self {
+        if let Some(expires_after_ms) = self.expires_after_ms.take() {
+            self.expires_at_ms = Some(now_ms.saturating_add(expires_after_ms));
+        }
+        self
+    }
+
+    /// True when this matcher can be expressed with the original id-only override API.
+    pub fn is_id_only(&self) -> bool {
+        self.conditions.is_empty()
+            && self.expires_at_ms.is_none()
+            && self.expires_after_ms.is_none()
+    }
+
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        matches!(self.expires_at_ms, Some(expires_at) if expires_at <= now_ms)
+    }
+
+    pub fn matches_conditions(&self, user: &StatsigUser, environment_tier: Option<&str>) -> bool {
+        self.conditions
+            .iter()
+            .all(|c| c.matches(user, environment_tier))
+    }
+
+    pub fn matches_id(&self, user: &StatsigUser) -> bool {
+        let id = match &self.id {
+            Some(id) => id,
+            None => return true,
+        };
+
+        if user.get_user_id() == Some(id.as_str()) {
+            return true;
+        }
+
+        user.get_custom_ids()
+            .is_some_and(|ids| ids.values().any(|v| *v == id.as_str()))
+    }
+
+    pub fn matches(&self, user: &StatsigUser, environment_tier: Option<&str>, now_ms: u64) -> bool {
+        !self.is_expired(now_ms)
+            && self.matches_id(user)
+            && self.matches_conditions(user, environment_tier)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideSpecType {
+    Gate,
+    DynamicConfig,
+    Experiment,
+    Layer,
+    ParameterStore,
+}
+
+/// An override currently held by an [`crate::OverrideAdapter`], as returned by
+/// [`crate::Statsig::list_overrides`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActiveOverride {
+    pub spec_type: OverrideSpecType,
+    pub name: String,
+    pub matcher: OverrideMatcher,
+    pub value: Value,
+    pub group_name: Option<String>,
+}
+
+pub(crate) const ENVIRONMENT_TIER_FIELD: &str = "tier";
+
+fn get_field_as_string(
+    user: &StatsigUser,
+    field: &str,
+    environment_tier: Option<&str>,
+) -> Option<String> {
+    let data = &user.data;
+    let lowered = field.to_lowercase();
+
+    let top_level = match lowered.as_str() {
+        "userid" | "user_id" => &data.user_id,
+        "email" => &data.email,
+        "ip" => &data.ip,
+        "country" => &data.country,
+        "locale" => &data.locale,
+        "appversion" | "app_version" => &data.app_version,
+        "useragent" | "user_agent" => &data.user_agent,
+        ENVIRONMENT_TIER_FIELD | "environment" => {
+            let from_user = data
+                .statsig_environment
+                .as_ref()
+                .and_then(|env| env.get(ENVIRONMENT_TIER_FIELD))
+                .and_then(dynamic_value_to_string);
+            return from_user.or_else(|| environment_tier.map(str::to_string));
+        }
+        _ => &None,
+    };
+
+    if let Some(value) = top_level.as_ref().and_then(dynamic_value_to_string) {
+        return Some(value);
+    }
+
+    [&data.custom, &data.private_attributes, &data.custom_ids]
+        .into_iter()
+        .flatten()
+        .find_map(|map| map.get(field).or_else(|| map.get(lowered.as_str())))
+        .and_then(dynamic_value_to_string)
+}
+
+fn dynamic_value_to_string(value: &DynamicValue) -> Option<String> {
+    if let Some(s) = &value.string_value {
+        return Some(s.value.to_string());
+    }
+
+    match &value.json_value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
@@ -0,0 +1,666 @@
+use super::override_adapter_trait::OverridesUpdateListener;
+use super::override_matcher::{ActiveOverride, OverrideMatcher, OverrideSpecType};
+use super::statsig_local_override_adapter::StatsigLocalOverrideAdapter;
+use crate::clock::{get_clock, Clock};
+use crate::data_store_interface::{get_data_store_key, DataStoreTrait, RequestPath};
+use crate::evaluation::evaluator_result::EvaluatorResult;
+use crate::hashing::HashUtil;
+use crate::specs_adapter::DEFAULT_SYNC_INTERVAL_MS;
+use crate::specs_response::spec_types::Spec;
+use crate::{
+    log_d, log_e, log_w, OverrideAdapter, StatsigErr, StatsigOptions, StatsigRuntime, StatsigUser,
+};
+use arc_swap::ArcSwap;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+
+const TAG: &str = stringify!(StatsigDataStoreOverrideAdapter);
+const OVERRIDES_SOURCE: &str = "DataStore";
+const MAX_WRITE_ATTEMPTS: u32 = 5;
+
+/// An override stored in the data store, along with who set it and when.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SharedOverrideRecord {
+    #[serde(flatten)]
+    pub active: ActiveOverride,
+    pub set_by: Option<String>,
+    pub set_at_ms: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct SharedOverridesDocument {
+    #[serde(default)]
+    overrides: Vec<SharedOverrideRecord>,
+}
+
+enum PendingOp {
+    Upsert(SharedOverrideRecord),
+    RemoveMatcher(OverrideSpecType, String, OverrideMatcher),
+    RemoveId(OverrideSpecType, String, Option<String>),
+    RemoveAll,
+}
+
+/// Shares overrides across every process pointed at the same [`DataStoreTrait`].
+///
+/// Overrides set through the [`OverrideAdapter`] methods (eg. [`crate::Statsig::override_gate`])
+/// apply to this process immediately and are written to the data store in the background.
+/// Other processes pick them up on their next poll.
+///
+/// Precedence, highest first:
+/// 1. Process-local overrides set through [`Self::local_overrides`]
+/// 2. Shared overrides from the data store
+///
+/// The data store has no compare-and-swap, so writes re-read the latest document before
+/// applying queued changes. Two processes writing in the same instant can still race.
+/// Changes that fail to be written `MAX_WRITE_ATTEMPTS` times in a row are dropped, and the
+/// next sync replaces them with what the data store holds.
+pub struct StatsigDataStoreOverrideAdapter {
+    data_store: Arc<dyn DataStoreTrait>,
+    data_store_key: String,
+    sync_interval: Duration,
+    set_by: Option<String>,
+    local_overrides: StatsigLocalOverrideAdapter,
+    shared_records: Mutex<Vec<SharedOverrideRecord>>,
+    shared_overrides: ArcSwap<StatsigLocalOverrideAdapter>,
+    pending_ops: Mutex<Vec<PendingOp>>,
+    failed_write_attempts: AtomicU32,
+    // held by writes and syncs, so a sync cannot replace records with a document fetched
+    // before a write finished
+    persist_lock: tokio::sync::Mutex<()>,
+    write_notify: Arc<Notify>,
+    environment_tier: Option<String>,
+    clock: Arc<dyn Clock>,
+    listener: RwLock<Option<Arc<dyn OverridesUpdateListener>>>,
+}
+
+impl StatsigDataStoreOverrideAdapter {
+    /// `set_by` is recorded on every override written by this process, for auditing.
+    #[must_use]
+    pub fn new(
+        sdk_key: &str,
+        data_store: Arc<dyn DataStoreTrait>,
+        options: Option<&StatsigOptions>,
+        set_by: Option<String>,
+    ) -> Self {
+        let default_options = StatsigOptions::default();
+        let options_ref = options.unwrap_or(&default_options);
+
+        let data_store_key = get_data_store_key(
+            RequestPath::Overrides,
+            sdk_key,
+            &HashUtil::new(),
+            options_ref,
+        );
+
+        let clock = get_clock(Some(options_ref));
+        let environment_tier = options_ref.environment.clone();
+        let new_overrides = || {
+            StatsigLocalOverrideAdapter::with_environment_tier(environment_tier.clone())
+                .with_clock(clock.clone())
+        };
+
+        Self {
+            data_store,
+            data_store_key,
+            sync_interval: Duration::from_millis(u64::from(
+                options_ref
+                    .specs_sync_interval_ms
+                    .unwrap_or(DEFAULT_SYNC_INTERVAL_MS),
+            )),
+            set_by,
+            local_overrides: new_overrides(),
+            shared_records: Mutex::new(Vec::new()),
+            shared_overrides: ArcSwap::from_pointee(new_overrides()),
+            pending_ops: Mutex::new(Vec::new()),
+            failed_write_attempts: AtomicU32::new(0),
+            persist_lock: tokio::sync::Mutex::new(()),
+            write_notify: Arc::new(Notify::new()),
+            environment_tier,
+            clock,
+            listener: RwLock::new(None),
+        }
+    }
+
+    /// Overrides that only apply to this process. These take precedence over shared overrides
+    /// and are never written to the data store.
+    pub fn local_overrides(&self) -> &StatsigLocalOverrideAdapter {
+        &self.local_overrides
+    }
+
+    /// The shared overrides currently known to this process, including audit details.
+    pub fn list_shared_overrides(&self) -> Vec<SharedOverrideRecord> {
+        let now = self.clock.now_ms();
+        self.shared_records
+            .lock()
+            .iter()
+            .filter(|r| !r.active.matcher.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Fetches the latest shared overrides from the data store.
+    pub async fn sync_from_data_store(&self) -> Result<(), StatsigErr> {
+        let _guard = self.persist_lock.lock().await;
+
+        let mut records = self.fetch_document().await?.overrides;
+
+        // Keep changes that are still waiting to be written
+        for op in self.pending_ops.lock().iter() {
+            apply_op(&mut records, op);
+        }
+
+        if *self.shared_records.lock() == records {
+            return Ok(());
+        }
+
+        let override_count = records.len();
+        self.replace_shared_records(records);
+        if let Some(listener) = self.listener.read().as_ref() {
+            listener.did_reload_overrides(OVERRIDES_SOURCE, override_count);
+        }
+        Ok(())
+    }
+
+    /// Writes any queued changes to the data store.
+    pub async fn flush_pending_writes(&self) -> Result<(), StatsigErr> {
+        let _guard = self.persist_lock.lock().await;
+
+        let ops: Vec<PendingOp> = std::mem::take(&mut *self.pending_ops.lock());
+        if ops.is_empty() {
+            return Ok(());
+        }
+
+        let result = self.write_ops(&ops).await;
+        let Err(e) = &result else {
+            self.failed_write_attempts.store(0, Ordering::Relaxed);
+            return result;
+        };
+
+        let attempts = self.failed_write_attempts.fetch_add(1, Ordering::Relaxed) + 1;
+        if attempts >= MAX_WRITE_ATTEMPTS {
+            self.failed_write_attempts.store(0, Ordering::Relaxed);
+            log_e!(
+                TAG,
+                "Dropping {} override change(s) after {} failed writes: {}",
+                ops.len(),
+                attempts,
+                e
+            );
+            return result;
+        }
+
+        // Put the changes back in front of anything queued since
+        let mut pending = self.pending_ops.lock();
+        let newer = std::mem::replace(&mut *pending, ops);
+        pending.extend(newer);
+        drop(pending);
+
+        result
+    }
+
+    async fn write_ops(&self, ops: &[PendingOp]) -> Result<(), StatsigErr> {
+        let mut records = self.fetch_document().await?.overrides;
+        for op in ops {
+            apply_op(&mut records, op);
+        }
+
+        let now = self.clock.now_ms();
+        records.retain(|r| !r.active.matcher.is_expired(now));
+
+        let document = SharedOverridesDocument { overrides: records };
+        let serialized = serde_json::to_string(&document)
+            .map_err(|e| StatsigErr::SerializationError(e.to_string()))?;
+
+        self.data_store
+            .set(&self.data_store_key, &serialized, Some(now))
+            .await?;
+
+        let mut records = document.overrides;
+        for op in self.pending_ops.lock().iter() {
+            apply_op(&mut records, op);
+        }
+        self.replace_shared_records(records);
+
+        log_d!(TAG, "Wrote {} override change(s) to data store", ops.len());
+        Ok(())
+    }
+
+    async fn fetch_document(&self) -> Result<SharedOverridesDocument, StatsigErr> {
+        let response = self.data_store.get(&self.data_store_key).await?;
+        match response.result {
+            Some(data) if !data.is_empty() => serde_json::from_str(&data)
+                .map_err(|e| StatsigErr::JsonParseError(TAG.to_string(), e.to_string())),
+            _ => Ok(SharedOverridesDocument::default()),
+        }
+    }
+
+    fn replace_shared_records(&self, records: Vec<SharedOverrideRecord>) {
+        let overrides =
+            StatsigLocalOverrideAdapter::with_environment_tier(self.environment_tier.clone())
+                .with_clock(self.clock.clone());
+        for record in &records {
+            if let Err(e) = overrides.insert_active_override(record.active.clone()) {
+                log_w!(TAG, "Skipping shared override: {}", e);
+            }
+        }
+
+        *self.shared_records.lock() = records;
+        self.shared_overrides.store(Arc::new(overrides));
+    }
+
+    fn enqueue(&self, op: PendingOp) {
+        {
+            let mut records = self.shared_records.lock();
+            apply_op(&mut records, &op);
+            let snapshot = records.clone();
+            drop(records);
+            self.replace_shared_records(snapshot);
+        }
+
+        self.pending_ops.lock().push(op);
+        // Wakes the background task. Before start, changes are written on the first sync.
+        self.write_notify.notify_one();
+    }
+
+    fn enqueue_upsert(
+        &self,
+        spec_type: OverrideSpecType,
+        name: &str,
+        value: Value,
+        group_name: Option<String>,
+        matcher: OverrideMatcher,
+    ) {
+        let now = self.clock.now_ms();
+        self.enqueue(PendingOp::Upsert(SharedOverrideRecord {
+            active: ActiveOverride {
+                spec_type,
+                name: name.to_string(),
+                matcher: matcher.with_expiry_from(now),
+                value,
+                group_name,
+            },
+            set_by: self.set_by.clone(),
+            set_at_ms: now,
+        }));
+    }
+
+    async fn run_flush(weak_self: &Weak<Self>) {
+        let strong_self = match weak_self.upgrade() {
+            Some(strong_self) => strong_self,
+            None => return,
+        };
+
+        if let Err(e) = strong_self.flush_pending_writes().await {
+            log_e!(TAG, "Failed to write overrides to data store: {}", e);
+        }
+    }
+
+    async fn run_background_sync(weak_self: &Weak<Self>) {
+        let strong_self = match weak_self.upgrade() {
+            Some(strong_self) => strong_self,
+            None => return,
+        };
+
+        Self::run_flush(weak_self).await;
+
+        if let Err(e) = strong_self.sync_from_data_store().await {
+            log_w!(TAG, "Failed to sync overrides from data store: {}", e);
+        }
+    }
+}
+
+impl OverrideAdapter for StatsigDataStoreOverrideAdapter {
+    fn initialize(&self, listener: Arc<dyn OverridesUpdateListener>) {
+        *self.listener.write() = Some(listener);
+    }
+
+    fn start(self: Arc<Self>, statsig_runtime: &Arc<StatsigRuntime>) -> Result<(), StatsigErr> {
+        let weak_self = Arc::downgrade(&self);
+        let interval_duration = self.sync_interval;
+        let write_notify = self.write_notify.clone();
+
+        statsig_runtime.spawn(
+            "data_store_override_bg_sync",
+            move |rt_shutdown_notify| async move {
+                Self::run_background_sync(&weak_self).await;
+
+                loop {
+                    tokio::select! {
+                        () = sleep(interval_duration) => {
+                            Self::run_background_sync(&weak_self).await;
+                        }
+                        () = write_notify.notified() => {
+                            Self::run_flush(&weak_self).await;
+                        }
+                        () = rt_shutdown_notify.notified() => {
+                            log_d!(TAG, "Runtime shutdown. Shutting down override background sync");
+                            break;
+                        }
+                    }
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn get_gate_override(
+        &self,
+        user: &StatsigUser,
+        gate_name: &str,
+        result: &mut EvaluatorResult,
+    ) -> bool {
+        self.local_overrides
+            .get_gate_override(user, gate_name, result)
+            || self
+                .shared_overrides
+                .load()
+                .get_gate_override(user, gate_name, result)
+    }
+
+    fn get_dynamic_config_override(
+        &self,
+        user: &StatsigUser,
+        dynamic_config_name: &str,
+        result: &mut EvaluatorResult,
+    ) -> bool {
+        self.local_overrides
+            .get_dynamic_config_override(user, dynamic_config_name, result)
+            || self.shared_overrides.load().get_dynamic_config_override(
+                user,
+                dynamic_config_name,
+                result,
+            )
+    }
+
+    fn get_experiment_override(
+        &self,
+        user: &StatsigUser,
+        experiment_name: &str,
+        result: &mut EvaluatorResult,
+        opt_spec: Option<&Spec>,
+    ) -> bool {
+        self.local_overrides
+            .get_experiment_override(user, experiment_name, result, opt_spec)
+            || self.shared_overrides.load().get_experiment_override(
+                user,
+                experiment_name,
+                result,
+                opt_spec,
+            )
+    }
+
+    fn get_layer_override(
+        &self,
+        user: &StatsigUser,
+        layer_name: &str,
+        result: &mut EvaluatorResult,
+    ) -> bool {
+        self.local_overrides
+            .get_layer_override(user, layer_name, result)
+            || self
+                .shared_overrides
+                .load()
+                .get_layer_override(user, layer_name, result)
+    }
+
+    fn get_parameter_store_override(
+        &self,
+        user: &StatsigUser,
+        parameter_store_name: &str,
+        result: &mut EvaluatorResult,
+    ) -> bool {
+        self.local_overrides
+            .get_parameter_store_override(user, parameter_store_name, result)
+            || self.shared_overrides.load().get_parameter_store_override(
+                user,
+                parameter_store_name,
+                result,
+            )
+    }
+
+    fn override_gate(&self, key: &str, value: bool, id: Option<&str>) {
+        self.override_gate_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn override_dynamic_config(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.override_dynamic_config_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn override_experiment(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.override_experiment_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn override_experiment_by_group_name(&self, key: &str, group_name: &str, id: Option<&str>) {
+        self.override_experiment_by_group_name_with_matcher(
+            key,
+            group_name,
+            OverrideMatcher::from_id(id),
+        );
+    }
+
+    fn override_layer(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.override_layer_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn override_parameter_store(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.override_parameter_store_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn remove_gate_override(&self, key: &str, id: Option<&str>) {
+        self.enqueue(PendingOp::RemoveId(
+            OverrideSpecType::Gate,
+            key.to_string(),
+            id.map(str::to_string),
+        ));
+    }
+
+    fn remove_dynamic_config_override(&self, key: &str, id: Option<&str>) {
+        self.enqueue(PendingOp::RemoveId(
+            OverrideSpecType::DynamicConfig,
+            key.to_string(),
+            id.map(str::to_string),
+        ));
+    }
+
+    fn remove_experiment_override(&self, key: &str, id: Option<&str>) {
+        self.enqueue(PendingOp::RemoveId(
+            OverrideSpecType::Experiment,
+            key.to_string(),
+            id.map(str::to_string),
+        ));
+    }
+
+    fn remove_layer_override(&self, key: &str, id: Option<&str>) {
+        self.enqueue(PendingOp::RemoveId(
+            OverrideSpecType::Layer,
+            key.to_string(),
+            id.map(str::to_string),
+        ));
+    }
+
+    fn remove_parameter_store_override(&self, key: &str, id: Option<&str>) {
+        self.enqueue(PendingOp::RemoveId(
+            OverrideSpecType::ParameterStore,
+            key.to_string(),
+            id.map(str::to_string),
+        ));
+    }
+
+    fn remove_all_overrides(&self) {
+        self.enqueue(PendingOp::RemoveAll);
+    }
+
+    fn override_gate_with_matcher(&self, key: &str, value: bool, matcher: OverrideMatcher) {
+        self.enqueue_upsert(
+            OverrideSpecType::Gate,
+            key,
+            Value::Bool(value),
+            None,
+            matcher,
+        );
+    }
+
+    fn override_dynamic_config_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        self.enqueue_upsert(
+            OverrideSpecType::DynamicConfig,
+            key,
+            Value::Object(value.into_iter().collect()),
+            None,
+            matcher,
+        );
+    }
+
+    fn override_experiment_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        self.enqueue_upsert(
+            OverrideSpecType::Experiment,
+            key,
+            Value::Object(value.into_iter().collect()),
+            None,
+            matcher,
+        );
+    }
+
+    fn override_experiment_by_group_name_with_matcher(
+        &self,
+        key: &str,
+        group_name: &str,
+        matcher: OverrideMatcher,
+    ) {
+        self.enqueue_upsert(
+            OverrideSpecType::Experiment,
+            key,
+            Value::Null,
+            Some(group_name.to_string()),
+            matcher,
+        );
+    }
+
+    fn override_layer_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        self.enqueue_upsert(
+            OverrideSpecType::Layer,
+            key,
+            Value::Object(value.into_iter().collect()),
+            None,
+            matcher,
+        );
+    }
+
+    fn override_parameter_store_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        self.enqueue_upsert(
+            OverrideSpecType::ParameterStore,
+            key,
+            Value::Object(value.into_iter().collect()),
+            None,
+            matcher,
+        );
+    }
+
+    fn remove_gate_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.enqueue(PendingOp::RemoveMatcher(
+            OverrideSpecType::Gate,
+            key.to_string(),
+            matcher.clone(),
+        ));
+    }
+
+    fn remove_dynamic_config_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.enqueue(PendingOp::RemoveMatcher(
+            OverrideSpecType::DynamicConfig,
+            key.to_string(),
+            matcher.clone(),
+        ));
+    }
+
+    fn remove_experiment_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.enqueue(PendingOp::RemoveMatcher(
+            OverrideSpecType::Experiment,
+            key.to_string(),
+            matcher.clone(),
+        ));
+    }
+
+    fn remove_layer_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.enqueue(PendingOp::RemoveMatcher(
+            OverrideSpecType::Layer,
+            key.to_string(),
+            matcher.clone(),
+        ));
+    }
+
+    fn remove_parameter_store_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.enqueue(PendingOp::RemoveMatcher(
+            OverrideSpecType::ParameterStore,
+            key.to_string(),
+            matcher.clone(),
+        ));
+    }
+
+    fn list_overrides(&self) -> Vec<ActiveOverride> {
+        let mut listed = self.local_overrides.list_overrides();
+        listed.extend(self.shared_overrides.load().list_overrides());
+        listed
+    }
+}
+
+fn is_same_target(
+    record: &SharedOverrideRecord,
+    spec_type: &OverrideSpecType,
+    name: &str,
+    matcher: &OverrideMatcher,
+) -> bool {
+    let active = &record.active;
+    active.spec_type == *spec_type
+        && active.name == name
+        && active.matcher.id == matcher.id
+        && active.matcher.conditions == matcher.conditions
+}
+
+fn apply_op(records: &mut Vec<SharedOverrideRecord>, op: &PendingOp) {
+    match op {
+        PendingOp::Upsert(record) => {
+            let active = &record.active;
+            records
+                .retain(|r| !is_same_target(r, &active.spec_type, &active.name, &active.matcher));
+            records.push(record.clone());
+        }
+        PendingOp::RemoveMatcher(spec_type, name, matcher) => {
+            records.retain(|r| !is_same_target(r, spec_type, name, matcher));
+        }
+        PendingOp::RemoveId(spec_type, name, id) => {
+            records.retain(|r| {
+                let active = &r.active;
+                let same_spec = active.spec_type == *spec_type && active.name == *name;
+                !(same_spec && (id.is_none() || active.matcher.id == *id))
+            });
+        }
+        PendingOp::RemoveAll => records.clear(),
+    }
+}
@@ -0,0 +1,521 @@
+use super::override_adapter_trait::OverridesUpdateListener;
+use super::override_matcher::{ActiveOverride, OverrideMatcher};
+use super::statsig_local_override_adapter::StatsigLocalOverrideAdapter;
+use crate::clock::{get_clock, Clock};
+use crate::evaluation::evaluator_result::EvaluatorResult;
+use crate::hashing::hash_one;
+use crate::specs_response::spec_types::Spec;
+use crate::{
+    log_d, log_e, OverrideAdapter, StatsigErr, StatsigOptions, StatsigRuntime, StatsigUser,
+};
+use arc_swap::ArcSwap;
+use parking_lot::{Mutex, RwLock};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::time::sleep;
+
+const TAG: &str = stringify!(StatsigFileOverrideAdapter);
+const OVERRIDES_SOURCE: &str = "File";
+const DEFAULT_POLL_INTERVAL_MS: u64 = 1_000;
+
+/// Loads overrides from a JSON or YAML file (chosen by the `.yaml`/`.yml` extension) and
+/// polls it for changes once started. A file that fails to parse is reported through the
+/// output logger and the previously loaded overrides are kept.
+///
+/// Overrides set at runtime (eg. [`crate::Statsig::override_gate`]) take precedence over
+/// the file and are not affected by reloads.
+///
+/// ```json
+/// {
+///   "gates": {
+///     "kill_switch": false,
+///     "beta_feature": [{ "value": true, "conditions": [{ "field": "email", "operator": "ends_with", "values": ["@ourco.com"] }] }]
+///   },
+///   "dynamic_configs": { "a_config": { "value": { "limit": 10 } } },
+///   "experiments": { "an_experiment": { "group_name": "Control", "id": "a_user" } },
+///   "layers": { "a_layer": { "value": { "color": "red" } } },
+///   "parameter_stores": { "a_store": { "value": { "a_param": 1 } } }
+/// }
+/// ```
+pub struct StatsigFileOverrideAdapter {
+    file_path: String,
+    poll_interval: Duration,
+    runtime_overrides: StatsigLocalOverrideAdapter,
+    file_overrides: ArcSwap<StatsigLocalOverrideAdapter>,
+    last_content_hash: Mutex<Option<u64>>,
+    last_reload_error: Mutex<Option<String>>,
+    environment_tier: Option<String>,
+    clock: Arc<dyn Clock>,
+    listener: RwLock<Option<Arc<dyn OverridesUpdateListener>>>,
+}
+
+impl StatsigFileOverrideAdapter {
+    /// `options` should be the ones given to [`crate::Statsig`], so `tier` conditions and
+    /// expiry see the same environment and clock.
+    #[must_use]
+    pub fn new(
+        file_path: &str,
+        poll_interval_ms: Option<u64>,
+        options: Option<&StatsigOptions>,
+    ) -> Self {
+        let environment_tier = options.and_then(|o| o.environment.clone());
+        let clock = get_clock(options);
+        let new_overrides = || {
+            StatsigLocalOverrideAdapter::with_environment_tier(environment_tier.clone())
+                .with_clock(clock.clone())
+        };
+
+        let adapter = Self {
+            file_path: file_path.to_string(),
+            poll_interval: Duration::from_millis(
+                poll_interval_ms.unwrap_or(DEFAULT_POLL_INTERVAL_MS),
+            ),
+            runtime_overrides: new_overrides(),
+            file_overrides: ArcSwap::from_pointee(new_overrides()),
+            last_content_hash: Mutex::new(None),
+            last_reload_error: Mutex::new(None),
+            environment_tier,
+            clock,
+            listener: RwLock::new(None),
+        };
+
+        if let Err(e) = adapter.reload() {
+            log_e!(TAG, "Failed to load overrides from {}: {}", file_path, e);
+        }
+
+        adapter
+    }
+
+    /// Re-reads the file and swaps in its overrides. On error the current overrides are kept.
+    pub fn reload(&self) -> Result<(), StatsigErr> {
+        let bytes = std::fs::read(&self.file_path).map_err(|e| {
+            StatsigErr::FileError(format!("Failed to read {}: {e}", self.file_path))
+        })?;
+
+        let content_hash = hash_one(&bytes);
+        let mut last_content_hash = self.last_content_hash.lock();
+        if *last_content_hash == Some(content_hash) {
+            return Ok(());
+        }
+
+        // Remember the hash even on failure so a bad file is only reported once
+        *last_content_hash = Some(content_hash);
+        drop(last_content_hash);
+
+        let file = parse_overrides_file(&self.file_path, &bytes)?;
+        let overrides =
+            StatsigLocalOverrideAdapter::with_environment_tier(self.environment_tier.clone())
+                .with_clock(self.clock.clone());
+        build_overrides(file, &overrides)?;
+        let override_count = overrides.list_overrides().len();
+        self.file_overrides.store(Arc::new(overrides));
+
+        log_d!(TAG, "Loaded overrides from {}", self.file_path);
+        if let Some(listener) = self.listener.read().as_ref() {
+            listener.did_reload_overrides(OVERRIDES_SOURCE, override_count);
+        }
+        Ok(())
+    }
+
+    async fn run_background_reload(weak_self: &Weak<Self>) {
+        let strong_self = match weak_self.upgrade() {
+            Some(strong_self) => strong_self,
+            None => return,
+        };
+
+        let error = strong_self.reload().err().map(|e| e.to_string());
+        let mut last_error = strong_self.last_reload_error.lock();
+        if *last_error == error {
+            return;
+        }
+
+        // Only report when the error changes, the file is polled frequently
+        if let Some(e) = &error {
+            log_e!(
+                TAG,
+                "Failed to reload overrides from {}, keeping previous overrides: {}",
+                strong_self.file_path,
+                e
+            );
+        }
+        *last_error = error;
+    }
+}
+
+impl OverrideAdapter for StatsigFileOverrideAdapter {
+    fn initialize(&self, listener: Arc<dyn OverridesUpdateListener>) {
+        *self.listener.write() = Some(listener);
+    }
+
+    fn start(self: Arc<Self>, statsig_runtime: &Arc<StatsigRuntime>) -> Result<(), StatsigErr> {
+        let weak_self = Arc::downgrade(&self);
+        let interval_duration = self.poll_interval;
+
+        statsig_runtime.spawn(
+            "file_override_bg_reload",
+            move |rt_shutdown_notify| async move {
+                loop {
+                    tokio::select! {
+                        () = sleep(interval_duration) => {
+                            Self::run_background_reload(&weak_self).await;
+                        }
+                        () = rt_shutdown_notify.notified() => {
+                            log_d!(TAG, "Runtime shutdown. Shutting down override file watcher");
+                            break;
+                        }
+                    }
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn get_gate_override(
+        &self,
+        user: &StatsigUser,
+        gate_name: &str,
+        result: &mut EvaluatorResult,
+    ) -> bool {
+        self.runtime_overrides
+            .get_gate_override(user, gate_name, result)
+            || self
+                .file_overrides
+                .load()
+                .get_gate_override(user, gate_name, result)
+    }
+
+    fn get_dynamic_config_override(
+        &self,
+        user: &StatsigUser,
+        dynamic_config_name: &str,
+        result: &mut EvaluatorResult,
+    ) -> bool {
+        self.runtime_overrides
+            .get_dynamic_config_override(user, dynamic_config_name, result)
+            || self.file_overrides.load().get_dynamic_config_override(
+                user,
+                dynamic_config_name,
+                result,
+            )
+    }
+
+    fn get_experiment_override(
+        &self,
+        user: &StatsigUser,
+        experiment_name: &str,
+        result: &mut EvaluatorResult,
+        opt_spec: Option<&Spec>,
+    ) -> bool {
+        self.runtime_overrides
+            .get_experiment_override(user, experiment_name, result, opt_spec)
+            || self.file_overrides.load().get_experiment_override(
+                user,
+                experiment_name,
+                result,
+                opt_spec,
+            )
+    }
+
+    fn get_layer_override(
+        &self,
+        user: &StatsigUser,
+        layer_name: &str,
+        result: &mut EvaluatorResult,
+    ) -> bool {
+        self.runtime_overrides
+            .get_layer_override(user, layer_name, result)
+            || self
+                .file_overrides
+                .load()
+                .get_layer_override(user, layer_name, result)
+    }
+
+    fn get_parameter_store_override(
+        &self,
+        user: &StatsigUser,
+        parameter_store_name: &str,
+        result: &mut EvaluatorResult,
+    ) -> bool {
+        self.runtime_overrides
+            .get_parameter_store_override(user, parameter_store_name, result)
+            || self.file_overrides.load().get_parameter_store_override(
+                user,
+                parameter_store_name,
+                result,
+            )
+    }
+
+    fn override_gate(&self, key: &str, value: bool, id: Option<&str>) {
+        self.runtime_overrides.override_gate(key, value, id);
+    }
+
+    fn override_dynamic_config(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.runtime_overrides
+            .override_dynamic_config(key, value, id);
+    }
+
+    fn override_experiment(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.runtime_overrides.override_experiment(key, value, id);
+    }
+
+    fn override_experiment_by_group_name(&self, key: &str, group_name: &str, id: Option<&str>) {
+        self.runtime_overrides
+            .override_experiment_by_group_name(key, group_name, id);
+    }
+
+    fn override_layer(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.runtime_overrides.override_layer(key, value, id);
+    }
+
+    fn override_parameter_store(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.runtime_overrides
+            .override_parameter_store(key, value, id);
+    }
+
+    fn remove_gate_override(&self, key: &str, id: Option<&str>) {
+        self.runtime_overrides.remove_gate_override(key, id);
+    }
+
+    fn remove_dynamic_config_override(&self, key: &str, id: Option<&str>) {
+        self.runtime_overrides
+            .remove_dynamic_config_override(key, id);
+    }
+
+    fn remove_experiment_override(&self, key: &str, id: Option<&str>) {
+        self.runtime_overrides.remove_experiment_override(key, id);
+    }
+
+    fn remove_layer_override(&self, key: &str, id: Option<&str>) {
+        self.runtime_overrides.remove_layer_override(key, id);
+    }
+
+    fn remove_parameter_store_override(&self, key: &str, id: Option<&str>) {
+        self.runtime_overrides
+            .remove_parameter_store_override(key, id);
+    }
+
+    fn remove_all_overrides(&self) {
+        self.runtime_overrides.remove_all_overrides();
+    }
+
+    fn override_gate_with_matcher(&self, key: &str, value: bool, matcher: OverrideMatcher) {
+        self.runtime_overrides
+            .override_gate_with_matcher(key, value, matcher);
+    }
+
+    fn override_dynamic_config_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        self.runtime_overrides
+            .override_dynamic_config_with_matcher(key, value, matcher);
+    }
+
+    fn override_experiment_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        self.runtime_overrides
+            .override_experiment_with_matcher(key, value, matcher);
+    }
+
+    fn override_experiment_by_group_name_with_matcher(
+        &self,
+        key: &str,
+        group_name: &str,
+        matcher: OverrideMatcher,
+    ) {
+        self.runtime_overrides
+            .override_experiment_by_group_name_with_matcher(key, group_name, matcher);
+    }
+
+    fn override_layer_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        self.runtime_overrides
+            .override_layer_with_matcher(key, value, matcher);
+    }
+
+    fn override_parameter_store_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
+        self.runtime_overrides
+            .override_parameter_store_with_matcher(key, value, matcher);
+    }
+
+    fn remove_gate_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.runtime_overrides
+            .remove_gate_override_with_matcher(key, matcher);
+    }
+
+    fn remove_dynamic_config_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.runtime_overrides
+            .remove_dynamic_config_override_with_matcher(key, matcher);
+    }
+
+    fn remove_experiment_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.runtime_overrides
+            .remove_experiment_override_with_matcher(key, matcher);
+    }
+
+    fn remove_layer_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.runtime_overrides
+            .remove_layer_override_with_matcher(key, matcher);
+    }
+
+    fn remove_parameter_store_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
+        self.runtime_overrides
+            .remove_parameter_store_override_with_matcher(key, matcher);
+    }
+
+    fn list_overrides(&self) -> Vec<ActiveOverride> {
+        let mut listed = self.runtime_overrides.list_overrides();
+        listed.extend(self.file_overrides.load().list_overrides());
+        listed
+    }
+}
+
+// ------------------------------------------------------------------------------- [ File Format ]
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct OverridesFile {
+    gates: HashMap<String, FileOverrideSpec>,
+    dynamic_configs: HashMap<String, FileOverrideSpec>,
+    experiments: HashMap<String, FileOverrideSpec>,
+    layers: HashMap<String, FileOverrideSpec>,
+    parameter_stores: HashMap<String, FileOverrideSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FileOverrideSpec {
+    Many(Vec<FileOverrideEntry>),
+    One(FileOverrideEntry),
+    Bool(bool),
+}
+
+#[derive(Deserialize)]
+struct FileOverrideEntry {
+    value: Option<Value>,
+    group_name: Option<String>,
+    #[serde(flatten)]
+    matcher: OverrideMatcher,
+}
+
+impl FileOverrideSpec {
+    fn into_entries(self) -> Vec<FileOverrideEntry> {
+        match self {
+            FileOverrideSpec::Many(entries) => entries,
+            FileOverrideSpec::One(entry) => vec![entry],
+            FileOverrideSpec::Bool(value) => vec![FileOverrideEntry {
+                value: Some(Value::Bool(value)),
+                group_name: None,
+                matcher: OverrideMatcher::new(),
+            }],
+        }
+    }
+}
+
+fn parse_overrides_file(file_path: &str, bytes: &[u8]) -> Result<OverridesFile, StatsigErr> {
+    let is_yaml = Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"));
+
+    if is_yaml {
+        return serde_yaml::from_slice(bytes)
+            .map_err(|e| StatsigErr::SerializationError(format!("Invalid overrides YAML: {e}")));
+    }
+
+    serde_json::from_slice(bytes)
+        .map_err(|e| StatsigErr::JsonParseError(TAG.to_string(), e.to_string()))
+}
+
+fn build_overrides(
+    file: OverridesFile,
+    adapter: &StatsigLocalOverrideAdapter,
+) -> Result<(), StatsigErr> {
+    for (name, spec) in file.gates {
+        for entry in spec.into_entries() {
+            let value = match entry.value {
+                Some(Value::Bool(value)) => value,
+                _ => return Err(invalid_entry("gate", &name, "expected a boolean value")),
+            };
+            adapter.override_gate_with_matcher(&name, value, entry.matcher);
+        }
+    }
+
+    for (name, spec) in file.dynamic_configs {
+        for entry in spec.into_entries() {
+            let value = take_object_value(entry.value, "dynamic config", &name)?;
+            adapter.override_dynamic_config_with_matcher(&name, value, entry.matcher);
+        }
+    }
+
+    for (name, spec) in file.experiments {
+        for entry in spec.into_entries() {
+            if let Some(group_name) = &entry.group_name {
+                adapter.override_experiment_by_group_name_with_matcher(
+                    &name,
+                    group_name,
+                    entry.matcher,
+                );
+                continue;
+            }
+
+            let value = take_object_value(entry.value, "experiment", &name)?;
+            adapter.override_experiment_with_matcher(&name, value, entry.matcher);
+        }
+    }
+
+    for (name, spec) in file.layers {
+        for entry in spec.into_entries() {
+            let value = take_object_value(entry.value, "layer", &name)?;
+            adapter.override_layer_with_matcher(&name, value, entry.matcher);
+        }
+    }
+
+    for (name, spec) in file.parameter_stores {
+        for entry in spec.into_entries() {
+            let value = take_object_value(entry.value, "parameter store", &name)?;
+            adapter.override_parameter_store_with_matcher(&name, value, entry.matcher);
+        }
+    }
+
+    Ok(())
+}
+
+fn take_object_value(
+    value: Option<Value>,
+    kind: &str,
+    name: &str,
+) -> Result<HashMap<String, Value>, StatsigErr> {
+    match value {
+        Some(Value::Object(map)) => Ok(object_to_hash_map(map)),
+        _ => Err(invalid_entry(kind, name, "expected an object value")),
+    }
+}
+
+fn object_to_hash_map(map: Map<String, Value>) -> HashMap<String, Value> {
+    map.into_iter().collect()
+}
+
+fn invalid_entry(kind: &str, name: &str, reason: &str) -> StatsigErr {
+    StatsigErr::SerializationError(format!("Invalid {kind} override '{name}': {reason}"))
+}
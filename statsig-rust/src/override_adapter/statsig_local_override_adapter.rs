@@ -1,25 +1,110 @@
+use super::override_matcher::{ActiveOverride, OverrideMatcher, OverrideSpecType};
+use crate::clock::{Clock, SystemClock};
 use crate::evaluation::dynamic_returnable::DynamicReturnable;
 use crate::evaluation::evaluator_result::EvaluatorResult;
 use crate::interned_string::InternedString;
 use crate::specs_response::param_store_types::{Parameter, StaticValueParameter};
 use crate::specs_response::spec_types::Spec;
-use crate::{log_d, read_lock_or_return, write_lock_or_noop, OverrideAdapter, StatsigUser};
+use crate::{
+    log_d, read_lock_or_return, write_lock_or_noop, OverrideAdapter, StatsigErr, StatsigUser,
+};
 use parking_lot::RwLock;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 enum ExperimentOverrides {
     Value(DynamicReturnable),
     GroupName(String),
 }
 
+struct OverrideRecord<T> {
+    matcher: OverrideMatcher,
+    value: T,
+}
+
+struct SpecOverrides<T> {
+    // Overrides without attribute conditions, keyed by id (or NO_ID_OVERRIDE)
+    by_id: HashMap<String, OverrideRecord<T>>,
+    // Attribute based overrides, most recently set last
+    conditional: Vec<OverrideRecord<T>>,
+}
+
+impl<T> Default for SpecOverrides<T> {
+    fn default() -> Self {
+        Self {
+            by_id: HashMap::new(),
+            conditional: Vec::new(),
+        }
+    }
+}
+
+impl<T> SpecOverrides<T> {
+    fn insert(&mut self, matcher: OverrideMatcher, value: T, now: u64) {
+        self.prune_expired(now);
+        let matcher = matcher.with_expiry_from(now);
+
+        if matcher.conditions.is_empty() {
+            let id_key = matcher.id.as_deref().unwrap_or(NO_ID_OVERRIDE).to_string();
+            self.by_id.insert(id_key, OverrideRecord { matcher, value });
+            return;
+        }
+
+        self.conditional
+            .retain(|r| !is_same_target(&r.matcher, &matcher));
+        self.conditional.push(OverrideRecord { matcher, value });
+    }
+
+    fn remove_id(&mut self, id: &str) {
+        self.by_id.remove(id);
+        self.conditional
+            .retain(|r| r.matcher.id.as_deref() != Some(id));
+    }
+
+    fn remove_matcher(&mut self, matcher: &OverrideMatcher) {
+        if matcher.conditions.is_empty() {
+            self.by_id
+                .remove(matcher.id.as_deref().unwrap_or(NO_ID_OVERRIDE));
+            return;
+        }
+
+        self.conditional
+            .retain(|r| !is_same_target(&r.matcher, matcher));
+    }
+
+    fn prune_expired(&mut self, now: u64) {
+        self.by_id.retain(|_, r| !r.matcher.is_expired(now));
+        self.conditional.retain(|r| !r.matcher.is_expired(now));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.by_id.is_empty() && self.conditional.is_empty()
+    }
+
+    fn active_records(&self, now: u64) -> impl Iterator<Item = &OverrideRecord<T>> {
+        self.by_id
+            .values()
+            .chain(self.conditional.iter())
+            .filter(move |r| !r.matcher.is_expired(now))
+    }
+
+    fn get_by_id(&self, id: &str, now: u64) -> Option<&T> {
+        self.by_id
+            .get(id)
+            .filter(|r| !r.matcher.is_expired(now))
+            .map(|r| &r.value)
+    }
+}
+
+type OverrideMap<T> = HashMap<String, SpecOverrides<T>>;
+
 #[derive(Default)]
 struct OverrideStore {
-    pub gate: HashMap<String, HashMap<String, bool>>,
-    pub config: HashMap<String, HashMap<String, DynamicReturnable>>,
-    pub experiment: HashMap<String, HashMap<String, ExperimentOverrides>>,
-    pub layer: HashMap<String, HashMap<String, DynamicReturnable>>,
-    pub parameter_store: HashMap<String, HashMap<String, HashMap<String, Parameter>>>,
+    pub gate: OverrideMap<bool>,
+    pub config: OverrideMap<DynamicReturnable>,
+    pub experiment: OverrideMap<ExperimentOverrides>,
+    pub layer: OverrideMap<DynamicReturnable>,
+    pub parameter_store: OverrideMap<HashMap<String, Parameter>>,
 }
 
 const TAG: &str = stringify!(StatsigLocalOverrideAdapter);
@@ -33,6 +118,9 @@ lazy_static::lazy_static! {
 #[derive(Default)]
 pub struct StatsigLocalOverrideAdapter {
     store: RwLock<OverrideStore>,
+    environment_tier: Option<String>,
+    // Expiry is checked against this clock. The system clock when not set.
+    clock: Option<Arc<dyn Clock>>,
 }
 
 impl StatsigLocalOverrideAdapter {
@@ -40,6 +128,104 @@ impl StatsigLocalOverrideAdapter {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// The tier used for `tier` conditions when the user does not carry its own statsig environment.
+    #[must_use]
+    pub fn with_environment_tier(environment_tier: Option<String>) -> Self {
+        Self {
+            environment_tier,
+            ..Self::default()
+        }
+    }
+
+    /// The clock used for expiry, usually the one given to `StatsigOptions::clock`.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    fn now_ms(&self) -> u64 {
+        match &self.clock {
+            Some(clock) => clock.now_ms(),
+            None => SystemClock.now_ms(),
+        }
+    }
+
+    /// Re-applies an override previously returned by `list_overrides`.
+    pub(crate) fn insert_active_override(&self, active: ActiveOverride) -> Result<(), StatsigErr> {
+        let ActiveOverride {
+            spec_type,
+            name,
+            matcher,
+            value,
+            group_name,
+        } = active;
+
+        match (spec_type, value) {
+            (OverrideSpecType::Gate, Value::Bool(value)) => {
+                self.override_gate_with_matcher(&name, value, matcher);
+            }
+            (OverrideSpecType::Experiment, _) if group_name.is_some() => {
+                let group_name = group_name.unwrap_or_default();
+                self.override_experiment_by_group_name_with_matcher(&name, &group_name, matcher);
+            }
+            (OverrideSpecType::DynamicConfig, Value::Object(map)) => {
+                self.override_dynamic_config_with_matcher(
+                    &name,
+                    map.into_iter().collect(),
+                    matcher,
+                );
+            }
+            (OverrideSpecType::Experiment, Value::Object(map)) => {
+                self.override_experiment_with_matcher(&name, map.into_iter().collect(), matcher);
+            }
+            (OverrideSpecType::Layer, Value::Object(map)) => {
+                self.override_layer_with_matcher(&name, map.into_iter().collect(), matcher);
+            }
+            (OverrideSpecType::ParameterStore, Value::Object(map)) => {
+                self.override_parameter_store_with_matcher(
+                    &name,
+                    map.into_iter().collect(),
+                    matcher,
+                );
+            }
+            (spec_type, _) => {
+                return Err(StatsigErr::SerializationError(format!(
+                    "Invalid {spec_type:?} override value for '{name}'"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn find_override<T, F>(
+        &self,
+        user: &StatsigUser,
+        overrides: &SpecOverrides<T>,
+        apply_override: F,
+        result: &mut EvaluatorResult,
+    ) -> bool
+    where
+        F: Fn(&T, &mut EvaluatorResult),
+    {
+        let found = find_override_for_user(
+            user,
+            overrides,
+            self.environment_tier.as_deref(),
+            self.now_ms(),
+        );
+
+        match found {
+            Some(value) => {
+                apply_override(value, result);
+                mark_result_as_override(result);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl OverrideAdapter for StatsigLocalOverrideAdapter {
@@ -58,7 +244,7 @@ impl OverrideAdapter for StatsigLocalOverrideAdapter {
 
         log_d!(TAG, "gate_overrides found for {}", gate_name);
 
-        find_override_for_user(
+        self.find_override(
             user,
             gate_overrides,
             |value, res| {
@@ -81,7 +267,7 @@ impl OverrideAdapter for StatsigLocalOverrideAdapter {
             None => return false,
         };
 
-        find_override_for_user(
+        self.find_override(
             user,
             config_overrides,
             |value, res| {
@@ -105,7 +291,7 @@ impl OverrideAdapter for StatsigLocalOverrideAdapter {
             None => return false,
         };
 
-        find_override_for_user(
+        self.find_override(
             user,
             experiment_overrides,
             |override_value, res| match override_value {
@@ -133,7 +319,7 @@ impl OverrideAdapter for StatsigLocalOverrideAdapter {
             None => return false,
         };
 
-        find_override_for_user(
+        self.find_override(
             user,
             layer_overrides,
             |value, res| {
@@ -156,73 +342,150 @@ impl OverrideAdapter for StatsigLocalOverrideAdapter {
             None => return false,
         };
 
-        find_override_for_user(
+        self.find_override(
             user,
             parameter_store_overrides,
             |value, res| {
-                let mut serialized = HashMap::with_capacity(value.len());
-                for (param_name, parameter) in value.iter() {
-                    if let Ok(serialized_param) = serde_json::to_value(parameter) {
-                        serialized.insert(param_name.clone(), serialized_param);
-                    }
-                }
-                res.json_value = Some(DynamicReturnable::from_map(serialized));
+                res.json_value = Some(DynamicReturnable::from_map(serialize_parameters(value)));
             },
             result,
         )
     }
 
     fn override_gate(&self, key: &str, value: bool, id: Option<&str>) {
+        self.override_gate_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn override_dynamic_config(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.override_dynamic_config_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn override_experiment(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.override_experiment_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn override_experiment_by_group_name(&self, key: &str, group_name: &str, id: Option<&str>) {
+        self.override_experiment_by_group_name_with_matcher(
+            key,
+            group_name,
+            OverrideMatcher::from_id(id),
+        );
+    }
+
+    fn override_layer(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.override_layer_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn override_parameter_store(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+        self.override_parameter_store_with_matcher(key, value, OverrideMatcher::from_id(id));
+    }
+
+    fn remove_gate_override(&self, key: &str, id: Option<&str>) {
+        let mut store = write_lock_or_noop!(TAG, self.store);
+        remove_override(&mut store.gate, key, id);
+    }
+
+    fn remove_dynamic_config_override(&self, key: &str, id: Option<&str>) {
+        let mut store = write_lock_or_noop!(TAG, self.store);
+        remove_override(&mut store.config, key, id);
+    }
+
+    fn remove_experiment_override(&self, key: &str, id: Option<&str>) {
+        let mut store = write_lock_or_noop!(TAG, self.store);
+        remove_override(&mut store.experiment, key, id);
+    }
+
+    fn remove_layer_override(&self, key: &str, id: Option<&str>) {
+        let mut store = write_lock_or_noop!(TAG, self.store);
+        remove_override(&mut store.layer, key, id);
+    }
+
+    fn remove_parameter_store_override(&self, key: &str, id: Option<&str>) {
+        let mut store = write_lock_or_noop!(TAG, self.store);
+        remove_override(&mut store.parameter_store, key, id);
+    }
+
+    fn remove_all_overrides(&self) {
+        let mut store = write_lock_or_noop!(TAG, self.store);
+        store.gate.clear();
+        store.config.clear();
+        store.experiment.clear();
+        store.layer.clear();
+        store.parameter_store.clear();
+    }
+
+    fn override_gate_with_matcher(&self, key: &str, value: bool, matcher: OverrideMatcher) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        let id_str = id.unwrap_or(NO_ID_OVERRIDE);
         store
             .gate
             .entry(key.to_string())
             .or_default()
-            .insert(id_str.to_string(), value);
+            .insert(matcher, value, self.now_ms());
     }
 
-    fn override_dynamic_config(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+    fn override_dynamic_config_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        let id_str = id.unwrap_or(NO_ID_OVERRIDE);
-        store
-            .config
-            .entry(key.to_string())
-            .or_default()
-            .insert(id_str.to_string(), DynamicReturnable::from_map(value));
+        store.config.entry(key.to_string()).or_default().insert(
+            matcher,
+            DynamicReturnable::from_map(value),
+            self.now_ms(),
+        );
     }
 
-    fn override_experiment(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+    fn override_experiment_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        let id_str = id.unwrap_or(NO_ID_OVERRIDE);
         store.experiment.entry(key.to_string()).or_default().insert(
-            id_str.to_string(),
+            matcher,
             ExperimentOverrides::Value(DynamicReturnable::from_map(value)),
+            self.now_ms(),
         );
     }
 
-    fn override_experiment_by_group_name(&self, key: &str, group_name: &str, id: Option<&str>) {
+    fn override_experiment_by_group_name_with_matcher(
+        &self,
+        key: &str,
+        group_name: &str,
+        matcher: OverrideMatcher,
+    ) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        let id_str = id.unwrap_or(NO_ID_OVERRIDE);
         store.experiment.entry(key.to_string()).or_default().insert(
-            id_str.to_string(),
+            matcher,
             ExperimentOverrides::GroupName(group_name.to_string()),
+            self.now_ms(),
         );
     }
 
-    fn override_layer(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+    fn override_layer_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        let id_str = id.unwrap_or(NO_ID_OVERRIDE);
-        store
-            .layer
-            .entry(key.to_string())
-            .or_default()
-            .insert(id_str.to_string(), DynamicReturnable::from_map(value));
+        store.layer.entry(key.to_string()).or_default().insert(
+            matcher,
+            DynamicReturnable::from_map(value),
+            self.now_ms(),
+        );
     }
 
-    fn override_parameter_store(&self, key: &str, value: HashMap<String, Value>, id: Option<&str>) {
+    fn override_parameter_store_with_matcher(
+        &self,
+        key: &str,
+        value: HashMap<String, Value>,
+        matcher: OverrideMatcher,
+    ) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        let id_str = id.unwrap_or(NO_ID_OVERRIDE);
         let mut parameters = HashMap::with_capacity(value.len());
 
         for (param_name, param_value) in value {
@@ -242,107 +505,173 @@ impl OverrideAdapter for StatsigLocalOverrideAdapter {
             .parameter_store
             .entry(key.to_string())
             .or_default()
-            .insert(id_str.to_string(), parameters);
+            .insert(matcher, parameters, self.now_ms());
     }
 
-    fn remove_gate_override(&self, key: &str, id: Option<&str>) {
+    fn remove_gate_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        match id {
-            None => {
-                store.gate.remove(key);
-            }
-            Some(id_str) => {
-                if let Some(overrides) = store.gate.get_mut(key) {
-                    overrides.remove(&id_str.to_string());
-                }
-            }
-        }
+        remove_override_with_matcher(&mut store.gate, key, matcher);
     }
 
-    fn remove_dynamic_config_override(&self, key: &str, id: Option<&str>) {
+    fn remove_dynamic_config_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        match id {
-            None => {
-                store.config.remove(key);
-            }
-            Some(id_str) => {
-                if let Some(overrides) = store.config.get_mut(key) {
-                    overrides.remove(&id_str.to_string());
-                }
-            }
-        }
+        remove_override_with_matcher(&mut store.config, key, matcher);
     }
 
-    fn remove_experiment_override(&self, key: &str, id: Option<&str>) {
+    fn remove_experiment_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        match id {
-            None => {
-                store.experiment.remove(key);
-            }
-            Some(id_str) => {
-                if let Some(overrides) = store.experiment.get_mut(key) {
-                    overrides.remove(&id_str.to_string());
-                }
-            }
-        }
+        remove_override_with_matcher(&mut store.experiment, key, matcher);
     }
 
-    fn remove_layer_override(&self, key: &str, id: Option<&str>) {
+    fn remove_layer_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        match id {
-            None => {
-                store.layer.remove(key);
-            }
-            Some(id_str) => {
-                if let Some(overrides) = store.layer.get_mut(key) {
-                    overrides.remove(&id_str.to_string());
-                }
-            }
-        }
+        remove_override_with_matcher(&mut store.layer, key, matcher);
     }
 
-    fn remove_parameter_store_override(&self, key: &str, id: Option<&str>) {
+    fn remove_parameter_store_override_with_matcher(&self, key: &str, matcher: &OverrideMatcher) {
         let mut store = write_lock_or_noop!(TAG, self.store);
-        match id {
-            None => {
-                store.parameter_store.remove(key);
-            }
-            Some(id_str) => {
-                if let Some(overrides) = store.parameter_store.get_mut(key) {
-                    overrides.remove(&id_str.to_string());
-                }
+        remove_override_with_matcher(&mut store.parameter_store, key, matcher);
+    }
+
+    fn list_overrides(&self) -> Vec<ActiveOverride> {
+        let store = read_lock_or_return!(TAG, self.store, vec![]);
+        let now = self.now_ms();
+        let mut listed = Vec::new();
+
+        list_active(&mut listed, &store.gate, OverrideSpecType::Gate, now, |v| {
+            (Value::Bool(*v), None)
+        });
+        list_active(
+            &mut listed,
+            &store.config,
+            OverrideSpecType::DynamicConfig,
+            now,
+            |v| (returnable_to_value(v), None),
+        );
+        list_active(
+            &mut listed,
+            &store.experiment,
+            OverrideSpecType::Experiment,
+            now,
+            |v| match v {
+                ExperimentOverrides::Value(map) => (returnable_to_value(map), None),
+                ExperimentOverrides::GroupName(name) => (Value::Null, Some(name.clone())),
+            },
+        );
+        list_active(
+            &mut listed,
+            &store.layer,
+            OverrideSpecType::Layer,
+            now,
+            |v| (returnable_to_value(v), None),
+        );
+        list_active(
+            &mut listed,
+            &store.parameter_store,
+            OverrideSpecType::ParameterStore,
+            now,
+            |v| {
+                (
+                    Value::Object(serialize_parameters(v).into_iter().collect()),
+                    None,
+                )
+            },
+        );
+
+        listed
+    }
+}
+
+fn is_same_target(a: &OverrideMatcher, b: &OverrideMatcher) -> bool {
+    a.id == b.id && a.conditions == b.conditions
+}
+
+fn remove_override<T>(overrides: &mut OverrideMap<T>, key: &str, id: Option<&str>) {
+    match id {
+        None => {
+            overrides.remove(key);
+        }
+        Some(id_str) => {
+            if let Some(spec_overrides) = overrides.get_mut(key) {
+                spec_overrides.remove_id(id_str);
             }
         }
     }
+}
 
-    fn remove_all_overrides(&self) {
-        let mut store = write_lock_or_noop!(TAG, self.store);
-        store.gate.clear();
-        store.config.clear();
-        store.experiment.clear();
-        store.layer.clear();
-        store.parameter_store.clear();
+fn remove_override_with_matcher<T>(
+    overrides: &mut OverrideMap<T>,
+    key: &str,
+    matcher: &OverrideMatcher,
+) {
+    if let Some(spec_overrides) = overrides.get_mut(key) {
+        spec_overrides.remove_matcher(matcher);
+        if spec_overrides.is_empty() {
+            overrides.remove(key);
+        }
     }
 }
 
-fn find_override_for_user<T, F>(
-    user: &StatsigUser,
-    overrides: &HashMap<String, T>,
-    apply_override: F,
-    result: &mut EvaluatorResult,
-) -> bool
-where
-    F: Fn(&T, &mut EvaluatorResult),
+fn list_active<T, F>(
+    listed: &mut Vec<ActiveOverride>,
+    overrides: &OverrideMap<T>,
+    spec_type: OverrideSpecType,
+    now: u64,
+    to_value: F,
+) where
+    F: Fn(&T) -> (Value, Option<String>),
 {
-    if check_user_id_override(user, overrides, &apply_override, result) {
-        return true;
+    for (name, spec_overrides) in overrides {
+        for record in spec_overrides.active_records(now) {
+            let (value, group_name) = to_value(&record.value);
+            listed.push(ActiveOverride {
+                spec_type: spec_type.clone(),
+                name: name.clone(),
+                matcher: record.matcher.clone(),
+                value,
+                group_name,
+            });
+        }
+    }
+}
+
+fn returnable_to_value(returnable: &DynamicReturnable) -> Value {
+    match returnable.get_json() {
+        Some(map) => Value::Object(map.into_iter().collect()),
+        None => Value::Null,
     }
+}
 
-    if check_custom_ids_override(user, overrides, &apply_override, result) {
-        return true;
+fn serialize_parameters(parameters: &HashMap<String, Parameter>) -> HashMap<String, Value> {
+    let mut serialized = HashMap::with_capacity(parameters.len());
+    for (param_name, parameter) in parameters {
+        if let Ok(serialized_param) = serde_json::to_value(parameter) {
+            serialized.insert(param_name.clone(), serialized_param);
+        }
+    }
+    serialized
+}
+
+// Precedence: user ID, then custom IDs, then attribute matchers (latest first), then the default.
+fn find_override_for_user<'a, T>(
+    user: &StatsigUser,
+    overrides: &'a SpecOverrides<T>,
+    environment_tier: Option<&str>,
+    now: u64,
+) -> Option<&'a T> {
+    if let Some(value) = check_user_id_override(user, overrides, now) {
+        return Some(value);
     }
 
-    check_default_override(overrides, apply_override, result)
+    if let Some(value) = check_custom_ids_override(user, overrides, now) {
+        return Some(value);
+    }
+
+    if let Some(value) = check_conditional_override(user, overrides, environment_tier, now) {
+        return Some(value);
+    }
+
+    check_default_override(overrides, now)
 }
 
 fn mark_result_as_override(result: &mut EvaluatorResult) {
@@ -350,66 +679,30 @@ fn mark_result_as_override(result: &mut EvaluatorResult) {
     result.rule_id = Some(OVERRIDE_RULE_ID.clone());
 }
 
-fn check_default_override<T, F>(
-    overrides: &HashMap<String, T>,
-    apply_override: F,
-    result: &mut EvaluatorResult,
-) -> bool
-where
-    F: Fn(&T, &mut EvaluatorResult),
-{
-    if let Some(override_value) = overrides.get(NO_ID_OVERRIDE) {
-        log_d!(TAG, "default override found");
-        apply_override(override_value, result);
-        mark_result_as_override(result);
-        return true;
-    }
-    false
+fn check_default_override<T>(overrides: &SpecOverrides<T>, now: u64) -> Option<&T> {
+    let value = overrides.get_by_id(NO_ID_OVERRIDE, now)?;
+    log_d!(TAG, "default override found");
+    Some(value)
 }
 
-fn check_user_id_override<T, F>(
+fn check_user_id_override<'a, T>(
     user: &StatsigUser,
-    overrides: &HashMap<String, T>,
-    apply_override: F,
-    result: &mut EvaluatorResult,
-) -> bool
-where
-    F: Fn(&T, &mut EvaluatorResult),
-{
-    let user_id = match &user.data.user_id {
-        Some(id) => id,
-        None => return false,
-    };
-
-    let id_string = match &user_id.string_value {
-        Some(s) => &s.value,
-        None => return false,
-    };
-
-    let override_value = match overrides.get(id_string.as_str()) {
-        Some(v) => v,
-        None => return false,
-    };
+    overrides: &'a SpecOverrides<T>,
+    now: u64,
+) -> Option<&'a T> {
+    let id_string = user.get_user_id()?;
+    let value = overrides.get_by_id(id_string, now)?;
 
     log_d!(TAG, "override found for user ID {}", id_string);
-    apply_override(override_value, result);
-    mark_result_as_override(result);
-    true
+    Some(value)
 }
 
-fn check_custom_ids_override<T, F>(
+fn check_custom_ids_override<'a, T>(
     user: &StatsigUser,
-    overrides: &HashMap<String, T>,
-    apply_override: F,
-    result: &mut EvaluatorResult,
-) -> bool
-where
-    F: Fn(&T, &mut EvaluatorResult),
-{
-    let custom_ids = match &user.data.custom_ids {
-        Some(ids) => ids,
-        None => return false,
-    };
+    overrides: &'a SpecOverrides<T>,
+    now: u64,
+) -> Option<&'a T> {
+    let custom_ids = user.data.custom_ids.as_ref()?;
 
     for custom_id_value in custom_ids.values() {
         let id_string = match &custom_id_value.string_value {
@@ -417,18 +710,29 @@ where
             None => continue,
         };
 
-        let override_value = match overrides.get(id_string.as_str()) {
-            Some(v) => v,
-            None => continue,
-        };
-
-        log_d!(TAG, "override found for custom ID {}", id_string);
-        apply_override(override_value, result);
-        mark_result_as_override(result);
-        return true;
+        if let Some(value) = overrides.get_by_id(id_string.as_str(), now) {
+            log_d!(TAG, "override found for custom ID {}", id_string);
+            return Some(value);
+        }
     }
 
-    false
+    None
+}
+
+fn check_conditional_override<'a, T>(
+    user: &StatsigUser,
+    overrides: &'a SpecOverrides<T>,
+    environment_tier: Option<&str>,
+    now: u64,
+) -> Option<&'a T> {
+    let record = overrides
+        .conditional
+        .iter()
+        .rev()
+        .find(|r| r.matcher.matches(user, environment_tier, now))?;
+
+    log_d!(TAG, "attribute override found");
+    Some(&record.value)
 }
 
 fn get_experiment_with_group_name(
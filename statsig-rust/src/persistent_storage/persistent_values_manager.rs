@@ -1,8 +1,7 @@
 use std::sync::Arc;
 
-use chrono::Utc;
-
 use crate::{
+    clock::Clock,
     evaluation::evaluation_types::{
         BaseEvaluation, ExperimentEvaluation, ExtraExposureInfo, LayerEvaluation,
     },
@@ -22,6 +21,7 @@ use crate::evaluation::evaluator_result::EvaluatorResult;
 
 pub struct PersistentValuesManager {
     pub persistent_storage: Arc<dyn PersistentStorage>,
+    pub clock: Arc<dyn Clock>,
 }
 
 const TAG: &str = "PersistentValuesManager";
@@ -198,7 +198,7 @@ impl PersistentValuesManager {
             }
         };
 
-        let storage_key = match get_persistent_storage_key(user.user_ref, id_type.as_str()) {
+        let storage_key = match get_persistent_storage_key(&user.user_ref, id_type.as_str()) {
             Some(key) => key,
             None => {
                 return (curr_result, curr_details);
@@ -227,7 +227,7 @@ impl PersistentValuesManager {
                 );
 
             if keep_sticky {
-                let sticky_details = Self::make_evaluation_details_from_sticky_value(found);
+                let sticky_details = self.make_evaluation_details_from_sticky_value(found);
                 let sticky_result =
                     Self::make_evaluation_result_from_sticky_value(curr_result, found);
                 return (sticky_result, sticky_details);
@@ -272,7 +272,7 @@ impl PersistentValuesManager {
             }
         };
 
-        let storage_key = match get_persistent_storage_key(user.user_ref, id_type.as_str()) {
+        let storage_key = match get_persistent_storage_key(&user.user_ref, id_type.as_str()) {
             Some(key) => key,
             None => {
                 return (curr_result, curr_details);
@@ -303,7 +303,7 @@ impl PersistentValuesManager {
                 return (curr_result, curr_details);
             }
 
-            let sticky_details = Self::make_evaluation_details_from_sticky_value(found);
+            let sticky_details = self.make_evaluation_details_from_sticky_value(found);
             let sticky_result = Self::make_evaluation_result_from_sticky_value(curr_result, found);
             return (sticky_result, sticky_details);
         }
@@ -321,11 +321,14 @@ impl PersistentValuesManager {
 
     // -------------------------------------------------------------------------- [ Raw Experiment/Layer Helpers ]
 
-    fn make_evaluation_details_from_sticky_value(sticky_value: &StickyValues) -> EvaluationDetails {
+    fn make_evaluation_details_from_sticky_value(
+        &self,
+        sticky_value: &StickyValues,
+    ) -> EvaluationDetails {
         EvaluationDetails {
             reason: "Persisted".to_owned(),
             lcut: sticky_value.time,
-            received_at: Some(Utc::now().timestamp_millis() as u64),
+            received_at: Some(self.clock.now_ms()),
             version: sticky_value.config_version,
         }
     }
@@ -416,7 +419,7 @@ impl PersistentValuesManager {
         //    create a new sticky value and save it, returning the current experiment
 
         let id_type = curr_experiment.id_type.as_str();
-        let storage_key = match get_persistent_storage_key(user.user_ref, id_type) {
+        let storage_key = match get_persistent_storage_key(&user.user_ref, id_type) {
             Some(key) => key,
             None => return curr_experiment,
         };
@@ -450,7 +453,11 @@ impl PersistentValuesManager {
                 );
 
             if keep_sticky {
-                return make_experiment_from_sticky_value(curr_experiment, found);
+                return make_experiment_from_sticky_value(
+                    curr_experiment,
+                    found,
+                    self.clock.now_ms(),
+                );
             }
 
             // enforceOverrides / enforceTargeting: the live evaluation wins, so
@@ -511,7 +518,7 @@ impl PersistentValuesManager {
 
         let id_type = curr_layer.id_type.as_str();
         let config_name = curr_layer.name.as_str();
-        let storage_key = match get_persistent_storage_key(user.user_ref, id_type) {
+        let storage_key = match get_persistent_storage_key(&user.user_ref, id_type) {
             Some(key) => key,
             None => return curr_layer,
         };
@@ -540,7 +547,7 @@ impl PersistentValuesManager {
                 return curr_layer;
             }
 
-            return make_layer_from_sticky_value(curr_layer, found);
+            return make_layer_from_sticky_value(curr_layer, found, self.clock.now_ms());
         }
 
         let is_in_experiment = curr_layer
@@ -597,11 +604,12 @@ impl PersistentValuesManager {
 fn make_experiment_from_sticky_value(
     curr_experiment: Experiment,
     sticky_value: &StickyValues,
+    received_at: u64,
 ) -> Experiment {
     let details = EvaluationDetails {
         reason: "Persisted".to_owned(),
         lcut: sticky_value.time,
-        received_at: Some(Utc::now().timestamp_millis() as u64),
+        received_at: Some(received_at),
         version: sticky_value.config_version,
     };
 
@@ -687,11 +695,15 @@ fn sticky_value_to_experiment_evaluation(
 
 // -------------------------------------------------------------------------- [ Layer Helpers ]
 
-fn make_layer_from_sticky_value(curr_layer: Layer, sticky_value: &StickyValues) -> Layer {
+fn make_layer_from_sticky_value(
+    curr_layer: Layer,
+    sticky_value: &StickyValues,
+    received_at: u64,
+) -> Layer {
     let details = EvaluationDetails {
         reason: "Persisted".to_owned(),
         lcut: sticky_value.time,
-        received_at: Some(Utc::now().timestamp_millis() as u64),
+        received_at: Some(received_at),
         version: sticky_value.config_version,
     };
 
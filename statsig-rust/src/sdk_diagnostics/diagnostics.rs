@@ -141,7 +141,7 @@ impl Diagnostics {
         };
 
         self.event_logger.enqueue(EnqueuePassthroughOp {
-            event: StatsigEventInternal::new_diagnostic_event(metadata),
+            event: StatsigEventInternal::new_diagnostic_event(self.event_logger.now_ms(), metadata),
         });
         self.clear_markers(&context_type);
     }
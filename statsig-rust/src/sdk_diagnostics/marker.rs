@@ -91,6 +91,9 @@ pub struct Marker {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     content_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    running_tasks: Option<Vec<String>>,
 }
 
 impl Marker {
@@ -115,6 +118,7 @@ impl Marker {
             config_spec_ready: None,
             source: None,
             content_type: None,
+            running_tasks: None,
         }
     }
 
@@ -191,6 +195,11 @@ impl Marker {
         self.content_type = content_type;
         self
     }
+    #[must_use]
+    pub fn with_running_tasks(mut self, task_tags: Vec<String>) -> Self {
+        self.running_tasks = Some(task_tags);
+        self
+    }
     // TODO add more as needed
 }
 
@@ -1,12 +1,18 @@
 use crate::{
     log_e,
-    sdk_event_emitter::{SdkEvent, SdkEventCode},
+    override_adapter::override_adapter_trait::OverridesUpdateListener,
+    sdk_event_emitter::{
+        event_stream::DEFAULT_EVENT_STREAM_CAPACITY, OwnedSdkEvent, SdkEvent, SdkEventCode,
+        SdkEventFilter, SdkEventStream,
+    },
     statsig_types::{DynamicConfig, Experiment, Layer},
     Statsig,
 };
 use dashmap::DashMap;
 use std::ops::Deref;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
 
 const TAG: &str = "SdkEventEmitter";
 
@@ -60,6 +66,7 @@ impl SubscriptionID {
 pub struct SdkEventEmitter {
     listeners: DashMap<u8, Vec<Listener>>,
     listener_count: AtomicUsize,
+    stream_sender: OnceLock<broadcast::Sender<OwnedSdkEvent>>,
 }
 
 impl SdkEventEmitter {
@@ -84,6 +91,16 @@ impl SdkEventEmitter {
         sub_id
     }
 
+    /// Returns a stream of the events accepted by `filter`. Unlike `subscribe` callbacks,
+    /// stream consumers run on their own task and can await.
+    pub fn event_stream(&self, filter: SdkEventFilter) -> SdkEventStream {
+        let sender = self
+            .stream_sender
+            .get_or_init(|| broadcast::channel(DEFAULT_EVENT_STREAM_CAPACITY).0);
+
+        SdkEventStream::new(sender.subscribe(), filter)
+    }
+
     pub fn unsubscribe(&self, event: &str) {
         let code = SdkEventCode::from_name(event).as_raw();
         if let Some((_, listeners)) = self.listeners.remove(&code) {
@@ -113,6 +130,8 @@ impl SdkEventEmitter {
     }
 
     pub(crate) fn emit(&self, event: SdkEvent) {
+        self.emit_to_streams(&event);
+
         if self.listener_count.load(Ordering::Relaxed) == 0 {
             return;
         }
@@ -124,6 +143,19 @@ impl SdkEventEmitter {
         self.emit_to_listeners(&event, self.listeners.get(&event_code).as_deref());
     }
 
+    fn emit_to_streams(&self, event: &SdkEvent) {
+        let Some(sender) = self.stream_sender.get() else {
+            return;
+        };
+
+        if sender.receiver_count() == 0 {
+            return;
+        }
+
+        // an error only means every stream was dropped in the meantime
+        let _ = sender.send(event.to_owned_event());
+    }
+
     fn emit_to_listeners(&self, event: &SdkEvent, listeners: Option<&Vec<Listener>>) {
         let listeners = match listeners {
             Some(listeners) => listeners,
@@ -141,6 +173,15 @@ impl SdkEventEmitter {
     }
 }
 
+impl OverridesUpdateListener for SdkEventEmitter {
+    fn did_reload_overrides(&self, source: &str, override_count: usize) {
+        self.emit(SdkEvent::OverridesReloaded {
+            source,
+            override_count,
+        });
+    }
+}
+
 impl Deref for Statsig {
     type Target = SdkEventEmitter;
 
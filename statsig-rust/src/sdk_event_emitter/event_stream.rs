@@ -0,0 +1,123 @@
+use crate::{
+    log_w,
+    sdk_event_emitter::{OwnedSdkEvent, SdkEventCode},
+};
+use futures::Stream;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+const TAG: &str = "SdkEventStream";
+
+pub const DEFAULT_EVENT_STREAM_CAPACITY: usize = 1024;
+
+/// Selects which events an [`SdkEventStream`] yields. An empty filter accepts everything.
+#[derive(Clone, Debug, Default)]
+pub struct SdkEventFilter {
+    codes: Option<HashSet<SdkEventCode>>,
+    entity_names: Option<HashSet<String>>,
+}
+
+impl SdkEventFilter {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Only accept events with one of the given codes. `SdkEventCode::All` accepts every code.
+    pub fn with_codes(mut self, codes: impl IntoIterator<Item = SdkEventCode>) -> Self {
+        let codes: HashSet<_> = codes.into_iter().collect();
+        self.codes = if codes.contains(&SdkEventCode::All) {
+            None
+        } else {
+            Some(codes)
+        };
+        self
+    }
+
+    /// Only accept events about one of the given gates, configs, experiments or layers.
+    /// Events without an entity (e.g. `specs_updated`) are rejected.
+    pub fn with_entity_names<S: Into<String>>(
+        mut self,
+        entity_names: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.entity_names = Some(entity_names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn matches(&self, event: &OwnedSdkEvent) -> bool {
+        if let Some(codes) = &self.codes {
+            if !codes.contains(&event.get_code()) {
+                return false;
+            }
+        }
+
+        match &self.entity_names {
+            Some(names) => event
+                .get_entity_name()
+                .is_some_and(|name| names.contains(name)),
+            None => true,
+        }
+    }
+}
+
+type BoxedEventStream = Pin<Box<dyn Stream<Item = OwnedSdkEvent> + Send>>;
+
+/// Asynchronous subscription to SDK events, created with `SdkEventEmitter::event_stream`.
+///
+/// Events are delivered through a bounded broadcast channel shared by all streams. A stream that
+/// falls more than the channel capacity behind skips the overwritten events; the number skipped
+/// is logged and available from [`SdkEventStream::lagged_count`].
+pub struct SdkEventStream {
+    inner: BoxedEventStream,
+    lagged: Arc<AtomicU64>,
+}
+
+impl SdkEventStream {
+    pub(crate) fn new(
+        receiver: broadcast::Receiver<OwnedSdkEvent>,
+        filter: SdkEventFilter,
+    ) -> Self {
+        let lagged = Arc::new(AtomicU64::new(0));
+        let lagged_clone = lagged.clone();
+
+        let inner = futures::stream::unfold(
+            (receiver, filter, lagged_clone),
+            |(mut receiver, filter, lagged)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) if filter.matches(&event) => {
+                            return Some((event, (receiver, filter, lagged)));
+                        }
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(skipped)) => {
+                            lagged.fetch_add(skipped, Ordering::Relaxed);
+                            log_w!(TAG, "Event stream lagged, skipped {} event(s)", skipped);
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        );
+
+        Self {
+            inner: Box::pin(inner),
+            lagged,
+        }
+    }
+
+    /// Total number of events this stream missed because it fell behind.
+    pub fn lagged_count(&self) -> u64 {
+        self.lagged.load(Ordering::Relaxed)
+    }
+}
+
+impl Stream for SdkEventStream {
+    type Item = OwnedSdkEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
@@ -1,6 +1,9 @@
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
-use crate::{specs_response::spec_types::SpecsResponseFull, SpecsSource};
+use crate::{
+    logging_utils::sanitize_secret_key, networking::NetworkError,
+    specs_response::spec_types::SpecsResponseFull, SpecsSource, StatsigErr,
+};
 
 const TAG: &str = "SdkEvent";
 
@@ -14,6 +17,17 @@ pub enum SdkEventCode {
     ExperimentEvaluated = 4,
     LayerEvaluated = 5,
     SpecsUpdated = 6,
+    SpecsSyncFailed = 7,
+    IdListAdded = 8,
+    IdListUpdated = 9,
+    IdListRemoved = 10,
+    EventsDropped = 11,
+    EventFlushFailed = 12,
+    OverrideSet = 13,
+    OverrideRemoved = 14,
+    ShutdownStarted = 15,
+    ShutdownCompleted = 16,
+    OverridesReloaded = 17,
 }
 
 impl SdkEventCode {
@@ -25,6 +39,17 @@ impl SdkEventCode {
             SdkEvent::EXPERIMENT_EVALUATED => SdkEventCode::ExperimentEvaluated,
             SdkEvent::LAYER_EVALUATED => SdkEventCode::LayerEvaluated,
             SdkEvent::SPECS_UPDATED => SdkEventCode::SpecsUpdated,
+            SdkEvent::SPECS_SYNC_FAILED => SdkEventCode::SpecsSyncFailed,
+            SdkEvent::ID_LIST_ADDED => SdkEventCode::IdListAdded,
+            SdkEvent::ID_LIST_UPDATED => SdkEventCode::IdListUpdated,
+            SdkEvent::ID_LIST_REMOVED => SdkEventCode::IdListRemoved,
+            SdkEvent::EVENTS_DROPPED => SdkEventCode::EventsDropped,
+            SdkEvent::EVENT_FLUSH_FAILED => SdkEventCode::EventFlushFailed,
+            SdkEvent::OVERRIDE_SET => SdkEventCode::OverrideSet,
+            SdkEvent::OVERRIDE_REMOVED => SdkEventCode::OverrideRemoved,
+            SdkEvent::SHUTDOWN_STARTED => SdkEventCode::ShutdownStarted,
+            SdkEvent::SHUTDOWN_COMPLETED => SdkEventCode::ShutdownCompleted,
+            SdkEvent::OVERRIDES_RELOADED => SdkEventCode::OverridesReloaded,
             _ => SdkEventCode::Unknown,
         }
     }
@@ -67,6 +92,60 @@ pub enum SdkEvent<'a> {
         rule_id: Option<&'a str>,
         // not giving value as it wouldn't trigger an exposure
     },
+    SpecsSyncFailed {
+        source: &'a SpecsSource,
+        source_api: &'a Option<String>,
+        #[serde(serialize_with = "serialize_sync_error")]
+        error: &'a StatsigErr,
+    },
+    IdListAdded {
+        list_name: &'a str,
+        file_id: Option<&'a str>,
+        size: u64,
+        id_count: usize,
+    },
+    IdListUpdated {
+        list_name: &'a str,
+        file_id: Option<&'a str>,
+        size: u64,
+        id_count: usize,
+    },
+    IdListRemoved {
+        list_name: &'a str,
+        size: u64,
+        id_count: usize,
+    },
+    EventsDropped {
+        count: u64,
+        reason: &'a str,
+    },
+    EventFlushFailed {
+        event_count: u64,
+        error: &'a str,
+        will_retry: bool,
+    },
+    OverrideSet {
+        entity_name: &'a str,
+        override_type: &'a str,
+        id: Option<&'a str>,
+    },
+    OverrideRemoved {
+        // None when every override was removed at once
+        entity_name: Option<&'a str>,
+        override_type: &'a str,
+        id: Option<&'a str>,
+    },
+    ShutdownStarted {
+        timeout_ms: u64,
+    },
+    ShutdownCompleted {
+        success: bool,
+        duration_ms: u64,
+    },
+    OverridesReloaded {
+        source: &'a str,
+        override_count: usize,
+    },
 }
 
 impl SdkEvent<'_> {
@@ -76,6 +155,17 @@ impl SdkEvent<'_> {
     pub const EXPERIMENT_EVALUATED: &'static str = "experiment_evaluated";
     pub const LAYER_EVALUATED: &'static str = "layer_evaluated";
     pub const SPECS_UPDATED: &'static str = "specs_updated";
+    pub const SPECS_SYNC_FAILED: &'static str = "specs_sync_failed";
+    pub const ID_LIST_ADDED: &'static str = "id_list_added";
+    pub const ID_LIST_UPDATED: &'static str = "id_list_updated";
+    pub const ID_LIST_REMOVED: &'static str = "id_list_removed";
+    pub const EVENTS_DROPPED: &'static str = "events_dropped";
+    pub const EVENT_FLUSH_FAILED: &'static str = "event_flush_failed";
+    pub const OVERRIDE_SET: &'static str = "override_set";
+    pub const OVERRIDE_REMOVED: &'static str = "override_removed";
+    pub const SHUTDOWN_STARTED: &'static str = "shutdown_started";
+    pub const SHUTDOWN_COMPLETED: &'static str = "shutdown_completed";
+    pub const OVERRIDES_RELOADED: &'static str = "overrides_reloaded";
 
     pub fn get_code(&self) -> SdkEventCode {
         match self {
@@ -84,6 +174,17 @@ impl SdkEvent<'_> {
             SdkEvent::ExperimentEvaluated { .. } => SdkEventCode::ExperimentEvaluated,
             SdkEvent::LayerEvaluated { .. } => SdkEventCode::LayerEvaluated,
             SdkEvent::SpecsUpdated { .. } => SdkEventCode::SpecsUpdated,
+            SdkEvent::SpecsSyncFailed { .. } => SdkEventCode::SpecsSyncFailed,
+            SdkEvent::IdListAdded { .. } => SdkEventCode::IdListAdded,
+            SdkEvent::IdListUpdated { .. } => SdkEventCode::IdListUpdated,
+            SdkEvent::IdListRemoved { .. } => SdkEventCode::IdListRemoved,
+            SdkEvent::EventsDropped { .. } => SdkEventCode::EventsDropped,
+            SdkEvent::EventFlushFailed { .. } => SdkEventCode::EventFlushFailed,
+            SdkEvent::OverrideSet { .. } => SdkEventCode::OverrideSet,
+            SdkEvent::OverrideRemoved { .. } => SdkEventCode::OverrideRemoved,
+            SdkEvent::ShutdownStarted { .. } => SdkEventCode::ShutdownStarted,
+            SdkEvent::ShutdownCompleted { .. } => SdkEventCode::ShutdownCompleted,
+            SdkEvent::OverridesReloaded { .. } => SdkEventCode::OverridesReloaded,
         }
     }
 
@@ -95,6 +196,17 @@ impl SdkEvent<'_> {
             SdkEventCode::ExperimentEvaluated => SdkEvent::EXPERIMENT_EVALUATED,
             SdkEventCode::LayerEvaluated => SdkEvent::LAYER_EVALUATED,
             SdkEventCode::SpecsUpdated => SdkEvent::SPECS_UPDATED,
+            SdkEventCode::SpecsSyncFailed => SdkEvent::SPECS_SYNC_FAILED,
+            SdkEventCode::IdListAdded => SdkEvent::ID_LIST_ADDED,
+            SdkEventCode::IdListUpdated => SdkEvent::ID_LIST_UPDATED,
+            SdkEventCode::IdListRemoved => SdkEvent::ID_LIST_REMOVED,
+            SdkEventCode::EventsDropped => SdkEvent::EVENTS_DROPPED,
+            SdkEventCode::EventFlushFailed => SdkEvent::EVENT_FLUSH_FAILED,
+            SdkEventCode::OverrideSet => SdkEvent::OVERRIDE_SET,
+            SdkEventCode::OverrideRemoved => SdkEvent::OVERRIDE_REMOVED,
+            SdkEventCode::ShutdownStarted => SdkEvent::SHUTDOWN_STARTED,
+            SdkEventCode::ShutdownCompleted => SdkEvent::SHUTDOWN_COMPLETED,
+            SdkEventCode::OverridesReloaded => SdkEvent::OVERRIDES_RELOADED,
             SdkEventCode::Unknown => "unknown",
         }
     }
@@ -113,6 +225,335 @@ impl SdkEvent<'_> {
             }
         }
     }
+
+    /// Copies the event into an [`OwnedSdkEvent`] that can outlive the evaluation call.
+    pub fn to_owned_event(&self) -> OwnedSdkEvent {
+        match self {
+            SdkEvent::SpecsUpdated {
+                source,
+                source_api,
+                values,
+            } => OwnedSdkEvent::SpecsUpdated {
+                source: (*source).clone(),
+                source_api: (*source_api).clone(),
+                lcut: values.time,
+                checksum: values.checksum.clone(),
+            },
+            SdkEvent::GateEvaluated {
+                gate_name,
+                rule_id,
+                value,
+                reason,
+            } => OwnedSdkEvent::GateEvaluated {
+                gate_name: gate_name.to_string(),
+                rule_id: rule_id.to_string(),
+                value: *value,
+                reason: reason.to_string(),
+            },
+            SdkEvent::DynamicConfigEvaluated {
+                config_name,
+                reason,
+                rule_id,
+                value,
+            } => OwnedSdkEvent::DynamicConfigEvaluated {
+                config_name: config_name.to_string(),
+                reason: reason.to_string(),
+                rule_id: rule_id.map(str::to_string),
+                value: value.cloned(),
+            },
+            SdkEvent::ExperimentEvaluated {
+                experiment_name,
+                reason,
+                rule_id,
+                value,
+                group_name,
+            } => OwnedSdkEvent::ExperimentEvaluated {
+                experiment_name: experiment_name.to_string(),
+                reason: reason.to_string(),
+                rule_id: rule_id.map(str::to_string),
+                value: value.cloned(),
+                group_name: group_name.map(str::to_string),
+            },
+            SdkEvent::LayerEvaluated {
+                layer_name,
+                reason,
+                rule_id,
+            } => OwnedSdkEvent::LayerEvaluated {
+                layer_name: layer_name.to_string(),
+                reason: reason.to_string(),
+                rule_id: rule_id.map(str::to_string),
+            },
+            SdkEvent::SpecsSyncFailed {
+                source,
+                source_api,
+                error,
+            } => OwnedSdkEvent::SpecsSyncFailed {
+                source: (*source).clone(),
+                source_api: (*source_api).clone(),
+                error: SpecsSyncError::from(*error),
+            },
+            SdkEvent::IdListAdded {
+                list_name,
+                file_id,
+                size,
+                id_count,
+            } => OwnedSdkEvent::IdListAdded {
+                list_name: list_name.to_string(),
+                file_id: file_id.map(str::to_string),
+                size: *size,
+                id_count: *id_count,
+            },
+            SdkEvent::IdListUpdated {
+                list_name,
+                file_id,
+                size,
+                id_count,
+            } => OwnedSdkEvent::IdListUpdated {
+                list_name: list_name.to_string(),
+                file_id: file_id.map(str::to_string),
+                size: *size,
+                id_count: *id_count,
+            },
+            SdkEvent::IdListRemoved {
+                list_name,
+                size,
+                id_count,
+            } => OwnedSdkEvent::IdListRemoved {
+                list_name: list_name.to_string(),
+                size: *size,
+                id_count: *id_count,
+            },
+            SdkEvent::EventsDropped { count, reason } => OwnedSdkEvent::EventsDropped {
+                count: *count,
+                reason: reason.to_string(),
+            },
+            SdkEvent::EventFlushFailed {
+                event_count,
+                error,
+                will_retry,
+            } => OwnedSdkEvent::EventFlushFailed {
+                event_count: *event_count,
+                error: error.to_string(),
+                will_retry: *will_retry,
+            },
+            SdkEvent::OverrideSet {
+                entity_name,
+                override_type,
+                id,
+            } => OwnedSdkEvent::OverrideSet {
+                entity_name: entity_name.to_string(),
+                override_type: override_type.to_string(),
+                id: id.map(str::to_string),
+            },
+            SdkEvent::OverrideRemoved {
+                entity_name,
+                override_type,
+                id,
+            } => OwnedSdkEvent::OverrideRemoved {
+                entity_name: entity_name.map(str::to_string),
+                override_type: override_type.to_string(),
+                id: id.map(str::to_string),
+            },
+            SdkEvent::ShutdownStarted { timeout_ms } => OwnedSdkEvent::ShutdownStarted {
+                timeout_ms: *timeout_ms,
+            },
+            SdkEvent::ShutdownCompleted {
+                success,
+                duration_ms,
+            } => OwnedSdkEvent::ShutdownCompleted {
+                success: *success,
+                duration_ms: *duration_ms,
+            },
+            SdkEvent::OverridesReloaded {
+                source,
+                override_count,
+            } => OwnedSdkEvent::OverridesReloaded {
+                source: source.to_string(),
+                override_count: *override_count,
+            },
+        }
+    }
+}
+
+/// Serializable summary of a failed specs sync. Request URLs are left out and the message is
+/// sanitized, so the SDK key never reaches event consumers.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecsSyncError {
+    pub kind: String,
+    pub status_code: Option<u16>,
+    pub message: String,
+}
+
+impl From<&NetworkError> for SpecsSyncError {
+    fn from(error: &NetworkError) -> Self {
+        let (status_code, message) = match error {
+            NetworkError::ShutdownError(_) | NetworkError::DisableNetworkOn(_) => {
+                (None, String::new())
+            }
+            NetworkError::SerializationError(_, message) => (None, message.clone()),
+            NetworkError::RequestFailed(_, status, message)
+            | NetworkError::RetriesExhausted(_, status, _, message)
+            | NetworkError::RequestNotRetryable(_, status, message) => (*status, message.clone()),
+        };
+
+        Self {
+            kind: error.name().to_string(),
+            status_code,
+            message: sanitize_secret_key(&message),
+        }
+    }
+}
+
+impl From<&StatsigErr> for SpecsSyncError {
+    fn from(error: &StatsigErr) -> Self {
+        if let StatsigErr::NetworkError(error) = error {
+            return Self::from(error);
+        }
+
+        Self {
+            kind: error.name().to_string(),
+            status_code: None,
+            message: sanitize_secret_key(&error.to_string()),
+        }
+    }
+}
+
+fn serialize_sync_error<S: Serializer>(
+    error: &&StatsigErr,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    SpecsSyncError::from(*error).serialize(serializer)
+}
+
+/// An [`SdkEvent`] that owns its data, as delivered by event streams.
+///
+/// `SpecsUpdated` only carries the update metadata; the specs themselves are too large to copy
+/// per subscriber.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum OwnedSdkEvent {
+    SpecsUpdated {
+        source: SpecsSource,
+        source_api: Option<String>,
+        lcut: u64,
+        checksum: Option<String>,
+    },
+    GateEvaluated {
+        gate_name: String,
+        rule_id: String,
+        value: bool,
+        reason: String,
+    },
+    DynamicConfigEvaluated {
+        config_name: String,
+        reason: String,
+        rule_id: Option<String>,
+        value: Option<crate::DynamicReturnable>,
+    },
+    ExperimentEvaluated {
+        experiment_name: String,
+        reason: String,
+        rule_id: Option<String>,
+        value: Option<crate::DynamicReturnable>,
+        group_name: Option<String>,
+    },
+    LayerEvaluated {
+        layer_name: String,
+        reason: String,
+        rule_id: Option<String>,
+    },
+    SpecsSyncFailed {
+        source: SpecsSource,
+        source_api: Option<String>,
+        error: SpecsSyncError,
+    },
+    IdListAdded {
+        list_name: String,
+        file_id: Option<String>,
+        size: u64,
+        id_count: usize,
+    },
+    IdListUpdated {
+        list_name: String,
+        file_id: Option<String>,
+        size: u64,
+        id_count: usize,
+    },
+    IdListRemoved {
+        list_name: String,
+        size: u64,
+        id_count: usize,
+    },
+    EventsDropped {
+        count: u64,
+        reason: String,
+    },
+    EventFlushFailed {
+        event_count: u64,
+        error: String,
+        will_retry: bool,
+    },
+    OverrideSet {
+        entity_name: String,
+        override_type: String,
+        id: Option<String>,
+    },
+    OverrideRemoved {
+        entity_name: Option<String>,
+        override_type: String,
+        id: Option<String>,
+    },
+    ShutdownStarted {
+        timeout_ms: u64,
+    },
+    ShutdownCompleted {
+        success: bool,
+        duration_ms: u64,
+    },
+    OverridesReloaded {
+        source: String,
+        override_count: usize,
+    },
+}
+
+impl OwnedSdkEvent {
+    pub fn get_code(&self) -> SdkEventCode {
+        match self {
+            OwnedSdkEvent::GateEvaluated { .. } => SdkEventCode::GateEvaluated,
+            OwnedSdkEvent::DynamicConfigEvaluated { .. } => SdkEventCode::DynamicConfigEvaluated,
+            OwnedSdkEvent::ExperimentEvaluated { .. } => SdkEventCode::ExperimentEvaluated,
+            OwnedSdkEvent::LayerEvaluated { .. } => SdkEventCode::LayerEvaluated,
+            OwnedSdkEvent::SpecsUpdated { .. } => SdkEventCode::SpecsUpdated,
+            OwnedSdkEvent::SpecsSyncFailed { .. } => SdkEventCode::SpecsSyncFailed,
+            OwnedSdkEvent::IdListAdded { .. } => SdkEventCode::IdListAdded,
+            OwnedSdkEvent::IdListUpdated { .. } => SdkEventCode::IdListUpdated,
+            OwnedSdkEvent::IdListRemoved { .. } => SdkEventCode::IdListRemoved,
+            OwnedSdkEvent::EventsDropped { .. } => SdkEventCode::EventsDropped,
+            OwnedSdkEvent::EventFlushFailed { .. } => SdkEventCode::EventFlushFailed,
+            OwnedSdkEvent::OverrideSet { .. } => SdkEventCode::OverrideSet,
+            OwnedSdkEvent::OverrideRemoved { .. } => SdkEventCode::OverrideRemoved,
+            OwnedSdkEvent::ShutdownStarted { .. } => SdkEventCode::ShutdownStarted,
+            OwnedSdkEvent::ShutdownCompleted { .. } => SdkEventCode::ShutdownCompleted,
+            OwnedSdkEvent::OverridesReloaded { .. } => SdkEventCode::OverridesReloaded,
+        }
+    }
+
+    /// The gate, config, experiment or layer this event is about, if any. Override events report
+    /// the overridden entity.
+    pub fn get_entity_name(&self) -> Option<&str> {
+        match self {
+            OwnedSdkEvent::GateEvaluated { gate_name, .. } => Some(gate_name),
+            OwnedSdkEvent::DynamicConfigEvaluated { config_name, .. } => Some(config_name),
+            OwnedSdkEvent::ExperimentEvaluated {
+                experiment_name, ..
+            } => Some(experiment_name),
+            OwnedSdkEvent::LayerEvaluated { layer_name, .. } => Some(layer_name),
+            OwnedSdkEvent::OverrideSet { entity_name, .. } => Some(entity_name),
+            OwnedSdkEvent::OverrideRemoved { entity_name, .. } => entity_name.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Clone)]
@@ -1,8 +1,10 @@
 pub mod event_emitter;
+pub mod event_stream;
 pub mod event_types;
 
 pub use event_emitter::{SdkEventEmitter, SubscriptionID};
-pub use event_types::{SdkEvent, SdkEventCode};
+pub use event_stream::{SdkEventFilter, SdkEventStream};
+pub use event_types::{OwnedSdkEvent, SdkEvent, SdkEventCode, SpecsSyncError};
 
 #[cfg(test)]
 mod event_emitter_tests;
@@ -1,12 +1,13 @@
-use chrono::Utc;
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::clock::{get_clock, Clock};
 use crate::data_store_interface::{DataStoreCacheKeys, DataStoreTrait};
 use crate::evaluation::evaluator::SpecType;
+use crate::evaluation::guarded_regex::{GuardedRegexes, RegexSafetyOptions};
 use crate::global_configs::GlobalConfigs;
-use crate::id_lists_adapter::{IdList, IdListsUpdateListener};
+use crate::id_lists_adapter::{IdList, IdListStorageConfig, IdListsUpdateListener};
 use crate::interned_string::InternedString;
 use crate::networking::ResponseData;
 use crate::observability::observability_client_adapter::{MetricType, ObservabilityEvent};
@@ -28,6 +29,7 @@ pub struct SpecStoreData {
     pub time_received_at: Option<u64>,
     pub values: SpecsResponseFull,
     pub id_lists: HashMap<String, IdList>,
+    pub guarded_regexes: GuardedRegexes,
 }
 
 const TAG: &str = stringify!(SpecStore);
@@ -41,6 +43,9 @@ pub struct SpecStore {
     ops_stats: Arc<OpsStatsForInstance>,
     global_configs: Arc<GlobalConfigs>,
     event_emitter: Arc<SdkEventEmitter>,
+    clock: Arc<dyn Clock>,
+    id_list_storage: IdListStorageConfig,
+    regex_safety: Option<RegexSafetyOptions>,
 }
 
 impl SpecStore {
@@ -53,8 +58,12 @@ impl SpecStore {
         options: Option<&StatsigOptions>,
     ) -> SpecStore {
         let mut data_store = None;
+        let mut id_list_storage = IdListStorageConfig::default();
+        let mut regex_safety = None;
         if let Some(options) = options {
             data_store = options.data_store.clone();
+            id_list_storage = options.id_list_storage.clone().unwrap_or_default();
+            regex_safety = options.regex_safety.clone();
         }
 
         SpecStore {
@@ -65,12 +74,16 @@ impl SpecStore {
                 source: SpecsSource::Uninitialized,
                 source_api: None,
                 id_lists: HashMap::new(),
+                guarded_regexes: GuardedRegexes::default(),
             })),
             event_emitter,
             data_store,
             statsig_runtime,
             ops_stats: OPS_STATS.get_for_instance(sdk_key),
             global_configs: GlobalConfigs::get_instance(sdk_key),
+            clock: get_clock(options),
+            id_list_storage,
+            regex_safety,
         }
     }
 
@@ -237,6 +250,32 @@ struct ApplyResult {
     time_received_at: u64,
 }
 
+enum IdListChangeKind {
+    Added,
+    Updated,
+    Removed,
+}
+
+struct IdListChange {
+    kind: IdListChangeKind,
+    list_name: String,
+    file_id: Option<String>,
+    size: u64,
+    id_count: usize,
+}
+
+impl IdListChange {
+    fn new(kind: IdListChangeKind, list: &IdList) -> Self {
+        Self {
+            kind,
+            list_name: list.metadata.name.clone(),
+            file_id: list.metadata.file_id.clone(),
+            size: list.metadata.size,
+            id_count: list.ids.len(),
+        }
+    }
+}
+
 impl SpecStore {
     fn specs_update_prep(&self, specs_update: &mut SpecsUpdate) -> Result<PrepResult, StatsigErr> {
         let response_format = self.get_spec_response_format(specs_update);
@@ -304,6 +343,11 @@ impl SpecStore {
         // DANGER: try_update_global_configs contains its own locks
         self.try_update_global_configs(&next_values);
 
+        let guarded_regexes = match &self.regex_safety {
+            Some(options) => GuardedRegexes::compile(&next_values, options),
+            None => GuardedRegexes::default(),
+        };
+
         let mut data = write_lock_or_else!(self.data, {
             let msg = "Failed to acquire write lock for swap_current_with_next";
             log_e!(TAG, "{}", msg);
@@ -312,9 +356,10 @@ impl SpecStore {
 
         let prev_source = std::mem::replace(&mut data.source, specs_update.source.clone());
         let prev_lcut = data.values.time;
-        let time_received_at = Utc::now().timestamp_millis() as u64;
+        let time_received_at = self.clock.now_ms();
 
         data.values = *next_values;
+        data.guarded_regexes = guarded_regexes;
         data.time_received_at = Some(time_received_at);
         data.source_api = specs_update.source_api.clone();
 
@@ -411,6 +456,32 @@ impl SpecStore {
         });
     }
 
+    fn emit_id_list_change_sdk_event(&self, change: &IdListChange) {
+        let list_name = change.list_name.as_str();
+        let file_id = change.file_id.as_deref();
+        let (size, id_count) = (change.size, change.id_count);
+
+        self.event_emitter.emit(match change.kind {
+            IdListChangeKind::Added => SdkEvent::IdListAdded {
+                list_name,
+                file_id,
+                size,
+                id_count,
+            },
+            IdListChangeKind::Updated => SdkEvent::IdListUpdated {
+                list_name,
+                file_id,
+                size,
+                id_count,
+            },
+            IdListChangeKind::Removed => SdkEvent::IdListRemoved {
+                list_name,
+                size,
+                id_count,
+            },
+        });
+    }
+
     fn get_spec_response_format(&self, update: &SpecsUpdate) -> SpecsFormat {
         let content_type = update.data.get_header_ref("content-type");
         if content_type.map(|s| s.as_str().contains("application/octet-stream")) != Some(true) {
@@ -601,7 +672,7 @@ impl SpecStore {
         source_api: Option<String>,
         response_format: SpecsFormat,
     ) {
-        let delay = (Utc::now().timestamp_millis() as u64).saturating_sub(lcut);
+        let delay = self.clock.now_ms().saturating_sub(lcut);
         log_d!(TAG, "Updated ({:?})", source);
 
         if *prev_source == SpecsSource::Uninitialized || *prev_source == SpecsSource::Loading {
@@ -633,6 +704,19 @@ impl SpecsUpdateListener for SpecStore {
         self.set_values(update)
     }
 
+    fn did_fail_specs_update(
+        &self,
+        source: SpecsSource,
+        source_api: Option<String>,
+        error: &StatsigErr,
+    ) {
+        self.event_emitter.emit(SdkEvent::SpecsSyncFailed {
+            source: &source,
+            source_api: &source_api,
+            error,
+        });
+    }
+
     fn get_current_specs_info(&self) -> SpecsInfo {
         let data = read_lock_or_else!(self.data, {
             log_e!(
@@ -689,19 +773,43 @@ impl IdListsUpdateListener for SpecStore {
             return;
         });
 
+        let mut changes = Vec::new();
+
         // delete any id_lists that are not in the updates
-        data.id_lists.retain(|name, _| updates.contains_key(name));
+        data.id_lists.retain(|name, list| {
+            let keep = updates.contains_key(name);
+            if !keep {
+                changes.push(IdListChange::new(IdListChangeKind::Removed, list));
+            }
+            keep
+        });
 
         for (list_name, update) in updates {
             if let Some(entry) = data.id_lists.get_mut(&list_name) {
                 // update existing
+                let prev_size = entry.metadata.size;
+                let prev_file_id = entry.metadata.file_id.clone();
                 entry.apply_update(update);
+
+                if entry.metadata.size != prev_size || entry.metadata.file_id != prev_file_id {
+                    changes.push(IdListChange::new(IdListChangeKind::Updated, entry));
+                }
             } else {
                 // add new
-                let mut list = IdList::new(update.new_metadata.clone());
+                let mut list = IdList::with_storage(
+                    update.new_metadata.clone(),
+                    self.id_list_storage.mode_for(&list_name),
+                );
                 list.apply_update(update);
+                changes.push(IdListChange::new(IdListChangeKind::Added, &list));
                 data.id_lists.insert(list_name, list);
             }
         }
+
+        // emit after the write lock is released so listeners can evaluate against the store
+        drop(data);
+        for change in changes {
+            self.emit_id_list_change_sdk_event(&change);
+        }
     }
 }
@@ -24,6 +24,7 @@ mod statsig_local_file_specs_adapter;
 mod specs_adapter_trait;
 
 pub const DEFAULT_INIT_TIMEOUT_MS: u64 = 3000;
+pub(crate) const DEFAULT_SYNC_INTERVAL_MS: u32 = 10_000;
 
 const TAG: &str = "SpecAdapterConfig";
 
@@ -98,6 +98,10 @@ pub trait SpecsAdapter: Send + Sync {
         statsig_runtime: &Arc<StatsigRuntime>,
     ) -> Result<(), StatsigErr>;
 
+    /// Called by Statsig::update_options when `specs_sync_interval_ms` changes.
+    /// Adapters that poll on an interval should apply it to their running background sync.
+    fn update_sync_interval(&self, _sync_interval_ms: u32) {}
+
     fn get_type_name(&self) -> String;
 }
 
@@ -157,6 +161,16 @@ pub trait SpecsUpdateListener: Send + Sync {
     fn did_receive_specs_update(&self, update: SpecsUpdate) -> Result<(), StatsigErr>;
 
     fn get_current_specs_info(&self) -> SpecsInfo;
+
+    /// Called when an adapter gives up on a sync, e.g. after a network failure or a failed data
+    /// store read.
+    fn did_fail_specs_update(
+        &self,
+        _source: SpecsSource,
+        _source_api: Option<String>,
+        _error: &StatsigErr,
+    ) {
+    }
 }
 
 impl fmt::Debug for dyn SpecsAdapter {
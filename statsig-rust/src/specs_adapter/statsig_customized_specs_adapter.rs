@@ -172,6 +172,12 @@ impl SpecsAdapter for StatsigCustomizedSpecsAdapter {
         Ok(())
     }
 
+    fn update_sync_interval(&self, sync_interval_ms: u32) {
+        for adapter in &self.adapters {
+            adapter.update_sync_interval(sync_interval_ms);
+        }
+    }
+
     fn get_type_name(&self) -> String {
         stringify!(StatsigCustomizedSpecsAdapter).to_string()
     }
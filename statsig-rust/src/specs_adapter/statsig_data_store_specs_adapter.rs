@@ -1,12 +1,13 @@
 use super::config_spec_background_sync_metrics::log_config_sync_overall_latency;
 use super::response_format::get_specs_response_format;
-use super::statsig_http_specs_adapter::DEFAULT_SYNC_INTERVAL_MS;
+use super::DEFAULT_SYNC_INTERVAL_MS;
 use super::{SpecsSource, SpecsUpdate};
 use crate::data_store_interface::{
     DataStoreBytesResponse, DataStoreCacheKeys, DataStoreResponse, DataStoreTrait, RequestPath,
 };
 use crate::networking::ResponseData;
 use crate::observability::ops_stats::{OpsStatsForInstance, OPS_STATS};
+use crate::utils::sync_interval::SyncInterval;
 use crate::{
     log_d, log_e, log_w, read_lock_or_else, unwrap_or_else, write_lock_or_else, SpecsAdapter,
     SpecsUpdateListener,
@@ -18,14 +19,14 @@ use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::{sync::Arc, time::Duration};
 use tokio::sync::Notify;
-use tokio::time::{self, sleep};
+use tokio::time;
 
 const TAG: &str = "StatsigDataStoreSpecsAdapter";
 
 pub struct StatsigDataStoreSpecsAdapter {
     data_store: Arc<dyn DataStoreTrait>,
     cache_keys: DataStoreCacheKeys,
-    sync_interval: Duration,
+    sync_interval: SyncInterval,
     ops_stats: Arc<OpsStatsForInstance>,
     listener: RwLock<Option<Arc<dyn SpecsUpdateListener>>>,
     shutdown_notify: Arc<Notify>,
@@ -49,7 +50,7 @@ impl StatsigDataStoreSpecsAdapter {
         StatsigDataStoreSpecsAdapter {
             data_store,
             cache_keys: DataStoreCacheKeys::from_selected_key(data_store_key),
-            sync_interval: Duration::from_millis(u64::from(
+            sync_interval: SyncInterval::new(u64::from(
                 options_ref
                     .specs_sync_interval_ms
                     .unwrap_or(DEFAULT_SYNC_INTERVAL_MS),
@@ -146,6 +147,10 @@ impl SpecsAdapter for StatsigDataStoreSpecsAdapter {
             .map_err(|e| StatsigErr::DataStoreFailure(format!("Failed to shutdown: {e}")))?
     }
 
+    fn update_sync_interval(&self, sync_interval_ms: u32) {
+        self.sync_interval.update(u64::from(sync_interval_ms));
+    }
+
     fn get_type_name(&self) -> String {
         stringify!(StatsigDataStoreSpecAdapter).to_string()
     }
@@ -288,7 +293,11 @@ impl StatsigDataStoreSpecsAdapter {
     async fn execute_background_sync(&self, rt_shutdown_notify: &Arc<Notify>) {
         loop {
             tokio::select! {
-                () = sleep(self.sync_interval) => self.execute_background_sync_impl().await,
+                elapsed = self.sync_interval.wait() => {
+                    if elapsed {
+                        self.execute_background_sync_impl().await;
+                    }
+                }
                 () = rt_shutdown_notify.notified() => {
                     log_d!(TAG, "Runtime shutdown. Shutting down specs background sync");
                     break;
@@ -301,12 +310,28 @@ impl StatsigDataStoreSpecsAdapter {
         }
     }
 
+    fn notify_listener_of_sync_failure(&self, error: &StatsigErr) {
+        let read_lock = read_lock_or_else!(self.listener, {
+            log_w!(TAG, "Unable to acquire read lock on listener");
+            return;
+        });
+
+        if let Some(listener) = read_lock.as_ref() {
+            listener.did_fail_specs_update(
+                SpecsSource::Adapter("DataStore".to_string()),
+                Some("datastore".to_string()),
+                error,
+            );
+        }
+    }
+
     async fn execute_background_sync_impl(&self) {
         let sync_start_ms = Utc::now().timestamp_millis() as u64;
         let update = match self.load_cached_specs().await {
             Ok(update) => update,
             Err(e) => {
                 log_w!(TAG, "Failed to read for data store: {e}");
+                self.notify_listener_of_sync_failure(&e);
                 return;
             }
         };
@@ -269,6 +269,7 @@ impl StatsigGrpcSpecsAdapter {
             tokio::select! {
                 result = self.handle_grpc_request_stream() => {
                     if let Err(err) = result {
+                        self.notify_listener_of_sync_failure(&err);
                         let attempt = self.retry_state.retry_attempts.fetch_add(1, Ordering::SeqCst);
                         if attempt > RETRY_LIMIT {
                             log_error_to_statsig_and_console!(&self.ops_stats, TAG, StatsigErr::GrpcError(format!("gRPC stream failure, exhaust retry limit: {err:?}")));
@@ -387,6 +388,21 @@ impl StatsigGrpcSpecsAdapter {
         }
     }
 
+    fn notify_listener_of_sync_failure(&self, error: &StatsigErr) {
+        if let Some(lock) = self
+            .listener
+            .try_read_for(std::time::Duration::from_secs(5))
+        {
+            if let Some(listener) = lock.as_ref() {
+                listener.did_fail_specs_update(
+                    SpecsSource::Adapter("GRPC".to_string()),
+                    None,
+                    error,
+                );
+            }
+        }
+    }
+
     fn get_current_specs_info(&self) -> Option<SpecsInfo> {
         match self
             .listener
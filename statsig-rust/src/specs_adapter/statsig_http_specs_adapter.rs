@@ -1,5 +1,6 @@
 use super::config_spec_background_sync_metrics::log_config_sync_overall_latency;
 use super::response_format::{get_specs_response_format, SpecsResponseFormat};
+use crate::clock::{get_clock, Clock};
 use crate::networking::{NetworkClient, NetworkError, RequestArgs, ResponseData};
 use crate::observability::ops_stats::{OpsStatsForInstance, OPS_STATS};
 use crate::observability::sdk_errors_observer::ErrorBoundaryEvent;
@@ -10,6 +11,7 @@ use crate::specs_response::spec_types::SpecsResponseNoUpdates;
 use crate::statsig_err::StatsigErr;
 use crate::statsig_metadata::StatsigMetadata;
 use crate::utils::get_api_from_url;
+use crate::utils::sync_interval::SyncInterval;
 use crate::DEFAULT_INIT_TIMEOUT_MS;
 use crate::{
     log_d, log_e, log_error_to_statsig_and_console, SpecsSource, StatsigOptions, StatsigRuntime,
@@ -23,9 +25,8 @@ use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Weak};
 use std::time::Duration;
 use tokio::sync::Notify;
-use tokio::time::sleep;
 
-use super::SpecsInfo;
+use super::{SpecsInfo, DEFAULT_SYNC_INTERVAL_MS};
 
 pub struct NetworkResponse {
     pub data: ResponseData,
@@ -43,7 +44,6 @@ struct ProcessSpecDataOutcome {
 }
 
 pub const DEFAULT_SPECS_URL: &str = "https://api.statsigcdn.com/v2/download_config_specs";
-pub const DEFAULT_SYNC_INTERVAL_MS: u32 = 10_000;
 
 #[allow(unused)]
 pub const INIT_DICT_ID: &str = "null";
@@ -72,7 +72,7 @@ pub struct StatsigHttpSpecsAdapter {
     specs_url: String,
     fallback_url: Option<String>,
     init_timeout_ms: u64,
-    sync_interval_duration: Duration,
+    sync_interval: Arc<SyncInterval>,
     ops_stats: Arc<OpsStatsForInstance>,
     shutdown_notify: Arc<Notify>,
     allow_dcs_deltas: bool,
@@ -86,6 +86,7 @@ pub struct StatsigHttpSpecsAdapter {
     // Unix-epoch millis of the last forced full resync (0 = never), used to
     // throttle how often a stalled delta stream forces a full download.
     last_forced_full_resync_at_ms: AtomicU64,
+    clock: Arc<dyn Clock>,
 }
 
 // OB client -- START
@@ -154,11 +155,11 @@ impl StatsigHttpSpecsAdapter {
             specs_url,
             fallback_url,
             init_timeout_ms,
-            sync_interval_duration: Duration::from_millis(u64::from(
+            sync_interval: Arc::new(SyncInterval::new(u64::from(
                 options_ref
                     .specs_sync_interval_ms
                     .unwrap_or(DEFAULT_SYNC_INTERVAL_MS),
-            )),
+            ))),
             ops_stats: OPS_STATS.get_for_instance(sdk_key),
             shutdown_notify: Arc::new(Notify::new()),
             allow_dcs_deltas: enable_dcs_deltas,
@@ -167,6 +168,7 @@ impl StatsigHttpSpecsAdapter {
             delta_no_progress_count: AtomicU32::new(0),
             delta_no_progress_threshold,
             last_forced_full_resync_at_ms: AtomicU64::new(0),
+            clock: get_clock(options),
         }
     }
 
@@ -417,7 +419,7 @@ impl StatsigHttpSpecsAdapter {
         }
 
         let count = self.delta_no_progress_count.fetch_add(1, Ordering::SeqCst) + 1;
-        let now_ms = Utc::now().timestamp_millis().max(0) as u64;
+        let now_ms = self.clock.now_ms();
         if !forced_resync_due(
             count,
             self.delta_no_progress_threshold,
@@ -553,9 +555,37 @@ impl StatsigHttpSpecsAdapter {
             deltas_used,
         );
 
+        if let Err(error @ StatsigErr::NetworkError(_)) = &result {
+            self.notify_listener_of_sync_failure(&source_api, error);
+        }
+
         result
     }
 
+    fn notify_listener_of_sync_failure(&self, source_api: &str, error: &StatsigErr) {
+        if matches!(
+            error,
+            StatsigErr::NetworkError(
+                NetworkError::DisableNetworkOn(_) | NetworkError::ShutdownError(_)
+            )
+        ) {
+            return;
+        }
+
+        if let Some(lock) = self
+            .listener
+            .try_read_for(std::time::Duration::from_secs(5))
+        {
+            if let Some(listener) = lock.as_ref() {
+                listener.did_fail_specs_update(
+                    SpecsSource::Network,
+                    Some(source_api.to_string()),
+                    error,
+                );
+            }
+        }
+    }
+
     async fn process_spec_data(
         &self,
         response: Result<NetworkResponse, NetworkError>,
@@ -681,13 +711,16 @@ impl SpecsAdapter for StatsigHttpSpecsAdapter {
         statsig_runtime: &Arc<StatsigRuntime>,
     ) -> Result<(), StatsigErr> {
         let weak_self: Weak<StatsigHttpSpecsAdapter> = Arc::downgrade(&self);
-        let interval_duration = self.sync_interval_duration;
+        let sync_interval = self.sync_interval.clone();
         let shutdown_notify = self.shutdown_notify.clone();
 
         statsig_runtime.spawn("http_specs_bg_sync", move |rt_shutdown_notify| async move {
             loop {
                 tokio::select! {
-                    () = sleep(interval_duration) => {
+                    elapsed = sync_interval.wait() => {
+                        if !elapsed {
+                            continue;
+                        }
                         if let Some(strong_self) = weak_self.upgrade() {
                             Self::run_background_sync(strong_self).await;
                         } else {
@@ -719,6 +752,10 @@ impl SpecsAdapter for StatsigHttpSpecsAdapter {
         Ok(())
     }
 
+    fn update_sync_interval(&self, sync_interval_ms: u32) {
+        self.sync_interval.update(u64::from(sync_interval_ms));
+    }
+
     fn get_type_name(&self) -> String {
         stringify!(StatsigHttpSpecsAdapter).to_string()
     }
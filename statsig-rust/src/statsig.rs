@@ -1,3 +1,4 @@
+use crate::clock::{get_clock, Clock};
 use crate::console_capture::console_capture_handler::ConsoleCaptureHandler;
 use crate::console_capture::console_capture_instances::{
     ConsoleCaptureInstance, CONSOLE_CAPTURE_REGISTRY,
@@ -5,6 +6,7 @@ use crate::console_capture::console_capture_instances::{
 use crate::console_capture::console_log_line_levels::StatsigLogLineLevel;
 use crate::data_store_interface::{get_data_store_key, RequestPath};
 use crate::evaluation::cmab_evaluator::{get_cmab_ranked_list, CMABRankedGroup};
+use crate::evaluation::cmab_learner::CMABLearner;
 use crate::evaluation::country_lookup::CountryLookup;
 use crate::evaluation::dynamic_value::DynamicValue;
 use crate::evaluation::evaluation_details::EvaluationDetails;
@@ -15,15 +17,16 @@ use crate::evaluation::evaluator_result::{
     result_to_dynamic_config_eval, result_to_experiment_eval, result_to_gate_eval,
     result_to_layer_eval, EvaluatorResult,
 };
+use crate::evaluation::guarded_regex::RegexGuard;
 use crate::evaluation::user_agent_parsing::{ParsedUserAgentValue, UserAgentParser};
-use crate::event_logging::event_logger::{EventLogger, ExposureTrigger};
+use crate::event_logging::event_logger::{EventLogger, ExposureTrigger, FlushScheduling};
 use crate::event_logging::event_queue::queued_config_expo::EnqueueConfigExpoOp;
 use crate::event_logging::event_queue::queued_experiment_expo::EnqueueExperimentExpoOp;
 use crate::event_logging::event_queue::queued_expo::EnqueueExposureOp;
 use crate::event_logging::event_queue::queued_gate_expo::EnqueueGateExpoOp;
 use crate::event_logging::event_queue::queued_layer_param_expo::EnqueueLayerParamExpoOp;
 use crate::event_logging::event_queue::queued_passthrough::EnqueuePassthroughOp;
-use crate::event_logging::statsig_event_internal::StatsigEventInternal;
+use crate::event_logging::statsig_event_internal::{StatsigEventInternal, CMAB_REWARD_EVENT_NAME};
 use crate::event_logging_adapter::EventLoggingAdapter;
 use crate::event_logging_adapter::StatsigHttpEventLoggingAdapter;
 use crate::gcir::gcir_formatter::GCIRFormatter;
@@ -38,13 +41,16 @@ use crate::observability::diagnostics_observer::DiagnosticsObserver;
 use crate::observability::observability_client_adapter::{MetricType, ObservabilityEvent};
 use crate::observability::ops_stats::{OpsStatsForInstance, OPS_STATS};
 use crate::observability::sdk_errors_observer::{ErrorBoundaryEvent, SDKErrorsObserver};
-use crate::output_logger::{initialize_output_logger, shutdown_output_logger};
+use crate::output_logger::{
+    initialize_output_logger, set_output_log_level, shutdown_output_logger,
+};
+use crate::override_adapter::override_matcher::{ActiveOverride, OverrideMatcher};
 use crate::persistent_storage::persistent_values_manager::{
     PersistentValuesManager, StickyRuleFilter,
 };
 use crate::sdk_diagnostics::diagnostics::{ContextType, Diagnostics};
 use crate::sdk_diagnostics::marker::{ActionType, KeyType, Marker};
-use crate::sdk_event_emitter::SdkEventEmitter;
+use crate::sdk_event_emitter::{SdkEvent, SdkEventEmitter};
 use crate::spec_store::{SpecStore, SpecStoreData};
 use crate::specs_adapter::{StatsigCustomizedSpecsAdapter, StatsigHttpSpecsAdapter};
 use crate::specs_response::param_store_types::Parameter;
@@ -63,6 +69,7 @@ use crate::statsig_types::{
 };
 #[cfg(feature = "ffi-support")]
 use crate::statsig_types_raw::{DynamicConfigRaw, ExperimentRaw, FeatureGateRaw, LayerRaw};
+use crate::user::user_enricher::{EnrichedUser, MemoizedUserEnricher};
 use crate::user::StatsigUserInternal;
 use crate::utils::get_loggable_sdk_key;
 use crate::{
@@ -78,7 +85,6 @@ use crate::{
         LayerEvaluationOptions, ParameterStoreEvaluationOptions,
     },
 };
-use chrono::Utc;
 use parking_lot::Mutex;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -96,6 +102,7 @@ const TAG: &str = stringify!(Statsig);
 const ERROR_SDK_KEY: &str = "__STATSIG_ERROR_SDK_KEY__";
 const INIT_IP_TAG: &str = "INIT_COUNTRY_LOOKUP";
 const INIT_UA_TAG: &str = "INIT_UA";
+const CMAB_RETRAIN_TAG: &str = "CMAB_RETRAIN";
 
 lazy_static::lazy_static! {
     static ref SHARED_INSTANCE: Mutex<Option<Arc<Statsig>>> = Mutex::new(None);
@@ -103,6 +110,8 @@ lazy_static::lazy_static! {
 
 pub struct Statsig {
     pub statsig_runtime: Arc<StatsigRuntime>,
+    /// The options this instance was created with. Changes made through
+    /// [`Statsig::update_options`] are reflected by [`Statsig::get_current_options`].
     pub options: Arc<StatsigOptions>,
     pub event_emitter: Arc<SdkEventEmitter>,
 
@@ -119,6 +128,9 @@ pub struct Statsig {
     // (from set_default_environment_from_server). A Mutex here serialized all
     // concurrent evaluations; ArcSwapOption makes reads lock-free.
     fallback_environment: arc_swap::ArcSwapOption<HashMap<String, DynamicValue>>,
+    // Read on every evaluation that falls back to global custom fields, replaced by update_options
+    global_custom_fields: arc_swap::ArcSwapOption<HashMap<String, DynamicValue>>,
+    current_options: Mutex<Arc<StatsigOptions>>,
     ops_stats: Arc<OpsStatsForInstance>,
     console_capture: Arc<ConsoleCaptureInstance>,
     error_observer: Arc<dyn OpsStatsEventObserver>,
@@ -127,6 +139,9 @@ pub struct Statsig {
     background_tasks_started: Arc<AtomicBool>,
     persistent_values_manager: Option<Arc<PersistentValuesManager>>,
     initialize_details: Mutex<InitializeDetails>,
+    clock: Arc<dyn Clock>,
+    regex_guard: Option<RegexGuard>,
+    user_enricher: Option<MemoizedUserEnricher>,
 }
 
 pub struct StatsigContext {
@@ -160,8 +175,18 @@ impl Drop for Statsig {
 
 impl Statsig {
     pub fn new(sdk_key: &str, options: Option<Arc<StatsigOptions>>) -> Self {
-        let statsig_runtime = StatsigRuntime::get_runtime();
+        Self::new_with_flush_scheduling(sdk_key, options, FlushScheduling::Internal)
+    }
+
+    pub(crate) fn new_with_flush_scheduling(
+        sdk_key: &str,
+        options: Option<Arc<StatsigOptions>>,
+        flush_scheduling: FlushScheduling,
+    ) -> Self {
         let options = options.map(|o| o.validate_and_fix()).unwrap_or_default();
+        let statsig_runtime = StatsigRuntime::get_runtime_with_options(
+            options.runtime_options.clone().unwrap_or_default(),
+        );
 
         initialize_output_logger(
             &options.output_log_level,
@@ -169,6 +194,12 @@ impl Statsig {
         );
 
         let hashing = Arc::new(HashUtil::new());
+        let clock = get_clock(Some(&options));
+        let regex_guard = options
+            .regex_safety
+            .clone()
+            .map(|regex_safety| RegexGuard::new(sdk_key, regex_safety));
+        let user_enricher = options.user_enricher.clone().map(MemoizedUserEnricher::new);
 
         let data_store_key =
             get_data_store_key(RequestPath::RulesetsV2, sdk_key, &hashing, &options);
@@ -178,11 +209,22 @@ impl Statsig {
         let event_logging_adapter = initialize_event_logging_adapter(sdk_key, &options);
         let override_adapter = match options.override_adapter.as_ref() {
             Some(adapter) => Some(Arc::clone(adapter)),
-            None => Some(Arc::new(StatsigLocalOverrideAdapter::new()) as Arc<dyn OverrideAdapter>),
+            None => Some(Arc::new(
+                StatsigLocalOverrideAdapter::with_environment_tier(options.environment.clone())
+                    .with_clock(clock.clone()),
+            ) as Arc<dyn OverrideAdapter>),
         };
 
-        let event_logger =
-            EventLogger::new(sdk_key, &options, &event_logging_adapter, &statsig_runtime);
+        let event_emitter = Arc::new(SdkEventEmitter::default());
+
+        let event_logger = EventLogger::new(
+            sdk_key,
+            &options,
+            &event_logging_adapter,
+            &statsig_runtime,
+            event_emitter.clone(),
+            flush_scheduling,
+        );
 
         let diagnostics = Arc::new(Diagnostics::new(event_logger.clone(), sdk_key));
         let diagnostics_observer: Arc<dyn OpsStatsEventObserver> =
@@ -202,8 +244,6 @@ impl Statsig {
             &options.observability_client,
         );
 
-        let event_emitter = Arc::new(SdkEventEmitter::default());
-
         let spec_store = Arc::new(SpecStore::new(
             sdk_key,
             data_store_key,
@@ -220,6 +260,7 @@ impl Statsig {
         let persistent_values_manager = options.persistent_storage.clone().map(|storage| {
             Arc::new(PersistentValuesManager {
                 persistent_storage: storage,
+                clock: clock.clone(),
             })
         });
 
@@ -230,6 +271,10 @@ impl Statsig {
 
         Statsig {
             sdk_key: sdk_key.to_string(),
+            global_custom_fields: arc_swap::ArcSwapOption::from_pointee(
+                options.global_custom_fields.clone(),
+            ),
+            current_options: Mutex::new(options.clone()),
             options,
             hashing,
             statsig_environment: environment,
@@ -250,6 +295,9 @@ impl Statsig {
             persistent_values_manager,
             initialize_details: Mutex::new(InitializeDetails::default()),
             event_emitter,
+            clock,
+            regex_guard,
+            user_enricher,
         }
     }
 
@@ -332,6 +380,70 @@ impl Statsig {
         }
     }
 
+    /// Changes settings that are safe to change while running. Only the fields set in `update`
+    /// are applied. These are `output_log_level`, `specs_sync_interval_ms`,
+    /// `id_lists_sync_interval_ms`, `event_logging_max_queue_size`,
+    /// `event_logging_max_pending_batch_queue_size`, `event_logging_max_flush_interval_ms`,
+    /// `disable_all_logging`, `global_custom_fields`, `exposure_dedupe_max_keys` and
+    /// `console_capture_options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StatsigErr::InvalidOperation`] and applies nothing if `update` sets any other
+    /// field or an out of range value.
+    pub fn update_options(&self, update: StatsigOptions) -> Result<(), StatsigErr> {
+        update.validate_runtime_update()?;
+
+        let mut current_options = self
+            .current_options
+            .try_lock_for(Duration::from_secs(5))
+            .ok_or_else(|| StatsigErr::LockFailure("Failed to lock current_options".to_string()))?;
+        let merged = Arc::new(current_options.merge_runtime_update(&update));
+
+        if let Some(level) = &update.output_log_level {
+            set_output_log_level(level);
+        }
+
+        if let Some(interval_ms) = update.specs_sync_interval_ms {
+            self.specs_adapter.inner.update_sync_interval(interval_ms);
+        }
+
+        if let (Some(interval_ms), Some(adapter)) = (
+            update.id_lists_sync_interval_ms,
+            &self.id_lists_adapter.inner,
+        ) {
+            adapter.update_sync_interval(interval_ms);
+        }
+
+        self.event_logger.update_options(&merged);
+
+        if update.global_custom_fields.is_some() {
+            self.global_custom_fields
+                .store(merged.global_custom_fields.clone().map(Arc::new));
+        }
+
+        if update.console_capture_options.is_some() || update.global_custom_fields.is_some() {
+            self.console_capture
+                .update(&merged, &self.statsig_environment);
+        }
+
+        log_d!(TAG, "Updated options");
+        *current_options = merged;
+        Ok(())
+    }
+
+    /// The options this instance was created with, plus any changes from
+    /// [`Statsig::update_options`].
+    pub fn get_current_options(&self) -> Arc<StatsigOptions> {
+        match self.current_options.try_lock_for(Duration::from_secs(5)) {
+            Some(options) => options.clone(),
+            None => {
+                log_e!(TAG, "Failed to lock current_options");
+                self.options.clone()
+            }
+        }
+    }
+
     pub async fn shutdown(&self) -> Result<(), StatsigErr> {
         self.shutdown_with_timeout(Duration::from_secs(3)).await
     }
@@ -343,6 +455,10 @@ impl Statsig {
             timeout.as_millis()
         );
 
+        self.event_emitter.emit(SdkEvent::ShutdownStarted {
+            timeout_ms: timeout.as_millis() as u64,
+        });
+
         let start = Instant::now();
         let shutdown_result = tokio::select! {
             () = tokio::time::sleep(timeout) => {
@@ -360,6 +476,9 @@ impl Statsig {
 
                 shutdown_output_logger();
 
+                // before the data store is shut down with the specs adapter
+                self.event_logger.save_exposure_dedupe_snapshot().await;
+
                 try_join!(
                     id_list_shutdown,
                     self.event_logger.shutdown(&self.statsig_runtime),
@@ -379,6 +498,11 @@ impl Statsig {
             }
         };
 
+        self.event_emitter.emit(SdkEvent::ShutdownCompleted {
+            success: shutdown_result.is_ok(),
+            duration_ms: start.elapsed().as_millis() as u64,
+        });
+
         self.statsig_runtime.shutdown();
         shutdown_result
     }
@@ -570,6 +694,7 @@ impl Statsig {
 
         self.event_logger.enqueue(EnqueuePassthroughOp {
             event: StatsigEventInternal::new_custom_event(
+                self.clock.now_ms(),
                 user_internal.to_loggable(),
                 event_name.to_string(),
                 value.map(|v| json!(v)),
@@ -588,6 +713,7 @@ impl Statsig {
         let user_internal = self.internalize_user(user);
         self.event_logger.enqueue(EnqueuePassthroughOp {
             event: StatsigEventInternal::new_custom_event(
+                self.clock.now_ms(),
                 user_internal.to_loggable(),
                 event_name.to_string(),
                 value.map(|v| json!(v)),
@@ -607,6 +733,7 @@ impl Statsig {
 
         self.event_logger.enqueue(EnqueuePassthroughOp {
             event: StatsigEventInternal::new_custom_event_with_typed_metadata(
+                self.clock.now_ms(),
                 user_internal.to_loggable(),
                 event_name.to_string(),
                 value.map(|v| json!(v)),
@@ -626,6 +753,7 @@ impl Statsig {
 
         self.event_logger.enqueue(EnqueuePassthroughOp {
             event: StatsigEventInternal::new_custom_event_with_typed_metadata(
+                self.clock.now_ms(),
                 user_internal.to_loggable(),
                 event_name.to_string(),
                 value.map(|v| json!(v)),
@@ -648,7 +776,7 @@ impl Statsig {
                 log_level,
                 value,
                 metadata,
-                None,
+                self.clock.now_ms(),
             ),
         });
     }
@@ -681,7 +809,7 @@ impl Statsig {
 
         self.event_logger
             .enqueue(EnqueueLayerParamExpoOp::LayerOwned(
-                Utc::now().timestamp_millis() as u64,
+                self.clock.now_ms(),
                 Box::new(layer),
                 parameter_name,
                 ExposureTrigger::Auto,
@@ -694,6 +822,10 @@ impl Statsig {
             .flush_all_pending_events(&self.statsig_runtime)
             .await;
     }
+
+    pub(crate) async fn run_scheduled_flush_tick(&self) {
+        self.event_logger.run_scheduled_flush_tick().await;
+    }
 }
 
 // ------------------------------------------------------------------------------- [ Parameter Store ]
@@ -975,6 +1107,7 @@ impl Statsig {
 
         self.event_logger.enqueue(EnqueuePassthroughOp {
             event: StatsigEventInternal::new_custom_event(
+                self.clock.now_ms(),
                 user_internal.to_loggable(),
                 "statsig::identify".to_string(),
                 None,
@@ -1027,12 +1160,104 @@ impl Statsig {
         experiment.rule_id = group_id;
 
         self.event_logger.enqueue(EnqueueExperimentExpoOp {
-            exposure_time: Utc::now().timestamp_millis() as u64,
+            exposure_time: self.clock.now_ms(),
             user: &user_internal,
             experiment: &experiment,
             trigger: ExposureTrigger::Manual,
         });
     }
+
+    /// Logs the reward observed after `user` was shown `group_id`. The event carries the same
+    /// `config` and `ruleID` as the group's exposure so the two can be joined. The reward is
+    /// also recorded to the `CMABLearner`, if one is configured, so there is no need to call
+    /// [`Statsig::record_cmab_outcome`] as well.
+    pub fn log_cmab_reward(
+        &self,
+        user: &StatsigUser,
+        cmab_name: &str,
+        group_id: &str,
+        reward: f64,
+        metadata: Option<HashMap<String, String>>,
+    ) {
+        if !reward.is_finite() {
+            log_w!(TAG, "Ignoring non-finite reward for CMAB {}", cmab_name);
+            return;
+        }
+
+        let user_internal = self.internalize_user(user);
+
+        let mut typed_metadata: HashMap<String, Value> = metadata
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(k, v)| (k, Value::String(v)))
+            .collect();
+        typed_metadata.insert("config".into(), json!(cmab_name));
+        typed_metadata.insert("ruleID".into(), json!(group_id));
+
+        self.event_logger.enqueue(EnqueuePassthroughOp {
+            event: StatsigEventInternal::new_custom_event_with_typed_metadata(
+                self.clock.now_ms(),
+                user_internal.to_loggable(),
+                CMAB_REWARD_EVENT_NAME.to_string(),
+                Some(json!(reward)),
+                Some(typed_metadata),
+            ),
+        });
+
+        if let Some(learner) = &self.options.cmab_learner {
+            self.record_to_cmab_learner(learner, &user_internal, cmab_name, group_id, reward);
+        }
+    }
+
+    /// Records the reward observed after `user` was shown `group_id`, for the configured
+    /// `CMABLearner`, without logging a reward event. Context features are read from the user.
+    /// No-op without a learner. Use [`Statsig::log_cmab_reward`] to also log the reward.
+    pub fn record_cmab_outcome(
+        &self,
+        user: &StatsigUser,
+        cmab_name: &str,
+        group_id: &str,
+        reward: f64,
+    ) {
+        let Some(learner) = &self.options.cmab_learner else {
+            log_w!(
+                TAG,
+                "record_cmab_outcome called without a cmab_learner in StatsigOptions"
+            );
+            return;
+        };
+
+        let user_internal = self.internalize_user(user);
+        self.record_to_cmab_learner(learner, &user_internal, cmab_name, group_id, reward);
+    }
+
+    /// Records the outcome and, when a refit is due, runs it on the runtime rather than the
+    /// caller's thread.
+    fn record_to_cmab_learner(
+        &self,
+        learner: &Arc<CMABLearner>,
+        user_internal: &StatsigUserInternal,
+        cmab_name: &str,
+        group_id: &str,
+        reward: f64,
+    ) {
+        let context = learner.get_context_for_user(user_internal);
+        if !learner.record_outcome(cmab_name, group_id, &context, reward) {
+            return;
+        }
+
+        let learner = learner.clone();
+        let cmab_name = cmab_name.to_string();
+        let spawn_result = self
+            .statsig_runtime
+            .spawn(CMAB_RETRAIN_TAG, move |_| async move {
+                learner.train(&cmab_name);
+            });
+
+        if let Err(e) = spawn_result {
+            log_w!(TAG, "Failed to spawn CMAB retrain task: {}", e);
+        }
+    }
 }
 
 // ------------------------------------------------------------------------------- [ Override ]
@@ -1041,6 +1266,7 @@ impl Statsig {
     pub fn override_gate(&self, gate_name: &str, value: bool, id: Option<&str>) {
         if let Some(adapter) = &self.override_adapter {
             adapter.override_gate(gate_name, value, id);
+            self.emit_override_set("gate", gate_name, id);
         }
     }
 
@@ -1052,6 +1278,7 @@ impl Statsig {
     ) {
         if let Some(adapter) = &self.override_adapter {
             adapter.override_dynamic_config(config_name, value, id);
+            self.emit_override_set("dynamic_config", config_name, id);
         }
     }
 
@@ -1063,6 +1290,7 @@ impl Statsig {
     ) {
         if let Some(adapter) = &self.override_adapter {
             adapter.override_layer(layer_name, value, id);
+            self.emit_override_set("layer", layer_name, id);
         }
     }
 
@@ -1074,6 +1302,7 @@ impl Statsig {
     ) {
         if let Some(adapter) = &self.override_adapter {
             adapter.override_parameter_store(param_name, value, id);
+            self.emit_override_set("parameter_store", param_name, id);
         }
     }
 
@@ -1085,6 +1314,7 @@ impl Statsig {
     ) {
         if let Some(adapter) = &self.override_adapter {
             adapter.override_experiment(experiment_name, value, id);
+            self.emit_override_set("experiment", experiment_name, id);
         }
     }
 
@@ -1096,44 +1326,213 @@ impl Statsig {
     ) {
         if let Some(adapter) = &self.override_adapter {
             adapter.override_experiment_by_group_name(experiment_name, group_name, id);
+            self.emit_override_set("experiment", experiment_name, id);
         }
     }
 
     pub fn remove_gate_override(&self, gate_name: &str, id: Option<&str>) {
         if let Some(adapter) = &self.override_adapter {
             adapter.remove_gate_override(gate_name, id);
+            self.emit_override_removed("gate", Some(gate_name), id);
         }
     }
 
     pub fn remove_dynamic_config_override(&self, config_name: &str, id: Option<&str>) {
         if let Some(adapter) = &self.override_adapter {
             adapter.remove_dynamic_config_override(config_name, id);
+            self.emit_override_removed("dynamic_config", Some(config_name), id);
         }
     }
 
     pub fn remove_experiment_override(&self, experiment_name: &str, id: Option<&str>) {
         if let Some(adapter) = &self.override_adapter {
             adapter.remove_experiment_override(experiment_name, id);
+            self.emit_override_removed("experiment", Some(experiment_name), id);
         }
     }
 
     pub fn remove_layer_override(&self, layer_name: &str, id: Option<&str>) {
         if let Some(adapter) = &self.override_adapter {
             adapter.remove_layer_override(layer_name, id);
+            self.emit_override_removed("layer", Some(layer_name), id);
         }
     }
 
     pub fn remove_parameter_store_override(&self, parameter_store_name: &str, id: Option<&str>) {
         if let Some(adapter) = &self.override_adapter {
             adapter.remove_parameter_store_override(parameter_store_name, id);
+            self.emit_override_removed("parameter_store", Some(parameter_store_name), id);
         }
     }
 
     pub fn remove_all_overrides(&self) {
         if let Some(adapter) = &self.override_adapter {
             adapter.remove_all_overrides();
+            self.emit_override_removed("all", None, None);
+        }
+    }
+
+    pub fn override_gate_with_matcher(
+        &self,
+        gate_name: &str,
+        value: bool,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            let id = matcher.id.clone();
+            adapter.override_gate_with_matcher(gate_name, value, matcher);
+            self.emit_override_set("gate", gate_name, id.as_deref());
+        }
+    }
+
+    pub fn override_dynamic_config_with_matcher(
+        &self,
+        config_name: &str,
+        value: HashMap<String, serde_json::Value>,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            let id = matcher.id.clone();
+            adapter.override_dynamic_config_with_matcher(config_name, value, matcher);
+            self.emit_override_set("dynamic_config", config_name, id.as_deref());
+        }
+    }
+
+    pub fn override_layer_with_matcher(
+        &self,
+        layer_name: &str,
+        value: HashMap<String, serde_json::Value>,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            let id = matcher.id.clone();
+            adapter.override_layer_with_matcher(layer_name, value, matcher);
+            self.emit_override_set("layer", layer_name, id.as_deref());
+        }
+    }
+
+    pub fn override_parameter_store_with_matcher(
+        &self,
+        param_name: &str,
+        value: HashMap<String, serde_json::Value>,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            let id = matcher.id.clone();
+            adapter.override_parameter_store_with_matcher(param_name, value, matcher);
+            self.emit_override_set("parameter_store", param_name, id.as_deref());
+        }
+    }
+
+    pub fn override_experiment_with_matcher(
+        &self,
+        experiment_name: &str,
+        value: HashMap<String, serde_json::Value>,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            let id = matcher.id.clone();
+            adapter.override_experiment_with_matcher(experiment_name, value, matcher);
+            self.emit_override_set("experiment", experiment_name, id.as_deref());
+        }
+    }
+
+    pub fn override_experiment_by_group_name_with_matcher(
+        &self,
+        experiment_name: &str,
+        group_name: &str,
+        matcher: OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            let id = matcher.id.clone();
+            adapter.override_experiment_by_group_name_with_matcher(
+                experiment_name,
+                group_name,
+                matcher,
+            );
+            self.emit_override_set("experiment", experiment_name, id.as_deref());
         }
     }
+
+    pub fn remove_gate_override_with_matcher(&self, gate_name: &str, matcher: &OverrideMatcher) {
+        if let Some(adapter) = &self.override_adapter {
+            adapter.remove_gate_override_with_matcher(gate_name, matcher);
+            self.emit_override_removed("gate", Some(gate_name), matcher.id.as_deref());
+        }
+    }
+
+    pub fn remove_dynamic_config_override_with_matcher(
+        &self,
+        config_name: &str,
+        matcher: &OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            adapter.remove_dynamic_config_override_with_matcher(config_name, matcher);
+            self.emit_override_removed("dynamic_config", Some(config_name), matcher.id.as_deref());
+        }
+    }
+
+    pub fn remove_experiment_override_with_matcher(
+        &self,
+        experiment_name: &str,
+        matcher: &OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            adapter.remove_experiment_override_with_matcher(experiment_name, matcher);
+            self.emit_override_removed("experiment", Some(experiment_name), matcher.id.as_deref());
+        }
+    }
+
+    pub fn remove_layer_override_with_matcher(&self, layer_name: &str, matcher: &OverrideMatcher) {
+        if let Some(adapter) = &self.override_adapter {
+            adapter.remove_layer_override_with_matcher(layer_name, matcher);
+            self.emit_override_removed("layer", Some(layer_name), matcher.id.as_deref());
+        }
+    }
+
+    pub fn remove_parameter_store_override_with_matcher(
+        &self,
+        parameter_store_name: &str,
+        matcher: &OverrideMatcher,
+    ) {
+        if let Some(adapter) = &self.override_adapter {
+            adapter.remove_parameter_store_override_with_matcher(parameter_store_name, matcher);
+            self.emit_override_removed(
+                "parameter_store",
+                Some(parameter_store_name),
+                matcher.id.as_deref(),
+            );
+        }
+    }
+
+    /// Lists every active (non-expired) override along with the matcher it applies to.
+    pub fn list_overrides(&self) -> Vec<ActiveOverride> {
+        match &self.override_adapter {
+            Some(adapter) => adapter.list_overrides(),
+            None => vec![],
+        }
+    }
+
+    fn emit_override_set(&self, override_type: &str, entity_name: &str, id: Option<&str>) {
+        self.event_emitter.emit(SdkEvent::OverrideSet {
+            entity_name,
+            override_type,
+            id,
+        });
+    }
+
+    fn emit_override_removed(
+        &self,
+        override_type: &str,
+        entity_name: Option<&str>,
+        id: Option<&str>,
+    ) {
+        self.event_emitter.emit(SdkEvent::OverrideRemoved {
+            entity_name,
+            override_type,
+            id,
+        });
+    }
 }
 
 // ------------------------------------------------------------------------------- [ Debugging ]
@@ -1209,7 +1608,7 @@ impl Statsig {
             self.event_logger.increment_non_exposure_checks(gate_name);
         } else {
             self.event_logger.enqueue(EnqueueGateExpoOp {
-                exposure_time: Utc::now().timestamp_millis() as u64,
+                exposure_time: self.clock.now_ms(),
                 user: &user_internal,
                 queried_gate_name: gate_name,
                 evaluation: evaluation.map(Cow::Owned),
@@ -1246,7 +1645,7 @@ impl Statsig {
             self.event_logger.increment_non_exposure_checks(gate_name);
         } else {
             self.event_logger.enqueue(EnqueueGateExpoOp {
-                exposure_time: Utc::now().timestamp_millis() as u64,
+                exposure_time: self.clock.now_ms(),
                 user: &user_internal,
                 queried_gate_name: gate_name,
                 evaluation: evaluation.as_ref().map(Cow::Borrowed),
@@ -1268,6 +1667,7 @@ impl Statsig {
             self.evaluate_spec_raw(&user_internal, gate_name, &SpecType::Gate, None);
 
         self.event_logger.enqueue(EnqueueExposureOp::gate_exposure(
+            self.clock.now_ms(),
             &user_internal,
             &interned_gate_name,
             ExposureTrigger::Manual,
@@ -1321,7 +1721,7 @@ impl Statsig {
                 .increment_non_exposure_checks(dynamic_config_name);
         } else {
             self.event_logger.enqueue(EnqueueConfigExpoOp {
-                exposure_time: Utc::now().timestamp_millis() as u64,
+                exposure_time: self.clock.now_ms(),
                 user: &user_internal,
                 config: &dynamic_config,
                 trigger: ExposureTrigger::Auto,
@@ -1350,6 +1750,7 @@ impl Statsig {
 
         self.event_logger
             .enqueue(EnqueueExposureOp::dynamic_config_exposure(
+                self.clock.now_ms(),
                 &user_internal,
                 &interned_dynamic_config_name,
                 ExposureTrigger::Manual,
@@ -1382,9 +1783,20 @@ impl Statsig {
         options: ExperimentEvaluationOptions,
     ) -> Experiment {
         let user_internal = self.internalize_user(user);
+        self.get_experiment_for_user_internal(&user_internal, experiment_name, options)
+    }
+
+    /// Evaluates and logs an experiment for a user that is already enriched, e.g. the user of
+    /// an `experiment_group` condition.
+    pub(crate) fn get_experiment_for_user_internal(
+        &self,
+        user_internal: &StatsigUserInternal,
+        experiment_name: &str,
+        options: ExperimentEvaluationOptions,
+    ) -> Experiment {
         let disable_exposure_logging = options.disable_exposure_logging;
         let mut experiment = self.get_experiment_impl(
-            &user_internal,
+            user_internal,
             experiment_name,
             Some(options.disable_exposure_logging),
         );
@@ -1393,11 +1805,11 @@ impl Statsig {
         // (no enforce flags, or no sticky value found) never takes the lock and
         // never holds it across persistent_storage save/delete calls.
         let reeval = |name: &str, spec_type: &SpecType, filter: StickyRuleFilter| {
-            self.reeval_sticky_rule_filter(&user_internal, name, spec_type, filter)
+            self.reeval_sticky_rule_filter(user_internal, name, spec_type, filter)
         };
         experiment = PersistentValuesManager::try_apply_sticky_value_to_experiment(
             &self.persistent_values_manager,
-            &user_internal,
+            user_internal,
             &options,
             experiment,
             &reeval,
@@ -1413,8 +1825,8 @@ impl Statsig {
                 .increment_non_exposure_checks(experiment_name);
         } else {
             self.event_logger.enqueue(EnqueueExperimentExpoOp {
-                exposure_time: Utc::now().timestamp_millis() as u64,
-                user: &user_internal,
+                exposure_time: self.clock.now_ms(),
+                user: user_internal,
                 experiment: &experiment,
                 trigger: ExposureTrigger::Auto,
             });
@@ -1433,6 +1845,7 @@ impl Statsig {
 
         self.event_logger
             .enqueue(EnqueueExposureOp::experiment_exposure(
+                self.clock.now_ms(),
                 &user_internal,
                 &interned_experiment_name,
                 ExposureTrigger::Manual,
@@ -1682,6 +2095,7 @@ impl Statsig {
 
         self.event_logger
             .enqueue(EnqueueExposureOp::layer_param_exposure(
+                self.clock.now_ms(),
                 &user_internal,
                 &interned_layer_name,
                 interned_parameter_name,
@@ -1726,6 +2140,7 @@ impl Statsig {
             self.event_logger.increment_non_exposure_checks(gate_name);
         } else {
             self.event_logger.enqueue(EnqueueExposureOp::gate_exposure(
+                self.clock.now_ms(),
                 &user_internal,
                 &interned_gate_name,
                 ExposureTrigger::Auto,
@@ -1777,6 +2192,7 @@ impl Statsig {
         } else {
             self.event_logger
                 .enqueue(EnqueueExposureOp::dynamic_config_exposure(
+                    self.clock.now_ms(),
                     &user_internal,
                     &interned_dynamic_config_name,
                     ExposureTrigger::Auto,
@@ -1878,6 +2294,7 @@ impl Statsig {
         } else {
             self.event_logger
                 .enqueue(EnqueueExposureOp::dynamic_config_exposure(
+                    self.clock.now_ms(),
                     &user_internal,
                     &interned_experiment_name,
                     ExposureTrigger::Auto,
@@ -1999,6 +2416,7 @@ impl Statsig {
 
         self.event_logger
             .enqueue(EnqueueExposureOp::layer_param_exposure_from_partial_raw(
+                self.clock.now_ms(),
                 interned_parameter_name,
                 ExposureTrigger::Auto,
                 partial_raw,
@@ -2021,19 +2439,20 @@ impl Statsig {
         None
     }
 
-    pub(crate) fn get_value_from_global_custom_fields(&self, key: &str) -> Option<&DynamicValue> {
-        if let Some(env) = &self.options.global_custom_fields {
-            return env.get(key);
-        }
+    pub(crate) fn now_ms(&self) -> u64 {
+        self.clock.now_ms()
+    }
 
-        None
+    pub(crate) fn regex_guard(&self) -> Option<&RegexGuard> {
+        self.regex_guard.as_ref()
     }
 
-    pub(crate) fn use_global_custom_fields<T>(
-        &self,
-        f: impl FnOnce(Option<&HashMap<String, DynamicValue>>) -> T,
-    ) -> T {
-        f(self.options.global_custom_fields.as_ref())
+    pub(crate) fn enrich_user(&self, user: &StatsigUser) -> Option<EnrichedUser> {
+        self.user_enricher.as_ref()?.enrich(user)
+    }
+
+    pub(crate) fn get_global_custom_fields(&self) -> Option<Arc<HashMap<String, DynamicValue>>> {
+        self.global_custom_fields.load_full()
     }
 
     pub(crate) fn use_statsig_env<T>(
@@ -2207,11 +2626,25 @@ impl Statsig {
             );
         }
 
+        if let Some(adapter) = &self.override_adapter {
+            adapter.initialize(self.event_emitter.clone());
+            if let Err(e) = adapter.clone().start(&self.statsig_runtime) {
+                log_error_to_statsig_and_console!(
+                    self.ops_stats.clone(),
+                    TAG,
+                    StatsigErr::UnstartedAdapter(format!("Failed to start override adapter: {e}"))
+                );
+            }
+        }
+
         let spec_info = self.spec_store.get_current_specs_info();
         let duration = start_time.elapsed().as_millis() as u64;
 
         self.set_default_environment_from_server();
 
+        // after the specs are loaded, so the dedupe TTL from the sdk configs is known
+        self.event_logger.warm_exposure_dedupe().await;
+
         if self.options.wait_for_country_lookup_init.unwrap_or(false) {
             match init_country_lookup {
                 Some(Ok(task_id)) => {
@@ -2316,6 +2749,7 @@ impl Statsig {
             Some(self),
             disable_exposure_logging,
         )
+        .with_guarded_regexes(&data.guarded_regexes)
     }
 
     /// Re-evaluates a spec restricted to a single rule subset (targeting-only or
@@ -2738,7 +3172,8 @@ impl Statsig {
                 let marker = Marker::new(KeyType::Overall, ActionType::End, None)
                     .with_is_success(success)
                     .with_config_spec_ready(specs_info.source != SpecsSource::NoValues)
-                    .with_source(source_str);
+                    .with_source(source_str)
+                    .with_running_tasks(self.statsig_runtime.get_running_task_tags());
 
                 if let Some(msg) = &error_message {
                     marker.with_message(msg.to_string())
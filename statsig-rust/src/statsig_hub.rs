@@ -0,0 +1,273 @@
+use crate::event_logging::event_logger::FlushScheduling;
+use crate::event_logging::event_logger_constants::EventLoggerConstants;
+use crate::init_details::InitializeDetails;
+use crate::statsig_runtime::{StatsigRuntime, StatsigRuntimeOptions};
+use crate::utils::get_loggable_sdk_key;
+use crate::{log_d, log_w, Statsig, StatsigErr, StatsigOptions};
+use futures::future::join_all;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+const TAG: &str = stringify!(StatsigHub);
+const HUB_FLUSH_TAG: &str = "HUB_EVT_LOG_FLUSH_LOOP";
+const HUB_PROJECT_FLUSH_TAG: &str = "HUB_PROJECT_FLUSH";
+
+struct HubProject {
+    statsig: Arc<Statsig>,
+    /// Set while a flush tick for the project is running, so ticks never pile up.
+    is_flushing: Arc<AtomicBool>,
+}
+
+type Projects = RwLock<HashMap<String, HubProject>>;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsigHubProjectDiagnostics {
+    /// The loggable prefix of the project's SDK key.
+    pub sdk_key: String,
+    pub initialize_details: InitializeDetails,
+    pub running_tasks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsigHubDiagnostics {
+    pub projects: Vec<StatsigHubProjectDiagnostics>,
+    pub hub_running_tasks: Vec<String>,
+}
+
+/// Hosts several Statsig projects in one process, keyed by SDK key.
+///
+/// Every project runs on the same tokio runtime and sends requests through one shared HTTP
+/// client. A single scheduler owned by the hub drives scheduled flushes, with each project
+/// flushed on its own task so a slow project does not hold up the others. Full batches are
+/// still flushed as soon as they fill up. Country and user agent parser data and interned
+/// strings are process wide. Specs, overrides, ID lists and event queues stay isolated per
+/// project.
+pub struct StatsigHub {
+    runtime_options: StatsigRuntimeOptions,
+    statsig_runtime: Arc<StatsigRuntime>,
+    projects: Arc<Projects>,
+    /// Stops the flush scheduler without touching the project flushes it spawned.
+    scheduler_stop_notify: Arc<Notify>,
+}
+
+impl StatsigHub {
+    pub fn new() -> Result<Self, StatsigErr> {
+        Self::with_runtime_options(StatsigRuntimeOptions::default())
+    }
+
+    /// Resolves a single runtime handle from `runtime_options` up front. Fails when no
+    /// runtime is available and the fallback runtime is disabled.
+    pub fn with_runtime_options(
+        mut runtime_options: StatsigRuntimeOptions,
+    ) -> Result<Self, StatsigErr> {
+        let handle =
+            StatsigRuntime::get_runtime_with_options(runtime_options.clone()).get_handle()?;
+        runtime_options.handle = Some(handle);
+
+        let hub = Self {
+            statsig_runtime: StatsigRuntime::get_runtime_with_options(runtime_options.clone()),
+            runtime_options,
+            projects: Arc::new(RwLock::new(HashMap::new())),
+            scheduler_stop_notify: Arc::new(Notify::new()),
+        };
+
+        hub.spawn_flush_scheduler()?;
+        Ok(hub)
+    }
+
+    /// Creates the project. Any `runtime_options` in `options` are replaced by the hub's.
+    pub fn add_project(
+        &self,
+        sdk_key: &str,
+        options: Option<Arc<StatsigOptions>>,
+    ) -> Result<Arc<Statsig>, StatsigErr> {
+        let mut projects = self.projects.write();
+        if projects.contains_key(sdk_key) {
+            return Err(StatsigErr::InvalidOperation(format!(
+                "Project {} is already in the hub",
+                get_loggable_sdk_key(sdk_key)
+            )));
+        }
+
+        let mut options = options.map(|o| (*o).clone()).unwrap_or_default();
+        if options.runtime_options.is_some() {
+            log_w!(
+                TAG,
+                "Ignoring runtime_options for {}, projects share the hub runtime",
+                get_loggable_sdk_key(sdk_key)
+            );
+        }
+        options.runtime_options = Some(self.runtime_options.clone());
+        options.share_http_connection_pool = Some(true);
+
+        let statsig = Arc::new(Statsig::new_with_flush_scheduling(
+            sdk_key,
+            Some(Arc::new(options)),
+            FlushScheduling::External,
+        ));
+        projects.insert(
+            sdk_key.to_string(),
+            HubProject {
+                statsig: statsig.clone(),
+                is_flushing: Arc::new(AtomicBool::new(false)),
+            },
+        );
+
+        log_d!(TAG, "Added project {}", get_loggable_sdk_key(sdk_key));
+        Ok(statsig)
+    }
+
+    pub fn get_project(&self, sdk_key: &str) -> Option<Arc<Statsig>> {
+        self.projects
+            .read()
+            .get(sdk_key)
+            .map(|project| project.statsig.clone())
+    }
+
+    pub fn get_sdk_keys(&self) -> Vec<String> {
+        self.projects.read().keys().cloned().collect()
+    }
+
+    /// Removes the project and shuts it down, flushing its remaining events.
+    pub async fn remove_project(&self, sdk_key: &str) -> Result<(), StatsigErr> {
+        let project = self.projects.write().remove(sdk_key).ok_or_else(|| {
+            StatsigErr::InvalidOperation(format!(
+                "Project {} is not in the hub",
+                get_loggable_sdk_key(sdk_key)
+            ))
+        })?;
+
+        project.statsig.shutdown().await
+    }
+
+    /// Initializes every project concurrently. Results are keyed by SDK key.
+    pub async fn initialize_all(&self) -> HashMap<String, InitializeDetails> {
+        let projects = self.get_projects();
+        let results = join_all(projects.iter().map(|(sdk_key, statsig)| async move {
+            let details = match statsig.initialize_with_details().await {
+                Ok(details) => details,
+                Err(e) => InitializeDetails::from_error("Failed to initialize", Some(e)),
+            };
+            (sdk_key.clone(), details)
+        }))
+        .await;
+
+        results.into_iter().collect()
+    }
+
+    pub async fn flush_all(&self) {
+        let projects = self.get_projects();
+        join_all(projects.iter().map(|(_, statsig)| statsig.flush_events())).await;
+    }
+
+    /// Stops the flush scheduler and waits for in-flight project flushes, then shuts down
+    /// every project. Returns the first error.
+    pub async fn shutdown(&self) -> Result<(), StatsigErr> {
+        self.scheduler_stop_notify.notify_one();
+        self.statsig_runtime
+            .await_tasks_with_tag(HUB_FLUSH_TAG)
+            .await;
+        // a project flush owns the batch it dequeued, aborting it would drop those events
+        self.statsig_runtime
+            .await_tasks_with_tag(HUB_PROJECT_FLUSH_TAG)
+            .await;
+
+        let projects: Vec<Arc<Statsig>> = self
+            .projects
+            .write()
+            .drain()
+            .map(|(_, project)| project.statsig)
+            .collect();
+
+        let results = join_all(projects.iter().map(|statsig| statsig.shutdown())).await;
+        self.statsig_runtime.shutdown();
+
+        results.into_iter().find(|r| r.is_err()).unwrap_or(Ok(()))
+    }
+
+    pub fn get_diagnostics(&self) -> StatsigHubDiagnostics {
+        let projects = self
+            .get_projects()
+            .into_iter()
+            .map(|(sdk_key, statsig)| StatsigHubProjectDiagnostics {
+                sdk_key: get_loggable_sdk_key(&sdk_key),
+                initialize_details: statsig.get_initialize_details(),
+                running_tasks: statsig.statsig_runtime.get_running_task_tags(),
+            })
+            .collect();
+
+        StatsigHubDiagnostics {
+            projects,
+            hub_running_tasks: self.statsig_runtime.get_running_task_tags(),
+        }
+    }
+
+    fn get_projects(&self) -> Vec<(String, Arc<Statsig>)> {
+        self.projects
+            .read()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.statsig.clone()))
+            .collect()
+    }
+
+    fn spawn_flush_scheduler(&self) -> Result<(), StatsigErr> {
+        let weak_projects: Weak<Projects> = Arc::downgrade(&self.projects);
+        let tick_interval = Duration::from_millis(EventLoggerConstants::tick_interval_ms());
+        let statsig_runtime = self.statsig_runtime.clone();
+        let scheduler_stop_notify = self.scheduler_stop_notify.clone();
+
+        self.statsig_runtime
+            .spawn(HUB_FLUSH_TAG, move |rt_shutdown_notify| async move {
+                loop {
+                    tokio::select! {
+                        () = tokio::time::sleep(tick_interval) => {}
+                        () = rt_shutdown_notify.notified() => return,
+                        () = scheduler_stop_notify.notified() => return,
+                    }
+
+                    let Some(projects) = weak_projects.upgrade() else {
+                        return;
+                    };
+                    let instances: Vec<(Arc<Statsig>, Arc<AtomicBool>)> = projects
+                        .read()
+                        .values()
+                        .map(|p| (p.statsig.clone(), p.is_flushing.clone()))
+                        .collect();
+                    drop(projects);
+
+                    for (statsig, is_flushing) in instances {
+                        Self::spawn_project_flush_tick(&statsig_runtime, statsig, is_flushing);
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+
+    /// Skipped when the project's previous tick is still running.
+    fn spawn_project_flush_tick(
+        statsig_runtime: &Arc<StatsigRuntime>,
+        statsig: Arc<Statsig>,
+        is_flushing: Arc<AtomicBool>,
+    ) {
+        if is_flushing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let is_flushing_clone = is_flushing.clone();
+        let result = statsig_runtime.spawn(HUB_PROJECT_FLUSH_TAG, move |_| async move {
+            statsig.run_scheduled_flush_tick().await;
+            is_flushing_clone.store(false, Ordering::Release);
+        });
+
+        if let Err(e) = result {
+            log_w!(TAG, "Failed to spawn project flush tick: {}", e);
+            is_flushing.store(false, Ordering::Release);
+        }
+    }
+}
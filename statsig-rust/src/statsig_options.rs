@@ -1,18 +1,26 @@
 use serde::ser::SerializeStruct;
 use serde::{Serialize, Serializer};
 
+use crate::clock::Clock;
 use crate::console_capture::console_capture_options::ConsoleCaptureOptions;
 use crate::data_store_interface::{DataStoreKeyVersion, DataStoreTrait};
+use crate::evaluation::cmab_learner::CMABLearner;
 use crate::evaluation::dynamic_value::DynamicValue;
+use crate::evaluation::guarded_regex::RegexSafetyOptions;
 use crate::event_logging::event_logger;
+use crate::event_logging::exposure_dedupe_persistence::ExposureDedupePersistence;
+use crate::event_logging::exposure_sampling_policy::ExposureSamplingPolicy;
+use crate::event_logging::redaction_policy::RedactionPolicy;
 use crate::event_logging_adapter::EventLoggingAdapter;
-use crate::id_lists_adapter::IdListsAdapter;
+use crate::id_lists_adapter::{IdListStorageConfig, IdListsAdapter};
 use crate::networking::proxy_config::ProxyConfig;
 use crate::output_logger::{LogLevel, OutputLogProvider};
 use crate::persistent_storage::persistent_storage_trait::PersistentStorage;
+use crate::statsig_runtime::StatsigRuntimeOptions;
+use crate::user::UserEnricher;
 use crate::{
     log_d, log_w, serialize_if_not_none, ConfigCompressionMode, ObservabilityClient,
-    OverrideAdapter, SpecAdapterConfig, SpecsAdapter,
+    OverrideAdapter, SpecAdapterConfig, SpecsAdapter, StatsigErr,
 };
 use std::collections::{HashMap, HashSet};
 use std::fmt;
@@ -20,6 +28,7 @@ use std::sync::{Arc, Weak};
 
 pub const DEFAULT_INIT_TIMEOUT_MS: u64 = 3000;
 const MIN_SYNC_INTERVAL: u32 = 1000;
+const MIN_MAX_FLUSH_INTERVAL: u32 = 1000;
 const TEST_ENV_FLAG: &str = "STATSIG_RUNNING_TESTS";
 
 #[derive(Clone, Default)]
@@ -31,6 +40,9 @@ pub struct StatsigOptions {
     pub disable_country_lookup: Option<bool>,
     pub disable_network: Option<bool>, // Disable all out-going network including get configs, log_events...
     pub log_event_connection_reuse: Option<bool>,
+    /// Sends every request through one process wide HTTP client, so instances share its
+    /// connection pool. `StatsigHub` sets this for its projects.
+    pub share_http_connection_pool: Option<bool>,
 
     pub enable_id_lists: Option<bool>,
     pub enable_dcs_deltas: Option<bool>,
@@ -51,12 +63,26 @@ pub struct StatsigOptions {
     pub event_logging_flush_interval_ms: Option<u32>,
     pub event_logging_max_pending_batch_queue_size: Option<u32>,
     pub event_logging_max_queue_size: Option<u32>,
+    /// Longest time queued events wait for a scheduled flush, and the cap on flush backoff
+    /// after failures. Defaults to 60 seconds.
+    pub event_logging_max_flush_interval_ms: Option<u32>,
 
     /// Hard upper bound on unique `ExposureSamplingKey`s in the exposure-dedupe LRU
     /// cache. Defaults to [`crate::event_logging::exposure_sampling::SAMPLING_MAX_KEYS`]
     /// (100_000). `Some(0)` is treated as "use the default".
     pub exposure_dedupe_max_keys: Option<u32>,
 
+    /// Skips exposure logging for users whose user agent is detected as a bot or crawler.
+    /// Evaluation is unaffected. Custom events are still logged.
+    pub disable_bot_exposure_logging: Option<bool>,
+
+    /// Keeps the exposure dedupe keys across restarts. See [`ExposureDedupePersistence`].
+    pub exposure_dedupe_persistence: Option<ExposureDedupePersistence>,
+
+    /// Decides how exposures are sampled, in place of the project's sampling config. See
+    /// [`ExposureSamplingPolicy`].
+    pub exposure_sampling_policy: Option<Arc<dyn ExposureSamplingPolicy>>,
+
     pub fallback_to_statsig_api: Option<bool>,
     pub global_custom_fields: Option<HashMap<String, DynamicValue>>,
 
@@ -68,6 +94,9 @@ pub struct StatsigOptions {
     pub id_lists_request_timeout_ms: Option<u64>,
     pub id_lists_url: Option<String>,
     pub download_id_list_file_api: Option<String>,
+    /// How ID list members are held in memory, selectable per list name. Defaults to
+    /// [`IdListStorageMode::HashSet`] for every list.
+    pub id_list_storage: Option<IdListStorageConfig>,
 
     pub init_timeout_ms: Option<u64>,
     pub log_event_url: Option<String>,
@@ -88,12 +117,33 @@ pub struct StatsigOptions {
 
     pub proxy_config: Option<ProxyConfig>,
 
+    /// Redacts user fields, custom IDs and event payloads before events are queued for logging.
+    /// Evaluation always uses the unredacted user.
+    pub redaction_policy: Option<RedactionPolicy>,
+
     pub console_capture_options: Option<ConsoleCaptureOptions>,
 
+    /// Trains CMAB models locally from outcomes recorded with `Statsig::record_cmab_outcome`.
+    /// Trained models take precedence over the weights shipped in specs.
+    pub cmab_learner: Option<Arc<CMABLearner>>,
+
+    /// Which tokio runtime background tasks run on. See [`StatsigRuntimeOptions`].
+    pub runtime_options: Option<StatsigRuntimeOptions>,
+
+    /// Backtracking and input length limits for `str_matches` conditions.
+    pub regex_safety: Option<RegexSafetyOptions>,
+
+    /// Adds derived fields to every user before it is evaluated. See [`UserEnricher`].
+    pub user_enricher: Option<Arc<dyn UserEnricher>>,
+
     pub use_third_party_ua_parser: Option<bool>,
     pub disable_disk_access: Option<bool>,
 
     pub experimental_flags: Option<HashSet<String>>,
+
+    /// Where the SDK reads the current time from. Defaults to the system clock.
+    /// See [`crate::ManualClock`] for tests.
+    pub clock: Option<Arc<dyn Clock>>,
 }
 
 impl StatsigOptions {
@@ -204,12 +254,48 @@ impl StatsigOptionsBuilder {
         self
     }
 
+    #[must_use]
+    pub fn event_logging_max_flush_interval_ms(
+        mut self,
+        event_logging_max_flush_interval_ms: Option<u32>,
+    ) -> Self {
+        self.inner.event_logging_max_flush_interval_ms = event_logging_max_flush_interval_ms;
+        self
+    }
+
     #[must_use]
     pub fn exposure_dedupe_max_keys(mut self, exposure_dedupe_max_keys: Option<u32>) -> Self {
         self.inner.exposure_dedupe_max_keys = exposure_dedupe_max_keys;
         self
     }
 
+    #[must_use]
+    pub fn disable_bot_exposure_logging(
+        mut self,
+        disable_bot_exposure_logging: Option<bool>,
+    ) -> Self {
+        self.inner.disable_bot_exposure_logging = disable_bot_exposure_logging;
+        self
+    }
+
+    #[must_use]
+    pub fn exposure_dedupe_persistence(
+        mut self,
+        exposure_dedupe_persistence: Option<ExposureDedupePersistence>,
+    ) -> Self {
+        self.inner.exposure_dedupe_persistence = exposure_dedupe_persistence;
+        self
+    }
+
+    #[must_use]
+    pub fn exposure_sampling_policy(
+        mut self,
+        exposure_sampling_policy: Option<Arc<dyn ExposureSamplingPolicy>>,
+    ) -> Self {
+        self.inner.exposure_sampling_policy = exposure_sampling_policy;
+        self
+    }
+
     // ID Lists
 
     #[must_use]
@@ -263,6 +349,12 @@ impl StatsigOptionsBuilder {
         self
     }
 
+    #[must_use]
+    pub fn id_list_storage(mut self, id_list_storage: Option<IdListStorageConfig>) -> Self {
+        self.inner.id_list_storage = id_list_storage;
+        self
+    }
+
     // Other
 
     #[must_use]
@@ -359,6 +451,18 @@ impl StatsigOptionsBuilder {
         self
     }
 
+    #[must_use]
+    pub fn share_http_connection_pool(mut self, share_http_connection_pool: Option<bool>) -> Self {
+        self.inner.share_http_connection_pool = share_http_connection_pool;
+        self
+    }
+
+    #[must_use]
+    pub fn regex_safety(mut self, regex_safety: Option<RegexSafetyOptions>) -> Self {
+        self.inner.regex_safety = regex_safety;
+        self
+    }
+
     #[must_use]
     pub fn use_third_party_ua_parser(mut self, use_third_party_ua_parser: Option<bool>) -> Self {
         self.inner.use_third_party_ua_parser = use_third_party_ua_parser;
@@ -377,6 +481,24 @@ impl StatsigOptionsBuilder {
         self
     }
 
+    #[must_use]
+    pub fn redaction_policy(mut self, redaction_policy: Option<RedactionPolicy>) -> Self {
+        self.inner.redaction_policy = redaction_policy;
+        self
+    }
+
+    #[must_use]
+    pub fn cmab_learner(mut self, cmab_learner: Option<Arc<CMABLearner>>) -> Self {
+        self.inner.cmab_learner = cmab_learner;
+        self
+    }
+
+    #[must_use]
+    pub fn runtime_options(mut self, runtime_options: Option<StatsigRuntimeOptions>) -> Self {
+        self.inner.runtime_options = runtime_options;
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> StatsigOptions {
         self.inner
@@ -404,6 +526,18 @@ impl StatsigOptionsBuilder {
         self.inner.data_store = data_store;
         self
     }
+
+    #[must_use]
+    pub fn user_enricher(mut self, user_enricher: Option<Arc<dyn UserEnricher>>) -> Self {
+        self.inner.user_enricher = user_enricher;
+        self
+    }
+
+    #[must_use]
+    pub fn clock(mut self, clock: Option<Arc<dyn Clock>>) -> Self {
+        self.inner.clock = clock;
+        self
+    }
 }
 
 impl Serialize for StatsigOptions {
@@ -430,6 +564,26 @@ impl Serialize for StatsigOptions {
 
         serialize_if_not_none!(state, "log_event_url", &self.log_event_url);
         serialize_if_not_none!(state, "disable_all_logging", &self.disable_all_logging);
+        serialize_if_not_none!(
+            state,
+            "disable_bot_exposure_logging",
+            &self.disable_bot_exposure_logging
+        );
+        serialize_if_not_none!(
+            state,
+            "exposure_dedupe_persistence",
+            &get_display_name(&self.exposure_dedupe_persistence)
+        );
+        serialize_if_not_none!(
+            state,
+            "exposure_sampling_policy",
+            &get_if_set(&self.exposure_sampling_policy)
+        );
+        serialize_if_not_none!(
+            state,
+            "event_logging_max_flush_interval_ms",
+            &self.event_logging_max_flush_interval_ms
+        );
         serialize_if_not_none!(state, "disable_network", &self.disable_network);
 
         serialize_if_not_none!(state, "id_lists_url", &self.id_lists_url);
@@ -439,6 +593,7 @@ impl Serialize for StatsigOptions {
             &self.download_id_list_file_api
         );
         serialize_if_not_none!(state, "enable_id_lists", &self.enable_id_lists);
+        serialize_if_not_none!(state, "id_list_storage", &self.id_list_storage);
         serialize_if_not_none!(state, "enable_dcs_deltas", &self.enable_dcs_deltas);
         serialize_if_not_none!(
             state,
@@ -484,6 +639,16 @@ impl Serialize for StatsigOptions {
         serialize_if_not_none!(state, "service_name", &get_if_set(&self.service_name));
         serialize_if_not_none!(state, "global_custom_fields", &self.global_custom_fields);
         serialize_if_not_none!(state, "experimental_flags", &self.experimental_flags);
+        serialize_if_not_none!(
+            state,
+            "redaction_policy",
+            &get_if_set(&self.redaction_policy)
+        );
+        serialize_if_not_none!(state, "cmab_learner", &get_if_set(&self.cmab_learner));
+        serialize_if_not_none!(state, "runtime_options", &get_if_set(&self.runtime_options));
+        serialize_if_not_none!(state, "clock", &get_if_set(&self.clock));
+        serialize_if_not_none!(state, "regex_safety", &self.regex_safety);
+        serialize_if_not_none!(state, "user_enricher", &get_if_set(&self.user_enricher));
 
         state.end()
     }
@@ -556,6 +721,16 @@ impl StatsigOptions {
             mut_ref.event_logging_max_pending_batch_queue_size = None;
         }
 
+        if is_max_flush_interval_invalid(&self.event_logging_max_flush_interval_ms) {
+            log_w!(
+                TAG,
+                "Invalid 'event_logging_max_flush_interval_ms', value cannot be lower than {}, received {:?}",
+                MIN_MAX_FLUSH_INTERVAL,
+                &self.event_logging_max_flush_interval_ms
+            );
+            mut_ref.event_logging_max_flush_interval_ms = None;
+        }
+
         if should_fix_null_url(&self.specs_url) {
             log_d!(TAG, "Setting specs_url to be default url");
             mut_ref.specs_url = None;
@@ -587,6 +762,13 @@ fn is_sync_interval_invalid(interval_ms: &Option<u32>) -> bool {
     false
 }
 
+fn is_max_flush_interval_invalid(interval_ms: &Option<u32>) -> bool {
+    if let Some(interval) = interval_ms {
+        return *interval < MIN_MAX_FLUSH_INTERVAL;
+    }
+    false
+}
+
 fn should_fix_null_url(maybe_url: &Option<String>) -> bool {
     if let Some(url) = maybe_url {
         return url.is_empty() || url.eq_ignore_ascii_case("null");
@@ -609,3 +791,240 @@ fn bounds_check_loggging_pending_queue_size(queue_size: &Option<u32>) -> bool {
     }
     false
 }
+
+//-------------------------------Runtime Updates---------------------------------
+
+impl StatsigOptions {
+    /// Checks that an update for `Statsig::update_options` only sets fields that can change
+    /// at runtime, and that their values are valid. Invalid values are rejected rather than
+    /// reset to their defaults as `validate_and_fix` does.
+    pub(crate) fn validate_runtime_update(&self) -> Result<(), StatsigErr> {
+        let fixed_fields = self.get_set_fields_fixed_at_init();
+        if !fixed_fields.is_empty() {
+            return Err(StatsigErr::InvalidOperation(format!(
+                "These options cannot be changed after Statsig is created: {}",
+                fixed_fields.join(", ")
+            )));
+        }
+
+        if std::env::var(TEST_ENV_FLAG).is_ok() {
+            return Ok(());
+        }
+
+        let mut errors = vec![];
+        if is_sync_interval_invalid(&self.specs_sync_interval_ms) {
+            errors.push(format!(
+                "'specs_sync_interval_ms' must be at least {MIN_SYNC_INTERVAL}"
+            ));
+        }
+        if is_sync_interval_invalid(&self.id_lists_sync_interval_ms) {
+            errors.push(format!(
+                "'id_lists_sync_interval_ms' must be at least {MIN_SYNC_INTERVAL}"
+            ));
+        }
+        if bounds_check_logging_batch_size(&self.event_logging_max_queue_size) {
+            errors.push(format!(
+                "'event_logging_max_queue_size' must be between {} and {}",
+                event_logger::MIN_BATCH_SIZE,
+                event_logger::MAX_BATCH_SIZE
+            ));
+        }
+        if bounds_check_loggging_pending_queue_size(
+            &self.event_logging_max_pending_batch_queue_size,
+        ) {
+            errors.push(format!(
+                "'event_logging_max_pending_batch_queue_size' must be at least {}",
+                event_logger::MIN_PENDING_BATCH_COUNT
+            ));
+        }
+        if is_max_flush_interval_invalid(&self.event_logging_max_flush_interval_ms) {
+            errors.push(format!(
+                "'event_logging_max_flush_interval_ms' must be at least {MIN_MAX_FLUSH_INTERVAL}"
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(StatsigErr::InvalidOperation(errors.join("; ")))
+        }
+    }
+
+    /// Returns a copy of these options with every runtime field that is set in `update`
+    /// replaced. Fields left unset in `update` keep their current value.
+    pub(crate) fn merge_runtime_update(&self, update: &StatsigOptions) -> StatsigOptions {
+        let mut merged = self.clone();
+
+        macro_rules! merge_if_set {
+            ($($field:ident),*) => {
+                $(
+                    if update.$field.is_some() {
+                        merged.$field = update.$field.clone();
+                    }
+                )*
+            };
+        }
+
+        merge_if_set!(
+            output_log_level,
+            specs_sync_interval_ms,
+            id_lists_sync_interval_ms,
+            event_logging_max_queue_size,
+            event_logging_max_pending_batch_queue_size,
+            event_logging_max_flush_interval_ms,
+            disable_all_logging,
+            global_custom_fields,
+            exposure_dedupe_max_keys,
+            disable_bot_exposure_logging,
+            console_capture_options
+        );
+
+        merged
+    }
+
+    // Destructured without `..` so that a new field has to be classified here.
+    #[allow(deprecated)]
+    fn get_set_fields_fixed_at_init(&self) -> Vec<&'static str> {
+        let StatsigOptions {
+            data_store,
+            data_store_key_schema_version,
+            disable_all_logging: _,
+            disable_country_lookup,
+            disable_network,
+            log_event_connection_reuse,
+            share_http_connection_pool,
+            enable_id_lists,
+            enable_dcs_deltas,
+            dcs_delta_no_progress_threshold,
+            environment,
+            config_compression_mode,
+            event_logging_adapter,
+            event_logging_flush_interval_ms,
+            event_logging_max_pending_batch_queue_size: _,
+            event_logging_max_queue_size: _,
+            event_logging_max_flush_interval_ms: _,
+            exposure_dedupe_max_keys: _,
+            disable_bot_exposure_logging: _,
+            exposure_dedupe_persistence,
+            exposure_sampling_policy,
+            fallback_to_statsig_api,
+            global_custom_fields: _,
+            id_lists_adapter,
+            id_lists_sync_interval_ms: _,
+            id_lists_request_timeout_ms,
+            id_lists_url,
+            download_id_list_file_api,
+            id_list_storage,
+            init_timeout_ms,
+            log_event_url,
+            observability_client,
+            output_log_level: _,
+            output_logger_provider,
+            override_adapter,
+            persistent_storage,
+            service_name,
+            spec_adapters_config,
+            specs_adapter,
+            specs_sync_interval_ms: _,
+            specs_url,
+            wait_for_country_lookup_init,
+            wait_for_user_agent_init,
+            proxy_config,
+            redaction_policy,
+            console_capture_options: _,
+            cmab_learner,
+            runtime_options,
+            regex_safety,
+            user_enricher,
+            use_third_party_ua_parser,
+            disable_disk_access,
+            experimental_flags,
+            clock,
+        } = self;
+
+        [
+            ("data_store", data_store.is_some()),
+            (
+                "data_store_key_schema_version",
+                data_store_key_schema_version.is_some(),
+            ),
+            ("disable_country_lookup", disable_country_lookup.is_some()),
+            ("disable_network", disable_network.is_some()),
+            (
+                "log_event_connection_reuse",
+                log_event_connection_reuse.is_some(),
+            ),
+            (
+                "share_http_connection_pool",
+                share_http_connection_pool.is_some(),
+            ),
+            ("enable_id_lists", enable_id_lists.is_some()),
+            ("enable_dcs_deltas", enable_dcs_deltas.is_some()),
+            (
+                "dcs_delta_no_progress_threshold",
+                dcs_delta_no_progress_threshold.is_some(),
+            ),
+            ("environment", environment.is_some()),
+            ("config_compression_mode", config_compression_mode.is_some()),
+            ("event_logging_adapter", event_logging_adapter.is_some()),
+            (
+                "event_logging_flush_interval_ms",
+                event_logging_flush_interval_ms.is_some(),
+            ),
+            ("fallback_to_statsig_api", fallback_to_statsig_api.is_some()),
+            ("id_lists_adapter", id_lists_adapter.is_some()),
+            (
+                "id_lists_request_timeout_ms",
+                id_lists_request_timeout_ms.is_some(),
+            ),
+            ("id_lists_url", id_lists_url.is_some()),
+            (
+                "download_id_list_file_api",
+                download_id_list_file_api.is_some(),
+            ),
+            ("id_list_storage", id_list_storage.is_some()),
+            ("init_timeout_ms", init_timeout_ms.is_some()),
+            ("log_event_url", log_event_url.is_some()),
+            ("observability_client", observability_client.is_some()),
+            ("output_logger_provider", output_logger_provider.is_some()),
+            ("override_adapter", override_adapter.is_some()),
+            ("persistent_storage", persistent_storage.is_some()),
+            ("service_name", service_name.is_some()),
+            ("spec_adapters_config", spec_adapters_config.is_some()),
+            ("specs_adapter", specs_adapter.is_some()),
+            ("specs_url", specs_url.is_some()),
+            (
+                "wait_for_country_lookup_init",
+                wait_for_country_lookup_init.is_some(),
+            ),
+            (
+                "wait_for_user_agent_init",
+                wait_for_user_agent_init.is_some(),
+            ),
+            ("proxy_config", proxy_config.is_some()),
+            ("redaction_policy", redaction_policy.is_some()),
+            ("cmab_learner", cmab_learner.is_some()),
+            ("runtime_options", runtime_options.is_some()),
+            ("regex_safety", regex_safety.is_some()),
+            ("user_enricher", user_enricher.is_some()),
+            (
+                "exposure_dedupe_persistence",
+                exposure_dedupe_persistence.is_some(),
+            ),
+            (
+                "exposure_sampling_policy",
+                exposure_sampling_policy.is_some(),
+            ),
+            (
+                "use_third_party_ua_parser",
+                use_third_party_ua_parser.is_some(),
+            ),
+            ("disable_disk_access", disable_disk_access.is_some()),
+            ("experimental_flags", experimental_flags.is_some()),
+            ("clock", clock.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, is_set)| is_set.then_some(name))
+        .collect()
+    }
+}
@@ -5,7 +5,7 @@ use futures::future::join_all;
 use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::future::Future;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::runtime::{Builder, Handle, Runtime};
@@ -14,6 +14,27 @@ use tokio::task::JoinHandle;
 
 const TAG: &str = stringify!(StatsigRuntime);
 
+pub const DEFAULT_RUNTIME_WORKER_THREADS: usize = 5;
+pub const DEFAULT_RUNTIME_THREAD_NAME: &str = "statsig";
+
+/// Controls which tokio runtime background tasks are spawned on.
+///
+/// By default tasks run on the ambient runtime, falling back to a runtime owned by the SDK
+/// and shared by every instance in the process.
+#[derive(Clone, Debug, Default)]
+pub struct StatsigRuntimeOptions {
+    /// Runtime used for all background tasks, even when called from another runtime.
+    pub handle: Option<Handle>,
+    /// Never create the fallback runtime. Spawning fails with `StatsigErr::ThreadFailure`
+    /// when there is neither a `handle` nor an ambient runtime.
+    pub disable_fallback_runtime: bool,
+    /// Worker threads of the fallback runtime. Only applies if this instance creates it.
+    pub fallback_worker_threads: Option<usize>,
+    /// Thread names of the fallback runtime become `<prefix>-<n>`. Only applies if this
+    /// instance creates it.
+    pub fallback_thread_name_prefix: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct TaskId {
     tag: String,
@@ -21,6 +42,7 @@ struct TaskId {
 }
 
 pub struct StatsigRuntime {
+    options: StatsigRuntimeOptions,
     spawned_tasks: Arc<Mutex<HashMap<TaskId, JoinHandle<()>>>>,
     shutdown_notify: Arc<Notify>,
     is_shutdown: Arc<AtomicBool>,
@@ -29,9 +51,19 @@ pub struct StatsigRuntime {
 impl StatsigRuntime {
     #[must_use]
     pub fn get_runtime() -> Arc<StatsigRuntime> {
-        create_runtime_if_required();
+        Self::get_runtime_with_options(StatsigRuntimeOptions::default())
+    }
+
+    #[must_use]
+    pub fn get_runtime_with_options(options: StatsigRuntimeOptions) -> Arc<StatsigRuntime> {
+        if options.handle.is_some() {
+            log_d!(TAG, "Using provided tokio runtime handle");
+        } else if !options.disable_fallback_runtime {
+            create_runtime_if_required(&options);
+        }
 
         Arc::new(StatsigRuntime {
+            options,
             spawned_tasks: Arc::new(Mutex::new(HashMap::new())),
             shutdown_notify: Arc::new(Notify::new()),
             is_shutdown: Arc::new(AtomicBool::new(false)),
@@ -39,17 +71,27 @@ impl StatsigRuntime {
     }
 
     pub fn get_handle(&self) -> Result<Handle, StatsigErr> {
+        if let Some(handle) = &self.options.handle {
+            return Ok(handle.clone());
+        }
+
         if let Ok(handle) = Handle::try_current() {
             return Ok(handle);
         }
 
+        if self.options.disable_fallback_runtime {
+            return Err(StatsigErr::ThreadFailure(
+                "No tokio runtime available and the fallback runtime is disabled. Provide a handle in StatsigRuntimeOptions or call from within a tokio runtime".to_string(),
+            ));
+        }
+
         let global = StatsigGlobal::get();
         let mut rt = global
             .tokio_runtime
             .try_lock_for(Duration::from_secs(5))
             .ok_or_else(|| StatsigErr::LockFailure("Failed to lock tokio runtime".to_string()))?;
         if rt.is_none() {
-            *rt = Some(Arc::new(create_new_runtime()));
+            *rt = Some(Arc::new(create_new_runtime_with_options(&self.options)));
         }
         if let Some(rt) = rt.as_ref() {
             return Ok(rt.handle().clone());
@@ -188,6 +230,18 @@ impl StatsigRuntime {
             .collect()
     }
 
+    /// Sorted, deduplicated tags of the tasks that are still running.
+    pub fn get_running_task_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .get_running_task_ids()
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
     fn insert_join_handle(&self, tag: &str, handle: JoinHandle<()>) -> tokio::task::Id {
         let handle_id = handle.id();
         let task_id = TaskId {
@@ -209,20 +263,40 @@ impl StatsigRuntime {
 }
 
 pub fn create_new_runtime() -> Runtime {
+    create_new_runtime_with_options(&StatsigRuntimeOptions::default())
+}
+
+pub fn create_new_runtime_with_options(options: &StatsigRuntimeOptions) -> Runtime {
     #[cfg(not(target_family = "wasm"))]
-    return Builder::new_multi_thread()
-        .worker_threads(5)
-        .thread_name("statsig")
-        .enable_all()
-        .build()
-        .expect("Failed to create a tokio Runtime");
+    let mut builder = Builder::new_multi_thread();
+    #[cfg(not(target_family = "wasm"))]
+    builder.worker_threads(
+        options
+            .fallback_worker_threads
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_RUNTIME_WORKER_THREADS),
+    );
 
     #[cfg(target_family = "wasm")]
-    return Builder::new_current_thread()
-        .thread_name("statsig")
+    let mut builder = Builder::new_current_thread();
+
+    match options.fallback_thread_name_prefix.clone() {
+        Some(prefix) => {
+            let counter = AtomicUsize::new(0);
+            builder.thread_name_fn(move || {
+                let n = counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                format!("{prefix}-{n}")
+            });
+        }
+        None => {
+            builder.thread_name(DEFAULT_RUNTIME_THREAD_NAME);
+        }
+    }
+
+    builder
         .enable_all()
         .build()
-        .expect("Failed to create a tokio Runtime (single-threaded for wasm");
+        .expect("Failed to create a tokio Runtime")
 }
 
 fn remove_join_handle_with_id(
@@ -248,7 +322,7 @@ fn remove_join_handle_with_id(
     }
 }
 
-fn create_runtime_if_required() {
+fn create_runtime_if_required(options: &StatsigRuntimeOptions) {
     if Handle::try_current().is_ok() {
         log_d!(TAG, "External tokio runtime found");
         return;
@@ -266,7 +340,7 @@ fn create_runtime_if_required() {
         }
         None => {
             log_d!(TAG, "Creating new tokio runtime for StatsigGlobal");
-            let rt = Arc::new(create_new_runtime());
+            let rt = Arc::new(create_new_runtime_with_options(options));
 
             lock.replace(rt);
         }
@@ -13,7 +13,6 @@ use crate::user::StatsigUserLoggable;
 use crate::Statsig;
 use crate::StatsigUser;
 
-use chrono::Utc;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::{from_value, Value};
@@ -186,7 +185,7 @@ impl Layer {
         }
 
         logger.enqueue(EnqueueLayerParamExpoOp::LayerRef(
-            Utc::now().timestamp_millis() as u64,
+            logger.now_ms(),
             self,
             param_name,
             ExposureTrigger::Auto,
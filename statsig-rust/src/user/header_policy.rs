@@ -0,0 +1,282 @@
+use std::net::{IpAddr, SocketAddr};
+
+use http::{HeaderMap, Request};
+
+use super::user_agent_client_hints::UserAgentClientHints;
+use super::StatsigUserBuilder;
+use crate::{dyn_value, log_w};
+
+const TAG: &str = stringify!(HeaderPolicy);
+
+// values CDNs send when the country is unknown, or the client is on Tor
+const UNKNOWN_COUNTRIES: [&str; 3] = ["XX", "T1", "ZZ"];
+
+/// Where the client IP found by [`HeaderPolicy`] is put on the user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IpPlacement {
+    /// The user's `ip` field. It is logged with the user.
+    #[default]
+    Ip,
+    /// An `ip` private attribute, usable by `user_field` conditions but never logged.
+    PrivateAttribute,
+    /// Not set on the user.
+    Omit,
+}
+
+/// Which headers [`StatsigUserBuilder::from_http_request`] reads user fields from.
+///
+/// The client IP is the peer address passed to `from_http_request` (e.g. axum's
+/// `ConnectInfo<SocketAddr>`). The forwarded-for header is only read when trusted proxies are
+/// set, and only when the peer is one of them. It is read from right to left, skipping trusted
+/// proxies, so a client cannot choose its IP by sending its own header. When the peer is
+/// unknown, the header is ignored and no IP is set.
+#[derive(Clone, Debug)]
+pub struct HeaderPolicy {
+    user_agent_header: String,
+    forwarded_for_header: String,
+    locale_header: String,
+    /// Checked in order, the first known country is used.
+    country_headers: Vec<String>,
+    trusted_proxies: Vec<IpRange>,
+    ip_placement: IpPlacement,
+}
+
+impl Default for HeaderPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeaderPolicy {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            user_agent_header: "user-agent".to_string(),
+            forwarded_for_header: "x-forwarded-for".to_string(),
+            locale_header: "accept-language".to_string(),
+            country_headers: vec![
+                "cf-ipcountry".to_string(),
+                "cloudfront-viewer-country".to_string(),
+                "x-vercel-ip-country".to_string(),
+                "x-appengine-country".to_string(),
+            ],
+            trusted_proxies: Vec::new(),
+            ip_placement: IpPlacement::Ip,
+        }
+    }
+
+    #[must_use]
+    pub fn with_user_agent_header(mut self, header: &str) -> Self {
+        self.user_agent_header = header.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_forwarded_for_header(mut self, header: &str) -> Self {
+        self.forwarded_for_header = header.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_locale_header(mut self, header: &str) -> Self {
+        self.locale_header = header.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_country_headers(mut self, headers: &[&str]) -> Self {
+        self.country_headers = headers.iter().map(|h| h.to_string()).collect();
+        self
+    }
+
+    /// Addresses (`10.0.0.1`) or CIDR ranges (`10.0.0.0/8`, `fd00::/8`) of proxies in front of
+    /// the server. Invalid entries are skipped.
+    #[must_use]
+    pub fn with_trusted_proxies(mut self, proxies: &[&str]) -> Self {
+        self.trusted_proxies = proxies
+            .iter()
+            .filter_map(|proxy| {
+                let range = IpRange::parse(proxy);
+                if range.is_none() {
+                    log_w!(TAG, "Invalid trusted proxy '{}'", proxy);
+                }
+                range
+            })
+            .collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_ip_placement(mut self, ip_placement: IpPlacement) -> Self {
+        self.ip_placement = ip_placement;
+        self
+    }
+
+    pub(crate) fn apply<B>(
+        &self,
+        request: &Request<B>,
+        peer_addr: Option<SocketAddr>,
+        mut builder: StatsigUserBuilder,
+    ) -> StatsigUserBuilder {
+        let headers = request.headers();
+
+        builder = builder
+            .user_agent(get_header(headers, &self.user_agent_header).map(str::to_string))
+            .locale(get_header(headers, &self.locale_header).and_then(parse_accept_language))
+            .country(self.get_country(headers))
+            .user_agent_client_hints(UserAgentClientHints::from_headers(headers));
+
+        let ip = self
+            .get_client_ip(headers, peer_addr.map(|addr| addr.ip()))
+            .map(|ip| ip.to_string());
+
+        match (self.ip_placement, ip) {
+            (IpPlacement::Ip, ip) => builder.ip(ip),
+            (IpPlacement::PrivateAttribute, Some(ip)) => {
+                let mut private_attributes = builder.private_attributes.take().unwrap_or_default();
+                private_attributes.insert("ip".to_string(), dyn_value!(ip));
+                builder.private_attributes(Some(private_attributes))
+            }
+            (IpPlacement::PrivateAttribute | IpPlacement::Omit, _) => builder,
+        }
+    }
+
+    fn get_country(&self, headers: &HeaderMap) -> Option<String> {
+        self.country_headers.iter().find_map(|header| {
+            let country = get_header(headers, header)?.to_ascii_uppercase();
+            let is_known = country.len() == 2 && !UNKNOWN_COUNTRIES.contains(&country.as_str());
+            is_known.then_some(country)
+        })
+    }
+
+    fn get_client_ip(&self, headers: &HeaderMap, peer_addr: Option<IpAddr>) -> Option<IpAddr> {
+        // the header is only as trustworthy as the hop that sent it
+        let peer = peer_addr?;
+        if !self.is_trusted_proxy(&peer) {
+            return Some(peer);
+        }
+
+        let forwarded: Vec<&str> = headers
+            .get_all(self.forwarded_for_header.as_str())
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .collect();
+
+        let mut last_valid = peer;
+        for entry in forwarded.iter().rev() {
+            // anything before an unparseable entry cannot be trusted
+            let ip = parse_forwarded_ip(entry)?;
+            if !self.is_trusted_proxy(&ip) {
+                return Some(ip);
+            }
+            last_valid = ip;
+        }
+
+        // every hop was a trusted proxy
+        Some(last_valid)
+    }
+
+    fn is_trusted_proxy(&self, ip: &IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|range| range.contains(ip))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct IpRange {
+    network: IpAddr,
+    prefix_len: u32,
+}
+
+impl IpRange {
+    fn parse(input: &str) -> Option<Self> {
+        let (addr, prefix_len) = match input.trim().split_once('/') {
+            Some((addr, len)) => (addr, Some(len.parse::<u32>().ok()?)),
+            None => (input.trim(), None),
+        };
+
+        let network: IpAddr = addr.parse().ok()?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = prefix_len.unwrap_or(max_len);
+        if prefix_len > max_len {
+            return None;
+        }
+
+        // "::ffff:10.0.0.0/104" is the same range as "10.0.0.0/8"
+        if let IpAddr::V6(v6) = network {
+            if let (Some(v4), true) = (v6.to_ipv4_mapped(), prefix_len >= 96) {
+                return Some(Self {
+                    network: IpAddr::V4(v4),
+                    prefix_len: prefix_len - 96,
+                });
+            }
+        }
+
+        Some(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => prefix_matches(
+                u32::from(network).into(),
+                u32::from(ip).into(),
+                32,
+                self.prefix_len,
+            ),
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                prefix_matches(network.into(), ip.into(), 128, self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches(network: u128, ip: u128, bits: u32, prefix_len: u32) -> bool {
+    if prefix_len == 0 {
+        return true;
+    }
+    let shift = bits - prefix_len;
+    (network >> shift) == (ip >> shift)
+}
+
+fn get_header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    let value = headers.get(name)?.to_str().ok()?.trim();
+    (!value.is_empty()).then_some(value)
+}
+
+/// `1.2.3.4`, `1.2.3.4:5678`, `2001:db8::1` or `[2001:db8::1]:443`, optionally quoted.
+fn parse_forwarded_ip(entry: &str) -> Option<IpAddr> {
+    let entry = entry.trim_matches('"');
+    if let Ok(ip) = entry.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    entry.parse::<SocketAddr>().ok().map(|addr| addr.ip())
+}
+
+/// The language with the highest quality, e.g. `de-CH` for `fr;q=0.8, de-CH, en;q=0.5`.
+fn parse_accept_language(value: &str) -> Option<String> {
+    let mut best: Option<(&str, f32)> = None;
+
+    for entry in value.split(',') {
+        let mut parts = entry.split(';').map(str::trim);
+        let Some(tag) = parts.next().filter(|t| !t.is_empty() && *t != "*") else {
+            continue;
+        };
+
+        let quality = parts
+            .find_map(|p| p.strip_prefix("q="))
+            .map_or(Some(1.0), |q| q.parse::<f32>().ok())
+            .unwrap_or(0.0);
+
+        if quality > 0.0 && best.is_none_or(|(_, best_q)| quality > best_q) {
+            best = Some((tag, quality));
+        }
+    }
+
+    best.map(|(tag, _)| tag.to_string())
+}
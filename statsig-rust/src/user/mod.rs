@@ -1,12 +1,18 @@
+pub mod header_policy;
 pub mod into_optional;
 pub mod statsig_user;
 pub mod statsig_user_builder;
 pub mod statsig_user_internal;
 pub mod statsig_user_loggable;
 pub mod unit_id;
+pub mod user_agent_client_hints;
 pub mod user_data;
+pub mod user_enricher;
 
+pub use header_policy::{HeaderPolicy, IpPlacement};
 pub use statsig_user::StatsigUser;
 pub use statsig_user_builder::StatsigUserBuilder;
 pub use statsig_user_internal::StatsigUserInternal;
 pub use statsig_user_loggable::StatsigUserLoggable;
+pub use user_agent_client_hints::UserAgentClientHints;
+pub use user_enricher::{UserEnricher, UserEnrichment};
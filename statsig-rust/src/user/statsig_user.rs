@@ -6,6 +6,7 @@ use std::{collections::HashMap, sync::Arc};
 use super::{
     into_optional::IntoOptional,
     unit_id::UnitID,
+    user_agent_client_hints::UserAgentClientHints,
     user_data::{UserData, UserDataMap},
 };
 
@@ -215,6 +216,20 @@ impl StatsigUser {
     string_field_accessor!(self, get_locale, set_locale, locale);
     string_field_accessor!(self, get_app_version, set_app_version, app_version);
 
+    // ---------------------------------------- [ Client Hints ]
+
+    pub fn get_user_agent_client_hints(&self) -> Option<&UserAgentClientHints> {
+        self.data.user_agent_client_hints.as_ref()
+    }
+
+    pub fn set_user_agent_client_hints(
+        &mut self,
+        user_agent_client_hints: impl IntoOptional<UserAgentClientHints>,
+    ) {
+        let mut_data = Arc::make_mut(&mut self.data);
+        mut_data.user_agent_client_hints = user_agent_client_hints.into_optional();
+    }
+
     // ---------------------------------------- [ Map Fields ]
 
     map_field_accessor!(self, get_custom, set_custom, custom);
@@ -1,8 +1,11 @@
+use super::header_policy::HeaderPolicy;
 use super::unit_id::UnitID;
+use super::user_agent_client_hints::UserAgentClientHints;
 use super::user_data::{UserData, UserDataMap, UserDataMapOf, UserDataStringMap};
 use super::StatsigUser;
 use crate::dyn_value;
 use crate::evaluation::dynamic_value::DynamicValue;
+use std::net::SocketAddr;
 
 pub struct StatsigUserBuilder {
     pub user_id: Option<UnitID>,
@@ -14,6 +17,7 @@ pub struct StatsigUserBuilder {
     pub country: Option<DynamicValue>,
     pub locale: Option<DynamicValue>,
     pub app_version: Option<DynamicValue>,
+    pub user_agent_client_hints: Option<UserAgentClientHints>,
 
     pub custom: Option<UserDataMap>,
     pub private_attributes: Option<UserDataMap>,
@@ -40,6 +44,18 @@ impl StatsigUserBuilder {
         Self::new().custom_ids(Some(custom_ids))
     }
 
+    /// Fills `user_agent`, `ip`, `locale`, `country` and the User-Agent Client Hints from the
+    /// request headers, as described by `policy`. `peer_addr` is the address of the connection
+    /// the request came in on, `None` when unknown. The user ID still needs to be set.
+    #[must_use]
+    pub fn from_http_request<B>(
+        request: &http::Request<B>,
+        peer_addr: Option<SocketAddr>,
+        policy: &HeaderPolicy,
+    ) -> Self {
+        policy.apply(request, peer_addr, Self::new())
+    }
+
     fn new() -> Self {
         Self {
             user_id: None,
@@ -49,6 +65,7 @@ impl StatsigUserBuilder {
             country: None,
             locale: None,
             app_version: None,
+            user_agent_client_hints: None,
             custom: None,
             private_attributes: None,
             custom_ids: None,
@@ -122,6 +139,16 @@ impl StatsigUserBuilder {
         self
     }
 
+    pub fn user_agent_client_hints(
+        mut self,
+        user_agent_client_hints: Option<UserAgentClientHints>,
+    ) -> Self {
+        if let Some(user_agent_client_hints) = user_agent_client_hints {
+            self.user_agent_client_hints = Some(user_agent_client_hints);
+        }
+        self
+    }
+
     pub fn statsig_environment(mut self, statsig_environment: Option<UserDataStringMap>) -> Self {
         if let Some(statsig_environment) = statsig_environment {
             self.statsig_environment = Some(convert_str_map_to_dyn_values(statsig_environment));
@@ -171,6 +198,7 @@ impl StatsigUserBuilder {
             country: self.country,
             locale: self.locale,
             app_version: self.app_version,
+            user_agent_client_hints: self.user_agent_client_hints,
             custom: self.custom,
             private_attributes: self.private_attributes,
             statsig_environment: self.statsig_environment,
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::ops::Deref;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use chrono::Utc;
 
@@ -28,8 +30,31 @@ const VERSION_CHECK_THROTTLE_MS: u64 = 60_000;
 
 #[derive(Clone)]
 pub struct StatsigUserInternal<'statsig, 'user> {
-    pub user_ref: &'user StatsigUser,
+    /// The user being evaluated, including any fields added by the `UserEnricher`.
+    pub user_ref: UserRef<'user>,
     pub statsig_instance: Option<&'statsig Statsig>,
+    // Loaded once so evaluation sees a consistent set even if update_options replaces it
+    global_custom_fields: Option<Arc<HashMap<String, DynamicValue>>>,
+    // Set when enriched fields are kept out of logged events
+    logged_user: Option<UserRef<'user>>,
+}
+
+/// Either the user passed to the SDK, or an enriched copy of it.
+#[derive(Clone)]
+pub enum UserRef<'user> {
+    Borrowed(&'user StatsigUser),
+    Enriched(Arc<StatsigUser>),
+}
+
+impl Deref for UserRef<'_> {
+    type Target = StatsigUser;
+
+    fn deref(&self) -> &StatsigUser {
+        match self {
+            UserRef::Borrowed(user) => user,
+            UserRef::Enriched(user) => user,
+        }
+    }
 }
 
 static LAST_VERSION_CHECK: AtomicU64 = AtomicU64::new(0);
@@ -38,9 +63,20 @@ impl<'statsig, 'user> StatsigUserInternal<'statsig, 'user> {
     pub fn new(user: &'user StatsigUser, statsig_instance: Option<&'statsig Statsig>) -> Self {
         throttled_version_check(user);
 
+        let enriched = statsig_instance.and_then(|s| s.enrich_user(user));
+        let (user_ref, logged_user) = match enriched {
+            Some(enriched) => (
+                UserRef::Enriched(enriched.user),
+                enriched.logged_user.map(UserRef::Enriched),
+            ),
+            None => (UserRef::Borrowed(user), None),
+        };
+
         Self {
-            user_ref: user,
+            user_ref,
             statsig_instance,
+            global_custom_fields: statsig_instance.and_then(|s| s.get_global_custom_fields()),
+            logged_user,
         }
     }
 
@@ -81,13 +117,12 @@ impl<'statsig, 'user> StatsigUserInternal<'statsig, 'user> {
             }
         }
 
-        if let Some(instance) = &self.statsig_instance {
-            if let Some(val) = instance.get_value_from_global_custom_fields(&field.value) {
+        if let Some(global_custom_fields) = &self.global_custom_fields {
+            if let Some(val) = global_custom_fields.get(field.value.as_str()) {
                 return Some(val);
             }
 
-            if let Some(val) = instance.get_value_from_global_custom_fields(&field.lowercased_value)
-            {
+            if let Some(val) = global_custom_fields.get(field.lowercased_value.as_str()) {
                 return Some(val);
             }
         }
@@ -143,10 +178,11 @@ impl<'statsig, 'user> StatsigUserInternal<'statsig, 'user> {
             if environment.is_none() {
                 environment = statsig_instance.use_statsig_env(hashmap_to_user_data_map);
             }
-            global_custom = statsig_instance.use_global_custom_fields(|gc| gc.cloned());
+            global_custom = self.global_custom_fields.as_deref().cloned();
         }
 
-        StatsigUserLoggable::new(&self.user_ref.data, environment, global_custom)
+        let logged_user = self.logged_user.as_ref().unwrap_or(&self.user_ref);
+        StatsigUserLoggable::new(&logged_user.data, environment, global_custom)
     }
 
     pub fn get_hashed_private_attributes(&self) -> Option<String> {
@@ -0,0 +1,55 @@
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+const SEC_CH_UA: &str = "sec-ch-ua";
+const SEC_CH_UA_FULL_VERSION_LIST: &str = "sec-ch-ua-full-version-list";
+const SEC_CH_UA_PLATFORM: &str = "sec-ch-ua-platform";
+const SEC_CH_UA_PLATFORM_VERSION: &str = "sec-ch-ua-platform-version";
+const SEC_CH_UA_MOBILE: &str = "sec-ch-ua-mobile";
+
+/// User-Agent Client Hints, as sent in the `Sec-CH-UA*` request headers. Chromium browsers
+/// freeze most of the `User-Agent` string, so `ua_based` conditions read the browser and OS
+/// from these when they are set, and fall back to parsing the `User-Agent` string.
+///
+/// Values are the raw header values, e.g. `"Chromium";v="124", "Google Chrome";v="124"`.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAgentClientHints {
+    /// `Sec-CH-UA`, brands with their major versions.
+    pub brands: Option<String>,
+    /// `Sec-CH-UA-Full-Version-List`, brands with their full versions.
+    pub full_version_list: Option<String>,
+    /// `Sec-CH-UA-Platform`, e.g. `"Windows"`.
+    pub platform: Option<String>,
+    /// `Sec-CH-UA-Platform-Version`, e.g. `"15.0.0"`.
+    pub platform_version: Option<String>,
+    /// `Sec-CH-UA-Mobile`, `?1` when the browser is on a mobile device.
+    pub mobile: Option<bool>,
+}
+
+impl UserAgentClientHints {
+    /// Reads the `Sec-CH-UA*` headers. Returns `None` when none are present.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let get = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        let hints = Self {
+            brands: get(SEC_CH_UA),
+            full_version_list: get(SEC_CH_UA_FULL_VERSION_LIST),
+            platform: get(SEC_CH_UA_PLATFORM),
+            platform_version: get(SEC_CH_UA_PLATFORM_VERSION),
+            mobile: get(SEC_CH_UA_MOBILE).map(|v| v == "?1"),
+        };
+
+        (hints != Self::default()).then_some(hints)
+    }
+}
@@ -1,3 +1,4 @@
+use super::user_agent_client_hints::UserAgentClientHints;
 use crate::{evaluation::dynamic_value::DynamicValue, hashing};
 #[cfg(feature = "ordered_user_data_maps")]
 use indexmap::IndexMap;
@@ -14,9 +15,12 @@ pub type UserDataMapOf<T> = HashMap<String, T>;
 pub type UserDataMap = UserDataMapOf<DynamicValue>;
 pub type UserDataStringMap = UserDataMapOf<String>;
 
+/// The raw fields of a `StatsigUser`. New fields may be added in any release, so outside
+/// this crate build one from `UserData::default()` and assign the fields you need.
 #[skip_serializing_none]
 #[derive(Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct UserData {
     #[serde(rename = "userID")]
     pub user_id: Option<DynamicValue>,
@@ -29,6 +33,7 @@ pub struct UserData {
     pub country: Option<DynamicValue>,
     pub locale: Option<DynamicValue>,
     pub app_version: Option<DynamicValue>,
+    pub user_agent_client_hints: Option<UserAgentClientHints>,
     pub statsig_environment: Option<UserDataMap>,
 
     #[serde(skip_serializing)]
@@ -54,6 +59,34 @@ impl UserData {
         ])
     }
 
+    /// A hash of every field, including private attributes. Map entries are combined
+    /// independently of their order.
+    pub fn create_full_user_hash(&self) -> u64 {
+        let value_hash = |value: &Option<DynamicValue>| value.as_ref().map_or(0, |v| v.hash_value);
+        let map_hash = |map: &Option<UserDataMap>| {
+            map.as_ref().map_or(0, |map| {
+                map.iter().fold(0u64, |acc, (key, value)| {
+                    acc.wrapping_add(hashing::hash_one((key, value.hash_value)))
+                })
+            })
+        };
+
+        hashing::hash_one(vec![
+            value_hash(&self.user_id),
+            map_hash(&self.custom_ids),
+            value_hash(&self.email),
+            value_hash(&self.ip),
+            value_hash(&self.user_agent),
+            value_hash(&self.country),
+            value_hash(&self.locale),
+            value_hash(&self.app_version),
+            hashing::hash_one(&self.user_agent_client_hints),
+            map_hash(&self.statsig_environment),
+            map_hash(&self.private_attributes),
+            map_hash(&self.custom),
+        ])
+    }
+
     pub fn sum_custom_id_hashes(&self) -> u64 {
         self.custom_ids.as_ref().map_or(0, |custom_ids| {
             custom_ids
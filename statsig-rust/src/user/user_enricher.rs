@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use dashmap::DashMap;
+
+use super::user_data::{UserData, UserDataMap};
+use crate::{DynamicValue, StatsigUser};
+
+const MEMOIZED_USERS_MAX: usize = 1_000;
+
+/// Adds derived fields (account tier, tenant ID, cohort flags...) to every user before it is
+/// evaluated. Registered with `StatsigOptions::user_enricher`.
+///
+/// `enrich` is called at most once per evaluation call, and its result is memoized by a hash
+/// of all of the user's fields, so it is not called again for an identical user. It should
+/// therefore only depend on the user.
+pub trait UserEnricher: Send + Sync {
+    fn enrich(&self, user: &StatsigUser) -> UserEnrichment;
+}
+
+/// Fields added to a user by a [`UserEnricher`]. Fields already set on the user take
+/// precedence over enriched fields with the same name.
+///
+/// Enriched custom fields and private attributes are visible to `user_field` conditions, and
+/// enriched custom IDs can be used as unit IDs. Enriched custom IDs are always logged, so
+/// exposures can be attributed to the units they were bucketed by.
+#[derive(Clone, Debug, Default)]
+pub struct UserEnrichment {
+    pub custom: Option<HashMap<String, DynamicValue>>,
+    pub custom_ids: Option<HashMap<String, String>>,
+    pub private_attributes: Option<HashMap<String, DynamicValue>>,
+
+    /// Log the user as it was passed in plus the enriched custom IDs, without the enriched
+    /// custom fields. Private attributes are never logged.
+    pub exclude_from_logging: bool,
+}
+
+impl UserEnrichment {
+    fn is_empty(&self) -> bool {
+        self.custom.as_ref().is_none_or(HashMap::is_empty)
+            && self.custom_ids.as_ref().is_none_or(HashMap::is_empty)
+            && self
+                .private_attributes
+                .as_ref()
+                .is_none_or(HashMap::is_empty)
+    }
+}
+
+/// A user with its memoized enrichment applied.
+pub(crate) struct EnrichedUser {
+    pub user: Arc<StatsigUser>,
+    // the user to log in place of `user`, set when the enrichment is excluded from logging
+    pub logged_user: Option<Arc<StatsigUser>>,
+}
+
+/// Runs the configured [`UserEnricher`] and memoizes its results. The memo is a sharded
+/// `DashMap`, so concurrent evaluations only contend when their users share a shard, and it is
+/// cleared once it grows past `MEMOIZED_USERS_MAX`.
+///
+/// Only the enrichment is memoized, and it is applied to the incoming user on every call, so a
+/// full user hash collision can never hand one user's IDs or attributes to another.
+pub(crate) struct MemoizedUserEnricher {
+    enricher: Arc<dyn UserEnricher>,
+    // full user hash -> enrichment, `None` when nothing was added
+    memo: DashMap<u64, Option<Arc<UserEnrichment>>, ahash::RandomState>,
+    memo_entries: AtomicUsize,
+}
+
+impl MemoizedUserEnricher {
+    pub fn new(enricher: Arc<dyn UserEnricher>) -> Self {
+        Self {
+            enricher,
+            memo: DashMap::with_hasher(ahash::RandomState::default()),
+            memo_entries: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn enrich(&self, user: &StatsigUser) -> Option<EnrichedUser> {
+        let enrichment = self.get_enrichment(user)?;
+
+        Some(EnrichedUser {
+            user: Arc::new(apply_enrichment(user, &enrichment)),
+            logged_user: get_logged_user(user, &enrichment),
+        })
+    }
+
+    fn get_enrichment(&self, user: &StatsigUser) -> Option<Arc<UserEnrichment>> {
+        let user_hash = user.data.create_full_user_hash();
+        if let Some(memoized) = self.memo.get(&user_hash) {
+            return memoized.clone();
+        }
+
+        let enrichment = self.enricher.enrich(user);
+        let enrichment = (!enrichment.is_empty()).then(|| Arc::new(enrichment));
+
+        // swap(0) elects a single clearing thread, like `MemoSha256`
+        if self.memo_entries.load(Ordering::Relaxed) >= MEMOIZED_USERS_MAX
+            && self.memo_entries.swap(0, Ordering::Relaxed) >= MEMOIZED_USERS_MAX
+        {
+            self.memo.clear();
+        }
+
+        if self.memo.insert(user_hash, enrichment.clone()).is_none() {
+            self.memo_entries.fetch_add(1, Ordering::Relaxed);
+        }
+        enrichment
+    }
+}
+
+/// With `exclude_from_logging`, the user is logged with only the enriched custom IDs added,
+/// since those may be the unit IDs of the logged exposures.
+fn get_logged_user(user: &StatsigUser, enrichment: &UserEnrichment) -> Option<Arc<StatsigUser>> {
+    if !enrichment.exclude_from_logging {
+        return None;
+    }
+
+    let custom_ids_only = UserEnrichment {
+        custom_ids: enrichment.custom_ids.clone(),
+        ..UserEnrichment::default()
+    };
+    Some(Arc::new(apply_enrichment(user, &custom_ids_only)))
+}
+
+fn apply_enrichment(user: &StatsigUser, enrichment: &UserEnrichment) -> StatsigUser {
+    let mut data: UserData = user.data.as_ref().clone();
+
+    merge_into(&mut data.custom, enrichment.custom.as_ref(), |v| v.clone());
+    merge_into(
+        &mut data.private_attributes,
+        enrichment.private_attributes.as_ref(),
+        |v| v.clone(),
+    );
+    merge_into(&mut data.custom_ids, enrichment.custom_ids.as_ref(), |v| {
+        DynamicValue::from(v.clone())
+    });
+
+    StatsigUser {
+        data: Arc::new(data),
+        sdk_version: user.sdk_version,
+    }
+}
+
+fn merge_into<V>(
+    target: &mut Option<UserDataMap>,
+    source: Option<&HashMap<String, V>>,
+    to_value: impl Fn(&V) -> DynamicValue,
+) {
+    let Some(source) = source.filter(|s| !s.is_empty()) else {
+        return;
+    };
+
+    let target = target.get_or_insert_with(UserDataMap::default);
+    for (key, value) in source {
+        if !target.contains_key(key) {
+            target.insert(key.clone(), to_value(value));
+        }
+    }
+}
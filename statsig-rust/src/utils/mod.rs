@@ -1,3 +1,5 @@
+pub(crate) mod sync_interval;
+
 #[cfg(target_env = "gnu")]
 use crate::log_d;
 
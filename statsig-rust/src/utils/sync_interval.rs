@@ -0,0 +1,36 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// A background sync interval that can be changed while the sync loop is waiting.
+pub(crate) struct SyncInterval {
+    interval_ms: AtomicU64,
+    changed_notify: Notify,
+}
+
+impl SyncInterval {
+    pub fn new(interval_ms: u64) -> Self {
+        Self {
+            interval_ms: AtomicU64::new(interval_ms),
+            changed_notify: Notify::new(),
+        }
+    }
+
+    pub fn get(&self) -> Duration {
+        Duration::from_millis(self.interval_ms.load(Ordering::Relaxed))
+    }
+
+    pub fn update(&self, interval_ms: u64) {
+        self.interval_ms.store(interval_ms, Ordering::Relaxed);
+        self.changed_notify.notify_one();
+    }
+
+    /// Waits for the current interval. Returns false if the interval was changed before it
+    /// elapsed, in which case the caller should start waiting again without syncing.
+    pub async fn wait(&self) -> bool {
+        tokio::select! {
+            () = tokio::time::sleep(self.get()) => true,
+            () = self.changed_notify.notified() => false,
+        }
+    }
+}
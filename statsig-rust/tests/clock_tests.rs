@@ -0,0 +1,105 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use statsig_rust::{Clock, ManualClock, OverrideMatcher, Statsig, StatsigOptions, StatsigUser};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SDK_KEY: &str = "secret-clock_tests";
+
+// test_time_after_failing passes after this time (2041-02-01)
+const LAUNCH_TIME_MS: u64 = 2_243_291_880_000;
+
+async fn setup(clock: &Arc<ManualClock>) -> (Statsig, Arc<MockEventLoggingAdapter>) {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+    let statsig = Statsig::new(
+        SDK_KEY,
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(logging_adapter.clone()),
+            clock: Some(clock.clone()),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, logging_adapter)
+}
+
+#[tokio::test]
+async fn test_scheduled_launch_follows_clock() {
+    let clock = Arc::new(ManualClock::new(LAUNCH_TIME_MS - 1000));
+    let (statsig, logging_adapter) = setup(&clock).await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    assert!(!statsig.check_gate(&user, "test_time_after_failing"));
+
+    clock.advance(Duration::from_secs(2));
+    assert!(statsig.check_gate(&user, "test_time_after_failing"));
+
+    statsig.flush_events().await;
+    let exposure = logging_adapter.force_get_event_at(1);
+    assert_eq!(exposure["eventName"], "statsig::gate_exposure");
+    assert_eq!(exposure["time"], LAUNCH_TIME_MS + 1000);
+}
+
+#[tokio::test]
+async fn test_event_timestamps_use_clock() {
+    let clock = Arc::new(ManualClock::new(1_700_000_000_000));
+    let (statsig, logging_adapter) = setup(&clock).await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    statsig.log_event(&user, "first", None, None);
+    clock.advance(Duration::from_secs(30));
+    statsig.log_event(&user, "second", None, None);
+    statsig.flush_events().await;
+
+    assert_eq!(
+        logging_adapter.force_get_event_at(0)["time"],
+        1_700_000_000_000u64
+    );
+    assert_eq!(
+        logging_adapter.force_get_event_at(1)["time"],
+        1_700_000_030_000u64
+    );
+}
+
+#[tokio::test]
+async fn test_override_expiry_follows_clock() {
+    let clock = Arc::new(ManualClock::from_system_time());
+    let (statsig, _) = setup(&clock).await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    statsig.override_gate_with_matcher(
+        "test_public",
+        false,
+        OverrideMatcher::new().expires_at(clock.now_ms() + 60_000),
+    );
+    assert!(!statsig.check_gate(&user, "test_public"));
+
+    clock.advance(Duration::from_secs(60));
+    assert!(statsig.check_gate(&user, "test_public"));
+}
+
+#[tokio::test]
+async fn test_scheduled_flush_follows_clock() {
+    let clock = Arc::new(ManualClock::from_system_time());
+    let (statsig, logging_adapter) = setup(&clock).await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    statsig.log_event(&user, "queued", None, None);
+
+    // the default max flush interval is 60s, the next 1s tick only has to see the clock moved
+    clock.advance(Duration::from_secs(61));
+
+    assert_eventually!(|| logging_adapter
+        .no_diagnostics_logged_event_count
+        .load(Ordering::SeqCst)
+        == 1);
+
+    statsig.shutdown().await.unwrap();
+}
@@ -0,0 +1,229 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use statsig_rust::{
+    CMABContext, CMABExplorationMode, CMABLearner, CMABLearnerOptions, DynamicValue, Statsig,
+    StatsigOptions, StatsigUser, StatsigUserBuilder, StatsigUserDataMap,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+const CMAB_NAME: &str = "test_cmab";
+
+async fn setup(learner_options: CMABLearnerOptions) -> (Statsig, Arc<CMABLearner>) {
+    let learner = Arc::new(CMABLearner::new(learner_options));
+    let options = StatsigOptions {
+        specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+            "tests/data/dcs_with_cmab.json",
+        ))),
+        event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+        cmab_learner: Some(learner.clone()),
+        ..StatsigOptions::default()
+    };
+
+    let statsig = Statsig::new("secret-cmab_learner", Some(Arc::new(options)));
+    statsig.initialize().await.unwrap();
+    (statsig, learner)
+}
+
+fn user_in_country(id: usize, country: &str) -> StatsigUser {
+    StatsigUserBuilder::new_with_user_id(format!("user_{id}"))
+        .country(Some(country.to_string()))
+        .build()
+}
+
+fn user_with_age(id: usize, age: f64) -> StatsigUser {
+    let custom: StatsigUserDataMap = [("age".to_string(), DynamicValue::from(age))]
+        .into_iter()
+        .collect();
+
+    StatsigUserBuilder::new_with_user_id(format!("user_{id}"))
+        .custom(Some(custom))
+        .build()
+}
+
+fn country_learner_options(exploration: CMABExplorationMode) -> CMABLearnerOptions {
+    CMABLearnerOptions {
+        categorical_features: vec!["country".to_string()],
+        exploration,
+        min_records_per_group: 5,
+        retrain_interval: 1000,
+        ..CMABLearnerOptions::default()
+    }
+}
+
+fn record_country_outcomes(statsig: &Statsig) {
+    for i in 0..20 {
+        let us_user = user_in_country(i, "US");
+        statsig.record_cmab_outcome(&us_user, CMAB_NAME, "group_a", 1.0);
+        statsig.record_cmab_outcome(&us_user, CMAB_NAME, "group_b", 0.0);
+
+        let ca_user = user_in_country(i, "CA");
+        statsig.record_cmab_outcome(&ca_user, CMAB_NAME, "group_a", 0.0);
+        statsig.record_cmab_outcome(&ca_user, CMAB_NAME, "group_b", 1.0);
+    }
+}
+
+#[tokio::test]
+async fn test_trained_model_ranks_groups_per_context() {
+    let (statsig, learner) = setup(country_learner_options(CMABExplorationMode::SampleRate)).await;
+    record_country_outcomes(&statsig);
+    assert!(learner.train(CMAB_NAME));
+
+    let us_user = user_in_country(100, "US");
+    let ca_user = user_in_country(101, "CA");
+
+    let experiment = statsig.get_experiment(&us_user, CMAB_NAME);
+    assert_eq!(experiment.rule_id, "group_a");
+    assert_eq!(experiment.group_name.as_deref(), Some("Group A"));
+
+    let ranked = statsig.get_cmab_ranked_groups(&ca_user, CMAB_NAME);
+    assert_eq!(ranked[0].rule_id.as_str(), "group_b");
+    assert!(ranked[0].score > ranked[1].score);
+}
+
+#[tokio::test]
+async fn test_trained_group_configs_match_spec_shape() {
+    let (statsig, learner) = setup(CMABLearnerOptions {
+        numerical_features: vec!["age".to_string()],
+        ridge_lambda: 1e-6,
+        min_records_per_group: 5,
+        retrain_interval: 1000,
+        ..CMABLearnerOptions::default()
+    })
+    .await;
+
+    for i in 0..30 {
+        let age = (i % 10) as f64;
+        statsig.record_cmab_outcome(
+            &user_with_age(i, age),
+            CMAB_NAME,
+            "group_a",
+            2.0 * age + 1.0,
+        );
+    }
+    assert!(learner.train(CMAB_NAME));
+
+    let configs = learner.get_trained_group_configs(CMAB_NAME).unwrap();
+    let group_a = configs.get("group_a").unwrap();
+    assert_eq!(group_a.records, 30);
+    assert!((group_a.weights_numerical["age"] - 2.0).abs() < 1e-3);
+    assert!((group_a.intercept - 1.0).abs() < 1e-3);
+    assert!(!configs.contains_key("group_b"));
+}
+
+#[tokio::test]
+async fn test_retrains_after_interval() {
+    let learner = CMABLearner::new(CMABLearnerOptions {
+        min_records_per_group: 1,
+        retrain_interval: 3,
+        ..CMABLearnerOptions::default()
+    });
+    let context = CMABContext::new().with_categorical("country", "US");
+
+    learner.record(CMAB_NAME, "group_a", &context, 1.0);
+    learner.record(CMAB_NAME, "group_a", &context, 1.0);
+    assert!(learner.get_trained_group_configs(CMAB_NAME).is_none());
+
+    learner.record(CMAB_NAME, "group_a", &context, 1.0);
+    assert!(learner.get_trained_group_configs(CMAB_NAME).is_some());
+
+    learner.reset(CMAB_NAME);
+    assert!(learner.get_trained_group_configs(CMAB_NAME).is_none());
+    assert!(!learner.train(CMAB_NAME));
+}
+
+#[tokio::test]
+async fn test_ucb_prefers_uncertain_group() {
+    let (statsig, learner) = setup(CMABLearnerOptions {
+        min_records_per_group: 2,
+        retrain_interval: 1000,
+        exploration: CMABExplorationMode::Ucb { alpha: 5.0 },
+        ..CMABLearnerOptions::default()
+    })
+    .await;
+
+    let context = CMABContext::new();
+    for _ in 0..200 {
+        learner.record(CMAB_NAME, "group_a", &context, 0.5);
+    }
+    learner.record(CMAB_NAME, "group_b", &context, 0.4);
+    learner.record(CMAB_NAME, "group_b", &context, 0.4);
+    assert!(learner.train(CMAB_NAME));
+
+    let experiment = statsig.get_experiment(&user_in_country(1, "US"), CMAB_NAME);
+    assert_eq!(experiment.rule_id, "group_b");
+}
+
+#[tokio::test]
+async fn test_thompson_sampling_without_noise_is_greedy() {
+    let (statsig, learner) = setup(country_learner_options(
+        CMABExplorationMode::ThompsonSampling {
+            noise_variance: 0.0,
+        },
+    ))
+    .await;
+    record_country_outcomes(&statsig);
+    assert!(learner.train(CMAB_NAME));
+
+    for i in 0..10 {
+        let experiment = statsig.get_experiment(&user_in_country(i, "CA"), CMAB_NAME);
+        assert_eq!(experiment.rule_id, "group_b");
+    }
+}
+
+#[tokio::test]
+async fn test_thompson_sampling_is_stable_per_user() {
+    let (statsig, learner) = setup(country_learner_options(
+        CMABExplorationMode::ThompsonSampling {
+            noise_variance: 50.0,
+        },
+    ))
+    .await;
+    record_country_outcomes(&statsig);
+    assert!(learner.train(CMAB_NAME));
+
+    for i in 0..20 {
+        let user = user_in_country(i, "US");
+        let first = statsig.get_experiment(&user, CMAB_NAME).rule_id;
+        for _ in 0..5 {
+            assert_eq!(statsig.get_experiment(&user, CMAB_NAME).rule_id, first);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_outcomes_are_refit_in_the_background() {
+    let (statsig, learner) = setup(CMABLearnerOptions {
+        categorical_features: vec!["country".to_string()],
+        min_records_per_group: 5,
+        retrain_interval: 80,
+        ..CMABLearnerOptions::default()
+    })
+    .await;
+    record_country_outcomes(&statsig);
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while learner.get_trained_group_configs(CMAB_NAME).is_none() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("CMAB was not refit");
+
+    let configs = learner.get_trained_group_configs(CMAB_NAME).unwrap();
+    assert_eq!(configs["group_a"].records, 40);
+}
+
+#[tokio::test]
+async fn test_untrained_cmab_uses_spec_config() {
+    let (statsig, learner) = setup(country_learner_options(CMABExplorationMode::SampleRate)).await;
+
+    // below min_records_per_group
+    statsig.record_cmab_outcome(&user_in_country(1, "US"), CMAB_NAME, "group_a", 1.0);
+    assert!(!learner.train(CMAB_NAME));
+
+    let experiment = statsig.get_experiment(&user_in_country(1, "US"), CMAB_NAME);
+    assert!(experiment.rule_id.ends_with(":explore"));
+}
@@ -0,0 +1,219 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::{json, Value};
+use statsig_rust::specs_response::cmab_types::CMABConfig;
+use statsig_rust::{
+    CMABContext, CMABLearner, CMABLearnerOptions, CMABLoggedRecord, CMABOfflineEvaluator, Statsig,
+    StatsigErr, StatsigOptions, StatsigUser, StatsigUserBuilder,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const CMAB_NAME: &str = "test_cmab";
+
+fn user_in_country(id: usize, country: &str) -> StatsigUser {
+    StatsigUserBuilder::new_with_user_id(format!("user_{id}"))
+        .country(Some(country.to_string()))
+        .build()
+}
+
+fn country_record(country: &str, action: &str, reward: f64) -> CMABLoggedRecord {
+    CMABLoggedRecord {
+        context: CMABContext::new().with_categorical("country", country),
+        action: action.to_string(),
+        propensity: 0.5,
+        reward,
+    }
+}
+
+/// Logged under a uniform policy: group_a pays off in the US, group_b in CA.
+fn country_records() -> Vec<CMABLoggedRecord> {
+    let mut records = vec![];
+    for _ in 0..25 {
+        records.push(country_record("US", "group_a", 1.0));
+        records.push(country_record("US", "group_b", 0.0));
+        records.push(country_record("CA", "group_a", 0.0));
+        records.push(country_record("CA", "group_b", 1.0));
+    }
+    records
+}
+
+fn candidate_config(group_a_country: &str, sample_rate: f64) -> CMABConfig {
+    let group_config = |country: &str| {
+        json!({
+            "alpha": 0.0,
+            "intercept": 0.0,
+            "records": 100,
+            "weightsNumerical": {},
+            "weightsCategorical": {"country": {country: 1.0}}
+        })
+    };
+    let group_b_country = if group_a_country == "US" { "CA" } else { "US" };
+
+    serde_json::from_value(json!({
+        "salt": "salt-test-cmab",
+        "targetAppIDs": null,
+        "defaultValue": {"arm": "default"},
+        "idType": "userID",
+        "enabled": true,
+        "version": 1,
+        "sampleRate": sample_rate,
+        "higherIsBetter": true,
+        "groups": [
+            {"name": "Group A", "id": "group_a", "parameterValues": {"arm": "a"}},
+            {"name": "Group B", "id": "group_b", "parameterValues": {"arm": "b"}}
+        ],
+        "config": {
+            "group_a": group_config(group_a_country),
+            "group_b": group_config(group_b_country)
+        },
+        "targetingGateName": null
+    }))
+    .unwrap()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[tokio::test]
+async fn test_log_cmab_reward_links_to_exposure() {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+    let learner = Arc::new(CMABLearner::new(CMABLearnerOptions {
+        categorical_features: vec!["country".to_string()],
+        min_records_per_group: 1,
+        ..CMABLearnerOptions::default()
+    }));
+    let options = StatsigOptions {
+        specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+            "tests/data/dcs_with_cmab.json",
+        ))),
+        event_logging_adapter: Some(logging_adapter.clone()),
+        cmab_learner: Some(learner.clone()),
+        ..StatsigOptions::default()
+    };
+    let statsig = Statsig::new("secret-cmab_reward", Some(Arc::new(options)));
+    statsig.initialize().await.unwrap();
+
+    let user = user_in_country(1, "US");
+    statsig.log_cmab_exposure_for_group(&user, CMAB_NAME, "group_a".to_string());
+    statsig.log_cmab_reward(
+        &user,
+        CMAB_NAME,
+        "group_a",
+        1.5,
+        Some(HashMap::from([(
+            "source".to_string(),
+            "checkout".to_string(),
+        )])),
+    );
+    statsig.flush_events().await;
+
+    let exposure = logging_adapter.force_get_event_at(0);
+    let reward = logging_adapter.force_get_event_at(1);
+
+    assert_eq!(reward["eventName"], "statsig::cmab_reward");
+    assert_eq!(reward["value"], 1.5);
+    assert_eq!(reward["metadata"]["config"], exposure["metadata"]["config"]);
+    assert_eq!(reward["metadata"]["ruleID"], exposure["metadata"]["ruleID"]);
+    assert_eq!(reward["metadata"]["source"], "checkout");
+    assert_eq!(reward["user"]["userID"], Value::from("user_1"));
+
+    assert!(learner.train(CMAB_NAME));
+    let configs = learner.get_trained_group_configs(CMAB_NAME).unwrap();
+    assert_eq!(configs["group_a"].records, 1);
+}
+
+#[test]
+fn test_explored_groups_train_the_group_model() {
+    let learner = CMABLearner::new(CMABLearnerOptions {
+        min_records_per_group: 1,
+        retrain_interval: 1000,
+        ..CMABLearnerOptions::default()
+    });
+    let context = CMABContext::new().with_categorical("country", "US");
+
+    learner.record(CMAB_NAME, "group_b:explore", &context, 1.0);
+    learner.record(CMAB_NAME, "group_b", &context, 1.0);
+    assert!(learner.train(CMAB_NAME));
+
+    let configs = learner.get_trained_group_configs(CMAB_NAME).unwrap();
+    assert_eq!(configs["group_b"].records, 2);
+    assert!(!configs.contains_key("group_b:explore"));
+}
+
+#[test]
+fn test_offline_eval_of_good_and_bad_candidates() {
+    let records = country_records();
+
+    let good = CMABOfflineEvaluator::new(&candidate_config("US", 0.0))
+        .with_reward_model_lambda(1e-6)
+        .evaluate(&records)
+        .unwrap();
+    assert_eq!(good.records, 100);
+    assert_eq!(good.skipped_records, 0);
+    assert_close(good.logged_reward, 0.5);
+    assert_close(good.match_rate, 0.5);
+    assert_close(good.ips, 1.0);
+    assert_close(good.snips, 1.0);
+    assert_close(good.doubly_robust, 1.0);
+
+    let bad = CMABOfflineEvaluator::new(&candidate_config("CA", 0.0))
+        .with_reward_model_lambda(1e-6)
+        .evaluate(&records)
+        .unwrap();
+    assert_close(bad.ips, 0.0);
+    assert_close(bad.doubly_robust, 0.0);
+}
+
+#[test]
+fn test_offline_eval_accounts_for_exploration() {
+    // half of decisions explore evenly, so the right group is picked 75% of the time
+    let report = CMABOfflineEvaluator::new(&candidate_config("US", 0.5))
+        .with_reward_model_lambda(1e-6)
+        .evaluate(&country_records())
+        .unwrap();
+
+    assert_close(report.match_rate, 0.5);
+    assert_close(report.ips, 0.75);
+    assert_close(report.doubly_robust, 0.75);
+}
+
+#[test]
+fn test_offline_eval_from_jsonl() {
+    let jsonl = [
+        r#"{"context": {"categorical": {"country": "US"}}, "action": "group_a", "propensity": 0.5, "reward": 1.0}"#,
+        "",
+        r#"{"context": {"categorical": {"country": "CA"}}, "action": "group_b", "propensity": 0.5, "reward": 1.0}"#,
+        r#"{"action": "group_a", "propensity": 0.0, "reward": 1.0}"#,
+        r#"{"action": "group_c", "propensity": 0.5, "reward": 1.0}"#,
+    ]
+    .join("\n");
+
+    let report = CMABOfflineEvaluator::new(&candidate_config("US", 0.0))
+        .evaluate_jsonl(jsonl.as_bytes())
+        .unwrap();
+    assert_eq!(report.records, 2);
+    assert_eq!(report.skipped_records, 2);
+    assert_close(report.ips, 2.0);
+
+    let err = CMABOfflineEvaluator::new(&candidate_config("US", 0.0))
+        .evaluate_jsonl("{\"action\": 1}".as_bytes())
+        .unwrap_err();
+    match err {
+        StatsigErr::JsonParseError(_, message) => assert!(message.starts_with("line 1")),
+        other => panic!("Unexpected error {other:?}"),
+    }
+}
+
+#[test]
+fn test_offline_eval_without_usable_records() {
+    let records = vec![country_record("US", "group_c", 1.0)];
+    let result = CMABOfflineEvaluator::new(&candidate_config("US", 0.0)).evaluate(&records);
+    assert!(matches!(result, Err(StatsigErr::InvalidOperation(_))));
+}
@@ -0,0 +1,204 @@
+mod utils;
+
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::Value;
+use statsig_rust::{
+    EventDestination, EventDestinationFilter, LoggedEventKind, Statsig,
+    StatsigCompositeEventLoggingAdapter, StatsigErr, StatsigOptions, StatsigUser,
+};
+use std::sync::{atomic::Ordering, Arc};
+use std::time::{Duration, Instant};
+use utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+
+struct Destinations {
+    statsig: Arc<MockEventLoggingAdapter>,
+    warehouse: Arc<MockEventLoggingAdapter>,
+    debug: Arc<MockEventLoggingAdapter>,
+}
+
+async fn setup() -> (
+    Statsig,
+    Arc<StatsigCompositeEventLoggingAdapter>,
+    Destinations,
+) {
+    let destinations = Destinations {
+        statsig: Arc::new(MockEventLoggingAdapter::new()),
+        warehouse: Arc::new(MockEventLoggingAdapter::new()),
+        debug: Arc::new(MockEventLoggingAdapter::new()),
+    };
+
+    let composite = Arc::new(StatsigCompositeEventLoggingAdapter::new(
+        None,
+        vec![
+            EventDestination::new("statsig", destinations.statsig.clone()),
+            EventDestination::new("warehouse", destinations.warehouse.clone())
+                .with_filter(EventDestinationFilter::exposures_only())
+                .with_max_retries(2)
+                .with_retry_backoff_ms(1),
+            EventDestination::new("debug", destinations.debug.clone())
+                .with_filter(
+                    EventDestinationFilter::only_kinds(&[LoggedEventKind::Custom])
+                        .excluding_event_names(&["noisy_event"]),
+                )
+                .with_max_retries(0),
+        ],
+    ));
+
+    let statsig = Statsig::new(
+        &format!("secret-{}", uuid::Uuid::new_v4()),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(composite.clone()),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, composite, destinations)
+}
+
+fn logged_event_names(adapter: &MockEventLoggingAdapter) -> Vec<String> {
+    adapter
+        .logged_payloads
+        .lock()
+        .unwrap()
+        .iter()
+        .flat_map(|p| p.events.as_array().cloned().unwrap_or_default())
+        .filter_map(|e| e.get("eventName").and_then(Value::as_str).map(String::from))
+        .filter(|name| name != "statsig::diagnostics")
+        .collect()
+}
+
+fn log_sample_events(statsig: &Statsig) {
+    let user = StatsigUser::with_user_id("a_user".to_string());
+    statsig.check_gate(&user, "test_public");
+    statsig.get_experiment(&user, "experiment_with_many_params");
+    statsig.log_event(&user, "my_custom_event", None, None);
+    statsig.log_event(&user, "noisy_event", None, None);
+}
+
+#[tokio::test]
+async fn test_fans_out_with_per_destination_filters() {
+    let (statsig, _, destinations) = setup().await;
+
+    log_sample_events(&statsig);
+    statsig.shutdown().await.unwrap();
+
+    let all = logged_event_names(&destinations.statsig);
+    assert_eq!(all.len(), 4);
+
+    assert_eq!(
+        logged_event_names(&destinations.warehouse),
+        vec!["statsig::gate_exposure", "statsig::config_exposure"]
+    );
+    assert_eq!(
+        logged_event_names(&destinations.debug),
+        vec!["my_custom_event"]
+    );
+}
+
+#[tokio::test]
+async fn test_failing_destination_retries_independently() {
+    let (statsig, composite, destinations) = setup().await;
+
+    *destinations
+        .warehouse
+        .mocked_log_events_result
+        .lock()
+        .unwrap() = Err(StatsigErr::LogEventError("warehouse down".to_string()));
+
+    log_sample_events(&statsig);
+    statsig.shutdown().await.unwrap();
+
+    // one attempt plus two retries, and no resend to healthy destinations
+    assert_eq!(
+        destinations.warehouse.times_called.load(Ordering::SeqCst),
+        3
+    );
+    assert_eq!(destinations.statsig.times_called.load(Ordering::SeqCst), 1);
+    assert_eq!(logged_event_names(&destinations.statsig).len(), 4);
+
+    let stats = composite.destination_stats();
+    let warehouse = stats.iter().find(|s| s.name == "warehouse").unwrap();
+    assert_eq!(warehouse.requests_failed, 1);
+    assert_eq!(warehouse.retries, 2);
+    assert_eq!(warehouse.events_dropped, 2);
+    assert!(warehouse
+        .last_error
+        .as_deref()
+        .unwrap()
+        .contains("warehouse down"));
+
+    let statsig_stats = stats.iter().find(|s| s.name == "statsig").unwrap();
+    assert_eq!(statsig_stats.requests_succeeded, 1);
+    assert_eq!(statsig_stats.requests_failed, 0);
+}
+
+#[tokio::test]
+async fn test_retries_do_not_block_the_flush() {
+    let failing = Arc::new(MockEventLoggingAdapter::new());
+    *failing.mocked_log_events_result.lock().unwrap() =
+        Err(StatsigErr::LogEventError("down".to_string()));
+
+    let composite = Arc::new(StatsigCompositeEventLoggingAdapter::new(
+        None,
+        vec![EventDestination::new("failing", failing.clone())
+            .with_max_retries(1)
+            .with_retry_backoff_ms(500)],
+    ));
+
+    let statsig = Statsig::new(
+        &format!("secret-{}", uuid::Uuid::new_v4()),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(composite.clone()),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    log_sample_events(&statsig);
+    let start = Instant::now();
+    statsig.flush_events().await;
+    assert!(start.elapsed() < Duration::from_millis(400));
+    assert_eq!(failing.times_called.load(Ordering::SeqCst), 1);
+
+    // shutdown waits for the pending retries, including the one for the shutdown flush
+    statsig.shutdown().await.unwrap();
+
+    let stats = composite.destination_stats();
+    assert!(stats[0].requests_failed >= 1);
+    assert_eq!(stats[0].retries, stats[0].requests_failed);
+    assert_eq!(
+        failing.times_called.load(Ordering::SeqCst),
+        stats[0].requests_failed * 2
+    );
+}
+
+#[tokio::test]
+async fn test_reports_filtered_and_sent_counts() {
+    let (statsig, composite, _destinations) = setup().await;
+
+    log_sample_events(&statsig);
+    statsig.shutdown().await.unwrap();
+
+    let stats = composite.destination_stats();
+    let debug = stats.iter().find(|s| s.name == "debug").unwrap();
+    assert_eq!(debug.events_sent, 1);
+    assert!(debug.events_filtered >= 3);
+}
+
+#[test]
+fn test_filter_by_event_name() {
+    let filter = EventDestinationFilter::all().with_event_names(&["purchase"]);
+    assert!(filter.accepts("purchase"));
+    assert!(!filter.accepts("statsig::gate_exposure"));
+
+    let filter = EventDestinationFilter::only_kinds(&[LoggedEventKind::Diagnostics]);
+    assert!(filter.accepts("statsig::diagnostics"));
+    assert!(!filter.accepts("statsig::log_line"));
+}
@@ -24,6 +24,7 @@ pub mod specs_adapter_tests {
                 spec: "bg_sync_1".to_string(),
                 last_updated: 2,
                 zstd_dict_id: None,
+                has_updates: None,
             }))
             .await;
         data_store
@@ -107,6 +108,7 @@ pub mod specs_adapter_tests {
                 spec: "bg_sync_1".to_string(),
                 last_updated: 2,
                 zstd_dict_id: None,
+                has_updates: None,
             }))
             .await;
 
@@ -127,6 +129,7 @@ pub mod specs_adapter_tests {
                 spec: "bg_sync_2".to_string(),
                 last_updated: 3,
                 zstd_dict_id: None,
+                has_updates: None,
             }))
             .await;
         let _ = mock_listener.wait_for_next_update().await;
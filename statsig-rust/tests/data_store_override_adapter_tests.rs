@@ -0,0 +1,295 @@
+mod utils;
+
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use async_trait::async_trait;
+use futures::StreamExt;
+use statsig_rust::data_store_interface::{DataStoreResponse, DataStoreTrait, RequestPath};
+use statsig_rust::sdk_event_emitter::{OwnedSdkEvent, SdkEventCode, SdkEventFilter};
+use statsig_rust::{
+    ManualClock, OverrideAdapter, OverrideCondition, OverrideMatcher, Statsig,
+    StatsigDataStoreOverrideAdapter, StatsigErr, StatsigOptions, StatsigUser,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use utils::mock_scrapi::{Endpoint, EndpointStub, Method, MockScrapi, StubData};
+
+const SYNC_INTERVAL_MS: u32 = 20;
+
+#[derive(Default)]
+struct InMemoryDataStore {
+    values: Mutex<HashMap<String, String>>,
+    fail_writes: AtomicBool,
+    set_calls: AtomicUsize,
+}
+
+#[async_trait]
+impl DataStoreTrait for InMemoryDataStore {
+    async fn initialize(&self) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<DataStoreResponse, StatsigErr> {
+        Ok(DataStoreResponse {
+            result: self.values.lock().unwrap().get(key).cloned(),
+            time: None,
+        })
+    }
+
+    async fn set(&self, key: &str, value: &str, _time: Option<u64>) -> Result<(), StatsigErr> {
+        self.set_calls.fetch_add(1, Ordering::SeqCst);
+        if self.fail_writes.load(Ordering::SeqCst) {
+            return Err(StatsigErr::DataStoreFailure("Failed to set".to_string()));
+        }
+
+        self.values
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    async fn support_polling_updates_for(&self, _path: RequestPath) -> bool {
+        true
+    }
+}
+
+async fn create_instance(
+    data_store: Arc<InMemoryDataStore>,
+    set_by: &str,
+) -> (Statsig, Arc<StatsigDataStoreOverrideAdapter>, MockScrapi) {
+    let mock_scrapi = MockScrapi::new().await;
+    mock_scrapi
+        .stub(EndpointStub {
+            method: Method::POST,
+            response: StubData::String("{\"success\": true}".to_string()),
+            ..EndpointStub::with_endpoint(Endpoint::LogEvent)
+        })
+        .await;
+
+    let adapter_options = StatsigOptions {
+        specs_sync_interval_ms: Some(SYNC_INTERVAL_MS),
+        ..StatsigOptions::new()
+    };
+
+    let adapter = Arc::new(StatsigDataStoreOverrideAdapter::new(
+        "secret-key",
+        data_store,
+        Some(&adapter_options),
+        Some(set_by.to_string()),
+    ));
+
+    let statsig = Statsig::new(
+        "secret-key",
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            log_event_url: Some(mock_scrapi.url_for_endpoint(Endpoint::LogEvent)),
+            override_adapter: Some(adapter.clone()),
+            ..StatsigOptions::new()
+        })),
+    );
+
+    statsig.initialize().await.unwrap();
+    (statsig, adapter, mock_scrapi)
+}
+
+async fn wait_for_sync() {
+    tokio::time::sleep(Duration::from_millis(u64::from(SYNC_INTERVAL_MS) * 10)).await;
+}
+
+#[tokio::test]
+async fn test_override_is_shared_across_instances() {
+    let data_store = Arc::new(InMemoryDataStore::default());
+    let (statsig_a, _, _scrapi_a) = create_instance(data_store.clone(), "pod_a").await;
+    let (statsig_b, _, _scrapi_b) = create_instance(data_store.clone(), "pod_b").await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    assert!(statsig_b.check_gate(&user, "test_public"));
+
+    statsig_a.override_gate("test_public", false, None);
+    assert!(!statsig_a.check_gate(&user, "test_public"));
+
+    wait_for_sync().await;
+    assert!(!statsig_b.check_gate(&user, "test_public"));
+
+    statsig_b.remove_gate_override("test_public", None);
+    wait_for_sync().await;
+    assert!(statsig_a.check_gate(&user, "test_public"));
+
+    statsig_a.shutdown().await.unwrap();
+    statsig_b.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_local_overrides_take_precedence() {
+    let data_store = Arc::new(InMemoryDataStore::default());
+    let (statsig, adapter, _scrapi) = create_instance(data_store, "pod_a").await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    statsig.override_gate("test_public", false, None);
+    adapter
+        .local_overrides()
+        .override_gate("test_public", true, None);
+    assert!(statsig.check_gate(&user, "test_public"));
+
+    adapter
+        .local_overrides()
+        .remove_gate_override("test_public", None);
+    assert!(!statsig.check_gate(&user, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_records_who_set_each_override() {
+    let data_store = Arc::new(InMemoryDataStore::default());
+    let (statsig_a, _, _scrapi_a) = create_instance(data_store.clone(), "qa@ourco.com").await;
+    let (statsig_b, adapter_b, _scrapi_b) = create_instance(data_store.clone(), "pod_b").await;
+
+    statsig_a.override_experiment_by_group_name("experiment_with_many_params", "Control", None);
+    wait_for_sync().await;
+
+    let records = adapter_b.list_shared_overrides();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].set_by.as_deref(), Some("qa@ourco.com"));
+    assert_eq!(records[0].active.name, "experiment_with_many_params");
+    assert_eq!(records[0].active.group_name.as_deref(), Some("Control"));
+    assert!(records[0].set_at_ms > 0);
+
+    statsig_a.shutdown().await.unwrap();
+    statsig_b.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_concurrent_writers_do_not_drop_each_others_overrides() {
+    let data_store = Arc::new(InMemoryDataStore::default());
+    let adapter_a =
+        StatsigDataStoreOverrideAdapter::new("secret-key", data_store.clone(), None, None);
+    let adapter_b =
+        StatsigDataStoreOverrideAdapter::new("secret-key", data_store.clone(), None, None);
+
+    adapter_a.override_gate("gate_a", true, None);
+    adapter_a.flush_pending_writes().await.unwrap();
+
+    adapter_b.override_gate("gate_b", true, None);
+    adapter_b.flush_pending_writes().await.unwrap();
+
+    adapter_a.sync_from_data_store().await.unwrap();
+    let mut names: Vec<String> = adapter_a
+        .list_overrides()
+        .into_iter()
+        .map(|o| o.name)
+        .collect();
+    names.sort();
+
+    assert_eq!(names, vec!["gate_a", "gate_b"]);
+}
+
+#[tokio::test]
+async fn test_failed_writes_are_dropped_after_max_attempts() {
+    let data_store = Arc::new(InMemoryDataStore::default());
+    data_store.fail_writes.store(true, Ordering::SeqCst);
+    let adapter =
+        StatsigDataStoreOverrideAdapter::new("secret-key", data_store.clone(), None, None);
+
+    adapter.override_gate("a_gate", true, None);
+    for _ in 0..5 {
+        assert!(adapter.flush_pending_writes().await.is_err());
+    }
+    assert_eq!(data_store.set_calls.load(Ordering::SeqCst), 5);
+
+    // nothing is left to retry, and the next sync drops the unwritten override
+    data_store.fail_writes.store(false, Ordering::SeqCst);
+    adapter.flush_pending_writes().await.unwrap();
+    assert_eq!(data_store.set_calls.load(Ordering::SeqCst), 5);
+
+    adapter.sync_from_data_store().await.unwrap();
+    assert!(adapter.list_shared_overrides().is_empty());
+}
+
+#[tokio::test]
+async fn test_uses_the_options_clock() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let options = StatsigOptions {
+        clock: Some(clock.clone()),
+        ..StatsigOptions::new()
+    };
+    let adapter = StatsigDataStoreOverrideAdapter::new(
+        "secret-key",
+        Arc::new(InMemoryDataStore::default()),
+        Some(&options),
+        None,
+    );
+
+    adapter.override_gate_with_matcher("a_gate", true, OverrideMatcher::new().expires_at(2_000));
+    let records = adapter.list_shared_overrides();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].set_at_ms, 1_000);
+
+    clock.advance(Duration::from_secs(1));
+    assert!(adapter.list_shared_overrides().is_empty());
+    assert!(!adapter.get_gate_override(
+        &StatsigUser::with_user_id("a_user"),
+        "a_gate",
+        &mut Default::default()
+    ));
+}
+
+#[tokio::test]
+async fn test_tier_conditions_use_the_options_environment() {
+    let data_store = Arc::new(InMemoryDataStore::default());
+    let adapter_for = |environment: &str| {
+        let options = StatsigOptions {
+            environment: Some(environment.to_string()),
+            ..StatsigOptions::new()
+        };
+        StatsigDataStoreOverrideAdapter::new("secret-key", data_store.clone(), Some(&options), None)
+    };
+
+    let staging = adapter_for("staging");
+    staging.override_gate_with_matcher(
+        "a_gate",
+        true,
+        OverrideMatcher::new().with_condition(OverrideCondition::environment_tier("staging")),
+    );
+    staging.flush_pending_writes().await.unwrap();
+
+    let production = adapter_for("production");
+    production.sync_from_data_store().await.unwrap();
+
+    let user = StatsigUser::with_user_id("a_user");
+    assert!(staging.get_gate_override(&user, "a_gate", &mut Default::default()));
+    assert!(!production.get_gate_override(&user, "a_gate", &mut Default::default()));
+}
+
+#[tokio::test]
+async fn test_sync_emits_overrides_reloaded_event() {
+    let data_store = Arc::new(InMemoryDataStore::default());
+    let (statsig_a, _, _scrapi_a) = create_instance(data_store.clone(), "pod_a").await;
+    let (statsig_b, _, _scrapi_b) = create_instance(data_store.clone(), "pod_b").await;
+
+    let mut stream =
+        statsig_b.event_stream(SdkEventFilter::all().with_codes([SdkEventCode::OverridesReloaded]));
+
+    statsig_a.override_gate("test_public", false, None);
+    wait_for_sync().await;
+
+    let event = tokio::time::timeout(Duration::from_secs(1), stream.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        event,
+        OwnedSdkEvent::OverridesReloaded {
+            source: "DataStore".to_string(),
+            override_count: 1,
+        }
+    );
+}
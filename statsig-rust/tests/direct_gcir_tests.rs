@@ -118,10 +118,7 @@ fn generate_response(
         "companyID".to_string(),
         "123".to_string(),
     )]));
-    let user_internal = StatsigUserInternal {
-        user_ref: &user,
-        statsig_instance: None,
-    };
+    let user_internal = StatsigUserInternal::new(&user, None);
     let hashing = HashUtil::new();
 
     let mut ctx = EvaluatorContext::new(
@@ -0,0 +1,168 @@
+mod utils;
+
+use crate::utils::mock_data_store::MockDataStore;
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use statsig_rust::{ExposureDedupePersistence, ManualClock, Statsig, StatsigOptions, StatsigUser};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const GATE: &str = "test_public";
+
+async fn start_instance(options: StatsigOptions) -> (Statsig, Arc<MockEventLoggingAdapter>) {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+
+    let uuid = uuid::Uuid::new_v4();
+    let statsig = Statsig::new(
+        &format!("secret-exposure_dedupe_persistence_{uuid}"),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(logging_adapter.clone()),
+            ..options
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, logging_adapter)
+}
+
+/// The users with a logged exposure, after checking the gate for each of `user_ids`.
+async fn check_gate_and_get_exposed_users(
+    options: StatsigOptions,
+    user_ids: &[&str],
+) -> Vec<String> {
+    let (statsig, logging_adapter) = start_instance(options).await;
+
+    for user_id in user_ids {
+        statsig.check_gate(&StatsigUser::with_user_id(*user_id), GATE);
+    }
+    statsig.shutdown().await.unwrap();
+
+    let payloads = logging_adapter.logged_payloads.lock().unwrap();
+    payloads
+        .iter()
+        .flat_map(|p| p.events.as_array().cloned().unwrap_or_default())
+        .filter(|e| e["eventName"] == "statsig::gate_exposure")
+        .map(|e| e["user"]["userID"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn options_with(
+    persistence: ExposureDedupePersistence,
+    clock: &Arc<ManualClock>,
+) -> StatsigOptions {
+    StatsigOptions {
+        exposure_dedupe_persistence: Some(persistence),
+        clock: Some(clock.clone()),
+        ..StatsigOptions::new()
+    }
+}
+
+#[tokio::test]
+async fn test_dedupe_keys_survive_restart_with_file() {
+    let dir = TempDir::new().unwrap();
+    let persistence = ExposureDedupePersistence::file(dir.path().join("dedupe.bin"));
+    let clock = Arc::new(ManualClock::from_system_time());
+
+    let exposed = check_gate_and_get_exposed_users(
+        options_with(persistence.clone(), &clock),
+        &["user_a", "user_b"],
+    )
+    .await;
+    assert_eq!(exposed, vec!["user_a", "user_b"]);
+
+    clock.advance(Duration::from_secs(10));
+    let exposed = check_gate_and_get_exposed_users(
+        options_with(persistence, &clock),
+        &["user_a", "user_b", "user_c"],
+    )
+    .await;
+    assert_eq!(exposed, vec!["user_c"]);
+}
+
+#[tokio::test]
+async fn test_expired_dedupe_keys_are_not_loaded() {
+    let dir = TempDir::new().unwrap();
+    let persistence = ExposureDedupePersistence::file(dir.path().join("dedupe.bin"));
+    let clock = Arc::new(ManualClock::from_system_time());
+
+    check_gate_and_get_exposed_users(options_with(persistence.clone(), &clock), &["user_a"]).await;
+
+    // past the default 60 second dedupe window
+    clock.advance(Duration::from_secs(61));
+    let exposed =
+        check_gate_and_get_exposed_users(options_with(persistence, &clock), &["user_a"]).await;
+    assert_eq!(exposed, vec!["user_a"]);
+}
+
+#[tokio::test]
+async fn test_dedupe_keys_survive_restart_with_data_store() {
+    let data_store = Arc::new(MockDataStore::new_with_byte_cache(false));
+    let clock = Arc::new(ManualClock::from_system_time());
+    let options = || StatsigOptions {
+        data_store: Some(data_store.clone()),
+        ..options_with(ExposureDedupePersistence::data_store(), &clock)
+    };
+
+    check_gate_and_get_exposed_users(options(), &["user_a"]).await;
+    assert_eq!(data_store.num_set_bytes_calls(), 1);
+
+    // a 20 byte header and 40 bytes per key
+    assert_eq!(data_store.stored_json_bytes().unwrap().len(), 60);
+
+    let exposed = check_gate_and_get_exposed_users(options(), &["user_a", "user_b"]).await;
+    assert_eq!(exposed, vec!["user_b"]);
+}
+
+#[tokio::test]
+async fn test_unreadable_snapshot_is_ignored() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("dedupe.bin");
+    std::fs::write(&path, "not json").unwrap();
+    let clock = Arc::new(ManualClock::from_system_time());
+
+    let exposed = check_gate_and_get_exposed_users(
+        options_with(ExposureDedupePersistence::file(&path), &clock),
+        &["user_a"],
+    )
+    .await;
+    assert_eq!(exposed, vec!["user_a"]);
+    assert!(std::fs::read(&path).unwrap().starts_with(b"SXD1"));
+}
+
+#[tokio::test]
+async fn test_dedupe_keys_are_stored_as_text_without_byte_support() {
+    let data_store = Arc::new(MockDataStore::new(false));
+    let clock = Arc::new(ManualClock::from_system_time());
+    let options = StatsigOptions {
+        data_store: Some(data_store.clone()),
+        ..options_with(ExposureDedupePersistence::data_store(), &clock)
+    };
+
+    check_gate_and_get_exposed_users(options, &["user_a"]).await;
+    assert_eq!(data_store.num_set_calls(), 1);
+}
+
+#[tokio::test]
+async fn test_instances_sharing_a_data_store_merge_their_keys() {
+    let data_store = Arc::new(MockDataStore::new_with_byte_cache(false));
+    let clock = Arc::new(ManualClock::from_system_time());
+    let options = || StatsigOptions {
+        data_store: Some(data_store.clone()),
+        ..options_with(ExposureDedupePersistence::data_store(), &clock)
+    };
+
+    let (instance_a, _) = start_instance(options()).await;
+    let (instance_b, _) = start_instance(options()).await;
+    instance_a.check_gate(&StatsigUser::with_user_id("user_a"), GATE);
+    instance_b.check_gate(&StatsigUser::with_user_id("user_b"), GATE);
+    instance_b.shutdown().await.unwrap();
+    instance_a.shutdown().await.unwrap();
+
+    let exposed =
+        check_gate_and_get_exposed_users(options(), &["user_a", "user_b", "user_c"]).await;
+    assert_eq!(exposed, vec!["user_c"]);
+}
@@ -19,10 +19,8 @@ fn test_is_sampled_user_id_only() {
     let mut sampled_count = 0;
 
     for i in 0..100_000u64 {
-        let user_data = UserData {
-            user_id: Some(dyn_value!(i)),
-            ..Default::default()
-        };
+        let mut user_data = UserData::default();
+        user_data.user_id = Some(dyn_value!(i));
 
         if run_sampling_check(100, &user_data) {
             sampled_count += 1;
@@ -38,13 +36,11 @@ fn test_is_sampled_single_custom_id_only() {
     let mut sampled_count = 0;
 
     for i in 0..100_000u64 {
-        let user_data = UserData {
-            custom_ids: Some(StatsigUserDataMap::from([(
-                "test".to_string(),
-                dyn_value!(i),
-            )])),
-            ..Default::default()
-        };
+        let mut user_data = UserData::default();
+        user_data.custom_ids = Some(StatsigUserDataMap::from([(
+            "test".to_string(),
+            dyn_value!(i),
+        )]));
 
         if run_sampling_check(100, &user_data) {
             sampled_count += 1;
@@ -60,13 +56,11 @@ fn test_is_sampled_multiple_custom_ids_only() {
     let mut sampled_count = 0;
 
     for i in 0..100_000u64 {
-        let user_data = UserData {
-            custom_ids: Some(StatsigUserDataMap::from([
-                ("test".to_string(), dyn_value!(i)),
-                ("test2".to_string(), dyn_value!(i.wrapping_mul(i))),
-            ])),
-            ..Default::default()
-        };
+        let mut user_data = UserData::default();
+        user_data.custom_ids = Some(StatsigUserDataMap::from([
+            ("test".to_string(), dyn_value!(i)),
+            ("test2".to_string(), dyn_value!(i.wrapping_mul(i))),
+        ]));
 
         if run_sampling_check(100, &user_data) {
             sampled_count += 1;
@@ -82,14 +76,12 @@ fn test_duplicate_unit_ids() {
     let mut sampled_count = 0;
 
     for i in 0..100_000u64 {
-        let user_data = UserData {
-            user_id: Some(dyn_value!(i)),
-            custom_ids: Some(StatsigUserDataMap::from([(
-                "user_id".to_string(),
-                dyn_value!(i),
-            )])),
-            ..Default::default()
-        };
+        let mut user_data = UserData::default();
+        user_data.user_id = Some(dyn_value!(i));
+        user_data.custom_ids = Some(StatsigUserDataMap::from([(
+            "user_id".to_string(),
+            dyn_value!(i),
+        )]));
 
         if run_sampling_check(100, &user_data) {
             sampled_count += 1;
@@ -102,14 +94,12 @@ fn test_duplicate_unit_ids() {
 
 #[test]
 fn test_dedupe_key_is_stable_for_repeated_same_user_input() {
-    let user_data = UserData {
-        user_id: Some(dyn_value!("user-1")),
-        custom_ids: Some(StatsigUserDataMap::from([
-            ("companyID".to_string(), dyn_value!("company-a")),
-            ("teamID".to_string(), dyn_value!(99)),
-        ])),
-        ..Default::default()
-    };
+    let mut user_data = UserData::default();
+    user_data.user_id = Some(dyn_value!("user-1"));
+    user_data.custom_ids = Some(StatsigUserDataMap::from([
+        ("companyID".to_string(), dyn_value!("company-a")),
+        ("teamID".to_string(), dyn_value!(99)),
+    ]));
 
     let key_a = ExposureSamplingKey::new(
         Some(&fake_evaluation("spec_a", "rule_a")),
@@ -133,14 +123,12 @@ fn test_dedupe_key_is_stable_for_repeated_same_user_input() {
 
 #[test]
 fn test_dedupe_key_changes_when_unit_id_changes_for_experiment_id_type() {
-    let mut user_data = UserData {
-        user_id: Some(dyn_value!("user-1")),
-        custom_ids: Some(StatsigUserDataMap::from([(
-            "companyID".to_string(),
-            dyn_value!("company-a"),
-        )])),
-        ..Default::default()
-    };
+    let mut user_data = UserData::default();
+    user_data.user_id = Some(dyn_value!("user-1"));
+    user_data.custom_ids = Some(StatsigUserDataMap::from([(
+        "companyID".to_string(),
+        dyn_value!("company-a"),
+    )]));
 
     let key_a = ExposureSamplingKey::new(
         Some(&fake_evaluation("spec_a", "rule_a")),
@@ -165,10 +153,8 @@ fn test_dedupe_key_changes_when_unit_id_changes_for_experiment_id_type() {
 
 #[test]
 fn test_dedupe_key_changes_when_custom_ids_hash_scope_changes() {
-    let mut user_data = UserData {
-        user_id: Some(dyn_value!("user-1")),
-        ..Default::default()
-    };
+    let mut user_data = UserData::default();
+    user_data.user_id = Some(dyn_value!("user-1"));
 
     let key_no_custom = ExposureSamplingKey::new(
         Some(&fake_evaluation("spec_a", "rule_a")),
@@ -0,0 +1,198 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::{json, Value};
+use statsig_rust::{
+    ExposureSamplingDecision, ExposureSamplingInput, ExposureSamplingPolicy, Statsig,
+    StatsigOptions, StatsigUser,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+const DCS_EVAL_PROJ: &str = "tests/data/eval_proj_dcs.json";
+const DCS_WITH_SAMPLING: &str = "tests/data/dcs_with_sampling.json";
+const SAMPLED_GATE: &str = "test_rule_sampling";
+
+async fn setup(
+    specs_path: &str,
+    policy: Arc<dyn ExposureSamplingPolicy>,
+) -> (Statsig, Arc<MockEventLoggingAdapter>) {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+
+    let uuid = uuid::Uuid::new_v4();
+    let statsig = Statsig::new(
+        &format!("secret-exposure_sampling_policy_{uuid}"),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(specs_path))),
+            event_logging_adapter: Some(logging_adapter.clone()),
+            exposure_sampling_policy: Some(policy),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, logging_adapter)
+}
+
+fn logged_exposures(adapter: &MockEventLoggingAdapter) -> Vec<Value> {
+    let payloads = adapter.logged_payloads.lock().unwrap();
+    payloads
+        .iter()
+        .flat_map(|p| p.events.as_array().cloned().unwrap_or_default())
+        .filter(|e| e["eventName"] == "statsig::gate_exposure")
+        .collect()
+}
+
+fn check_gate_for_users(statsig: &Statsig, gate: &str, count: usize) {
+    for i in 0..count {
+        statsig.check_gate(&StatsigUser::with_user_id(format!("user_{i}")), gate);
+    }
+}
+
+/// Always logs users with an `@statsig.com` email, and drops everyone else.
+struct InternalUsersPolicy {
+    seen: Mutex<Vec<(String, String)>>,
+}
+
+impl ExposureSamplingPolicy for InternalUsersPolicy {
+    fn sample(&self, exposure: &ExposureSamplingInput) -> ExposureSamplingDecision {
+        self.seen
+            .lock()
+            .unwrap()
+            .push((exposure.spec_name.to_string(), exposure.rule_id.to_string()));
+
+        let email = exposure
+            .user
+            .email
+            .as_ref()
+            .and_then(|e| e.string_value.as_ref());
+        match email {
+            Some(email) if email.value.ends_with("@statsig.com") => ExposureSamplingDecision::Log,
+            _ => ExposureSamplingDecision::Drop,
+        }
+    }
+}
+
+/// Logs at most `budget` exposures, and stamps how many were seen for each one logged.
+struct BudgetPolicy {
+    budget: u64,
+    seen: AtomicU64,
+}
+
+impl ExposureSamplingPolicy for BudgetPolicy {
+    fn sample(&self, _: &ExposureSamplingInput) -> ExposureSamplingDecision {
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed) + 1;
+        if seen > self.budget {
+            return ExposureSamplingDecision::Drop;
+        }
+        ExposureSamplingDecision::LogWithRate(seen)
+    }
+}
+
+/// Samples by the spec's server sampling rate, halved.
+struct SpecRatePolicy;
+
+impl ExposureSamplingPolicy for SpecRatePolicy {
+    fn sample(&self, exposure: &ExposureSamplingInput) -> ExposureSamplingDecision {
+        match exposure.spec_sampling_rate {
+            Some(rate) => ExposureSamplingDecision::SampleByHash(rate / 2),
+            None => ExposureSamplingDecision::Default,
+        }
+    }
+}
+
+struct DefaultPolicy;
+
+impl ExposureSamplingPolicy for DefaultPolicy {
+    fn sample(&self, _: &ExposureSamplingInput) -> ExposureSamplingDecision {
+        ExposureSamplingDecision::Default
+    }
+}
+
+#[tokio::test]
+async fn test_policy_always_logs_internal_users() {
+    let policy = Arc::new(InternalUsersPolicy {
+        seen: Mutex::new(Vec::new()),
+    });
+    let (statsig, logging_adapter) = setup(DCS_EVAL_PROJ, policy.clone()).await;
+
+    let mut employee = StatsigUser::with_user_id("employee");
+    employee.set_email("qa@statsig.com");
+
+    check_gate_for_users(&statsig, "test_public", 5);
+    statsig.check_gate(&employee, "test_public");
+    statsig.shutdown().await.unwrap();
+
+    let exposures = logged_exposures(&logging_adapter);
+    assert_eq!(exposures.len(), 1);
+    assert_eq!(exposures[0]["user"]["userID"], "employee");
+
+    let metadata = &exposures[0]["statsigMetadata"];
+    assert_eq!(metadata["samplingMode"], json!("policy"));
+    assert_eq!(metadata["samplingDecision"], json!("log"));
+    assert_eq!(metadata["samplingRate"], json!(1));
+
+    let seen = policy.seen.lock().unwrap();
+    assert_eq!(seen.len(), 6);
+    assert_eq!(seen[0].0, "test_public");
+    assert_eq!(seen[0].1, exposures[0]["metadata"]["ruleID"]);
+}
+
+#[tokio::test]
+async fn test_policy_budget_caps_exposures() {
+    let policy = Arc::new(BudgetPolicy {
+        budget: 3,
+        seen: AtomicU64::new(0),
+    });
+    let (statsig, logging_adapter) = setup(DCS_EVAL_PROJ, policy).await;
+
+    check_gate_for_users(&statsig, "test_public", 10);
+    statsig.shutdown().await.unwrap();
+
+    let exposures = logged_exposures(&logging_adapter);
+    let rates: Vec<&Value> = exposures
+        .iter()
+        .map(|e| &e["statsigMetadata"]["samplingRate"])
+        .collect();
+    assert_eq!(rates, vec![&json!(1), &json!(2), &json!(3)]);
+    assert_eq!(
+        exposures[0]["statsigMetadata"]["samplingDecision"],
+        json!("log_with_rate")
+    );
+}
+
+#[tokio::test]
+async fn test_policy_samples_by_hash_with_spec_rate() {
+    let (statsig, logging_adapter) = setup(DCS_WITH_SAMPLING, Arc::new(SpecRatePolicy)).await;
+
+    check_gate_for_users(&statsig, SAMPLED_GATE, 2010);
+    statsig.shutdown().await.unwrap();
+
+    // sampled at 1 in 100, so ~20 events
+    let exposures = logged_exposures(&logging_adapter);
+    assert!(exposures.len() > 5 && exposures.len() < 40);
+
+    for exposure in exposures {
+        let metadata = &exposure["statsigMetadata"];
+        assert_eq!(metadata["samplingMode"], json!("policy"));
+        assert_eq!(metadata["samplingDecision"], json!("sample_by_hash"));
+        assert_eq!(metadata["samplingRate"], json!(100));
+    }
+}
+
+#[tokio::test]
+async fn test_policy_default_uses_built_in_sampling() {
+    let (statsig, logging_adapter) = setup(DCS_WITH_SAMPLING, Arc::new(DefaultPolicy)).await;
+
+    check_gate_for_users(&statsig, SAMPLED_GATE, 2010);
+    statsig.shutdown().await.unwrap();
+
+    let exposures = logged_exposures(&logging_adapter);
+    assert!(exposures.len() > 2 && exposures.len() < 20);
+
+    // the first exposure of a rule is always logged
+    assert_eq!(exposures[0]["statsigMetadata"].get("samplingMode"), None);
+    assert_eq!(exposures[1]["statsigMetadata"]["samplingMode"], json!("on"));
+    assert_eq!(exposures[1]["statsigMetadata"]["samplingRate"], json!(201));
+}
@@ -0,0 +1,219 @@
+mod utils;
+
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::json;
+use statsig_rust::output_logger::LogLevel;
+use statsig_rust::{
+    OverrideAdapter, Statsig, StatsigFileOverrideAdapter, StatsigOptions, StatsigUser,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use utils::mock_scrapi::{Endpoint, EndpointStub, Method, MockScrapi, StubData};
+
+const POLL_INTERVAL_MS: u64 = 10;
+
+async fn setup(file_name: &str, contents: &str) -> (Statsig, StatsigUser, TempDir, String) {
+    let mock_scrapi = MockScrapi::new().await;
+    mock_scrapi
+        .stub(EndpointStub {
+            method: Method::POST,
+            response: StubData::String("{\"success\": true}".to_string()),
+            ..EndpointStub::with_endpoint(Endpoint::LogEvent)
+        })
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join(file_name).to_string_lossy().to_string();
+    std::fs::write(&file_path, contents).unwrap();
+
+    let adapter = Arc::new(StatsigFileOverrideAdapter::new(
+        &file_path,
+        Some(POLL_INTERVAL_MS),
+        None,
+    ));
+
+    let statsig = Statsig::new(
+        "secret-key",
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            output_log_level: Some(LogLevel::Debug),
+            log_event_url: Some(mock_scrapi.url_for_endpoint(Endpoint::LogEvent)),
+            override_adapter: Some(adapter),
+            ..StatsigOptions::new()
+        })),
+    );
+
+    statsig.initialize().await.unwrap();
+
+    let user = StatsigUser::with_user_id("a_user".to_string());
+    (statsig, user, dir, file_path)
+}
+
+async fn wait_for_reload() {
+    tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS * 10)).await;
+}
+
+#[tokio::test]
+async fn test_loads_json_overrides() {
+    let contents = json!({
+        "gates": { "test_public": false },
+        "dynamic_configs": { "big_number": { "value": { "foo": -1.23 } } },
+        "experiments": {
+            "experiment_with_many_params": [
+                { "value": { "a_string": "for_a_user" }, "id": "a_user" },
+                { "value": { "a_string": "for_everyone" } }
+            ]
+        },
+        "layers": { "layer_with_many_params": { "value": { "a_string": "from_file" } } }
+    });
+
+    let (statsig, user, _dir, _) = setup("overrides.json", &contents.to_string()).await;
+    let other_user = StatsigUser::with_user_id("other_user".to_string());
+
+    assert!(!statsig.check_gate(&user, "test_public"));
+    assert_eq!(
+        statsig
+            .get_dynamic_config(&user, "big_number")
+            .get_f64("foo", 0f64),
+        -1.23
+    );
+    assert_eq!(
+        statsig
+            .get_experiment(&user, "experiment_with_many_params")
+            .get_string("a_string", "ERR".to_string()),
+        "for_a_user"
+    );
+    assert_eq!(
+        statsig
+            .get_experiment(&other_user, "experiment_with_many_params")
+            .get_string("a_string", "ERR".to_string()),
+        "for_everyone"
+    );
+    assert_eq!(
+        statsig
+            .get_layer(&user, "layer_with_many_params")
+            .get_string("a_string", "ERR".to_string()),
+        "from_file"
+    );
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_loads_yaml_overrides() {
+    let contents = r#"
+gates:
+  test_public:
+    - value: false
+      conditions:
+        - field: email
+          operator: ends_with
+          values: ["@ourco.com"]
+"#;
+
+    let (statsig, user, _dir, _) = setup("overrides.yaml", contents).await;
+
+    let mut employee = StatsigUser::with_user_id("employee".to_string());
+    employee.set_email("qa@ourco.com");
+
+    assert!(!statsig.check_gate(&employee, "test_public"));
+    assert!(statsig.check_gate(&user, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_hot_reloads_on_change() {
+    let (statsig, user, _dir, file_path) =
+        setup("overrides.json", r#"{"gates": {"test_public": false}}"#).await;
+
+    assert!(!statsig.check_gate(&user, "test_public"));
+
+    std::fs::write(&file_path, r#"{"gates": {}}"#).unwrap();
+    wait_for_reload().await;
+
+    assert!(statsig.check_gate(&user, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_keeps_previous_overrides_on_parse_error() {
+    let (statsig, user, _dir, file_path) =
+        setup("overrides.json", r#"{"gates": {"test_public": false}}"#).await;
+
+    std::fs::write(&file_path, r#"{"gates": {"test_public": "#).unwrap();
+    wait_for_reload().await;
+    assert!(!statsig.check_gate(&user, "test_public"));
+
+    std::fs::write(&file_path, r#"{"gates": {"test_public": "not_a_bool"}}"#).unwrap();
+    wait_for_reload().await;
+    assert!(!statsig.check_gate(&user, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_runtime_overrides_take_precedence_and_survive_reload() {
+    let (statsig, user, _dir, file_path) =
+        setup("overrides.json", r#"{"gates": {"test_public": false}}"#).await;
+
+    statsig.override_gate("test_public", true, None);
+    assert!(statsig.check_gate(&user, "test_public"));
+
+    std::fs::write(
+        &file_path,
+        r#"{"gates": {"test_public": false, "x": true}}"#,
+    )
+    .unwrap();
+    wait_for_reload().await;
+    assert!(statsig.check_gate(&user, "test_public"));
+
+    statsig.remove_gate_override("test_public", None);
+    assert!(!statsig.check_gate(&user, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_missing_file_has_no_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join("missing.json");
+    let adapter = StatsigFileOverrideAdapter::new(&file_path.to_string_lossy(), None, None);
+
+    assert!(adapter.reload().is_err());
+    assert!(adapter.list_overrides().is_empty());
+}
+
+#[test]
+fn test_tier_conditions_use_the_options_environment() {
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join("overrides.json");
+    let contents = json!({
+        "gates": {
+            "a_gate": [{
+                "value": true,
+                "conditions": [{ "field": "tier", "operator": "equals", "values": ["staging"] }]
+            }]
+        }
+    });
+    std::fs::write(&file_path, contents.to_string()).unwrap();
+
+    let user = StatsigUser::with_user_id("a_user");
+    let adapter_for = |environment: &str| {
+        let options = StatsigOptions {
+            environment: Some(environment.to_string()),
+            ..StatsigOptions::new()
+        };
+        StatsigFileOverrideAdapter::new(&file_path.to_string_lossy(), None, Some(&options))
+    };
+
+    let staging = adapter_for("staging");
+    assert!(staging.get_gate_override(&user, "a_gate", &mut Default::default()));
+
+    let production = adapter_for("production");
+    assert!(!production.get_gate_override(&user, "a_gate", &mut Default::default()));
+}
@@ -0,0 +1,186 @@
+#[cfg(all(test, feature = "with_grpc"))]
+pub mod forward_proxy_adapters_tests {
+    use mock_forward_proxy::{wait_one_ms, MockForwardProxy};
+    use parking_lot::Mutex;
+    use serde_json::json;
+    use sigstat_grpc::*;
+    use statsig_rust::log_event_payload::{LogEventPayload, LogEventRequest};
+    use statsig_rust::{
+        EventLoggingAdapter, IdListMetadata, IdListUpdate, IdListsAdapter, IdListsUpdateListener,
+        SpecAdapterConfig, SpecsAdapterType, StatsigGrpcEventLoggingAdapter,
+        StatsigGrpcIdListsAdapter, StatsigOptions, StatsigRuntime,
+    };
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct TestIdListsListener {
+        metadata: Mutex<HashMap<String, IdListMetadata>>,
+        changesets: Mutex<HashMap<String, String>>,
+    }
+
+    impl IdListsUpdateListener for TestIdListsListener {
+        fn get_current_id_list_metadata(&self) -> HashMap<String, IdListMetadata> {
+            self.metadata.lock().clone()
+        }
+
+        fn did_receive_id_list_updates(&self, updates: HashMap<String, IdListUpdate>) {
+            let mut metadata = self.metadata.lock();
+            let mut changesets = self.changesets.lock();
+            metadata.retain(|name, _| updates.contains_key(name));
+
+            for (name, update) in updates {
+                if let Some(changeset) = update.raw_changeset {
+                    changesets
+                        .entry(name.clone())
+                        .or_default()
+                        .push_str(&changeset);
+                }
+                metadata.insert(name, update.new_metadata);
+            }
+        }
+    }
+
+    fn create_config(mock_proxy: &MockForwardProxy) -> SpecAdapterConfig {
+        SpecAdapterConfig {
+            adapter_type: SpecsAdapterType::NetworkGrpcWebsocket,
+            specs_url: Some(format!("http://{}", mock_proxy.proxy_address)),
+            init_timeout_ms: 3000,
+            authentication_mode: None,
+            ca_cert_path: None,
+            client_cert_path: None,
+            client_key_path: None,
+            domain_name: None,
+        }
+    }
+
+    fn create_log_event_request(event_count: usize) -> LogEventRequest {
+        let events = (0..event_count)
+            .map(|i| json!({"eventName": format!("event_{i}"), "time": 1}))
+            .collect::<Vec<_>>();
+
+        LogEventRequest {
+            payload: LogEventPayload {
+                events: json!(events),
+                statsig_metadata: json!({"sdkType": "statsig-server-core"}),
+            },
+            event_count: event_count as u64,
+            retries: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_id_lists_sync_through_proxy() {
+        let statsig_rt = StatsigRuntime::get_runtime();
+        let mock_proxy = MockForwardProxy::spawn().await;
+        mock_proxy.stub_id_list("list_a", "file_1", 1, "+a\n+b\n");
+
+        let options = StatsigOptions {
+            id_lists_sync_interval_ms: Some(10),
+            ..StatsigOptions::default()
+        };
+        let adapter = Arc::new(StatsigGrpcIdListsAdapter::new(
+            "secret-key",
+            &create_config(&mock_proxy),
+            &options,
+        ));
+        let listener = Arc::new(TestIdListsListener::default());
+
+        adapter
+            .clone()
+            .start(&statsig_rt, listener.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            listener.changesets.lock().get("list_a").unwrap(),
+            "+a\n+b\n"
+        );
+
+        mock_proxy.stub_id_list("list_a", "file_1", 1, "+a\n+b\n-a\n");
+        adapter
+            .clone()
+            .schedule_background_sync(&statsig_rt)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            listener.changesets.lock().get("list_a").unwrap(),
+            "+a\n+b\n-a\n"
+        );
+
+        let requests = mock_proxy.get_received_id_list_file_requests();
+        assert_eq!(requests[0].range_start, 0);
+        assert_eq!(requests[1].range_start, 6);
+        assert_eq!(requests[1].file_id.as_deref(), Some("file_1"));
+
+        adapter.shutdown(Duration::from_millis(10)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_id_lists_removed_from_manifest() {
+        let statsig_rt = StatsigRuntime::get_runtime();
+        let mock_proxy = MockForwardProxy::spawn().await;
+        mock_proxy.stub_id_list("list_a", "file_1", 1, "+a\n");
+        mock_proxy.stub_id_list("list_b", "file_2", 1, "+b\n");
+
+        let adapter = Arc::new(StatsigGrpcIdListsAdapter::new(
+            "secret-key",
+            &create_config(&mock_proxy),
+            &StatsigOptions::default(),
+        ));
+        let listener = Arc::new(TestIdListsListener::default());
+        adapter
+            .clone()
+            .start(&statsig_rt, listener.clone())
+            .await
+            .unwrap();
+        assert_eq!(listener.metadata.lock().len(), 2);
+
+        mock_proxy.remove_stubbed_id_list("list_b");
+        adapter
+            .clone()
+            .start(&statsig_rt, listener.clone())
+            .await
+            .unwrap();
+
+        let metadata = listener.metadata.lock();
+        assert!(metadata.contains_key("list_a"));
+        assert!(!metadata.contains_key("list_b"));
+    }
+
+    #[tokio::test]
+    async fn test_log_events_are_streamed_in_chunks() {
+        let mock_proxy = MockForwardProxy::spawn().await;
+        let adapter =
+            StatsigGrpcEventLoggingAdapter::new("secret-key", &create_config(&mock_proxy))
+                .with_chunk_size(2);
+
+        let result = adapter.log_events(create_log_event_request(5)).await;
+        assert!(result.unwrap());
+
+        let batches = mock_proxy.get_received_log_event_requests();
+        let counts = batches.iter().map(|b| b.event_count).collect::<Vec<_>>();
+        assert_eq!(counts, vec![2, 2, 1]);
+        assert!(batches.iter().all(|b| b.sdk_key == "secret-key"));
+
+        let payload: LogEventPayload = serde_json::from_str(&batches[2].payload).unwrap();
+        assert_eq!(payload.events[0]["eventName"], "event_4");
+        assert_eq!(payload.statsig_metadata["sdkType"], "statsig-server-core");
+    }
+
+    #[tokio::test]
+    async fn test_log_events_fails_when_proxy_is_down() {
+        let mock_proxy = MockForwardProxy::spawn().await;
+        let adapter =
+            StatsigGrpcEventLoggingAdapter::new("secret-key", &create_config(&mock_proxy));
+
+        mock_proxy.stop().await;
+        wait_one_ms().await;
+
+        let result = adapter.log_events(create_log_event_request(1)).await;
+        assert!(result.is_err());
+        assert!(mock_proxy.get_received_log_event_requests().is_empty());
+    }
+}
@@ -47,6 +47,7 @@ pub mod specs_adapter_tests {
                 spec: "bg_sync".to_string(),
                 last_updated: 123,
                 zstd_dict_id: None,
+                has_updates: None,
             }))
             .await;
 
@@ -73,6 +74,7 @@ pub mod specs_adapter_tests {
                 spec: "first_sync".to_string(),
                 last_updated: 123,
                 zstd_dict_id: None,
+                has_updates: None,
             }))
             .await;
 
@@ -87,6 +89,7 @@ pub mod specs_adapter_tests {
                 spec: "reconnected_sync".to_string(),
                 last_updated: 123,
                 zstd_dict_id: None,
+                has_updates: None,
             }))
             .await;
 
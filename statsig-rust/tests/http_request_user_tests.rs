@@ -0,0 +1,182 @@
+use http::Request;
+use statsig_rust::{
+    HeaderPolicy, IpPlacement, StatsigUser, StatsigUserBuilder, UserAgentClientHints,
+};
+use std::net::SocketAddr;
+
+fn build_user(request: &Request<()>, peer: &str, policy: &HeaderPolicy) -> StatsigUser {
+    let peer_addr = (!peer.is_empty()).then(|| peer.parse::<SocketAddr>().unwrap());
+    StatsigUserBuilder::from_http_request(request, peer_addr, policy)
+        .user_id(Some("a_user"))
+        .build()
+}
+
+fn request_with_headers(headers: &[(&str, &str)]) -> Request<()> {
+    let mut builder = Request::builder().uri("/");
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    builder.body(()).unwrap()
+}
+
+#[test]
+fn test_fills_fields_from_default_headers() {
+    let request = request_with_headers(&[
+        (
+            "User-Agent",
+            "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X)",
+        ),
+        ("X-Forwarded-For", "203.0.113.7"),
+        ("Accept-Language", "fr;q=0.8, de-CH, en;q=0.5"),
+        ("CF-IPCountry", "ch"),
+        (
+            "Sec-CH-UA",
+            r#""Chromium";v="124", "Google Chrome";v="124""#,
+        ),
+        ("Sec-CH-UA-Platform", r#""iOS""#),
+        ("Sec-CH-UA-Mobile", "?1"),
+    ]);
+
+    let policy = HeaderPolicy::new().with_trusted_proxies(&["10.0.0.0/8"]);
+    let user = build_user(&request, "10.0.0.1:443", &policy);
+
+    assert_eq!(user.get_user_id(), Some("a_user"));
+    assert_eq!(
+        user.get_user_agent(),
+        Some("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X)")
+    );
+    assert_eq!(user.get_ip(), Some("203.0.113.7"));
+    assert_eq!(user.get_locale(), Some("de-CH"));
+    assert_eq!(user.get_country(), Some("CH"));
+    assert_eq!(
+        user.get_user_agent_client_hints(),
+        Some(&UserAgentClientHints {
+            brands: Some(r#""Chromium";v="124", "Google Chrome";v="124""#.to_string()),
+            platform: Some(r#""iOS""#.to_string()),
+            mobile: Some(true),
+            ..UserAgentClientHints::default()
+        })
+    );
+}
+
+#[test]
+fn test_missing_headers_leave_fields_unset() {
+    let request = request_with_headers(&[("CF-IPCountry", "XX")]);
+
+    let user = build_user(&request, "", &HeaderPolicy::new());
+
+    assert_eq!(user.get_user_agent(), None);
+    assert_eq!(user.get_ip(), None);
+    assert_eq!(user.get_locale(), None);
+    assert_eq!(user.get_country(), None);
+    assert_eq!(user.get_user_agent_client_hints(), None);
+}
+
+#[test]
+fn test_client_ip_skips_trusted_proxies() {
+    let headers = [("X-Forwarded-For", "198.51.100.1, 203.0.113.7, 10.1.2.3")];
+
+    // the nearest hop is the client unless it is a trusted proxy
+    let request = request_with_headers(&headers);
+    assert_eq!(
+        build_user(&request, "10.0.0.1:443", &HeaderPolicy::new()).get_ip(),
+        Some("10.0.0.1")
+    );
+
+    let policy = HeaderPolicy::new().with_trusted_proxies(&["10.0.0.0/8"]);
+    assert_eq!(
+        build_user(&request, "10.0.0.1:443", &policy).get_ip(),
+        Some("203.0.113.7")
+    );
+
+    // an untrusted peer is the client, whatever the header says
+    assert_eq!(
+        build_user(&request, "192.0.2.1:443", &policy).get_ip(),
+        Some("192.0.2.1")
+    );
+
+    // a spoofed left-most entry is never used
+    let policy = HeaderPolicy::new().with_trusted_proxies(&["10.0.0.0/8", "203.0.113.7"]);
+    assert_eq!(
+        build_user(&request, "10.0.0.1:443", &policy).get_ip(),
+        Some("198.51.100.1")
+    );
+
+    let request =
+        request_with_headers(&[("X-Forwarded-For", "[2001:db8::1]:443, ::ffff:10.9.9.9")]);
+    let policy = HeaderPolicy::new().with_trusted_proxies(&["10.0.0.0/8"]);
+    assert_eq!(
+        build_user(&request, "10.0.0.1:443", &policy).get_ip(),
+        Some("2001:db8::1")
+    );
+}
+
+#[test]
+fn test_forwarded_for_is_ignored_without_peer() {
+    let request = request_with_headers(&[("X-Forwarded-For", "203.0.113.7, 10.1.2.3")]);
+
+    // the header cannot be trusted when nothing says a trusted proxy sent it
+    let policy = HeaderPolicy::new().with_trusted_proxies(&["10.0.0.0/8"]);
+    assert_eq!(build_user(&request, "", &policy).get_ip(), None);
+}
+
+#[test]
+fn test_forwarded_for_is_ignored_without_trusted_proxies() {
+    let request = request_with_headers(&[("X-Forwarded-For", "203.0.113.7")]);
+
+    assert_eq!(
+        build_user(&request, "", &HeaderPolicy::new()).get_ip(),
+        None
+    );
+    assert_eq!(
+        build_user(&request, "198.51.100.1:5000", &HeaderPolicy::new()).get_ip(),
+        Some("198.51.100.1")
+    );
+}
+
+#[test]
+fn test_custom_header_names() {
+    let request = request_with_headers(&[
+        ("X-Client-UA", "custom-agent/1.0"),
+        ("X-Real-IP", "203.0.113.9"),
+        ("X-Locale", "pt-BR"),
+        ("X-Geo-Country", "br"),
+        ("CF-IPCountry", "US"),
+    ]);
+
+    let policy = HeaderPolicy::new()
+        .with_user_agent_header("x-client-ua")
+        .with_forwarded_for_header("x-real-ip")
+        .with_locale_header("x-locale")
+        .with_country_headers(&["x-geo-country"])
+        .with_trusted_proxies(&["10.0.0.0/8"]);
+    let user = build_user(&request, "10.0.0.1:443", &policy);
+
+    assert_eq!(user.get_user_agent(), Some("custom-agent/1.0"));
+    assert_eq!(user.get_ip(), Some("203.0.113.9"));
+    assert_eq!(user.get_locale(), Some("pt-BR"));
+    assert_eq!(user.get_country(), Some("BR"));
+}
+
+#[test]
+fn test_ip_placement() {
+    let request = request_with_headers(&[]);
+
+    let policy = HeaderPolicy::new().with_ip_placement(IpPlacement::PrivateAttribute);
+    let user = build_user(&request, "203.0.113.7:5000", &policy);
+    assert_eq!(user.get_ip(), None);
+    let private_attributes = user.get_private_attributes().unwrap();
+    assert_eq!(
+        private_attributes["ip"]
+            .string_value
+            .as_ref()
+            .unwrap()
+            .value,
+        "203.0.113.7"
+    );
+
+    let policy = HeaderPolicy::new().with_ip_placement(IpPlacement::Omit);
+    let user = build_user(&request, "203.0.113.7:5000", &policy);
+    assert_eq!(user.get_ip(), None);
+    assert!(user.get_private_attributes().is_none());
+}
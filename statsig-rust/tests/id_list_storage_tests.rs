@@ -0,0 +1,234 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use statsig_rust::hashing::HashUtil;
+use statsig_rust::{
+    IdListMetadata, IdListStorageConfig, IdListStorageMode, IdListUpdate, IdListsAdapter,
+    IdListsUpdateListener, Statsig, StatsigErr, StatsigOptions, StatsigRuntime, StatsigUser,
+    StatsigUserBuilder,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Default)]
+struct ManualIdListsAdapter {
+    listener: Mutex<Option<Arc<dyn IdListsUpdateListener + Send + Sync>>>,
+}
+
+impl ManualIdListsAdapter {
+    fn push(&self, file_id: &str, changeset: String) {
+        let update = IdListUpdate {
+            new_metadata: IdListMetadata {
+                name: "company_id_list".to_string(),
+                url: "https://fake.statsig.com/company_id_list".to_string(),
+                file_id: Some(file_id.to_string()),
+                size: changeset.len() as u64,
+                creation_time: 1,
+            },
+            raw_changeset: Some(changeset),
+        };
+
+        let listener = self.listener.lock().clone().unwrap();
+        listener
+            .did_receive_id_list_updates(HashMap::from([("company_id_list".to_string(), update)]));
+    }
+}
+
+#[async_trait]
+impl IdListsAdapter for ManualIdListsAdapter {
+    async fn start(
+        self: Arc<Self>,
+        _statsig_runtime: &Arc<StatsigRuntime>,
+        listener: Arc<dyn IdListsUpdateListener + Send + Sync>,
+    ) -> Result<(), StatsigErr> {
+        *self.listener.lock() = Some(listener);
+        Ok(())
+    }
+
+    async fn shutdown(&self, _timeout: Duration) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
+    async fn schedule_background_sync(
+        self: Arc<Self>,
+        _statsig_runtime: &Arc<StatsigRuntime>,
+    ) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
+    fn get_type_name(&self) -> String {
+        "ManualIdListsAdapter".to_string()
+    }
+}
+
+async fn setup(config: IdListStorageConfig) -> (Statsig, Arc<ManualIdListsAdapter>) {
+    let adapter = Arc::new(ManualIdListsAdapter::default());
+    let statsig = Statsig::new(
+        "secret-id_list_storage_tests",
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+            id_lists_adapter: Some(adapter.clone()),
+            enable_id_lists: Some(true),
+            id_list_storage: Some(config),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, adapter)
+}
+
+// "marcos_1" is in the user ID half of the "test_id_list" gate, so the gate follows the company list
+fn user_for_company(company_id: &str) -> StatsigUser {
+    StatsigUserBuilder::new_with_user_id("marcos_1".to_string())
+        .custom_ids(Some(HashMap::from([(
+            "companyID".to_string(),
+            company_id.to_string(),
+        )])))
+        .build()
+}
+
+fn lookup_id(id: &str) -> String {
+    HashUtil::new().sha256(id).chars().take(8).collect()
+}
+
+async fn assert_storage_mode_behaves_like_a_set(mode: IdListStorageMode) {
+    let (statsig, adapter) = setup(IdListStorageConfig::new(mode)).await;
+
+    let changeset = format!(
+        "+{}\n+{}\n+{}\n+not_a_lookup_id\n",
+        lookup_id("company_a"),
+        lookup_id("company_b"),
+        lookup_id("company_c"),
+    );
+    adapter.push("file_1", changeset.clone());
+
+    assert!(statsig.check_gate(&user_for_company("company_a"), "test_id_list"));
+    assert!(statsig.check_gate(&user_for_company("company_b"), "test_id_list"));
+    assert!(!statsig.check_gate(&user_for_company("company_d"), "test_id_list"));
+
+    // appended changes: the last operation on an ID wins
+    let appended = format!(
+        "{changeset}-{b}\n+{d}\n-{d}\n-{c}\n+{c}\n",
+        b = lookup_id("company_b"),
+        c = lookup_id("company_c"),
+        d = lookup_id("company_d"),
+    );
+    adapter.push("file_1", appended[changeset.len()..].to_string());
+
+    assert!(statsig.check_gate(&user_for_company("company_a"), "test_id_list"));
+    assert!(!statsig.check_gate(&user_for_company("company_b"), "test_id_list"));
+    assert!(statsig.check_gate(&user_for_company("company_c"), "test_id_list"));
+    assert!(!statsig.check_gate(&user_for_company("company_d"), "test_id_list"));
+
+    // a new file replaces the list
+    adapter.push("file_2", format!("+{}\n", lookup_id("company_d")));
+
+    assert!(!statsig.check_gate(&user_for_company("company_a"), "test_id_list"));
+    assert!(statsig.check_gate(&user_for_company("company_d"), "test_id_list"));
+}
+
+#[tokio::test]
+async fn test_hash_set_storage() {
+    assert_storage_mode_behaves_like_a_set(IdListStorageMode::HashSet).await;
+}
+
+#[tokio::test]
+async fn test_compact_storage() {
+    assert_storage_mode_behaves_like_a_set(IdListStorageMode::Compact).await;
+}
+
+#[tokio::test]
+async fn test_mmap_storage() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_storage_mode_behaves_like_a_set(IdListStorageMode::Mmap {
+        directory: dir.path().to_string_lossy().to_string(),
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_mmap_storage_merges_many_changesets() {
+    let dir = tempfile::tempdir().unwrap();
+    let (statsig, adapter) = setup(IdListStorageConfig::new(IdListStorageMode::Mmap {
+        directory: dir.path().to_string_lossy().to_string(),
+    }))
+    .await;
+
+    let company = |i: usize| format!("company_{i}");
+    for round in 0..4 {
+        let mut changeset = String::new();
+        for i in (round..200).step_by(4) {
+            let id = lookup_id(&company(i));
+            changeset.push_str(&format!("+{id}\n+long_id_{i}_not_packed\n"));
+        }
+        for i in (0..200).step_by(10) {
+            changeset.push_str(&format!("-{}\n", lookup_id(&company(i))));
+        }
+        adapter.push("file_1", changeset);
+    }
+
+    for i in 0..200 {
+        let expected = i % 10 != 0;
+        assert_eq!(
+            statsig.check_gate(&user_for_company(&company(i)), "test_id_list"),
+            expected,
+            "{}",
+            company(i)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_mmap_storage_falls_back_to_memory_when_directory_is_missing() {
+    assert_storage_mode_behaves_like_a_set(IdListStorageMode::Mmap {
+        directory: "/this/directory/does/not/exist".to_string(),
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_bloom_storage() {
+    assert_storage_mode_behaves_like_a_set(IdListStorageMode::Bloom {
+        false_positive_rate: 0.0001,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_bloom_storage_false_positive_rate() {
+    let config = IdListStorageConfig::default().with_list(
+        "company_id_list",
+        IdListStorageMode::Bloom {
+            false_positive_rate: 0.01,
+        },
+    );
+    let (statsig, adapter) = setup(config).await;
+
+    let changeset: String = (0..5000)
+        .map(|i| format!("+{}\n", lookup_id(&format!("in_list_{i}"))))
+        .collect();
+    adapter.push("file_1", changeset);
+
+    for i in 0..5000 {
+        let user = user_for_company(&format!("in_list_{i}"));
+        assert!(statsig.check_gate(&user, "test_id_list"));
+    }
+
+    let false_positives = (0..5000)
+        .filter(|i| {
+            let user = user_for_company(&format!("not_in_list_{i}"));
+            statsig.check_gate(&user, "test_id_list")
+        })
+        .count();
+
+    // 1% of 5000 is 50, leave room for variance
+    assert!(false_positives < 100, "{false_positives} false positives");
+}
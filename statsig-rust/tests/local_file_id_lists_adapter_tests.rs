@@ -0,0 +1,161 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use statsig_rust::hashing::HashUtil;
+use statsig_rust::{
+    Statsig, StatsigLocalFileIdListsAdapter, StatsigOptions, StatsigUser, StatsigUserBuilder,
+};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+async fn setup(dir: &TempDir) -> (Statsig, Arc<StatsigLocalFileIdListsAdapter>) {
+    let adapter = Arc::new(
+        StatsigLocalFileIdListsAdapter::new(&dir.path().to_string_lossy())
+            .with_watch_interval_ms(10),
+    );
+    let statsig = Statsig::new(
+        "secret-local_file_id_lists_tests",
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+            id_lists_adapter: Some(adapter.clone()),
+            enable_id_lists: Some(true),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, adapter)
+}
+
+// "marcos_1" is in the user ID half of the "test_id_list" gate, so the gate follows the company list
+fn user_for_company(company_id: &str) -> StatsigUser {
+    StatsigUserBuilder::new_with_user_id("marcos_1".to_string())
+        .custom_ids(Some(HashMap::from([(
+            "companyID".to_string(),
+            company_id.to_string(),
+        )])))
+        .build()
+}
+
+fn lookup_id(id: &str) -> String {
+    HashUtil::new().sha256(id).chars().take(8).collect()
+}
+
+fn append(path: &Path, content: &str) {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+}
+
+fn is_in_list(statsig: &Statsig, company_id: &str) -> bool {
+    statsig.check_gate(&user_for_company(company_id), "test_id_list")
+}
+
+#[tokio::test]
+async fn test_loads_lists_on_initialize() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("company_id_list.txt"),
+        format!("{}\n+{}\n", lookup_id("company_a"), lookup_id("company_b")),
+    )
+    .unwrap();
+
+    let (statsig, _) = setup(&dir).await;
+
+    assert!(is_in_list(&statsig, "company_a"));
+    assert!(is_in_list(&statsig, "company_b"));
+    assert!(!is_in_list(&statsig, "company_c"));
+}
+
+#[tokio::test]
+async fn test_applies_appended_changesets() {
+    let dir = tempfile::tempdir().unwrap();
+    let changeset_path = dir.path().join("company_id_list.changeset");
+    fs::write(
+        dir.path().join("company_id_list"),
+        format!("{}\n", lookup_id("company_a")),
+    )
+    .unwrap();
+    append(&changeset_path, &format!("+{}\n", lookup_id("company_b")));
+
+    let (statsig, adapter) = setup(&dir).await;
+    assert!(is_in_list(&statsig, "company_a"));
+    assert!(is_in_list(&statsig, "company_b"));
+
+    append(
+        &changeset_path,
+        &format!("-{}\n+{}\n", lookup_id("company_a"), lookup_id("company_c")),
+    );
+    // a partial line is left until it is complete
+    append(&changeset_path, &format!("+{}", lookup_id("company_d")));
+    adapter.resync_from_directory().unwrap();
+
+    assert!(!is_in_list(&statsig, "company_a"));
+    assert!(is_in_list(&statsig, "company_b"));
+    assert!(is_in_list(&statsig, "company_c"));
+    assert!(!is_in_list(&statsig, "company_d"));
+
+    append(&changeset_path, "\n");
+    adapter.resync_from_directory().unwrap();
+    assert!(is_in_list(&statsig, "company_d"));
+
+    // truncating the changeset reloads the list from scratch
+    fs::write(&changeset_path, "").unwrap();
+    adapter.resync_from_directory().unwrap();
+
+    assert!(is_in_list(&statsig, "company_a"));
+    assert!(!is_in_list(&statsig, "company_b"));
+    assert!(!is_in_list(&statsig, "company_c"));
+}
+
+#[tokio::test]
+async fn test_watches_for_replaced_and_removed_lists() {
+    let dir = tempfile::tempdir().unwrap();
+    let list_path = dir.path().join("company_id_list.txt");
+    fs::write(&list_path, format!("{}\n", lookup_id("company_a"))).unwrap();
+
+    let (statsig, _) = setup(&dir).await;
+    assert!(is_in_list(&statsig, "company_a"));
+
+    fs::write(&list_path, format!("{}\n", lookup_id("company_b"))).unwrap();
+    assert_eventually!(|| !is_in_list(&statsig, "company_a") && is_in_list(&statsig, "company_b"));
+
+    fs::remove_file(&list_path).unwrap();
+    assert_eventually!(|| !is_in_list(&statsig, "company_b"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_missing_directory_fails_to_start() {
+    let adapter = Arc::new(StatsigLocalFileIdListsAdapter::new(
+        "/this/directory/does/not/exist",
+    ));
+    let statsig = Statsig::new(
+        "secret-local_file_id_lists_tests_missing",
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+            id_lists_adapter: Some(adapter),
+            enable_id_lists: Some(true),
+            ..StatsigOptions::new()
+        })),
+    );
+
+    let details = statsig.initialize_with_details().await.unwrap();
+    assert!(!is_in_list(&statsig, "company_a"));
+    assert!(details.init_success);
+}
@@ -4,13 +4,21 @@ use crate::utils::mock_specs_adapter::MockSpecsAdapter;
 use serde_json::json;
 use statsig_rust::output_logger::LogLevel;
 use statsig_rust::{
-    OverrideAdapter, Statsig, StatsigLocalOverrideAdapter, StatsigOptions, StatsigUser,
+    Clock, ManualClock, OverrideAdapter, OverrideCondition, OverrideMatcher, OverrideSpecType,
+    Statsig, StatsigLocalOverrideAdapter, StatsigOptions, StatsigUser,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use utils::mock_scrapi::{Endpoint, EndpointStub, Method, MockScrapi, StubData};
 
 async fn setup() -> (Statsig, StatsigUser, Arc<StatsigLocalOverrideAdapter>) {
+    setup_with_adapter(StatsigLocalOverrideAdapter::new()).await
+}
+
+async fn setup_with_adapter(
+    adapter: StatsigLocalOverrideAdapter,
+) -> (Statsig, StatsigUser, Arc<StatsigLocalOverrideAdapter>) {
     let mock_scrapi = MockScrapi::new().await;
     mock_scrapi
         .stub(EndpointStub {
@@ -20,7 +28,7 @@ async fn setup() -> (Statsig, StatsigUser, Arc<StatsigLocalOverrideAdapter>) {
         })
         .await;
 
-    let adapter = Arc::new(StatsigLocalOverrideAdapter::new());
+    let adapter = Arc::new(adapter);
     let user = StatsigUser::with_user_id("a_user".to_string());
     let statsig = Statsig::new(
         "secret-key",
@@ -898,3 +906,177 @@ async fn test_remove_all_overrides() {
 
     statsig.shutdown().await.unwrap();
 }
+
+#[tokio::test]
+async fn test_gate_override_by_email_suffix() {
+    let (statsig, user, adapter) = setup().await;
+
+    let mut employee = StatsigUser::with_user_id("employee".to_string());
+    employee.set_email("qa@ourco.com");
+
+    let mut customer = StatsigUser::with_user_id("customer".to_string());
+    customer.set_email("someone@gmail.com");
+
+    adapter.override_gate_with_matcher(
+        "test_public",
+        false,
+        OverrideMatcher::new().with_condition(OverrideCondition::ends_with("email", "@OurCo.com")),
+    );
+
+    assert!(!statsig.check_gate(&employee, "test_public"));
+    assert!(statsig.check_gate(&customer, "test_public"));
+    assert!(statsig.check_gate(&user, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_experiment_override_by_custom_field() {
+    let (statsig, user, adapter) = setup().await;
+
+    let mut beta_user = StatsigUser::with_user_id("beta".to_string());
+    beta_user.set_custom(HashMap::from([("plan".to_string(), "beta")]));
+
+    adapter.override_experiment_with_matcher(
+        "experiment_with_many_params",
+        HashMap::from([("a_string".to_string(), json!("overridden_value"))]),
+        OverrideMatcher::new().with_condition(OverrideCondition::equals("plan", "beta")),
+    );
+
+    let beta_value = statsig
+        .get_experiment(&beta_user, "experiment_with_many_params")
+        .get_string("a_string", "ERR".to_string());
+    let other_value = statsig
+        .get_experiment(&user, "experiment_with_many_params")
+        .get_string("a_string", "ERR".to_string());
+
+    assert_eq!(beta_value, "overridden_value");
+    assert_eq!(other_value, "test_2");
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_environment_tier_override() {
+    let (statsig, user, _) = setup().await;
+
+    let dev_adapter =
+        StatsigLocalOverrideAdapter::with_environment_tier(Some("development".into()));
+    let prod_adapter =
+        StatsigLocalOverrideAdapter::with_environment_tier(Some("production".into()));
+
+    for adapter in [&dev_adapter, &prod_adapter] {
+        adapter.override_gate_with_matcher(
+            "test_public",
+            false,
+            OverrideMatcher::new()
+                .with_condition(OverrideCondition::environment_tier("development")),
+        );
+    }
+
+    let mut result = Default::default();
+    assert!(dev_adapter.get_gate_override(&user, "test_public", &mut result));
+
+    let mut result = Default::default();
+    assert!(!prod_adapter.get_gate_override(&user, "test_public", &mut result));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_id_override_takes_precedence_over_attribute_override() {
+    let (statsig, _, adapter) = setup().await;
+
+    let mut user = StatsigUser::with_user_id("a_user".to_string());
+    user.set_email("qa@ourco.com");
+
+    adapter.override_gate_with_matcher(
+        "test_public",
+        false,
+        OverrideMatcher::new().with_condition(OverrideCondition::ends_with("email", "@ourco.com")),
+    );
+    adapter.override_gate("test_public", true, Some("a_user"));
+
+    assert!(statsig.check_gate(&user, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_expired_override_is_ignored() {
+    let clock = Arc::new(ManualClock::from_system_time());
+    let (statsig, user, adapter) =
+        setup_with_adapter(StatsigLocalOverrideAdapter::new().with_clock(clock.clone())).await;
+
+    adapter.override_gate_with_matcher("test_public", false, OverrideMatcher::new().expires_at(1));
+    assert!(statsig.check_gate(&user, "test_public"));
+    assert!(statsig.list_overrides().is_empty());
+
+    adapter.override_gate_with_matcher(
+        "test_public",
+        false,
+        OverrideMatcher::new().expires_in(Duration::from_millis(200)),
+    );
+    assert!(!statsig.check_gate(&user, "test_public"));
+
+    clock.advance(Duration::from_millis(199));
+    assert!(!statsig.check_gate(&user, "test_public"));
+
+    clock.advance(Duration::from_millis(1));
+    assert!(statsig.check_gate(&user, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_remove_attribute_override() {
+    let (statsig, _, adapter) = setup().await;
+
+    let mut employee = StatsigUser::with_user_id("employee".to_string());
+    employee.set_email("qa@ourco.com");
+
+    let matcher =
+        OverrideMatcher::new().with_condition(OverrideCondition::ends_with("email", "@ourco.com"));
+    adapter.override_gate_with_matcher("test_public", false, matcher.clone());
+    assert!(!statsig.check_gate(&employee, "test_public"));
+
+    statsig.remove_gate_override_with_matcher("test_public", &matcher);
+    assert!(statsig.check_gate(&employee, "test_public"));
+
+    statsig.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_list_overrides() {
+    let clock = Arc::new(ManualClock::from_system_time());
+    let (statsig, _, _) =
+        setup_with_adapter(StatsigLocalOverrideAdapter::new().with_clock(clock.clone())).await;
+
+    let matcher = OverrideMatcher::for_id("a_user")
+        .with_condition(OverrideCondition::equals("country", "NZ"))
+        .expires_in(Duration::from_secs(60));
+
+    statsig.override_gate("test_public", false, None);
+    statsig.override_experiment_by_group_name_with_matcher(
+        "experiment_with_many_params",
+        "Control",
+        matcher.clone(),
+    );
+
+    let mut listed = statsig.list_overrides();
+    listed.sort_by(|a, b| a.name.cmp(&b.name));
+
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0].spec_type, OverrideSpecType::Experiment);
+    // listed with the expiry counted from when it was set
+    assert_eq!(
+        listed[0].matcher,
+        matcher.expires_at(clock.now_ms() + 60_000)
+    );
+    assert_eq!(listed[0].group_name.as_deref(), Some("Control"));
+    assert_eq!(listed[1].spec_type, OverrideSpecType::Gate);
+    assert_eq!(listed[1].value, json!(false));
+    assert_eq!(listed[1].matcher, OverrideMatcher::new());
+
+    statsig.shutdown().await.unwrap();
+}
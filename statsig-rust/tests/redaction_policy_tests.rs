@@ -0,0 +1,225 @@
+mod utils;
+
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::Value;
+use statsig_rust::console_capture::console_log_line_levels::StatsigLogLineLevel;
+use statsig_rust::hashing::HashUtil;
+use statsig_rust::{
+    DynamicValue, FeatureGateEvaluationOptions, RedactionAction, RedactionPolicy, Statsig,
+    StatsigOptions, StatsigUser, StatsigUserBuilder,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+
+const EMAIL: &str = "jane.doe@example.com";
+const IP: &str = "203.0.113.42";
+const PHONE: &str = "+1-555-0100";
+const SSN: &str = "078-05-1120";
+
+async fn setup(policy: RedactionPolicy) -> (Statsig, Arc<MockEventLoggingAdapter>) {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+
+    let statsig = Statsig::new(
+        &format!("secret-{}", uuid::Uuid::new_v4()),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(logging_adapter.clone()),
+            redaction_policy: Some(policy),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, logging_adapter)
+}
+
+fn create_user() -> StatsigUser {
+    StatsigUserBuilder::new_with_user_id("a_user".to_string())
+        .email(Some(EMAIL.to_string()))
+        .ip(Some(IP.to_string()))
+        .country(Some("NZ".to_string()))
+        .custom_ids(Some(HashMap::from([(
+            "phoneID".to_string(),
+            PHONE.to_string(),
+        )])))
+        .custom(Some(HashMap::from([(
+            "ssn".to_string(),
+            DynamicValue::from(SSN),
+        )])))
+        .build()
+}
+
+fn logged_events(adapter: &MockEventLoggingAdapter) -> Vec<Value> {
+    adapter
+        .logged_payloads
+        .lock()
+        .unwrap()
+        .iter()
+        .flat_map(|p| p.events.as_array().cloned().unwrap_or_default())
+        .filter(|e| e["eventName"] != "statsig::diagnostics")
+        .collect()
+}
+
+#[tokio::test]
+async fn test_nothing_redacted_escapes() {
+    let policy = RedactionPolicy::new()
+        .user_field("email", RedactionAction::Hash)
+        .user_field("ip", RedactionAction::Drop)
+        .custom_id("phoneID", RedactionAction::Hash)
+        .custom_field("*", RedactionAction::Drop)
+        .event_metadata_key("card", RedactionAction::Truncate { max_length: 4 })
+        .log_line_value(RedactionAction::Hash);
+
+    let (statsig, adapter) = setup(policy).await;
+    let user = create_user();
+
+    statsig.check_gate(&user, "test_public");
+    statsig.get_experiment(&user, "experiment_with_many_params");
+    statsig.log_event(
+        &user,
+        "purchase",
+        None,
+        Some(HashMap::from([(
+            "card".to_string(),
+            "4111111111111111".to_string(),
+        )])),
+    );
+    statsig.forward_log_line_event(
+        &user,
+        StatsigLogLineLevel::Error,
+        Some(format!("failed to email {EMAIL}")),
+        None,
+    );
+    statsig.shutdown().await.unwrap();
+
+    let events = Value::Array(logged_events(&adapter));
+    assert_eq!(events.as_array().unwrap().len(), 4);
+
+    let leaks = RedactionPolicy::find_leaks(&events, &[EMAIL, IP, PHONE, SSN, "4111111111111111"]);
+    assert!(leaks.is_empty(), "Leaked: {leaks:?}");
+}
+
+#[tokio::test]
+async fn test_applies_each_action() {
+    let policy = RedactionPolicy::new()
+        .user_field("email", RedactionAction::Hash)
+        .user_field("ip", RedactionAction::Drop)
+        .user_field("country", RedactionAction::Truncate { max_length: 1 })
+        .custom_id("phoneID", RedactionAction::Hash);
+
+    let (statsig, adapter) = setup(policy).await;
+    let user = create_user();
+
+    statsig.log_event(&user, "my_event", None, None);
+    statsig.shutdown().await.unwrap();
+
+    let events = logged_events(&adapter);
+    let logged_user = &events[0]["user"];
+    let hashing = HashUtil::new();
+
+    assert_eq!(logged_user["email"], Value::from(hashing.sha256(EMAIL)));
+    assert!(logged_user.get("ip").is_none());
+    assert_eq!(logged_user["country"], "N");
+    assert_eq!(
+        logged_user["customIDs"]["phoneID"],
+        Value::from(hashing.sha256(PHONE))
+    );
+    assert_eq!(logged_user["userID"], "a_user");
+    assert_eq!(logged_user["custom"]["ssn"], SSN);
+}
+
+#[tokio::test]
+async fn test_evaluation_uses_unredacted_user() {
+    let policy = RedactionPolicy::new().user_field("email", RedactionAction::Drop);
+    let (statsig, adapter) = setup(policy).await;
+
+    let user = StatsigUserBuilder::new_with_user_id("a_user".to_string())
+        .email(Some("daniel@statsig.com".to_string()))
+        .build();
+
+    assert!(statsig.check_gate(&user, "test_email"));
+    assert_eq!(user.get_email(), Some("daniel@statsig.com"));
+
+    statsig.shutdown().await.unwrap();
+
+    let events = logged_events(&adapter);
+    assert!(events[0]["user"].get("email").is_none());
+}
+
+#[tokio::test]
+async fn test_log_line_reserved_metadata_is_kept() {
+    let policy = RedactionPolicy::new().log_line_metadata_key("*", RedactionAction::Drop);
+    let (statsig, adapter) = setup(policy).await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    statsig.forward_log_line_event(
+        &user,
+        StatsigLogLineLevel::Info,
+        Some("hello".to_string()),
+        Some(HashMap::from([(
+            "request_body".to_string(),
+            EMAIL.to_string(),
+        )])),
+    );
+    statsig.shutdown().await.unwrap();
+
+    let events = logged_events(&adapter);
+    let metadata = events[0]["metadata"].as_object().unwrap();
+    assert!(metadata.contains_key("status"));
+    assert!(metadata.contains_key("log_level"));
+    assert!(!metadata.contains_key("request_body"));
+}
+
+#[tokio::test]
+async fn test_non_exposed_checks_are_redacted_like_other_events() {
+    let policy = RedactionPolicy::new()
+        .user_field("userID", RedactionAction::Hash)
+        .custom_field("*", RedactionAction::Drop)
+        .event_metadata_key("*", RedactionAction::Drop);
+    let (statsig, adapter) = setup(policy).await;
+    let user = create_user();
+
+    statsig.check_gate_with_options(
+        &user,
+        "test_public",
+        FeatureGateEvaluationOptions {
+            disable_exposure_logging: true,
+        },
+    );
+    statsig.shutdown().await.unwrap();
+
+    let events = logged_events(&adapter);
+    let checks_event = events
+        .iter()
+        .find(|e| e["eventName"] == "statsig::non_exposed_checks")
+        .expect("non exposed checks event was not logged");
+
+    // internal event metadata is exempt from event rules
+    assert!(checks_event["metadata"]["checks"]
+        .as_str()
+        .unwrap()
+        .contains("test_public"));
+
+    let leaks = RedactionPolicy::find_leaks(&Value::Array(events), &[EMAIL, SSN, "a_user"]);
+    assert!(leaks.is_empty(), "Leaked: {leaks:?}");
+}
+
+#[test]
+fn test_policy_deserializes_from_json() {
+    let policy: RedactionPolicy = serde_json::from_str(
+        r#"{
+            "user_fields": {"email": {"action": "hash"}},
+            "event_metadata": {"*": {"action": "truncate", "max_length": 8}}
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(policy.user_fields["email"], RedactionAction::Hash);
+    assert_eq!(
+        policy.event_metadata["*"],
+        RedactionAction::Truncate { max_length: 8 }
+    );
+}
@@ -0,0 +1,186 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_observability_client::MockObservabilityClient;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::Value;
+use statsig_rust::{
+    ObservabilityClient, RegexSafetyOptions, Statsig, StatsigOptions, StatsigUser,
+    StatsigUserBuilder,
+};
+use std::fs;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+// the first rule of "test_email_regex" is `email str_matches "statsig"`
+const EMAIL_REGEX_GATE: &str = "test_email_regex";
+const EMAIL_REGEX_CONDITION: &str = "3174814662";
+
+async fn setup(
+    regex_safety: RegexSafetyOptions,
+    specs_path: &str,
+) -> (Statsig, Arc<MockObservabilityClient>) {
+    let obs_client = Arc::new(MockObservabilityClient::new());
+    let obs_client_dyn: Arc<dyn ObservabilityClient> = obs_client.clone();
+
+    let uuid = uuid::Uuid::new_v4();
+    let statsig = Statsig::new(
+        &format!("secret-regex_safety_{uuid}"),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(specs_path))),
+            event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+            observability_client: Some(Arc::downgrade(&obs_client_dyn)),
+            regex_safety: Some(regex_safety),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, obs_client)
+}
+
+/// Writes a copy of the eval project with the `test_email_regex` pattern replaced.
+fn write_specs_with_email_pattern(dir: &TempDir, pattern: &str) -> String {
+    let data = fs::read_to_string("tests/data/eval_proj_dcs.json").unwrap();
+    let mut json: Value = serde_json::from_str(&data).unwrap();
+    json["condition_map"][EMAIL_REGEX_CONDITION]["targetValue"] = Value::from(pattern);
+
+    let path = dir.path().join("eval_proj_dcs.json");
+    fs::write(&path, json.to_string()).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+fn user_with_email(email: &str) -> StatsigUser {
+    StatsigUserBuilder::new_with_user_id("a_user".to_string())
+        .email(Some(email.to_string()))
+        .build()
+}
+
+fn limit_exceeded_count(obs_client: &MockObservabilityClient, limit: &str) -> usize {
+    obs_client
+        .increment_calls
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(name, _, tags)| {
+            name == "statsig.sdk.regex_limit_exceeded"
+                && tags
+                    .as_ref()
+                    .and_then(|t| t.get("limit"))
+                    .is_some_and(|l| l == limit)
+        })
+        .count()
+}
+
+#[tokio::test]
+async fn test_matching_is_unchanged_within_limits() {
+    let (statsig, _) = setup(
+        RegexSafetyOptions {
+            backtrack_limit: Some(10_000),
+            max_input_length: Some(64),
+            prefer_linear_engine: None,
+        },
+        "tests/data/eval_proj_dcs.json",
+    )
+    .await;
+
+    assert!(statsig.check_gate(&user_with_email("dan@statsig.com"), EMAIL_REGEX_GATE));
+    assert!(!statsig.check_gate(&user_with_email("dan@example.com"), EMAIL_REGEX_GATE));
+    assert!(statsig.check_gate(&user_with_email("dan@statsig.com"), EMAIL_REGEX_GATE));
+}
+
+#[tokio::test]
+async fn test_max_input_length_fails_long_values() {
+    let (statsig, obs_client) = setup(
+        RegexSafetyOptions {
+            max_input_length: Some(16),
+            ..RegexSafetyOptions::default()
+        },
+        "tests/data/eval_proj_dcs.json",
+    )
+    .await;
+
+    let long_email = format!("{}@statsig.com", "a".repeat(32));
+    assert!(!statsig.check_gate(&user_with_email(&long_email), EMAIL_REGEX_GATE));
+    assert!(statsig.check_gate(&user_with_email("a@statsig.com"), EMAIL_REGEX_GATE));
+
+    assert_eventually!(|| limit_exceeded_count(&obs_client, "max_input_length") == 1);
+}
+
+#[tokio::test]
+async fn test_backtrack_limit_fails_runaway_patterns() {
+    let dir = tempfile::tempdir().unwrap();
+    let specs_path = write_specs_with_email_pattern(&dir, r"^(a+)+\1b");
+
+    let (statsig, obs_client) = setup(
+        RegexSafetyOptions {
+            backtrack_limit: Some(1_000),
+            ..RegexSafetyOptions::default()
+        },
+        &specs_path,
+    )
+    .await;
+
+    assert!(!statsig.check_gate(&user_with_email(&"a".repeat(30)), EMAIL_REGEX_GATE));
+    assert!(statsig.check_gate(&user_with_email("aab"), EMAIL_REGEX_GATE));
+
+    assert_eventually!(|| limit_exceeded_count(&obs_client, "backtrack_limit") == 1);
+}
+
+#[tokio::test]
+async fn test_prefer_linear_engine() {
+    let dir = tempfile::tempdir().unwrap();
+    let specs_path = write_specs_with_email_pattern(&dir, r"^(a+)+b$");
+
+    let (statsig, obs_client) = setup(
+        RegexSafetyOptions {
+            backtrack_limit: Some(1),
+            prefer_linear_engine: Some(true),
+            ..RegexSafetyOptions::default()
+        },
+        &specs_path,
+    )
+    .await;
+
+    // would exceed a backtrack limit of 1, but never backtracks on the linear engine
+    assert!(!statsig.check_gate(&user_with_email(&"a".repeat(30)), EMAIL_REGEX_GATE));
+    assert!(statsig.check_gate(
+        &user_with_email(&format!("{}b", "a".repeat(30))),
+        EMAIL_REGEX_GATE
+    ));
+    assert_eq!(limit_exceeded_count(&obs_client, "backtrack_limit"), 0);
+}
+
+#[tokio::test]
+async fn test_options_apply_per_instance() {
+    let dir = tempfile::tempdir().unwrap();
+    let specs_path = write_specs_with_email_pattern(&dir, r"^(a+)+\1b");
+    let email = "a".repeat(10);
+
+    let (lenient, lenient_obs_client) = setup(
+        RegexSafetyOptions {
+            backtrack_limit: Some(1_000_000),
+            ..RegexSafetyOptions::default()
+        },
+        &specs_path,
+    )
+    .await;
+    let (strict, strict_obs_client) = setup(
+        RegexSafetyOptions {
+            backtrack_limit: Some(100),
+            ..RegexSafetyOptions::default()
+        },
+        &specs_path,
+    )
+    .await;
+
+    // both instances share the interned spec values, but compile their own patterns
+    assert!(!lenient.check_gate(&user_with_email(&email), EMAIL_REGEX_GATE));
+    assert!(!strict.check_gate(&user_with_email(&email), EMAIL_REGEX_GATE));
+
+    assert_eventually!(|| limit_exceeded_count(&strict_obs_client, "backtrack_limit") == 1);
+    assert_eq!(
+        limit_exceeded_count(&lenient_obs_client, "backtrack_limit"),
+        0
+    );
+}
@@ -0,0 +1,402 @@
+mod utils;
+
+use crate::utils::mock_data_store::MockDataStore;
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use async_trait::async_trait;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use statsig_rust::networking::NetworkError;
+use statsig_rust::sdk_event_emitter::{
+    OwnedSdkEvent, SdkEvent, SdkEventCode, SdkEventFilter, SdkEventStream,
+};
+use statsig_rust::{
+    IdListMetadata, IdListUpdate, IdListsAdapter, IdListsUpdateListener, SpecsSource, Statsig,
+    StatsigErr, StatsigFileOverrideAdapter, StatsigOptions, StatsigRuntime, StatsigUser,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use utils::mock_scrapi::{self, Endpoint, EndpointStub, MockScrapi, StubData};
+
+#[derive(Default)]
+struct ManualIdListsAdapter {
+    listener: Mutex<Option<Arc<dyn IdListsUpdateListener + Send + Sync>>>,
+}
+
+impl ManualIdListsAdapter {
+    fn push(&self, lists: &[(&str, &str, &str)]) {
+        let updates = lists
+            .iter()
+            .map(|(name, file_id, changeset)| {
+                let update = IdListUpdate {
+                    raw_changeset: Some(changeset.to_string()),
+                    new_metadata: IdListMetadata {
+                        name: name.to_string(),
+                        url: format!("https://fake.statsig.com/{name}"),
+                        file_id: Some(file_id.to_string()),
+                        size: changeset.len() as u64,
+                        creation_time: 1,
+                    },
+                };
+                (name.to_string(), update)
+            })
+            .collect();
+
+        let listener = self.listener.lock().clone().unwrap();
+        listener.did_receive_id_list_updates(updates);
+    }
+}
+
+#[async_trait]
+impl IdListsAdapter for ManualIdListsAdapter {
+    async fn start(
+        self: Arc<Self>,
+        _statsig_runtime: &Arc<StatsigRuntime>,
+        listener: Arc<dyn IdListsUpdateListener + Send + Sync>,
+    ) -> Result<(), StatsigErr> {
+        *self.listener.lock() = Some(listener);
+        Ok(())
+    }
+
+    async fn shutdown(&self, _timeout: Duration) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
+    async fn schedule_background_sync(
+        self: Arc<Self>,
+        _statsig_runtime: &Arc<StatsigRuntime>,
+    ) -> Result<(), StatsigErr> {
+        Ok(())
+    }
+
+    fn get_type_name(&self) -> String {
+        "ManualIdListsAdapter".to_string()
+    }
+}
+
+fn create_options() -> StatsigOptions {
+    StatsigOptions {
+        specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+            "tests/data/eval_proj_dcs.json",
+        ))),
+        event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+        ..StatsigOptions::default()
+    }
+}
+
+async fn next_event(stream: &mut SdkEventStream) -> Option<OwnedSdkEvent> {
+    tokio::time::timeout(Duration::from_millis(100), stream.next())
+        .await
+        .ok()
+        .flatten()
+}
+
+#[tokio::test]
+async fn test_override_events() {
+    let statsig = Statsig::new(
+        "secret-lifecycle_overrides",
+        Some(Arc::new(create_options())),
+    );
+    let mut stream = statsig.event_stream(
+        SdkEventFilter::all()
+            .with_codes([SdkEventCode::OverrideSet, SdkEventCode::OverrideRemoved]),
+    );
+
+    statsig.override_gate("test_gate", true, Some("a_user"));
+    statsig.remove_gate_override("test_gate", Some("a_user"));
+    statsig.remove_all_overrides();
+
+    assert_eq!(
+        next_event(&mut stream).await.unwrap(),
+        OwnedSdkEvent::OverrideSet {
+            entity_name: "test_gate".to_string(),
+            override_type: "gate".to_string(),
+            id: Some("a_user".to_string()),
+        }
+    );
+    assert_eq!(
+        next_event(&mut stream).await.unwrap(),
+        OwnedSdkEvent::OverrideRemoved {
+            entity_name: Some("test_gate".to_string()),
+            override_type: "gate".to_string(),
+            id: Some("a_user".to_string()),
+        }
+    );
+    assert_eq!(
+        next_event(&mut stream).await.unwrap(),
+        OwnedSdkEvent::OverrideRemoved {
+            entity_name: None,
+            override_type: "all".to_string(),
+            id: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_shutdown_events() {
+    let statsig = Statsig::new(
+        "secret-lifecycle_shutdown",
+        Some(Arc::new(create_options())),
+    );
+    statsig.initialize().await.unwrap();
+
+    let mut stream = statsig.event_stream(SdkEventFilter::all().with_codes([
+        SdkEventCode::ShutdownStarted,
+        SdkEventCode::ShutdownCompleted,
+    ]));
+
+    statsig
+        .shutdown_with_timeout(Duration::from_millis(500))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        next_event(&mut stream).await.unwrap(),
+        OwnedSdkEvent::ShutdownStarted { timeout_ms: 500 }
+    );
+    match next_event(&mut stream).await.unwrap() {
+        OwnedSdkEvent::ShutdownCompleted { success, .. } => assert!(success),
+        other => panic!("Unexpected event {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_id_list_events() {
+    let adapter = Arc::new(ManualIdListsAdapter::default());
+    let options = StatsigOptions {
+        id_lists_adapter: Some(adapter.clone()),
+        ..create_options()
+    };
+    let statsig = Statsig::new("secret-lifecycle_id_lists", Some(Arc::new(options)));
+    statsig.initialize().await.unwrap();
+
+    let mut stream = statsig.event_stream(SdkEventFilter::all().with_codes([
+        SdkEventCode::IdListAdded,
+        SdkEventCode::IdListUpdated,
+        SdkEventCode::IdListRemoved,
+    ]));
+
+    adapter.push(&[("list_a", "file_1", "+a\n+b\n")]);
+    assert_eq!(
+        next_event(&mut stream).await.unwrap(),
+        OwnedSdkEvent::IdListAdded {
+            list_name: "list_a".to_string(),
+            file_id: Some("file_1".to_string()),
+            size: 6,
+            id_count: 2,
+        }
+    );
+
+    adapter.push(&[("list_a", "file_1", "-a\n"), ("list_b", "file_2", "+c\n")]);
+    let mut events = vec![
+        next_event(&mut stream).await.unwrap(),
+        next_event(&mut stream).await.unwrap(),
+    ];
+    events.sort_by_key(|e| e.get_code().as_raw());
+    assert_eq!(
+        events,
+        vec![
+            OwnedSdkEvent::IdListAdded {
+                list_name: "list_b".to_string(),
+                file_id: Some("file_2".to_string()),
+                size: 3,
+                id_count: 1,
+            },
+            OwnedSdkEvent::IdListUpdated {
+                list_name: "list_a".to_string(),
+                file_id: Some("file_1".to_string()),
+                size: 9,
+                id_count: 1,
+            },
+        ]
+    );
+
+    adapter.push(&[("list_b", "file_2", "")]);
+    assert_eq!(
+        next_event(&mut stream).await.unwrap(),
+        OwnedSdkEvent::IdListRemoved {
+            list_name: "list_a".to_string(),
+            size: 9,
+            id_count: 1,
+        }
+    );
+
+    // an empty changeset is not a change
+    assert!(next_event(&mut stream).await.is_none());
+}
+
+#[tokio::test]
+async fn test_flush_failure_events() {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+    *logging_adapter.mocked_log_events_result.lock().unwrap() =
+        Err(StatsigErr::NetworkError(NetworkError::RequestNotRetryable(
+            "https://fake.statsig.com/v1/log_event".to_string(),
+            Some(400),
+            "Bad Request".to_string(),
+        )));
+
+    let options = StatsigOptions {
+        event_logging_adapter: Some(logging_adapter),
+        ..create_options()
+    };
+    let statsig = Statsig::new("secret-lifecycle_flush", Some(Arc::new(options)));
+    statsig.initialize().await.unwrap();
+
+    let mut stream = statsig.event_stream(
+        SdkEventFilter::all()
+            .with_codes([SdkEventCode::EventFlushFailed, SdkEventCode::EventsDropped]),
+    );
+
+    statsig.log_event(
+        &StatsigUser::with_user_id("a_user".to_string()),
+        "my_event",
+        None,
+        None,
+    );
+    statsig.flush_events().await;
+
+    match next_event(&mut stream).await.unwrap() {
+        OwnedSdkEvent::EventFlushFailed {
+            error, will_retry, ..
+        } => {
+            assert!(error.contains("Bad Request"));
+            assert!(!will_retry);
+        }
+        other => panic!("Unexpected event {other:?}"),
+    }
+    match next_event(&mut stream).await.unwrap() {
+        OwnedSdkEvent::EventsDropped { count, reason } => {
+            assert!(count >= 1);
+            assert_eq!(reason, "flush_failure");
+        }
+        other => panic!("Unexpected event {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_specs_sync_failed_event_is_sanitized() {
+    let mock_scrapi = MockScrapi::new().await;
+    mock_scrapi
+        .stub(EndpointStub {
+            endpoint: Endpoint::DownloadConfigSpecs,
+            response: StubData::String(String::new()),
+            status: 500,
+            method: mock_scrapi::Method::GET,
+            delay_ms: 0,
+            res_headers: None,
+        })
+        .await;
+
+    let options = StatsigOptions {
+        specs_url: Some(mock_scrapi.url_for_endpoint(Endpoint::DownloadConfigSpecs)),
+        event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+        ..StatsigOptions::default()
+    };
+    let sdk_key = "secret-lifecycle_specs_sync_failed_1234567890";
+    let statsig = Statsig::new(sdk_key, Some(Arc::new(options)));
+
+    let raw_events = Arc::new(Mutex::new(Vec::new()));
+    let raw_events_clone = raw_events.clone();
+    statsig.subscribe(SdkEvent::SPECS_SYNC_FAILED, move |event| {
+        raw_events_clone
+            .lock()
+            .push(event.to_raw_json_string().unwrap());
+    });
+    let mut stream =
+        statsig.event_stream(SdkEventFilter::all().with_codes([SdkEventCode::SpecsSyncFailed]));
+
+    let _ = statsig.initialize().await;
+
+    match next_event(&mut stream).await.unwrap() {
+        OwnedSdkEvent::SpecsSyncFailed { source, error, .. } => {
+            assert_eq!(source, SpecsSource::Network);
+            assert_eq!(error.status_code, Some(500));
+        }
+        other => panic!("Unexpected event {other:?}"),
+    }
+
+    let raw = raw_events.lock().first().cloned().unwrap();
+    let json: serde_json::Value = serde_json::from_str(&raw).unwrap();
+    assert_eq!(json["event_name"], "specs_sync_failed");
+    assert_eq!(json["data"]["error"]["statusCode"], 500);
+    assert!(!raw.contains(sdk_key));
+}
+
+#[tokio::test]
+async fn test_data_store_specs_sync_failed_event() {
+    let options = StatsigOptions {
+        data_store: Some(Arc::new(MockDataStore::new(true))),
+        specs_sync_interval_ms: Some(1000),
+        disable_network: Some(true),
+        event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+        ..StatsigOptions::default()
+    };
+    let statsig = Statsig::new(
+        "secret-lifecycle_data_store_sync_failed",
+        Some(Arc::new(options)),
+    );
+    let mut stream =
+        statsig.event_stream(SdkEventFilter::all().with_codes([SdkEventCode::SpecsSyncFailed]));
+
+    let _ = statsig.initialize().await;
+
+    let event = tokio::time::timeout(Duration::from_secs(3), stream.next())
+        .await
+        .unwrap()
+        .unwrap();
+    match event {
+        OwnedSdkEvent::SpecsSyncFailed {
+            source,
+            source_api,
+            error,
+        } => {
+            assert_eq!(source, SpecsSource::Adapter("DataStore".to_string()));
+            assert_eq!(source_api.as_deref(), Some("datastore"));
+            assert_eq!(error.kind, "DataStoreFailure");
+        }
+        other => panic!("Unexpected event {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_file_override_reload_events() {
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join("overrides.json");
+    std::fs::write(&file_path, r#"{"gates": {"a_gate": true}}"#).unwrap();
+
+    let adapter = Arc::new(StatsigFileOverrideAdapter::new(
+        &file_path.to_string_lossy(),
+        Some(60_000),
+        None,
+    ));
+    let statsig = Statsig::new(
+        "secret-lifecycle_file_overrides",
+        Some(Arc::new(StatsigOptions {
+            override_adapter: Some(adapter.clone()),
+            ..create_options()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    let mut stream =
+        statsig.event_stream(SdkEventFilter::all().with_codes([SdkEventCode::OverridesReloaded]));
+
+    std::fs::write(
+        &file_path,
+        r#"{"gates": {"a_gate": true, "another_gate": false}}"#,
+    )
+    .unwrap();
+    adapter.reload().unwrap();
+
+    assert_eq!(
+        next_event(&mut stream).await.unwrap(),
+        OwnedSdkEvent::OverridesReloaded {
+            source: "File".to_string(),
+            override_count: 2,
+        }
+    );
+
+    // unchanged content is not reported again
+    adapter.reload().unwrap();
+    assert!(next_event(&mut stream).await.is_none());
+}
@@ -72,6 +72,8 @@ fn setup(sdk_key: &str) -> (Statsig, StatsigUser, mpsc::Receiver<ReceivedEvent>)
             } => {
                 result.data = EventData::SpecsUpdated(source.to_string(), values.time);
             }
+            // lifecycle events are covered in sdk_lifecycle_events_tests
+            _ => return,
         }
 
         tx.send(result).unwrap();
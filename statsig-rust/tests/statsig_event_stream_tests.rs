@@ -0,0 +1,111 @@
+mod utils;
+
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use futures::StreamExt;
+use statsig_rust::sdk_event_emitter::{
+    OwnedSdkEvent, SdkEventCode, SdkEventFilter, SdkEventStream,
+};
+use statsig_rust::{SpecsSource, Statsig, StatsigOptions, StatsigUser};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn setup(sdk_key: &str) -> (Statsig, StatsigUser) {
+    let specs_adapter = Arc::new(MockSpecsAdapter::with_data("tests/data/eval_proj_dcs.json"));
+    let options = StatsigOptions {
+        specs_adapter: Some(specs_adapter),
+        ..StatsigOptions::default()
+    };
+    let statsig = Statsig::new(sdk_key, Some(Arc::new(options)));
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    (statsig, user)
+}
+
+async fn next_event(stream: &mut SdkEventStream) -> Option<OwnedSdkEvent> {
+    tokio::time::timeout(Duration::from_millis(100), stream.next())
+        .await
+        .ok()
+        .flatten()
+}
+
+#[tokio::test]
+async fn test_stream_filters_by_code() {
+    let (statsig, user) = setup("secret-event_stream_codes");
+    let mut stream =
+        statsig.event_stream(SdkEventFilter::all().with_codes([SdkEventCode::GateEvaluated]));
+
+    statsig.get_dynamic_config(&user, "test_config");
+    statsig.check_gate(&user, "test_gate");
+
+    let event = next_event(&mut stream).await.unwrap();
+    assert_eq!(
+        event,
+        OwnedSdkEvent::GateEvaluated {
+            gate_name: "test_gate".to_string(),
+            rule_id: String::new(),
+            value: false,
+            reason: "Uninitialized".to_string(),
+        }
+    );
+    assert!(next_event(&mut stream).await.is_none());
+}
+
+#[tokio::test]
+async fn test_stream_filters_by_entity_name() {
+    let (statsig, user) = setup("secret-event_stream_entities");
+    let mut stream =
+        statsig.event_stream(SdkEventFilter::all().with_entity_names(["test_experiment"]));
+
+    statsig.check_gate(&user, "test_gate");
+    statsig.get_experiment(&user, "test_experiment");
+    statsig.get_experiment(&user, "other_experiment");
+
+    let event = next_event(&mut stream).await.unwrap();
+    assert_eq!(event.get_code(), SdkEventCode::ExperimentEvaluated);
+    assert_eq!(event.get_entity_name(), Some("test_experiment"));
+    assert!(next_event(&mut stream).await.is_none());
+}
+
+#[tokio::test]
+async fn test_stream_receives_specs_updated() {
+    let (statsig, _) = setup("secret-event_stream_specs_updated");
+    let mut stream =
+        statsig.event_stream(SdkEventFilter::all().with_codes([SdkEventCode::SpecsUpdated]));
+
+    statsig.initialize().await.unwrap();
+
+    match next_event(&mut stream).await.unwrap() {
+        OwnedSdkEvent::SpecsUpdated { source, lcut, .. } => {
+            assert_eq!(source, SpecsSource::Bootstrap);
+            assert_eq!(lcut, 1767981029384);
+        }
+        other => panic!("Unexpected event {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_stream_reports_lag() {
+    let (statsig, user) = setup("secret-event_stream_lag");
+    let mut stream = statsig.event_stream(SdkEventFilter::all());
+
+    for _ in 0..2000 {
+        statsig.check_gate(&user, "test_gate");
+    }
+
+    let event = next_event(&mut stream).await.unwrap();
+    assert_eq!(event.get_entity_name(), Some("test_gate"));
+    assert!(stream.lagged_count() > 0);
+}
+
+#[tokio::test]
+async fn test_dropped_stream_stops_receiving() {
+    let (statsig, user) = setup("secret-event_stream_dropped");
+    let stream = statsig.event_stream(SdkEventFilter::all());
+    drop(stream);
+
+    // emitting with no live streams is a no-op
+    statsig.check_gate(&user, "test_gate");
+
+    let mut stream = statsig.event_stream(SdkEventFilter::all());
+    assert!(next_event(&mut stream).await.is_none());
+}
@@ -0,0 +1,59 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use statsig_rust::{StatsigHub, StatsigOptions, StatsigUser};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SDK_KEY: &str = "secret-hub_shutdown_key";
+
+// Kept in its own binary, the tick overrides are process wide.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shutdown_waits_for_in_flight_scheduled_flush() {
+    std::env::set_var("STATSIG_TEST_OVERRIDE_TICK_INTERVAL_MS", "1");
+    std::env::set_var("STATSIG_TEST_OVERRIDE_MAX_FLUSH_INTERVAL_MS", "1");
+
+    let hub = StatsigHub::new().unwrap();
+    let adapter = Arc::new(MockEventLoggingAdapter::new());
+    adapter.log_delay_ms.store(300, Ordering::SeqCst);
+
+    let project = hub
+        .add_project(
+            SDK_KEY,
+            Some(Arc::new(StatsigOptions {
+                specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                    "tests/data/eval_proj_dcs.json",
+                ))),
+                event_logging_adapter: Some(adapter.clone()),
+                ..StatsigOptions::default()
+            })),
+        )
+        .unwrap();
+
+    // queued before the first tick, so the first scheduled flush carries them
+    let user = StatsigUser::with_user_id("a_user".to_string());
+    for i in 0..10 {
+        project.log_event(&user, &format!("event_{i}"), None, None);
+    }
+
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while adapter.times_called.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    })
+    .await
+    .expect("Hub did not start a scheduled flush");
+
+    hub.shutdown().await.unwrap();
+    assert_eq!(
+        adapter
+            .no_diagnostics_logged_event_count
+            .load(Ordering::SeqCst),
+        10
+    );
+
+    std::env::remove_var("STATSIG_TEST_OVERRIDE_TICK_INTERVAL_MS");
+    std::env::remove_var("STATSIG_TEST_OVERRIDE_MAX_FLUSH_INTERVAL_MS");
+}
@@ -0,0 +1,200 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use statsig_rust::{StatsigErr, StatsigHub, StatsigOptions, StatsigUser};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SDK_KEY_A: &str = "secret-hub_project_a_key";
+const SDK_KEY_B: &str = "secret-hub_project_b_key";
+
+fn create_options(
+    dcs_path: &str,
+    logging_adapter: &Arc<MockEventLoggingAdapter>,
+) -> StatsigOptions {
+    StatsigOptions {
+        specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(dcs_path))),
+        event_logging_adapter: Some(logging_adapter.clone()),
+        ..StatsigOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn test_projects_are_isolated() {
+    let hub = StatsigHub::new().unwrap();
+    let adapter_a = Arc::new(MockEventLoggingAdapter::new());
+    let adapter_b = Arc::new(MockEventLoggingAdapter::new());
+
+    let project_a = hub
+        .add_project(
+            SDK_KEY_A,
+            Some(Arc::new(create_options(
+                "tests/data/eval_proj_dcs.json",
+                &adapter_a,
+            ))),
+        )
+        .unwrap();
+    hub.add_project(
+        SDK_KEY_B,
+        Some(Arc::new(create_options(
+            "tests/data/eval_proj_dcs.json",
+            &adapter_b,
+        ))),
+    )
+    .unwrap();
+
+    let results = hub.initialize_all().await;
+    assert_eq!(results.len(), 2);
+    assert!(results.values().all(|details| details.init_success));
+
+    let project_b = hub.get_project(SDK_KEY_B).unwrap();
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    project_a.override_gate("test_public", false, None);
+    assert!(!project_a.check_gate(&user, "test_public"));
+    assert!(project_b.check_gate(&user, "test_public"));
+
+    project_a.log_event(&user, "only_in_a", None, None);
+    hub.flush_all().await;
+
+    assert_eq!(adapter_a.force_get_event_at(1)["eventName"], "only_in_a");
+    assert_eq!(
+        adapter_b
+            .no_diagnostics_logged_event_count
+            .load(Ordering::SeqCst),
+        1 // the gate exposure
+    );
+
+    hub.shutdown().await.unwrap();
+    assert!(hub.get_sdk_keys().is_empty());
+}
+
+#[tokio::test]
+async fn test_duplicate_and_missing_projects() {
+    let hub = StatsigHub::new().unwrap();
+    let adapter = Arc::new(MockEventLoggingAdapter::new());
+    let options = Arc::new(create_options("tests/data/eval_proj_dcs.json", &adapter));
+
+    hub.add_project(SDK_KEY_A, Some(options.clone())).unwrap();
+    assert!(matches!(
+        hub.add_project(SDK_KEY_A, Some(options)),
+        Err(StatsigErr::InvalidOperation(_))
+    ));
+
+    hub.remove_project(SDK_KEY_A).await.unwrap();
+    assert!(hub.get_project(SDK_KEY_A).is_none());
+    assert!(matches!(
+        hub.remove_project(SDK_KEY_A).await,
+        Err(StatsigErr::InvalidOperation(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_single_flush_scheduler() {
+    std::env::set_var("STATSIG_TEST_OVERRIDE_TICK_INTERVAL_MS", "1");
+    std::env::set_var("STATSIG_TEST_OVERRIDE_MAX_FLUSH_INTERVAL_MS", "1");
+
+    let hub = StatsigHub::new().unwrap();
+    let adapter_a = Arc::new(MockEventLoggingAdapter::new());
+    let adapter_b = Arc::new(MockEventLoggingAdapter::new());
+
+    let project_a = hub
+        .add_project(
+            SDK_KEY_A,
+            Some(Arc::new(create_options(
+                "tests/data/eval_proj_dcs.json",
+                &adapter_a,
+            ))),
+        )
+        .unwrap();
+    let project_b = hub
+        .add_project(
+            SDK_KEY_B,
+            Some(Arc::new(create_options(
+                "tests/data/eval_proj_dcs.json",
+                &adapter_b,
+            ))),
+        )
+        .unwrap();
+    hub.initialize_all().await;
+
+    let diagnostics = hub.get_diagnostics();
+    let scheduler_count = diagnostics
+        .hub_running_tasks
+        .iter()
+        .filter(|tag| *tag == "HUB_EVT_LOG_FLUSH_LOOP")
+        .count();
+    assert_eq!(scheduler_count, 1);
+    assert_eq!(diagnostics.projects.len(), 2);
+    for project in &diagnostics.projects {
+        assert!(project.initialize_details.init_success);
+        assert!(!project
+            .running_tasks
+            .contains(&"EVT_LOG_BG_LOOP".to_string()));
+    }
+    assert!(diagnostics
+        .projects
+        .iter()
+        .all(|p| p.sdk_key.len() < SDK_KEY_A.len()));
+
+    let user = StatsigUser::with_user_id("a_user".to_string());
+    project_a.log_event(&user, "event_a", None, None);
+    project_b.log_event(&user, "event_b", None, None);
+
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while adapter_a
+            .no_diagnostics_logged_event_count
+            .load(Ordering::SeqCst)
+            == 0
+            || adapter_b
+                .no_diagnostics_logged_event_count
+                .load(Ordering::SeqCst)
+                == 0
+        {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("Hub did not flush both projects");
+
+    hub.shutdown().await.unwrap();
+    std::env::remove_var("STATSIG_TEST_OVERRIDE_TICK_INTERVAL_MS");
+    std::env::remove_var("STATSIG_TEST_OVERRIDE_MAX_FLUSH_INTERVAL_MS");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_full_batches_are_flushed_without_waiting_for_a_tick() {
+    let hub = StatsigHub::new().unwrap();
+    let adapter = Arc::new(MockEventLoggingAdapter::new());
+
+    let project = hub
+        .add_project(
+            SDK_KEY_A,
+            Some(Arc::new(StatsigOptions {
+                event_logging_max_queue_size: Some(10),
+                // only 30 events fit in the queue, so the rest rely on size-triggered flushes
+                event_logging_max_pending_batch_queue_size: Some(3),
+                ..create_options("tests/data/eval_proj_dcs.json", &adapter)
+            })),
+        )
+        .unwrap();
+    hub.initialize_all().await;
+
+    let user = StatsigUser::with_user_id("a_user".to_string());
+    for i in 0..100 {
+        project.log_event(&user, &format!("event_{i}"), None, None);
+        if i % 10 == 9 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    hub.shutdown().await.unwrap();
+    assert_eq!(
+        adapter
+            .no_diagnostics_logged_event_count
+            .load(Ordering::SeqCst),
+        100
+    );
+}
@@ -0,0 +1,140 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::Value;
+use statsig_rust::statsig_runtime::{
+    create_new_runtime_with_options, StatsigRuntime, StatsigRuntimeOptions,
+};
+use statsig_rust::{Statsig, StatsigErr, StatsigOptions};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Builder;
+
+fn spawn_and_get_thread_name(runtime: &StatsigRuntime) -> String {
+    let (tx, rx) = mpsc::channel();
+    runtime
+        .spawn("thread_name_probe", move |_| async move {
+            let name = std::thread::current()
+                .name()
+                .unwrap_or_default()
+                .to_string();
+            tx.send(name).unwrap();
+        })
+        .unwrap();
+
+    rx.recv_timeout(Duration::from_secs(5)).unwrap()
+}
+
+#[test]
+fn test_provided_handle_is_used() {
+    let rt = Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("provided-rt")
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let runtime = StatsigRuntime::get_runtime_with_options(StatsigRuntimeOptions {
+        handle: Some(rt.handle().clone()),
+        ..StatsigRuntimeOptions::default()
+    });
+
+    assert_eq!(spawn_and_get_thread_name(&runtime), "provided-rt");
+}
+
+#[tokio::test]
+async fn test_provided_handle_takes_precedence_over_ambient_runtime() {
+    let rt = Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("provided-rt")
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let runtime = StatsigRuntime::get_runtime_with_options(StatsigRuntimeOptions {
+        handle: Some(rt.handle().clone()),
+        ..StatsigRuntimeOptions::default()
+    });
+
+    let name = tokio::task::spawn_blocking(move || spawn_and_get_thread_name(&runtime))
+        .await
+        .unwrap();
+    assert_eq!(name, "provided-rt");
+
+    tokio::task::spawn_blocking(move || drop(rt)).await.unwrap();
+}
+
+#[test]
+fn test_disabled_fallback_runtime_errors_without_handle() {
+    let runtime = StatsigRuntime::get_runtime_with_options(StatsigRuntimeOptions {
+        disable_fallback_runtime: true,
+        ..StatsigRuntimeOptions::default()
+    });
+
+    match runtime.spawn("no_runtime", |_| async {}) {
+        Err(StatsigErr::ThreadFailure(message)) => {
+            assert!(message.contains("fallback runtime is disabled"));
+        }
+        other => panic!("Unexpected result {other:?}"),
+    }
+    assert_eq!(runtime.get_num_active_tasks(), 0);
+}
+
+#[test]
+fn test_fallback_runtime_thread_options() {
+    let options = StatsigRuntimeOptions {
+        fallback_worker_threads: Some(2),
+        fallback_thread_name_prefix: Some("my-statsig".to_string()),
+        ..StatsigRuntimeOptions::default()
+    };
+    let rt = create_new_runtime_with_options(&options);
+    assert_eq!(rt.metrics().num_workers(), 2);
+
+    let runtime = StatsigRuntime::get_runtime_with_options(StatsigRuntimeOptions {
+        handle: Some(rt.handle().clone()),
+        ..options
+    });
+    assert!(spawn_and_get_thread_name(&runtime).starts_with("my-statsig-"));
+}
+
+#[tokio::test]
+async fn test_running_task_tags_in_init_diagnostics() {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+    let options = StatsigOptions {
+        specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+            "tests/data/eval_proj_dcs.json",
+        ))),
+        event_logging_adapter: Some(logging_adapter.clone()),
+        runtime_options: Some(StatsigRuntimeOptions {
+            handle: Some(tokio::runtime::Handle::current()),
+            disable_fallback_runtime: true,
+            ..StatsigRuntimeOptions::default()
+        }),
+        ..StatsigOptions::default()
+    };
+
+    let statsig = Statsig::new("secret-runtime_options", Some(Arc::new(options)));
+    statsig.initialize().await.unwrap();
+    // diagnostics are enqueued by an OpsStats listener task
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    statsig.flush_events().await;
+
+    let payload = logging_adapter.force_get_received_payloads();
+    let events = payload.events.as_array().unwrap();
+    let diagnostics = events
+        .iter()
+        .find(|e| e["eventName"] == "statsig::diagnostics")
+        .expect("No diagnostics event found");
+
+    let markers: Vec<Value> =
+        serde_json::from_str(diagnostics["metadata"]["markers"].as_str().unwrap()).unwrap();
+    let overall_end = markers
+        .iter()
+        .find(|m| m["key"] == "overall" && m["action"] == "end")
+        .unwrap();
+
+    let running_tasks = overall_end["runningTasks"].as_array().unwrap();
+    assert!(running_tasks.contains(&Value::from("EVT_LOG_BG_LOOP")));
+}
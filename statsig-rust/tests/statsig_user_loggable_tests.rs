@@ -10,11 +10,11 @@ use statsig_rust::{
 
 #[test]
 fn test_simple_serialization() {
+    let mut data = UserData::default();
+    data.user_id = Some(dyn_value!("a_user"));
+
     let loggable = StatsigUserLoggable {
-        data: Arc::new(UserData {
-            user_id: Some(dyn_value!("a_user")),
-            ..Default::default()
-        }),
+        data: Arc::new(data),
         environment: None,
         global_custom: None,
     };
@@ -36,14 +36,14 @@ fn test_empty_user_serialization() {
 
 #[test]
 fn test_private_attributes_serialization() {
+    let mut data = UserData::default();
+    data.private_attributes = Some(StatsigUserDataMap::from([(
+        "private_attribute_key".to_string(),
+        dyn_value!("a_private_attribute_value"),
+    )]));
+
     let loggable = StatsigUserLoggable {
-        data: Arc::new(UserData {
-            private_attributes: Some(StatsigUserDataMap::from([(
-                "private_attribute_key".to_string(),
-                dyn_value!("a_private_attribute_value"),
-            )])),
-            ..Default::default()
-        }),
+        data: Arc::new(data),
         environment: None,
         global_custom: None,
     };
@@ -54,32 +54,33 @@ fn test_private_attributes_serialization() {
 
 #[test]
 fn test_full_user_serialization() {
+    let mut data = UserData::default();
+    data.user_id = Some(dyn_value!("a_user"));
+    data.custom_ids = Some(StatsigUserDataMap::from([(
+        "custom_id".to_string(),
+        dyn_value!("a_value"),
+    )]));
+    data.email = Some(dyn_value!("a_email"));
+    data.ip = Some(dyn_value!("a_ip"));
+    data.user_agent = Some(dyn_value!("a_user_agent"));
+    data.country = Some(dyn_value!("a_country"));
+    data.locale = Some(dyn_value!("a_locale"));
+    data.app_version = Some(dyn_value!("a_app_version"));
+    data.custom = Some(StatsigUserDataMap::from([(
+        "custom_key".to_string(),
+        dyn_value!("a_custom_value"),
+    )]));
+    data.private_attributes = Some(StatsigUserDataMap::from([(
+        "private_attribute_key".to_string(),
+        dyn_value!("a_private_attribute_value"),
+    )]));
+    data.statsig_environment = Some(StatsigUserDataMap::from([(
+        "statsig_environment_key".to_string(),
+        dyn_value!("a_statsig_environment_value"),
+    )]));
+
     let loggable = StatsigUserLoggable {
-        data: Arc::new(UserData {
-            user_id: Some(dyn_value!("a_user")),
-            custom_ids: Some(StatsigUserDataMap::from([(
-                "custom_id".to_string(),
-                dyn_value!("a_value"),
-            )])),
-            email: Some(dyn_value!("a_email")),
-            ip: Some(dyn_value!("a_ip")),
-            user_agent: Some(dyn_value!("a_user_agent")),
-            country: Some(dyn_value!("a_country")),
-            locale: Some(dyn_value!("a_locale")),
-            app_version: Some(dyn_value!("a_app_version")),
-            custom: Some(StatsigUserDataMap::from([(
-                "custom_key".to_string(),
-                dyn_value!("a_custom_value"),
-            )])),
-            private_attributes: Some(StatsigUserDataMap::from([(
-                "private_attribute_key".to_string(),
-                dyn_value!("a_private_attribute_value"),
-            )])),
-            statsig_environment: Some(StatsigUserDataMap::from([(
-                "statsig_environment_key".to_string(),
-                dyn_value!("a_statsig_environment_value"),
-            )])),
-        }),
+        data: Arc::new(data),
         environment: None,
         global_custom: None,
     };
@@ -129,18 +130,18 @@ fn test_environment_serialization() {
 
 #[test]
 fn test_user_custom_overrides_global_custom() {
+    let mut data = UserData::default();
+    data.custom = Some(StatsigUserDataMap::from([(
+        "custom_key".to_string(),
+        dyn_value!("from_local_custom"),
+    )]));
+    data.private_attributes = Some(StatsigUserDataMap::from([(
+        "custom_key".to_string(),
+        dyn_value!("from_private_custom"),
+    )]));
+
     let loggable = StatsigUserLoggable {
-        data: Arc::new(UserData {
-            custom: Some(StatsigUserDataMap::from([(
-                "custom_key".to_string(),
-                dyn_value!("from_local_custom"),
-            )])),
-            private_attributes: Some(StatsigUserDataMap::from([(
-                "custom_key".to_string(),
-                dyn_value!("from_private_custom"),
-            )])),
-            ..Default::default()
-        }),
+        data: Arc::new(data),
         environment: None,
         global_custom: Some(HashMap::from([(
             "custom_key".to_string(),
@@ -162,15 +163,15 @@ fn test_user_custom_overrides_global_custom() {
 #[test]
 #[cfg(feature = "ordered_user_data_maps")]
 fn test_custom_serialization_preserves_user_data_order() {
+    let mut data = UserData::default();
+    data.custom = Some(StatsigUserDataMap::from([
+        ("first".to_string(), dyn_value!("1")),
+        ("second".to_string(), dyn_value!("2")),
+        ("third".to_string(), dyn_value!("3")),
+    ]));
+
     let loggable = StatsigUserLoggable {
-        data: Arc::new(UserData {
-            custom: Some(StatsigUserDataMap::from([
-                ("first".to_string(), dyn_value!("1")),
-                ("second".to_string(), dyn_value!("2")),
-                ("third".to_string(), dyn_value!("3")),
-            ])),
-            ..Default::default()
-        }),
+        data: Arc::new(data),
         environment: None,
         global_custom: None,
     };
@@ -185,14 +186,14 @@ fn test_custom_serialization_preserves_user_data_order() {
 #[test]
 #[cfg(feature = "ordered_user_data_maps")]
 fn test_custom_serialization_merges_without_duplicate_keys() {
+    let mut data = UserData::default();
+    data.custom = Some(StatsigUserDataMap::from([
+        ("shared".to_string(), dyn_value!("from_user")),
+        ("user_only".to_string(), dyn_value!("from_user_only")),
+    ]));
+
     let loggable = StatsigUserLoggable {
-        data: Arc::new(UserData {
-            custom: Some(StatsigUserDataMap::from([
-                ("shared".to_string(), dyn_value!("from_user")),
-                ("user_only".to_string(), dyn_value!("from_user_only")),
-            ])),
-            ..Default::default()
-        }),
+        data: Arc::new(data),
         environment: None,
         global_custom: Some(HashMap::from([(
             "shared".to_string(),
@@ -30,19 +30,13 @@ fn test_statsig_user_data_accepts_hashmap_fields_by_default() {
     let private_attributes = HashMap::from([("secret".to_string(), dyn_value!("value"))]);
     let statsig_environment = HashMap::from([("tier".to_string(), dyn_value!("production"))]);
 
-    let user = StatsigUser::new(statsig_rust::StatsigUserData {
-        user_id: Some(dyn_value!("user1")),
-        custom_ids: Some(custom_ids.clone()),
-        email: None,
-        ip: None,
-        user_agent: None,
-        country: None,
-        locale: None,
-        app_version: None,
-        statsig_environment: Some(statsig_environment.clone()),
-        private_attributes: Some(private_attributes.clone()),
-        custom: Some(custom.clone()),
-    });
+    let mut data = statsig_rust::StatsigUserData::default();
+    data.user_id = Some(dyn_value!("user1"));
+    data.custom_ids = Some(custom_ids.clone());
+    data.statsig_environment = Some(statsig_environment.clone());
+    data.private_attributes = Some(private_attributes.clone());
+    data.custom = Some(custom.clone());
+    let user = StatsigUser::new(data);
 
     assert_eq!(user.data.custom_ids, Some(custom_ids));
     assert_eq!(user.data.custom, Some(custom));
@@ -0,0 +1,106 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use statsig_rust::{Statsig, StatsigOptions, StatsigUser, StatsigUserData, UserAgentClientHints};
+use std::sync::Arc;
+
+// Chrome on Windows, the UA string has no mobile or Android information
+const DESKTOP_CHROME_UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36";
+
+async fn setup(use_third_party_ua_parser: bool) -> Statsig {
+    let uuid = uuid::Uuid::new_v4();
+    let statsig = Statsig::new(
+        &format!("secret-ua_client_hints_{uuid}"),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+            use_third_party_ua_parser: Some(use_third_party_ua_parser),
+            wait_for_user_agent_init: Some(true),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+    statsig
+}
+
+fn android_chrome_hints() -> UserAgentClientHints {
+    UserAgentClientHints {
+        brands: Some(r#""Chromium";v="124", "Google Chrome";v="124", "Not-A.Brand";v="99""#.into()),
+        platform: Some(r#""Android""#.into()),
+        platform_version: Some(r#""14.0.0""#.into()),
+        mobile: Some(true),
+        ..UserAgentClientHints::default()
+    }
+}
+
+fn user(user_agent: Option<&str>, hints: Option<UserAgentClientHints>) -> StatsigUser {
+    let mut user = StatsigUser::with_user_id("a_user");
+    user.set_user_agent(user_agent);
+    user.set_user_agent_client_hints(hints);
+    user
+}
+
+#[tokio::test]
+async fn test_client_hints_are_preferred_over_user_agent() {
+    for use_third_party_ua_parser in [false, true] {
+        let statsig = setup(use_third_party_ua_parser).await;
+
+        // "test_ua" passes for iOS or "Chrome Mobile"
+        let without_hints = user(Some(DESKTOP_CHROME_UA), None);
+        assert!(!statsig.check_gate(&without_hints, "test_ua"));
+
+        let with_hints = user(Some(DESKTOP_CHROME_UA), Some(android_chrome_hints()));
+        assert!(statsig.check_gate(&with_hints, "test_ua"));
+    }
+}
+
+#[tokio::test]
+async fn test_client_hints_without_user_agent() {
+    let statsig = setup(false).await;
+
+    let hints_only = user(None, Some(android_chrome_hints()));
+    assert!(statsig.check_gate(&hints_only, "test_ua"));
+}
+
+#[tokio::test]
+async fn test_user_agent_is_used_for_missing_hints() {
+    let statsig = setup(false).await;
+
+    // only the platform is known from the hints, the browser comes from the UA string
+    let hints = UserAgentClientHints {
+        platform: Some(r#""Windows""#.into()),
+        ..UserAgentClientHints::default()
+    };
+    let ios_ua = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/124.0.6367.111 Mobile/15E148 Safari/604.1";
+
+    assert!(statsig.check_gate(&user(Some(ios_ua), None), "test_ua"));
+    assert!(!statsig.check_gate(&user(Some(ios_ua), Some(hints)), "test_ua"));
+}
+
+#[tokio::test]
+async fn test_client_hints_from_user_json() {
+    let statsig = setup(false).await;
+
+    // the shape the FFI bindings pass to `statsig_user_create_from_data`
+    let data: StatsigUserData = serde_json::from_value(serde_json::json!({
+        "userID": "a_user",
+        "userAgent": DESKTOP_CHROME_UA,
+        "userAgentClientHints": {
+            "brands": r#""Chromium";v="124", "Google Chrome";v="124", "Not-A.Brand";v="99""#,
+            "platform": r#""Android""#,
+            "platformVersion": r#""14.0.0""#,
+            "mobile": true
+        }
+    }))
+    .unwrap();
+    let user = StatsigUser::new(data);
+
+    assert_eq!(
+        user.get_user_agent_client_hints(),
+        Some(&android_chrome_hints())
+    );
+    assert!(statsig.check_gate(&user, "test_ua"));
+}
@@ -0,0 +1,143 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::Value;
+use statsig_rust::{Statsig, StatsigOptions, StatsigUser};
+use std::fs;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+// the rules of "test_ua" are `os_name any iOS` and `browser_name any Chrome Mobile`
+const UA_GATE: &str = "test_ua";
+const OS_NAME_CONDITION: &str = "3149121879";
+const BROWSER_NAME_CONDITION: &str = "726226528";
+
+const IPAD_UA: &str = "Mozilla/5.0 (iPad; CPU OS 16_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.6 Mobile/15E148 Safari/604.1";
+const DESKTOP_UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36";
+const GOOGLEBOT_UA: &str =
+    "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+
+async fn setup(
+    specs_path: &str,
+    options: StatsigOptions,
+) -> (Statsig, Arc<MockEventLoggingAdapter>) {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+
+    let uuid = uuid::Uuid::new_v4();
+    let statsig = Statsig::new(
+        &format!("secret-ua_device_{uuid}"),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(specs_path))),
+            event_logging_adapter: Some(logging_adapter.clone()),
+            wait_for_user_agent_init: Some(true),
+            ..options
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, logging_adapter)
+}
+
+/// Writes a copy of the eval project where `test_ua` passes for tablets or bots.
+fn write_specs_with_device_conditions(dir: &TempDir) -> String {
+    let data = fs::read_to_string("tests/data/eval_proj_dcs.json").unwrap();
+    let mut json: Value = serde_json::from_str(&data).unwrap();
+
+    let os_name_condition = &mut json["condition_map"][OS_NAME_CONDITION];
+    os_name_condition["field"] = Value::from("device_type");
+    os_name_condition["targetValue"] = serde_json::json!(["tablet"]);
+
+    let browser_name_condition = &mut json["condition_map"][BROWSER_NAME_CONDITION];
+    browser_name_condition["field"] = Value::from("is_bot");
+    browser_name_condition["targetValue"] = serde_json::json!(["true"]);
+
+    let path = dir.path().join("eval_proj_dcs.json");
+    fs::write(&path, json.to_string()).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+fn user(user_id: &str, user_agent: &str) -> StatsigUser {
+    let mut user = StatsigUser::with_user_id(user_id);
+    user.set_user_agent(user_agent);
+    user
+}
+
+fn logged_events(adapter: &MockEventLoggingAdapter) -> Vec<Value> {
+    adapter
+        .logged_payloads
+        .lock()
+        .unwrap()
+        .iter()
+        .flat_map(|p| p.events.as_array().cloned().unwrap_or_default())
+        .filter(|e| e["eventName"] != "statsig::diagnostics")
+        .collect()
+}
+
+#[tokio::test]
+async fn test_device_fields_in_conditions() {
+    let dir = TempDir::new().unwrap();
+    let specs_path = write_specs_with_device_conditions(&dir);
+
+    for use_third_party_ua_parser in [false, true] {
+        let options = StatsigOptions {
+            use_third_party_ua_parser: Some(use_third_party_ua_parser),
+            ..StatsigOptions::new()
+        };
+        let (statsig, _) = setup(&specs_path, options).await;
+
+        assert!(statsig.check_gate(&user("a_user", IPAD_UA), UA_GATE));
+        assert!(statsig.check_gate(&user("a_user", GOOGLEBOT_UA), UA_GATE));
+        assert!(!statsig.check_gate(&user("a_user", DESKTOP_UA), UA_GATE));
+    }
+}
+
+#[tokio::test]
+async fn test_bot_exposures_are_not_logged() {
+    let options = StatsigOptions {
+        disable_bot_exposure_logging: Some(true),
+        ..StatsigOptions::new()
+    };
+    let (statsig, logging_adapter) = setup("tests/data/eval_proj_dcs.json", options).await;
+
+    let bot = user("a_bot", GOOGLEBOT_UA);
+    let person = user("a_person", DESKTOP_UA);
+
+    statsig.check_gate(&bot, UA_GATE);
+    statsig.get_experiment(&bot, "experiment_with_many_params");
+    statsig.check_gate(&person, UA_GATE);
+    statsig.log_event(&bot, "bot_event", None, None);
+    statsig.shutdown().await.unwrap();
+
+    let events = logged_events(&logging_adapter);
+    let event_users: Vec<(&str, &str)> = events
+        .iter()
+        .map(|e| {
+            (
+                e["eventName"].as_str().unwrap(),
+                e["user"]["userID"].as_str().unwrap(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        event_users,
+        vec![
+            ("statsig::gate_exposure", "a_person"),
+            ("bot_event", "a_bot"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_bot_exposures_are_logged_by_default() {
+    let (statsig, logging_adapter) =
+        setup("tests/data/eval_proj_dcs.json", StatsigOptions::new()).await;
+
+    statsig.check_gate(&user("a_bot", GOOGLEBOT_UA), UA_GATE);
+    statsig.shutdown().await.unwrap();
+
+    let events = logged_events(&logging_adapter);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["eventName"], "statsig::gate_exposure");
+}
@@ -0,0 +1,189 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::Value;
+use statsig_rust::{dyn_value, Statsig, StatsigErr, StatsigOptions, StatsigUser};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use utils::mock_scrapi::{Endpoint, EndpointStub, Method, MockScrapi, StubData};
+
+const SDK_KEY: &str = "secret-update_options";
+
+async fn setup() -> (Statsig, Arc<MockEventLoggingAdapter>) {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+    let statsig = Statsig::new(
+        SDK_KEY,
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(
+                "tests/data/eval_proj_dcs.json",
+            ))),
+            event_logging_adapter: Some(logging_adapter.clone()),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, logging_adapter)
+}
+
+fn get_logged_event_names(logging_adapter: &MockEventLoggingAdapter) -> Vec<String> {
+    logging_adapter
+        .logged_payloads
+        .lock()
+        .unwrap()
+        .iter()
+        .flat_map(|p| p.events.as_array().cloned().unwrap_or_default())
+        .filter_map(|e| e["eventName"].as_str().map(str::to_string))
+        .filter(|name| name != "statsig::diagnostics")
+        .collect()
+}
+
+#[tokio::test]
+async fn test_fields_fixed_at_init_are_rejected() {
+    let (statsig, logging_adapter) = setup().await;
+
+    let result = statsig.update_options(StatsigOptions {
+        disable_all_logging: Some(true),
+        specs_url: Some("https://example.com".to_string()),
+        environment: Some("staging".to_string()),
+        ..StatsigOptions::new()
+    });
+
+    match result {
+        Err(StatsigErr::InvalidOperation(message)) => {
+            assert!(message.contains("environment"));
+            assert!(message.contains("specs_url"));
+            assert!(!message.contains("disable_all_logging"));
+        }
+        other => panic!("Unexpected result {other:?}"),
+    }
+
+    // nothing from a rejected update is applied
+    let user = StatsigUser::with_user_id("a_user".to_string());
+    statsig.log_event(&user, "still_logged", None, None);
+    statsig.flush_events().await;
+    assert_eq!(
+        get_logged_event_names(&logging_adapter),
+        vec!["still_logged"]
+    );
+    assert_eq!(statsig.get_current_options().disable_all_logging, None);
+}
+
+#[tokio::test]
+async fn test_toggling_disable_all_logging() {
+    let (statsig, logging_adapter) = setup().await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    statsig
+        .update_options(StatsigOptions {
+            disable_all_logging: Some(true),
+            ..StatsigOptions::new()
+        })
+        .unwrap();
+    statsig.log_event(&user, "dropped", None, None);
+
+    statsig
+        .update_options(StatsigOptions {
+            disable_all_logging: Some(false),
+            ..StatsigOptions::new()
+        })
+        .unwrap();
+    statsig.log_event(&user, "kept", None, None);
+    statsig.flush_events().await;
+
+    assert_eq!(get_logged_event_names(&logging_adapter), vec!["kept"]);
+}
+
+#[tokio::test]
+async fn test_global_custom_fields_are_replaced() {
+    let (statsig, logging_adapter) = setup().await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    statsig
+        .update_options(StatsigOptions {
+            global_custom_fields: Some(HashMap::from([(
+                "region".to_string(),
+                dyn_value!("us-east"),
+            )])),
+            ..StatsigOptions::new()
+        })
+        .unwrap();
+    statsig.log_event(&user, "with_fields", None, None);
+    statsig.flush_events().await;
+
+    let event = logging_adapter.force_get_first_event();
+    assert_eq!(event["user"]["custom"]["region"], Value::from("us-east"));
+    assert!(statsig
+        .get_current_options()
+        .global_custom_fields
+        .as_ref()
+        .is_some_and(|fields| fields.contains_key("region")));
+    assert!(statsig.options.global_custom_fields.is_none());
+}
+
+#[tokio::test]
+async fn test_smaller_batch_size_triggers_flush() {
+    let (statsig, logging_adapter) = setup().await;
+    let user = StatsigUser::with_user_id("a_user".to_string());
+
+    statsig
+        .update_options(StatsigOptions {
+            event_logging_max_queue_size: Some(10),
+            ..StatsigOptions::new()
+        })
+        .unwrap();
+
+    for i in 0..10 {
+        statsig.log_event(&user, &format!("event_{i}"), None, None);
+    }
+
+    assert_eventually!(|| logging_adapter
+        .no_diagnostics_logged_event_count
+        .load(Ordering::SeqCst)
+        >= 10);
+}
+
+#[tokio::test]
+async fn test_specs_sync_is_rescheduled() {
+    std::env::set_var("STATSIG_RUNNING_TESTS", "true");
+
+    let mock_scrapi = MockScrapi::new().await;
+    mock_scrapi
+        .stub(EndpointStub {
+            method: Method::GET,
+            response: StubData::String(utils::helpers::load_contents("eval_proj_dcs.json")),
+            ..EndpointStub::with_endpoint(Endpoint::DownloadConfigSpecs)
+        })
+        .await;
+
+    let statsig = Statsig::new(
+        SDK_KEY,
+        Some(Arc::new(StatsigOptions {
+            specs_url: Some(mock_scrapi.url_for_endpoint(Endpoint::DownloadConfigSpecs)),
+            event_logging_adapter: Some(Arc::new(MockEventLoggingAdapter::new())),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    // the default interval is 10s, so no background sync happens yet
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let calls_before = mock_scrapi.times_called_for_endpoint(Endpoint::DownloadConfigSpecs);
+    assert_eq!(calls_before, 1);
+
+    statsig
+        .update_options(StatsigOptions {
+            specs_sync_interval_ms: Some(10),
+            ..StatsigOptions::new()
+        })
+        .unwrap();
+
+    assert_eventually!(
+        || mock_scrapi.times_called_for_endpoint(Endpoint::DownloadConfigSpecs) > calls_before + 2
+    );
+
+    statsig.shutdown().await.unwrap();
+}
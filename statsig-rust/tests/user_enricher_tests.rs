@@ -0,0 +1,200 @@
+mod utils;
+
+use crate::utils::mock_event_logging_adapter::MockEventLoggingAdapter;
+use crate::utils::mock_specs_adapter::MockSpecsAdapter;
+use serde_json::Value;
+use statsig_rust::{
+    DynamicValue, Statsig, StatsigOptions, StatsigUser, UserEnricher, UserEnrichment,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// Gives every user `level: 9`, `companyID: 12345` and a private `tier` attribute.
+struct LevelEnricher {
+    calls: AtomicU64,
+    exclude_from_logging: bool,
+}
+
+impl LevelEnricher {
+    fn new(exclude_from_logging: bool) -> Self {
+        Self {
+            calls: AtomicU64::new(0),
+            exclude_from_logging,
+        }
+    }
+}
+
+impl UserEnricher for LevelEnricher {
+    fn enrich(&self, _user: &StatsigUser) -> UserEnrichment {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+
+        UserEnrichment {
+            custom: Some(HashMap::from([(
+                "level".to_string(),
+                DynamicValue::from(9),
+            )])),
+            custom_ids: Some(HashMap::from([(
+                "companyID".to_string(),
+                "12345".to_string(),
+            )])),
+            private_attributes: Some(HashMap::from([(
+                "tier".to_string(),
+                DynamicValue::from("enterprise"),
+            )])),
+            exclude_from_logging: self.exclude_from_logging,
+        }
+    }
+}
+
+async fn setup(enricher: Arc<LevelEnricher>) -> (Statsig, Arc<MockEventLoggingAdapter>) {
+    setup_with_specs(enricher, "tests/data/eval_proj_dcs.json").await
+}
+
+async fn setup_with_specs(
+    enricher: Arc<LevelEnricher>,
+    specs_path: &str,
+) -> (Statsig, Arc<MockEventLoggingAdapter>) {
+    let logging_adapter = Arc::new(MockEventLoggingAdapter::new());
+
+    let uuid = uuid::Uuid::new_v4();
+    let statsig = Statsig::new(
+        &format!("secret-user_enricher_{uuid}"),
+        Some(Arc::new(StatsigOptions {
+            specs_adapter: Some(Arc::new(MockSpecsAdapter::with_data(specs_path))),
+            event_logging_adapter: Some(logging_adapter.clone()),
+            user_enricher: Some(enricher),
+            ..StatsigOptions::new()
+        })),
+    );
+    statsig.initialize().await.unwrap();
+
+    (statsig, logging_adapter)
+}
+
+#[tokio::test]
+async fn test_enriched_fields_are_evaluated() {
+    let (statsig, _) = setup(Arc::new(LevelEnricher::new(false))).await;
+    let user = StatsigUser::with_user_id("a_user");
+
+    // "level" > 8
+    assert!(statsig.check_gate(&user, "test_numeric"));
+    // "companyID" is 12345
+    assert!(statsig.check_gate(&user, "test_numeric_custom_id"));
+}
+
+#[tokio::test]
+async fn test_user_fields_take_precedence() {
+    let (statsig, _) = setup(Arc::new(LevelEnricher::new(false))).await;
+
+    let mut user = StatsigUser::with_user_id("a_user");
+    user.set_custom(Some(HashMap::from([("level".to_string(), 1)])));
+
+    assert!(!statsig.check_gate(&user, "test_numeric"));
+}
+
+#[tokio::test]
+async fn test_enrichment_is_memoized_per_user() {
+    let enricher = Arc::new(LevelEnricher::new(false));
+    let (statsig, _) = setup(enricher.clone()).await;
+
+    let user = StatsigUser::with_user_id("a_user");
+    let _ = statsig.check_gate(&user, "test_numeric");
+    let _ = statsig.get_dynamic_config(&user, "test_email_config");
+    let _ = statsig.check_gate(&StatsigUser::with_user_id("a_user"), "test_numeric");
+    assert_eq!(enricher.calls.load(Ordering::SeqCst), 1);
+
+    let _ = statsig.check_gate(&StatsigUser::with_user_id("b_user"), "test_numeric");
+    assert_eq!(enricher.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_enriched_fields_are_logged() {
+    let (statsig, logging_adapter) = setup(Arc::new(LevelEnricher::new(false))).await;
+
+    let _ = statsig.check_gate(&StatsigUser::with_user_id("a_user"), "test_numeric");
+    statsig.shutdown().await.unwrap();
+
+    let event = logging_adapter.force_get_first_event();
+    assert_eq!(event["user"]["custom"]["level"], Value::from(9));
+    assert_eq!(event["user"]["customIDs"]["companyID"], "12345");
+    assert!(event["user"].get("privateAttributes").is_none());
+}
+
+#[tokio::test]
+async fn test_enriched_custom_fields_can_be_excluded_from_logging() {
+    let (statsig, logging_adapter) = setup(Arc::new(LevelEnricher::new(true))).await;
+
+    assert!(statsig.check_gate(&StatsigUser::with_user_id("a_user"), "test_numeric"));
+    statsig.shutdown().await.unwrap();
+
+    let event = logging_adapter.force_get_first_event();
+    assert_eq!(event["user"]["userID"], "a_user");
+    assert!(event["user"].get("custom").is_none());
+    // custom IDs stay, since exposures may be bucketed by them
+    assert_eq!(event["user"]["customIDs"]["companyID"], "12345");
+}
+
+/// Writes the eval project with an extra `test_in_exp_group` gate, which passes when the user
+/// is in any group of `test_experiment_no_targeting`.
+fn write_specs_with_experiment_group_gate(dir: &TempDir) -> String {
+    let mut specs: Value =
+        serde_json::from_str(&std::fs::read_to_string("tests/data/eval_proj_dcs.json").unwrap())
+            .unwrap();
+
+    specs["condition_map"]["in_exp_group"] = serde_json::json!({
+        "type": "experiment_group",
+        "targetValue": ["Control", "Test", "Test2"],
+        "operator": "any",
+        "field": "test_experiment_no_targeting",
+        "additionalValues": {},
+        "idType": "userID"
+    });
+    specs["feature_gates"]["test_in_exp_group"] = serde_json::json!({
+        "type": "feature_gate",
+        "salt": "in_exp_group_salt",
+        "enabled": true,
+        "defaultValue": false,
+        "rules": [{
+            "name": "in_exp_group_rule",
+            "passPercentage": 100,
+            "conditions": ["in_exp_group"],
+            "returnValue": true,
+            "id": "in_exp_group_rule",
+            "salt": "in_exp_group_rule_salt",
+            "idType": "userID"
+        }],
+        "idType": "userID",
+        "entity": "feature_gate",
+        "version": 1
+    });
+
+    let path = dir.path().join("specs.json");
+    std::fs::write(&path, specs.to_string()).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[tokio::test]
+async fn test_nested_experiment_reuses_enriched_user() {
+    let dir = tempfile::tempdir().unwrap();
+    let specs_path = write_specs_with_experiment_group_gate(&dir);
+
+    let enricher = Arc::new(LevelEnricher::new(true));
+    let (statsig, logging_adapter) = setup_with_specs(enricher.clone(), &specs_path).await;
+
+    assert!(statsig.check_gate(&StatsigUser::with_user_id("a_user"), "test_in_exp_group"));
+    assert_eq!(enricher.calls.load(Ordering::SeqCst), 1);
+    statsig.shutdown().await.unwrap();
+
+    let nested_exposure = (0..2)
+        .map(|i| logging_adapter.force_get_event_at(i))
+        .find(|event| event["eventName"] == "statsig::config_exposure")
+        .expect("No nested experiment exposure was logged");
+    assert_eq!(
+        nested_exposure["metadata"]["config"],
+        "test_experiment_no_targeting"
+    );
+    assert!(nested_exposure["user"].get("custom").is_none());
+    assert_eq!(nested_exposure["user"]["customIDs"]["companyID"], "12345");
+}